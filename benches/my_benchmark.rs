@@ -1,6 +1,7 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use finance_solution::tvm::present_value::*;
 use finance_solution::cashflow::present_value_annuity::*;
+use finance_solution::cashflow::net_present_value::*;
 
 pub fn criterion_benchmark(c: &mut Criterion) {
 
@@ -16,6 +17,24 @@ pub fn criterion_benchmark(c: &mut Criterion) {
             black_box(5), 
             black_box(1_000), 
             black_box(false))));
+
+    // One hundred trials of 30 periods at the same rate, as in a Monte Carlo simulation.
+    let trials = (0..100)
+        .map(|trial| (0..=30).map(|period| if period == 0 { -10_000.0 } else { 500.0 + trial as f64 }).collect::<Vec<f64>>())
+        .collect::<Vec<_>>();
+    let rates = vec![0.034; 30];
+
+    c.bench_function("npv trials net_present_value_schedule", |b| b.iter(||
+        trials.iter()
+            .map(|cash_flows| net_present_value_schedule(black_box(&rates), black_box(cash_flows)))
+            .collect::<Vec<_>>()));
+
+    c.bench_function("npv trials DiscountFactors", |b| b.iter(|| {
+        let discount_factors = DiscountFactors::new(black_box(0.034), black_box(30));
+        trials.iter()
+            .map(|cash_flows| discount_factors.npv(black_box(cash_flows)))
+            .collect::<Vec<_>>()
+    }));
     
 }
 
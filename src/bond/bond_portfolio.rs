@@ -0,0 +1,127 @@
+//! **Portfolios of bonds.** A [`BondPortfolio`] holds several [`BondSolution`]s, such as the rungs
+//! of a bond ladder, and rolls them up into the total market value and value-weighted averages of
+//! the coupon, maturity, and duration.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! let mut portfolio = BondPortfolio::new();
+//! portfolio.add(bond_price_solution(1_000, 0.04, 2, 4, 0.045));
+//! portfolio.add(bond_price_solution(2_000, 0.05, 2, 10, 0.05));
+//! portfolio.add(bond_price_solution(1_500, 0.06, 2, 20, 0.055));
+//! dbg!(portfolio.total_market_value(), portfolio.weighted_average_maturity(), portfolio.portfolio_duration());
+//! ```
+
+use crate::*;
+
+/// A collection of [`BondSolution`]s that are summarized together. Each holding's market value is
+/// its dirty price, so the face value passed when pricing the bond sets the size of the position.
+///
+/// The averages are weighted by market value and are zero if the portfolio is empty or the total
+/// market value is zero.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // A three-rung ladder maturing in 2, 5, and 10 years with semiannual coupons.
+/// let mut portfolio = BondPortfolio::new();
+/// portfolio.add(bond_price_solution(1_000, 0.04, 2, 4, 0.045));
+/// portfolio.add(bond_price_solution(2_000, 0.05, 2, 10, 0.05));
+/// portfolio.add(bond_price_solution(1_500, 0.06, 2, 20, 0.055));
+/// assert_eq!(3, portfolio.holdings().len());
+/// assert_rounded_4!(4_547.6403, portfolio.total_market_value());
+/// assert_rounded_4!(4_500.0, portfolio.total_face_value());
+/// assert_rounded_6!(0.051246, portfolio.weighted_average_coupon());
+/// assert_rounded_4!(6.0585, portfolio.weighted_average_maturity());
+/// assert_rounded_4!(5.0362, portfolio.portfolio_duration());
+/// assert_rounded_4!(4.9081, portfolio.portfolio_modified_duration());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct BondPortfolio {
+    holdings: Vec<BondSolution>,
+}
+
+impl BondPortfolio {
+    /// Creates an empty portfolio.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a holding to the portfolio.
+    pub fn add(&mut self, solution: BondSolution) {
+        self.holdings.push(solution);
+    }
+
+    /// Returns the holdings in the order they were added.
+    pub fn holdings(&self) -> &[BondSolution] {
+        &self.holdings
+    }
+
+    /// Returns the sum of the dirty prices of the holdings.
+    pub fn total_market_value(&self) -> f64 {
+        self.holdings.iter().map(|holding| holding.dirty_price()).sum()
+    }
+
+    /// Returns the sum of the face values of the holdings.
+    pub fn total_face_value(&self) -> f64 {
+        self.holdings.iter().map(|holding| holding.face_value()).sum()
+    }
+
+    /// Returns the annual coupon rate of the holdings weighted by market value.
+    pub fn weighted_average_coupon(&self) -> f64 {
+        self.weighted_average(|holding| holding.coupon_rate())
+    }
+
+    /// Returns the time to maturity in years of the holdings weighted by market value.
+    pub fn weighted_average_maturity(&self) -> f64 {
+        self.weighted_average(|holding| holding.periods() / holding.coupons_per_year() as f64)
+    }
+
+    /// Returns the Macaulay duration in years of the portfolio, found as the average of each
+    /// holding's [`BondSolution::macaulay_duration`] weighted by market value.
+    pub fn portfolio_duration(&self) -> f64 {
+        self.weighted_average(|holding| holding.macaulay_duration())
+    }
+
+    /// Returns the modified duration of the portfolio, found as the average of each holding's
+    /// [`BondSolution::modified_duration`] weighted by market value. This approximates the
+    /// fractional change in the portfolio's value for a change of 1.0 in every holding's yield.
+    pub fn portfolio_modified_duration(&self) -> f64 {
+        self.weighted_average(|holding| holding.modified_duration())
+    }
+
+    fn weighted_average<F>(&self, value: F) -> f64
+        where F: Fn(&BondSolution) -> f64
+    {
+        let total_market_value = self.total_market_value();
+        if total_market_value == 0.0 {
+            return 0.0;
+        }
+        self.holdings.iter().map(|holding| value(holding) * holding.dirty_price()).sum::<f64>() / total_market_value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bond_portfolio_single_holding() {
+        // With one holding the averages are the holding's own figures.
+        let solution = bond_price_solution(5_000, 0.035, 4, 28, 0.04);
+        let mut portfolio = BondPortfolio::new();
+        portfolio.add(solution.clone());
+        assert_approx_equal!(solution.dirty_price(), portfolio.total_market_value());
+        assert_approx_equal!(0.035, portfolio.weighted_average_coupon());
+        assert_approx_equal!(7.0, portfolio.weighted_average_maturity());
+        assert_approx_equal!(solution.macaulay_duration(), portfolio.portfolio_duration());
+    }
+
+    #[test]
+    fn test_bond_portfolio_empty() {
+        let portfolio = BondPortfolio::new();
+        assert_eq!(0.0, portfolio.total_market_value());
+        assert_eq!(0.0, portfolio.weighted_average_coupon());
+        assert_eq!(0.0, portfolio.portfolio_duration());
+    }
+}
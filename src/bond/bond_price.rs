@@ -0,0 +1,174 @@
+//! **Bond price calculations.** Given a bond's coupon, maturity, and the market yield, what is the
+//! bond worth today?
+//!
+//! The price is the present value of the remaining coupons plus the present value of the face value
+//! repaid at maturity. These functions assume the price is being calculated on a coupon date so
+//! that each payment is a whole number of periods away.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // A 10-year bond with a face value of $1,000 paying a 5% coupon twice a year, priced to yield 6%.
+//! let solution = bond_price_solution(1_000, 0.05, 2, 20, 0.06);
+//! solution.print_table();
+//! ```
+
+use crate::*;
+
+/// Returns the **price of a bond** on a coupon date. The price is positive since it's the amount
+/// the buyer pays to receive the coupons and the face value.
+///
+/// Related functions:
+/// * To see the present value of each coupon and of the redemption use [`bond_price_solution`].
+///
+/// The formula is:
+///
+/// price = sum( coupon / (1 + y)<sup>t</sup> ) + face_value / (1 + y)<sup>n</sup>
+///
+/// where y is the yield per period, coupon is the face value times the coupon rate divided by the
+/// number of coupons per year, and t runs from 1 to n.
+///
+/// # Arguments
+/// * `face_value` - The amount repaid at maturity, also known as the par value.
+/// * `coupon_rate` - The annual coupon rate as a fraction of the face value. For instance 0.05
+/// would mean 5%.
+/// * `coupons_per_year` - The number of coupons paid each year, such as 2 for a semiannual bond.
+/// * `periods` - The number of coupon periods until maturity.
+/// * `yield_per_year` - The annual yield to maturity, compounded once per coupon period.
+///
+/// # Panics
+/// The call will fail if `coupons_per_year` is zero, if the yield per period is not greater than
+/// -1.0, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // A 10-year bond paying a 5% coupon twice a year and priced to yield 6% trades below par.
+/// let price = bond_price(1_000, 0.05, 2, 20, 0.06);
+/// assert_rounded_4!(925.6126, price);
+///
+/// // When the yield equals the coupon rate the bond trades at par.
+/// assert_rounded_4!(1_000.0000, bond_price(1_000, 0.05, 2, 20, 0.05));
+/// ```
+pub fn bond_price<T>(face_value: T, coupon_rate: f64, coupons_per_year: u32, periods: u32, yield_per_year: f64) -> f64
+    where T: Into<f64> + Copy
+{
+    bond_price_solution(face_value, coupon_rate, coupons_per_year, periods, yield_per_year).clean_price()
+}
+
+/// Returns the **price of a bond** on a coupon date along with the present value of each coupon and
+/// of the redemption.
+///
+/// Related functions:
+/// * To calculate only the price as an `f64` use [`bond_price`].
+///
+/// # Arguments
+/// * `face_value` - The amount repaid at maturity, also known as the par value.
+/// * `coupon_rate` - The annual coupon rate as a fraction of the face value. For instance 0.05
+/// would mean 5%.
+/// * `coupons_per_year` - The number of coupons paid each year, such as 2 for a semiannual bond.
+/// * `periods` - The number of coupon periods until maturity.
+/// * `yield_per_year` - The annual yield to maturity, compounded once per coupon period.
+///
+/// # Panics
+/// The call will fail if `coupons_per_year` is zero, if the yield per period is not greater than
+/// -1.0, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = bond_price_solution(1_000, 0.05, 2, 20, 0.06);
+/// dbg!(&solution);
+///
+/// // The price splits into the value of the coupons and the value of the face value.
+/// assert_rounded_4!(371.9369, solution.coupon_pv());
+/// assert_rounded_4!(553.6758, solution.redemption_pv());
+/// assert_rounded_4!(925.6126, solution.clean_price());
+///
+/// // About 40% of the bond's value comes from the coupons.
+/// assert_rounded_4!(0.4018, solution.coupon_pv_fraction());
+///
+/// // There's one entry for each coupon plus one for the redemption.
+/// assert_eq!(21, solution.series().len());
+/// ```
+pub fn bond_price_solution<T>(face_value: T, coupon_rate: f64, coupons_per_year: u32, periods: u32, yield_per_year: f64) -> BondSolution
+    where T: Into<f64> + Copy
+{
+    let face_value = face_value.into();
+    check_bond_parameters(face_value, coupon_rate, coupons_per_year, yield_per_year);
+    let coupon = face_value * coupon_rate / coupons_per_year as f64;
+    let periodic_yield = yield_per_year / coupons_per_year as f64;
+    let mut series = vec![];
+    for period in 1..=periods {
+        let discount_factor = 1.0 / (1.0 + periodic_yield).powi(period as i32);
+        series.push(BondCashflow::new(period as f64, BondCashflowType::Coupon, coupon, discount_factor));
+        if period == periods {
+            series.push(BondCashflow::new(period as f64, BondCashflowType::Redemption, face_value, discount_factor));
+        }
+    }
+    let symbolic_formula = "price = sum(coupon / (1 + y)^t) + face_value / (1 + y)^n";
+    BondSolution::new(face_value, coupon_rate, coupons_per_year, yield_per_year, series, symbolic_formula)
+}
+
+pub(crate) fn check_bond_parameters(face_value: f64, coupon_rate: f64, coupons_per_year: u32, yield_per_year: f64) {
+    assert!(face_value.is_finite(), "The face value must be finite (not NaN or infinity)");
+    assert!(coupon_rate.is_finite(), "The coupon rate must be finite (not NaN or infinity)");
+    assert!(yield_per_year.is_finite(), "The yield must be finite (not NaN or infinity)");
+    assert!(coupons_per_year > 0, "The number of coupons per year must be greater than zero.");
+    assert!(yield_per_year / (coupons_per_year as f64) > -1.0, "The yield per period must be greater than -1.0.");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bond_price_matches_annuity_plus_present_value() {
+        let (face_value, coupon_rate, coupons_per_year, periods, yield_per_year) = (1_000.0, 0.07, 2, 30, 0.045);
+        let solution = bond_price_solution(face_value, coupon_rate, coupons_per_year, periods, yield_per_year);
+        let periodic_yield = yield_per_year / coupons_per_year as f64;
+        let coupon = face_value * coupon_rate / coupons_per_year as f64;
+        // Both functions follow the cash flow sign convention so the results are negated.
+        let coupon_pv = -present_value_annuity(periodic_yield, periods, coupon, false);
+        let redemption_pv = -present_value(periodic_yield, periods, face_value, false);
+        assert_approx_equal!(coupon_pv, solution.coupon_pv());
+        assert_approx_equal!(redemption_pv, solution.redemption_pv());
+        assert_approx_equal!(coupon_pv + redemption_pv, solution.clean_price());
+        assert!(solution.clean_price() > face_value);
+    }
+
+    #[test]
+    fn test_bond_price_zero_coupon() {
+        let solution = bond_price_solution(100, 0.0, 1, 5, 0.04);
+        assert_approx_equal!(0.0, solution.coupon_pv());
+        assert_approx_equal!(0.0, solution.coupon_pv_fraction());
+        assert_approx_equal!(100.0 / 1.04_f64.powi(5), solution.clean_price());
+    }
+
+    #[test]
+    fn test_bond_horizon_value_at_yield() {
+        // Reinvesting at the yield earns the yield over any horizon, including one past maturity
+        // and one partway through a period.
+        let solution = bond_price_solution(1_000, 0.065, 2, 16, 0.052);
+        for horizon_periods in [1.0, 7.5, 16.0, 20.0].iter() {
+            let horizon = solution.horizon_value_solution(0.052, *horizon_periods);
+            assert_approx_equal!(0.052, horizon.yield_to_horizon());
+            assert_approx_equal!(solution.dirty_price() * 1.026_f64.powf(*horizon_periods), horizon.horizon_value());
+        }
+    }
+
+    #[test]
+    fn test_bond_horizon_value_at_maturity() {
+        let solution = bond_price_solution(1_000, 0.04, 1, 5, 0.05);
+        let horizon = solution.horizon_value_solution(0.0, 5.0);
+        assert_approx_equal!(0.0, horizon.price_at_horizon());
+        assert_approx_equal!(0.0, horizon.reinvestment_income());
+        assert_approx_equal!(1_200.0, horizon.horizon_value());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bond_price_zero_coupons_per_year() {
+        bond_price(1_000, 0.05, 0, 10, 0.05);
+    }
+}
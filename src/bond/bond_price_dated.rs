@@ -0,0 +1,198 @@
+//! **Bond prices on a settlement date between coupons.** Bonds usually trade between coupon dates,
+//! so the next coupon is only part of a period away. The buyer pays the seller the interest that
+//! has accrued since the last coupon in addition to the quoted clean price.
+//!
+//! The coupon dates are found by stepping back from the maturity date one coupon period at a time.
+//! The fraction of a period until the next coupon is measured with a [`DayCountBasis`]:
+//!
+//! w = days(settlement, next_coupon) / days(previous_coupon, next_coupon)
+//!
+//! dirty_price = sum( coupon / (1 + y)<sup>w + k</sup> ) + face_value / (1 + y)<sup>w + n - 1</sup>
+//!
+//! accrued_interest = coupon * (1 - w)
+//!
+//! clean_price = dirty_price - accrued_interest
+//!
+//! where y is the yield per period, k runs from 0 to n - 1, and n is the number of coupons remaining.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! use chrono::NaiveDate;
+//! let maturity = NaiveDate::from_ymd_opt(2017, 11, 15).unwrap();
+//! let settlement = NaiveDate::from_ymd_opt(2008, 2, 15).unwrap();
+//! let solution = bond_price_dated_solution(100, 0.0575, 2, maturity, settlement, 0.065, DayCountBasis::ActualActual);
+//! solution.print_table();
+//! ```
+
+use chrono::NaiveDate;
+
+use crate::*;
+
+/// Returns the **clean price of a bond** on a settlement date that may fall between coupon dates.
+///
+/// Related functions:
+/// * To see the accrued interest, the dirty price, and the present value of each payment use
+/// [`bond_price_dated_solution`].
+/// * For a bond priced on a coupon date use [`bond_price`].
+///
+/// See the [bond_price_dated](./index.html) module page for the formulas.
+///
+/// # Arguments
+/// * `face_value` - The amount repaid at maturity, also known as the par value.
+/// * `coupon_rate` - The annual coupon rate as a fraction of the face value. For instance 0.05
+/// would mean 5%.
+/// * `coupons_per_year` - The number of coupons paid each year, such as 2 for a semiannual bond.
+/// This must divide evenly into 12 months.
+/// * `maturity` - The date the bond matures and the face value and last coupon are paid.
+/// * `settlement` - The date the bond changes hands.
+/// * `yield_per_year` - The annual yield to maturity, compounded once per coupon period.
+/// * `basis` - The day count convention used to measure the fraction of the coupon period that has
+/// passed, such as [`DayCountBasis::ActualActual`] for US Treasuries or
+/// [`DayCountBasis::Thirty360`] for corporate bonds.
+///
+/// # Panics
+/// The call will fail if `settlement` is not before `maturity`, if `coupons_per_year` doesn't
+/// divide evenly into 12, if the yield per period is not greater than -1.0, or if any value is not
+/// finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// use chrono::NaiveDate;
+/// let maturity = NaiveDate::from_ymd_opt(2017, 11, 15).unwrap();
+/// let settlement = NaiveDate::from_ymd_opt(2008, 2, 15).unwrap();
+///
+/// // A 5.75% semiannual bond settled halfway between coupons and priced to yield 6.5%, quoted per
+/// // $100 of face value.
+/// let price = bond_price_dated(100, 0.0575, 2, maturity, settlement, 0.065, DayCountBasis::Thirty360);
+/// assert_rounded_6!(94.634362, price);
+///
+/// // On a coupon date this is the same as the price from bond_price().
+/// let settlement = NaiveDate::from_ymd_opt(2007, 11, 15).unwrap();
+/// let price = bond_price_dated(100, 0.0575, 2, maturity, settlement, 0.065, DayCountBasis::ActualActual);
+/// assert_rounded_6!(bond_price(100, 0.0575, 2, 20, 0.065), price);
+/// ```
+pub fn bond_price_dated<T>(face_value: T, coupon_rate: f64, coupons_per_year: u32, maturity: NaiveDate, settlement: NaiveDate, yield_per_year: f64, basis: DayCountBasis) -> f64
+    where T: Into<f64> + Copy
+{
+    bond_price_dated_solution(face_value, coupon_rate, coupons_per_year, maturity, settlement, yield_per_year, basis).clean_price()
+}
+
+/// Returns the **price of a bond** on a settlement date that may fall between coupon dates along
+/// with the accrued interest and the present value of each remaining payment.
+///
+/// Related functions:
+/// * To calculate only the clean price as an `f64` use [`bond_price_dated`].
+///
+/// # Arguments
+/// * `face_value` - The amount repaid at maturity, also known as the par value.
+/// * `coupon_rate` - The annual coupon rate as a fraction of the face value. For instance 0.05
+/// would mean 5%.
+/// * `coupons_per_year` - The number of coupons paid each year, such as 2 for a semiannual bond.
+/// This must divide evenly into 12 months.
+/// * `maturity` - The date the bond matures and the face value and last coupon are paid.
+/// * `settlement` - The date the bond changes hands.
+/// * `yield_per_year` - The annual yield to maturity, compounded once per coupon period.
+/// * `basis` - The day count convention used to measure the fraction of the coupon period that has
+/// passed.
+///
+/// # Panics
+/// The call will fail if `settlement` is not before `maturity`, if `coupons_per_year` doesn't
+/// divide evenly into 12, if the yield per period is not greater than -1.0, or if any value is not
+/// finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// use chrono::NaiveDate;
+/// let maturity = NaiveDate::from_ymd_opt(2017, 11, 15).unwrap();
+/// let settlement = NaiveDate::from_ymd_opt(2008, 2, 15).unwrap();
+/// let solution = bond_price_dated_solution(100, 0.0575, 2, maturity, settlement, 0.065, DayCountBasis::ActualActual);
+///
+/// // 92 of the 182 days in the coupon period have passed.
+/// assert_rounded_6!(1.453297, solution.accrued_interest());
+/// assert_rounded_6!(96.088746, solution.dirty_price());
+/// assert_rounded_6!(94.635449, solution.clean_price());
+///
+/// // The next coupon is 90 days away and there are 20 coupons left.
+/// let series = solution.series();
+/// assert_rounded_6!(90.0 / 182.0, series[0].time());
+/// assert_eq!(21, series.len());
+/// ```
+pub fn bond_price_dated_solution<T>(face_value: T, coupon_rate: f64, coupons_per_year: u32, maturity: NaiveDate, settlement: NaiveDate, yield_per_year: f64, basis: DayCountBasis) -> BondSolution
+    where T: Into<f64> + Copy
+{
+    let face_value = face_value.into();
+    check_bond_parameters(face_value, coupon_rate, coupons_per_year, yield_per_year);
+    assert!(12 % coupons_per_year == 0, "The number of coupons per year must divide evenly into 12 months.");
+    assert!(settlement < maturity, "The settlement date must be before the maturity date.");
+
+    // Step back from maturity to find the remaining coupon dates and the last coupon date on or
+    // before settlement. A bond maturing at the end of a month pays its coupons at month end.
+    let months_per_coupon = (12 / coupons_per_year) as i32;
+    let mut coupon_dates = vec![];
+    let mut previous_coupon = maturity;
+    while previous_coupon > settlement {
+        coupon_dates.push(previous_coupon);
+        previous_coupon = add_months(maturity, -months_per_coupon * coupon_dates.len() as i32, true);
+    }
+    coupon_dates.reverse();
+    let next_coupon = coupon_dates[0];
+
+    let days_in_period = basis.day_count(previous_coupon, next_coupon) as f64;
+    let fraction = basis.day_count(settlement, next_coupon) as f64 / days_in_period;
+    let coupon = face_value * coupon_rate / coupons_per_year as f64;
+    let periodic_yield = yield_per_year / coupons_per_year as f64;
+    let periods = coupon_dates.len();
+    let mut series = vec![];
+    for index in 0..periods {
+        let time = fraction + index as f64;
+        let discount_factor = 1.0 / (1.0 + periodic_yield).powf(time);
+        series.push(BondCashflow::new(time, BondCashflowType::Coupon, coupon, discount_factor));
+        if index == periods - 1 {
+            series.push(BondCashflow::new(time, BondCashflowType::Redemption, face_value, discount_factor));
+        }
+    }
+    let accrued_interest = coupon * (1.0 - fraction);
+    let symbolic_formula = "price = sum(coupon / (1 + y)^(w + k)) + face_value / (1 + y)^(w + n - 1) - coupon * (1 - w)";
+    BondSolution::new(face_value, coupon_rate, coupons_per_year, yield_per_year, series, symbolic_formula)
+        .with_accrued_interest(accrued_interest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_bond_price_dated_on_coupon_date() {
+        // A quarterly bond maturing at the end of a month keeps its coupons at month end.
+        let maturity = date(2030, 8, 31);
+        let settlement = date(2025, 2, 28);
+        let solution = bond_price_dated_solution(1_000, 0.06, 4, maturity, settlement, 0.07, DayCountBasis::Actual365);
+        let expected = bond_price_solution(1_000, 0.06, 4, 22, 0.07);
+        assert_approx_equal!(0.0, solution.accrued_interest());
+        assert_approx_equal!(expected.clean_price(), solution.clean_price());
+        assert_approx_equal!(22.0, solution.periods());
+    }
+
+    #[test]
+    fn test_bond_price_dated_accrued_interest() {
+        let maturity = date(2031, 6, 15);
+        let settlement = date(2026, 9, 15);
+        let solution = bond_price_dated_solution(1_000, 0.045, 2, maturity, settlement, 0.05, DayCountBasis::Thirty360);
+        // Half of the coupon period from June 15 to December 15 has passed.
+        assert_approx_equal!(11.25, solution.accrued_interest());
+        assert_approx_equal!(solution.clean_price() + solution.accrued_interest(), solution.dirty_price());
+        let sum_of_present_values: f64 = solution.series().iter().map(|entry| entry.present_value()).sum();
+        assert_approx_equal!(sum_of_present_values, solution.dirty_price());
+        // Rolling the dirty price forward half a period from the last coupon date gives the same
+        // value as pricing on that date.
+        let on_coupon_date = bond_price_dated_solution(1_000, 0.045, 2, maturity, date(2026, 6, 15), 0.05, DayCountBasis::Thirty360);
+        assert_approx_equal!(on_coupon_date.dirty_price() * 1.025_f64.powf(0.5), solution.dirty_price());
+    }
+}
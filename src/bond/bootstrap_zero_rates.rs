@@ -0,0 +1,198 @@
+//! **Bootstrapping zero rates from par yields.** A par yield is the coupon rate at which a bond
+//! maturing on a given date is priced at par. Given the par yields for maturities one coupon period
+//! apart, the zero rate (also called the spot rate) for each maturity is found one at a time. Each
+//! par bond's coupons before maturity are discounted using the discount factors already found, and
+//! what's left of the par price must be the present value of the final coupon plus the face value:
+//!
+//! d<sub>k</sub> = (1 - c<sub>k</sub> * (d<sub>1</sub> + d<sub>2</sub> + ... + d<sub>k-1</sub>)) / (1 + c<sub>k</sub>)
+//!
+//! where c<sub>k</sub> is the par yield for maturity k divided by the number of coupons per year.
+//! The zero rate is then the annual rate, compounded once per coupon period, that gives the same
+//! discount factor:
+//!
+//! z<sub>k</sub> = m * (d<sub>k</sub><sup>-1/k</sup> - 1)
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // Par yields for maturities of six months through two years.
+//! let solution = bootstrap_zero_rates_solution(&[0.05, 0.055, 0.06, 0.065], 2);
+//! solution.print_table();
+//! ```
+
+use crate::*;
+
+/// Returns the **zero rate for each maturity** bootstrapped from par yields for maturities one
+/// coupon period apart.
+///
+/// Related functions:
+/// * To see the discount factors as well use [`bootstrap_zero_rates_solution`].
+///
+/// See the [bootstrap_zero_rates](./index.html) module page for the formulas.
+///
+/// # Arguments
+/// * `par_yields` - The annual par yield for each maturity, starting with a maturity of one coupon
+/// period and adding one period each time.
+/// * `coupons_per_year` - The number of coupons paid each year, such as 2 for semiannual bonds. The
+/// zero rates are compounded at the same frequency.
+///
+/// # Panics
+/// The call will fail if `coupons_per_year` is zero, if any par yield is not finite or not greater
+/// than -1.0 per period, or if the par yields imply a discount factor that isn't positive.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // An upward sloping par curve has zero rates that rise faster than the par yields.
+/// let zero_rates = bootstrap_zero_rates(&[0.05, 0.055, 0.06, 0.065], 2);
+/// assert_rounded_6!(0.05, zero_rates[0]);
+/// assert_rounded_6!(0.055069, zero_rates[1]);
+/// assert_rounded_6!(0.060203, zero_rates[2]);
+/// assert_rounded_6!(0.065416, zero_rates[3]);
+/// ```
+pub fn bootstrap_zero_rates(par_yields: &[f64], coupons_per_year: u32) -> Vec<f64> {
+    bootstrap_zero_rates_solution(par_yields, coupons_per_year).zero_rates().to_vec()
+}
+
+/// Returns the **zero rate for each maturity** bootstrapped from par yields along with the implied
+/// discount factors.
+///
+/// Related functions:
+/// * To calculate only the zero rates use [`bootstrap_zero_rates`].
+///
+/// # Arguments
+/// * `par_yields` - The annual par yield for each maturity, starting with a maturity of one coupon
+/// period and adding one period each time.
+/// * `coupons_per_year` - The number of coupons paid each year, such as 2 for semiannual bonds. The
+/// zero rates are compounded at the same frequency.
+///
+/// # Panics
+/// The call will fail if `coupons_per_year` is zero, if any par yield is not finite or not greater
+/// than -1.0 per period, or if the par yields imply a discount factor that isn't positive.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = bootstrap_zero_rates_solution(&[0.05, 0.055, 0.06, 0.065], 2);
+/// assert_rounded_6!(0.975610, solution.discount_factors()[0]);
+/// assert_rounded_6!(0.879204, solution.discount_factors()[3]);
+/// assert_eq!(vec![0.5, 1.0, 1.5, 2.0], solution.times());
+///
+/// // Discounting the two-year par bond's cashflows with the discount factors gives par.
+/// let coupon = 0.065 / 2.0;
+/// let price = solution.discount_factors().iter().sum::<f64>() * coupon + solution.discount_factors()[3];
+/// assert_rounded_6!(1.0, price);
+/// ```
+pub fn bootstrap_zero_rates_solution(par_yields: &[f64], coupons_per_year: u32) -> ZeroCurveSolution {
+    assert!(coupons_per_year > 0, "The number of coupons per year must be greater than zero.");
+    let mut discount_factors: Vec<f64> = Vec::with_capacity(par_yields.len());
+    for par_yield in par_yields.iter() {
+        assert!(par_yield.is_finite(), "The par yields must be finite (not NaN or infinity)");
+        let coupon = par_yield / coupons_per_year as f64;
+        assert!(coupon > -1.0, "The par yields per period must be greater than -1.0 (-100%).");
+        // The earlier coupons are discounted at the zero rates already found.
+        let earlier_coupons_pv = coupon * discount_factors.iter().sum::<f64>();
+        let discount_factor = (1.0 - earlier_coupons_pv) / (1.0 + coupon);
+        assert!(discount_factor > 0.0, "The par yields imply a discount factor that is not positive.");
+        discount_factors.push(discount_factor);
+    }
+    ZeroCurveSolution::new(par_yields.to_vec(), coupons_per_year, discount_factors)
+}
+
+/// A zero rate curve bootstrapped from par yields. Created by calling
+/// [`bootstrap_zero_rates_solution`].
+#[derive(Clone, Debug)]
+pub struct ZeroCurveSolution {
+    par_yields: Vec<f64>,
+    coupons_per_year: u32,
+    zero_rates: Vec<f64>,
+    discount_factors: Vec<f64>,
+}
+
+impl ZeroCurveSolution {
+    pub(crate) fn new(par_yields: Vec<f64>, coupons_per_year: u32, discount_factors: Vec<f64>) -> Self {
+        let zero_rates = discount_factors.iter().enumerate()
+            .map(|(index, discount_factor)| coupons_per_year as f64 * (discount_factor.powf(-1.0 / (index + 1) as f64) - 1.0))
+            .collect::<Vec<f64>>();
+        assert!(zero_rates.iter().all(|zero_rate| zero_rate.is_finite()));
+        Self {
+            par_yields,
+            coupons_per_year,
+            zero_rates,
+            discount_factors,
+        }
+    }
+
+    /// Returns the annual par yield for each maturity.
+    pub fn par_yields(&self) -> &[f64] {
+        &self.par_yields
+    }
+
+    /// Returns the number of coupons per year, which is also the compounding frequency of the zero
+    /// rates.
+    pub fn coupons_per_year(&self) -> u32 {
+        self.coupons_per_year
+    }
+
+    /// Returns the annual zero rate for each maturity, compounded once per coupon period.
+    pub fn zero_rates(&self) -> &[f64] {
+        &self.zero_rates
+    }
+
+    /// Returns the present value of 1.0 received at each maturity.
+    pub fn discount_factors(&self) -> &[f64] {
+        &self.discount_factors
+    }
+
+    /// Returns each maturity in years.
+    pub fn times(&self) -> Vec<f64> {
+        (1..=self.zero_rates.len()).map(|period| period as f64 / self.coupons_per_year as f64).collect()
+    }
+
+    /// Prints a formatted table with the par yield, zero rate, and discount factor for each
+    /// maturity.
+    pub fn print_table(&self) {
+        self.print_table_locale_opt(None, None);
+    }
+
+    /// Prints a formatted table with the par yield, zero rate, and discount factor for each
+    /// maturity using the given locale and precision.
+    pub fn print_table_locale(&self, locale: &num_format::Locale, precision: usize) {
+        self.print_table_locale_opt(Some(locale), Some(precision));
+    }
+
+    fn print_table_locale_opt(&self, locale: Option<&num_format::Locale>, precision: Option<usize>) {
+        let columns = columns_with_strings(&[("period", "i", true), ("years", "f", true), ("par_yield", "r", true), ("zero_rate", "r", true), ("discount_factor", "r", true)]);
+        let data = self.times().iter().enumerate()
+            .map(|(index, time)| vec![(index + 1).to_string(), time.to_string(), self.par_yields[index].to_string(), self.zero_rates[index].to_string(), self.discount_factors[index].to_string()])
+            .collect::<Vec<_>>();
+        print_table_locale_opt(&columns, data, locale, precision);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bootstrap_zero_rates_flat_curve() {
+        // When every par yield is the same the zero rates equal it.
+        let zero_rates = bootstrap_zero_rates(&[0.04; 10], 4);
+        for zero_rate in zero_rates.iter() {
+            assert_approx_equal!(0.04, *zero_rate);
+        }
+    }
+
+    #[test]
+    fn test_bootstrap_zero_rates_reprices_par_bonds() {
+        // Each par bond discounted with the bootstrapped discount factors is worth par.
+        let par_yields = [0.021, 0.024, 0.0275, 0.029, 0.0315, 0.033];
+        let solution = bootstrap_zero_rates_solution(&par_yields, 1);
+        for (index, par_yield) in par_yields.iter().enumerate() {
+            let price = solution.discount_factors()[..=index].iter().sum::<f64>() * par_yield + solution.discount_factors()[index];
+            assert_approx_equal!(1.0, price);
+            // A zero coupon bond discounted at the zero rate matches the discount factor.
+            assert_approx_equal!(solution.discount_factors()[index], bond_price(1.0, 0.0, 1, index as u32 + 1, solution.zero_rates()[index]));
+        }
+    }
+}
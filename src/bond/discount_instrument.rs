@@ -0,0 +1,218 @@
+//! **Discount instrument yields.** Treasury bills, commercial paper, and other money market
+//! instruments pay no coupon. They're bought for less than their face value and the investor
+//! receives the face value at maturity.
+//!
+//! The bank discount yield is how these instruments are usually quoted. It's based on the face
+//! value and a 360-day year so it understates the return on the money actually invested. The bond
+//! equivalent yield restates it as a return on the price over a 365-day year so it can be compared
+//! with the yield on a coupon bond.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // A $100 T-bill bought for $98.50 with 90 days to maturity.
+//! let solution = discount_yield_solution(100, 98.5, 90, DayCountBasis::Actual360);
+//! dbg!(&solution);
+//! ```
+
+use crate::*;
+
+/// Returns the **bank discount yield** of an instrument bought at a discount to its face value.
+///
+/// Related functions:
+/// * To also calculate the bond equivalent yield and the holding period return use
+/// [`discount_yield_solution`].
+///
+/// The formula is:
+///
+/// discount_yield = (face_value - price) / face_value * days_in_year / days
+///
+/// # Arguments
+/// * `face_value` - The amount paid at maturity.
+/// * `price` - The amount paid for the instrument.
+/// * `days` - The number of days until maturity.
+/// * `basis` - The day count convention that sets the length of the year. Discount yields are
+/// normally quoted with [`DayCountBasis::Actual360`].
+///
+/// # Panics
+/// The call will fail if `face_value` or `price` are not greater than zero, if `price` is greater
+/// than `face_value`, or if `days` is zero.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // A $100 T-bill bought for $98.50 with 90 days to maturity.
+/// assert_rounded_6!(0.06, discount_yield(100, 98.5, 90, DayCountBasis::Actual360));
+/// ```
+pub fn discount_yield<F, P>(face_value: F, price: P, days: u32, basis: DayCountBasis) -> f64
+    where
+        F: Into<f64> + Copy,
+        P: Into<f64> + Copy
+{
+    discount_yield_solution(face_value, price, days, basis).discount_yield()
+}
+
+/// Returns the **bond equivalent yield** of an instrument bought at a discount to its face value.
+/// This is the return on the price paid, stated over a 365-day year.
+///
+/// The formula is:
+///
+/// bond_equivalent_yield = (face_value - price) / price * 365 / days
+///
+/// # Arguments
+/// * `face_value` - The amount paid at maturity.
+/// * `price` - The amount paid for the instrument.
+/// * `days` - The number of days until maturity.
+///
+/// # Panics
+/// The call will fail if `face_value` or `price` are not greater than zero, if `price` is greater
+/// than `face_value`, or if `days` is zero.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // The same T-bill yields more than its 6% discount yield as a bond equivalent yield.
+/// assert_rounded_6!(0.061760, bond_equivalent_yield(100, 98.5, 90));
+/// ```
+pub fn bond_equivalent_yield<F, P>(face_value: F, price: P, days: u32) -> f64
+    where
+        F: Into<f64> + Copy,
+        P: Into<f64> + Copy
+{
+    discount_yield_solution(face_value, price, days, DayCountBasis::Actual360).bond_equivalent_yield()
+}
+
+/// Returns the **bank discount yield, bond equivalent yield, and holding period return** of an
+/// instrument bought at a discount to its face value.
+///
+/// Related functions:
+/// * To calculate only the discount yield as an `f64` use [`discount_yield`].
+/// * To calculate only the bond equivalent yield as an `f64` use [`bond_equivalent_yield`].
+///
+/// # Arguments
+/// * `face_value` - The amount paid at maturity.
+/// * `price` - The amount paid for the instrument.
+/// * `days` - The number of days until maturity.
+/// * `basis` - The day count convention that sets the length of the year for the discount yield.
+/// The bond equivalent yield always uses a 365-day year.
+///
+/// # Panics
+/// The call will fail if `face_value` or `price` are not greater than zero, if `price` is greater
+/// than `face_value`, or if `days` is zero.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = discount_yield_solution(100, 98.5, 90, DayCountBasis::Actual360);
+/// assert_rounded_4!(1.5, solution.discount());
+/// assert_rounded_6!(0.06, solution.discount_yield());
+/// assert_rounded_6!(0.061760, solution.bond_equivalent_yield());
+///
+/// // The return over the 90 days on the $98.50 invested.
+/// assert_rounded_6!(0.015228, solution.holding_period_return());
+/// ```
+pub fn discount_yield_solution<F, P>(face_value: F, price: P, days: u32, basis: DayCountBasis) -> DiscountInstrumentSolution
+    where
+        F: Into<f64> + Copy,
+        P: Into<f64> + Copy
+{
+    let face_value = face_value.into();
+    let price = price.into();
+    assert!(face_value.is_finite() && face_value > 0.0, "The face value must be greater than zero.");
+    assert!(price.is_finite() && price > 0.0, "The price must be greater than zero.");
+    assert!(price <= face_value, "The price must not be greater than the face value for an instrument sold at a discount.");
+    assert!(days > 0, "The number of days to maturity must be greater than zero.");
+    DiscountInstrumentSolution::new(face_value, price, days, basis)
+}
+
+/// A record of the yields on an instrument bought at a discount to its face value. Created by
+/// calling [`discount_yield_solution`].
+#[derive(Clone, Debug)]
+pub struct DiscountInstrumentSolution {
+    face_value: f64,
+    price: f64,
+    days: u32,
+    basis: DayCountBasis,
+    discount_yield: f64,
+    bond_equivalent_yield: f64,
+    holding_period_return: f64,
+}
+
+impl DiscountInstrumentSolution {
+    pub(crate) fn new(face_value: f64, price: f64, days: u32, basis: DayCountBasis) -> Self {
+        let discount = face_value - price;
+        let discount_yield = discount / face_value * basis.days_in_year() / days as f64;
+        let holding_period_return = discount / price;
+        let bond_equivalent_yield = holding_period_return * 365.0 / days as f64;
+        Self {
+            face_value,
+            price,
+            days,
+            basis,
+            discount_yield,
+            bond_equivalent_yield,
+            holding_period_return,
+        }
+    }
+
+    /// Returns the amount paid at maturity.
+    pub fn face_value(&self) -> f64 {
+        self.face_value
+    }
+
+    /// Returns the amount paid for the instrument.
+    pub fn price(&self) -> f64 {
+        self.price
+    }
+
+    /// Returns the number of days until maturity.
+    pub fn days(&self) -> u32 {
+        self.days
+    }
+
+    /// Returns the day count convention used for the discount yield.
+    pub fn basis(&self) -> DayCountBasis {
+        self.basis
+    }
+
+    /// Returns the difference between the face value and the price.
+    pub fn discount(&self) -> f64 {
+        self.face_value - self.price
+    }
+
+    /// Returns the bank discount yield, based on the face value.
+    pub fn discount_yield(&self) -> f64 {
+        self.discount_yield
+    }
+
+    /// Returns the bond equivalent yield, based on the price and a 365-day year.
+    pub fn bond_equivalent_yield(&self) -> f64 {
+        self.bond_equivalent_yield
+    }
+
+    /// Returns the return on the price over the whole time until maturity.
+    pub fn holding_period_return(&self) -> f64 {
+        self.holding_period_return
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discount_yield_basis() {
+        // Quoting on a 365-day year scales the discount yield but not the bond equivalent yield.
+        let solution_360 = discount_yield_solution(1_000_000, 987_000, 120, DayCountBasis::Actual360);
+        let solution_365 = discount_yield_solution(1_000_000, 987_000, 120, DayCountBasis::Actual365);
+        assert_approx_equal!(solution_360.discount_yield() * 365.0 / 360.0, solution_365.discount_yield());
+        assert_approx_equal!(solution_360.bond_equivalent_yield(), solution_365.bond_equivalent_yield());
+        assert!(solution_360.bond_equivalent_yield() > solution_360.discount_yield());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_discount_yield_price_above_face_value() {
+        discount_yield(100, 101, 90, DayCountBasis::Actual360);
+    }
+}
@@ -0,0 +1,495 @@
+//! The internal module which supports the solution struct for the bond family of functions (e.g.,
+//! `bond_price`).
+
+// Import needed for the function references in the Rustdoc comments.
+use crate::*;
+use std::fmt;
+use std::ops::Deref;
+
+pub mod bond_price;
+#[doc(inline)]
+pub use bond_price::*;
+
+pub mod bond_price_dated;
+#[doc(inline)]
+pub use bond_price_dated::*;
+
+pub mod bond_portfolio;
+#[doc(inline)]
+pub use bond_portfolio::*;
+
+pub mod bootstrap_zero_rates;
+#[doc(inline)]
+pub use bootstrap_zero_rates::*;
+
+pub mod discount_instrument;
+#[doc(inline)]
+pub use discount_instrument::*;
+
+pub mod net_proceeds_cost_of_debt;
+#[doc(inline)]
+pub use net_proceeds_cost_of_debt::*;
+
+/// The kind of payment a bond cashflow represents.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BondCashflowType {
+    Coupon,
+    Redemption,
+}
+
+impl BondCashflowType {
+    /// Returns true if the variant is BondCashflowType::Coupon.
+    pub fn is_coupon(&self) -> bool {
+        matches!(self, BondCashflowType::Coupon)
+    }
+
+    /// Returns true if the variant is BondCashflowType::Redemption indicating the repayment of the
+    /// face value at maturity.
+    pub fn is_redemption(&self) -> bool {
+        matches!(self, BondCashflowType::Redemption)
+    }
+}
+
+impl fmt::Display for BondCashflowType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BondCashflowType::Coupon => write!(f, "Coupon"),
+            BondCashflowType::Redemption => write!(f, "Redemption"),
+        }
+    }
+}
+
+/// A record of a bond pricing calculation such as a call to [`bond_price_solution`]. The price is
+/// the sum of the discounted cashflows which can be seen in detail by calling
+/// [`series`](./struct.BondSolution.html#method.series).
+#[derive(Clone, Debug)]
+pub struct BondSolution {
+    face_value: f64,
+    coupon_rate: f64,
+    coupons_per_year: u32,
+    yield_per_year: f64,
+    periods: f64,
+    clean_price: f64,
+    accrued_interest: f64,
+    coupon_pv: f64,
+    redemption_pv: f64,
+    series: BondSeries,
+    symbolic_formula: String,
+}
+
+impl BondSolution {
+    pub(crate) fn new(face_value: f64, coupon_rate: f64, coupons_per_year: u32, yield_per_year: f64, series: Vec<BondCashflow>, symbolic_formula: &str) -> Self {
+        let periods = series.iter().map(|entry| entry.time).fold(0.0, f64::max);
+        let coupon_pv = series.iter().filter(|entry| entry.cashflow_type.is_coupon()).map(|entry| entry.present_value).sum::<f64>();
+        let redemption_pv = series.iter().filter(|entry| entry.cashflow_type.is_redemption()).map(|entry| entry.present_value).sum::<f64>();
+        let clean_price = series.iter().map(|entry| entry.present_value).sum::<f64>();
+        assert!(clean_price.is_finite());
+        assert_approx_equal!(clean_price, coupon_pv + redemption_pv);
+        Self {
+            face_value,
+            coupon_rate,
+            coupons_per_year,
+            yield_per_year,
+            periods,
+            clean_price,
+            accrued_interest: 0.0,
+            coupon_pv,
+            redemption_pv,
+            series: BondSeries::new(series),
+            symbolic_formula: symbolic_formula.to_string(),
+        }
+    }
+
+    // Records the interest accrued since the last coupon for a bond settled between coupon dates.
+    // The sum of the discounted cashflows is then the dirty price and the clean price excludes the
+    // accrued interest.
+    pub(crate) fn with_accrued_interest(mut self, accrued_interest: f64) -> Self {
+        assert!(accrued_interest.is_finite());
+        self.clean_price -= accrued_interest - self.accrued_interest;
+        self.accrued_interest = accrued_interest;
+        self
+    }
+
+    /// Returns the face value, also known as the par value, which is repaid at maturity.
+    pub fn face_value(&self) -> f64 {
+        self.face_value
+    }
+
+    /// Returns the annual coupon rate as a fraction of the face value.
+    pub fn coupon_rate(&self) -> f64 {
+        self.coupon_rate
+    }
+
+    /// Returns the number of coupons paid each year.
+    pub fn coupons_per_year(&self) -> u32 {
+        self.coupons_per_year
+    }
+
+    /// Returns the coupon paid each period.
+    pub fn coupon_payment(&self) -> f64 {
+        self.face_value * self.coupon_rate / self.coupons_per_year as f64
+    }
+
+    /// Returns the annual yield used to discount the cashflows. The yield per period is this value
+    /// divided by the number of coupons per year.
+    pub fn yield_per_year(&self) -> f64 {
+        self.yield_per_year
+    }
+
+    /// Returns the number of coupon periods until maturity, which may include a fraction of a
+    /// period for a bond settled between coupon dates.
+    pub fn periods(&self) -> f64 {
+        self.periods
+    }
+
+    /// Returns the price of the bond, the sum of the present values of the coupons and the
+    /// redemption less any accrued interest. On a coupon date there's no accrued interest so this
+    /// is the same as the dirty price.
+    pub fn clean_price(&self) -> f64 {
+        self.clean_price
+    }
+
+    /// Returns the interest accrued since the last coupon date, which the buyer pays the seller in
+    /// addition to the clean price. This is zero for a bond priced on a coupon date.
+    pub fn accrued_interest(&self) -> f64 {
+        self.accrued_interest
+    }
+
+    /// Returns the full price paid for the bond, the clean price plus the accrued interest. This is
+    /// the sum of the present values of the coupons and the redemption.
+    pub fn dirty_price(&self) -> f64 {
+        self.clean_price + self.accrued_interest
+    }
+
+    /// Returns the part of the dirty price that comes from the coupons.
+    pub fn coupon_pv(&self) -> f64 {
+        self.coupon_pv
+    }
+
+    /// Returns the part of the dirty price that comes from the repayment of the face value at
+    /// maturity.
+    pub fn redemption_pv(&self) -> f64 {
+        self.redemption_pv
+    }
+
+    /// Returns the fraction of the dirty price that comes from the coupons. The rest comes from the
+    /// redemption. A higher fraction generally means a shorter duration.
+    pub fn coupon_pv_fraction(&self) -> f64 {
+        let dirty_price = self.dirty_price();
+        if dirty_price == 0.0 {
+            0.0
+        } else {
+            self.coupon_pv / dirty_price
+        }
+    }
+
+    /// Returns the Macaulay duration in years, the average time until the bond's payments are
+    /// received weighted by the present value of each payment. It's zero if the dirty price is
+    /// zero.
+    ///
+    /// # Examples
+    /// ```
+    /// # use finance_solution::*;
+    /// // A 5-year bond paying a 5% coupon twice a year priced at par.
+    /// let solution = bond_price_solution(1_000, 0.05, 2, 10, 0.05);
+    /// assert_rounded_4!(4.4854, solution.macaulay_duration());
+    ///
+    /// // A zero coupon bond's duration is its maturity.
+    /// let solution = bond_price_solution(1_000, 0.0, 2, 10, 0.05);
+    /// assert_rounded_4!(5.0, solution.macaulay_duration());
+    /// ```
+    pub fn macaulay_duration(&self) -> f64 {
+        let dirty_price = self.dirty_price();
+        if dirty_price == 0.0 {
+            return 0.0;
+        }
+        let weighted_time = self.series.iter().map(|entry| entry.time * entry.present_value).sum::<f64>();
+        weighted_time / dirty_price / self.coupons_per_year as f64
+    }
+
+    /// Returns the modified duration in years, the Macaulay duration divided by one plus the yield
+    /// per period. This is approximately the fractional change in price for a change of 1.0 in the
+    /// annual yield, so a modified duration of 4.38 means a rise of 0.01 in the yield lowers the
+    /// price by about 4.38%.
+    ///
+    /// # Examples
+    /// ```
+    /// # use finance_solution::*;
+    /// let solution = bond_price_solution(1_000, 0.05, 2, 10, 0.05);
+    /// assert_rounded_4!(4.3760, solution.modified_duration());
+    /// ```
+    pub fn modified_duration(&self) -> f64 {
+        self.macaulay_duration() / (1.0 + self.yield_per_year / self.coupons_per_year as f64)
+    }
+
+    /// Returns a text version of the formula used to calculate the price with variables such as y
+    /// for the yield per period.
+    pub fn symbolic_formula(&self) -> &str {
+        &self.symbolic_formula
+    }
+
+    /// Returns each coupon and the redemption along with its discount factor and present value.
+    pub fn series(&self) -> BondSeries {
+        self.series.clone()
+    }
+
+    /// Prints a formatted table with the details of each cashflow.
+    pub fn print_table(&self) {
+        self.series.print_table();
+    }
+
+    /// Prints a formatted table with the details of each cashflow using the given locale and
+    /// precision for money amounts.
+    pub fn print_table_locale(&self, locale: &num_format::Locale, precision: usize) {
+        self.series.print_table_locale(locale, precision);
+    }
+
+    /// Returns the value of holding the bond until a horizon, with each payment received before
+    /// then reinvested at the given rate.
+    ///
+    /// For the breakdown between reinvested payments and the value of the bond at the horizon use
+    /// [`BondSolution::horizon_value_solution`].
+    ///
+    /// # Arguments
+    /// * `reinvestment_rate` - The annual rate earned on reinvested payments, compounded once per
+    /// coupon period like the yield.
+    /// * `horizon_periods` - The number of coupon periods the bond is held, which may include a
+    /// fraction of a period.
+    ///
+    /// # Panics
+    /// The call will fail if `horizon_periods` is not positive, if the reinvestment rate per period
+    /// is not greater than -1.0, or if either value is not finite.
+    ///
+    /// # Examples
+    /// ```
+    /// # use finance_solution::*;
+    /// // A 10-year bond paying a 5% coupon twice a year bought to yield 6% and held for five years
+    /// // with the coupons reinvested at 4%.
+    /// let solution = bond_price_solution(1_000, 0.05, 2, 20, 0.06);
+    /// assert_rounded_4!(1_231.0920, solution.horizon_value(0.04, 10.0));
+    /// ```
+    pub fn horizon_value(&self, reinvestment_rate: f64, horizon_periods: f64) -> f64 {
+        self.horizon_value_solution(reinvestment_rate, horizon_periods).horizon_value()
+    }
+
+    /// Returns the value of holding the bond until a horizon along with the part that comes from
+    /// reinvesting the payments received and the part that comes from the value of the bond at the
+    /// horizon.
+    ///
+    /// Payments received by the horizon, including the redemption if the bond has matured, grow at
+    /// the reinvestment rate until the horizon. The payments still to come are discounted back to
+    /// the horizon at the bond's yield, as if the yield hasn't changed.
+    ///
+    /// # Arguments
+    /// * `reinvestment_rate` - The annual rate earned on reinvested payments, compounded once per
+    /// coupon period like the yield.
+    /// * `horizon_periods` - The number of coupon periods the bond is held, which may include a
+    /// fraction of a period.
+    ///
+    /// # Panics
+    /// The call will fail if `horizon_periods` is not positive, if the reinvestment rate per period
+    /// is not greater than -1.0, or if either value is not finite.
+    ///
+    /// # Examples
+    /// ```
+    /// # use finance_solution::*;
+    /// let solution = bond_price_solution(1_000, 0.05, 2, 20, 0.06).horizon_value_solution(0.04, 10.0);
+    /// assert_rounded_4!(250.0, solution.payments_received());
+    /// assert_rounded_4!(23.7430, solution.reinvestment_income());
+    /// assert_rounded_4!(957.3490, solution.price_at_horizon());
+    /// assert_rounded_4!(1_231.0920, solution.horizon_value());
+    ///
+    /// // Reinvesting at less than the 6% yield earns a lower annual return.
+    /// assert_rounded_6!(0.057861, solution.yield_to_horizon());
+    /// ```
+    pub fn horizon_value_solution(&self, reinvestment_rate: f64, horizon_periods: f64) -> HorizonValueSolution {
+        assert!(reinvestment_rate.is_finite(), "The reinvestment rate must be finite (not NaN or infinity)");
+        assert!(horizon_periods.is_finite(), "The horizon must be finite (not NaN or infinity)");
+        assert!(horizon_periods > 0.0, "The horizon must be greater than zero.");
+        let periodic_reinvestment_rate = reinvestment_rate / self.coupons_per_year as f64;
+        assert!(periodic_reinvestment_rate > -1.0, "The reinvestment rate per period must be greater than -1.0 (-100%).");
+        let periodic_yield = self.yield_per_year / self.coupons_per_year as f64;
+        let (received, remaining): (Vec<&BondCashflow>, Vec<&BondCashflow>) = self.series.iter().partition(|entry| entry.time <= horizon_periods);
+        let payments_received = received.iter().map(|entry| entry.amount).sum::<f64>();
+        let reinvested_value = received.iter()
+            .map(|entry| entry.amount * (1.0 + periodic_reinvestment_rate).powf(horizon_periods - entry.time))
+            .sum::<f64>();
+        let price_at_horizon = remaining.iter()
+            .map(|entry| entry.amount / (1.0 + periodic_yield).powf(entry.time - horizon_periods))
+            .sum::<f64>();
+        HorizonValueSolution::new(reinvestment_rate, horizon_periods, self.coupons_per_year, self.dirty_price(), payments_received, reinvested_value, price_at_horizon)
+    }
+}
+
+/// The value of holding a bond until a horizon with the payments received along the way
+/// reinvested. Created by calling [`BondSolution::horizon_value_solution`].
+#[derive(Clone, Debug)]
+pub struct HorizonValueSolution {
+    reinvestment_rate: f64,
+    horizon_periods: f64,
+    coupons_per_year: u32,
+    purchase_price: f64,
+    payments_received: f64,
+    reinvested_value: f64,
+    price_at_horizon: f64,
+}
+
+impl HorizonValueSolution {
+    pub(crate) fn new(reinvestment_rate: f64, horizon_periods: f64, coupons_per_year: u32, purchase_price: f64, payments_received: f64, reinvested_value: f64, price_at_horizon: f64) -> Self {
+        assert!(reinvested_value.is_finite());
+        assert!(price_at_horizon.is_finite());
+        Self {
+            reinvestment_rate,
+            horizon_periods,
+            coupons_per_year,
+            purchase_price,
+            payments_received,
+            reinvested_value,
+            price_at_horizon,
+        }
+    }
+
+    /// Returns the annual rate earned on reinvested payments.
+    pub fn reinvestment_rate(&self) -> f64 {
+        self.reinvestment_rate
+    }
+
+    /// Returns the number of coupon periods the bond is held.
+    pub fn horizon_periods(&self) -> f64 {
+        self.horizon_periods
+    }
+
+    /// Returns the dirty price paid for the bond.
+    pub fn purchase_price(&self) -> f64 {
+        self.purchase_price
+    }
+
+    /// Returns the sum of the coupons and any redemption received by the horizon, before
+    /// reinvestment.
+    pub fn payments_received(&self) -> f64 {
+        self.payments_received
+    }
+
+    /// Returns the interest earned by reinvesting the payments received, sometimes called interest
+    /// on interest.
+    pub fn reinvestment_income(&self) -> f64 {
+        self.reinvested_value - self.payments_received
+    }
+
+    /// Returns the payments received along with the interest earned by reinvesting them, valued at
+    /// the horizon.
+    pub fn reinvested_value(&self) -> f64 {
+        self.reinvested_value
+    }
+
+    /// Returns the value at the horizon of the payments still to come, discounted at the bond's
+    /// yield. This is zero if the bond matures by the horizon.
+    pub fn price_at_horizon(&self) -> f64 {
+        self.price_at_horizon
+    }
+
+    /// Returns the total value at the horizon, the reinvested payments plus the value of the bond.
+    pub fn horizon_value(&self) -> f64 {
+        self.reinvested_value + self.price_at_horizon
+    }
+
+    /// Returns the total return over the holding period as a fraction of the purchase price.
+    pub fn total_return(&self) -> f64 {
+        self.horizon_value() / self.purchase_price - 1.0
+    }
+
+    /// Returns the annual return over the holding period, compounded once per coupon period so it
+    /// can be compared with the bond's yield.
+    pub fn yield_to_horizon(&self) -> f64 {
+        ((self.horizon_value() / self.purchase_price).powf(1.0 / self.horizon_periods) - 1.0) * self.coupons_per_year as f64
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BondSeries(Vec<BondCashflow>);
+
+impl BondSeries {
+    pub(crate) fn new(series: Vec<BondCashflow>) -> Self {
+        Self(series)
+    }
+
+    pub fn filter<P>(&self, predicate: P) -> Self
+        where P: Fn(&&BondCashflow) -> bool
+    {
+        Self(self.iter().filter(|x| predicate(x)).cloned().collect())
+    }
+
+    pub fn print_table(&self) {
+        self.print_table_locale_opt(None, None);
+    }
+
+    pub fn print_table_locale(&self, locale: &num_format::Locale, precision: usize) {
+        self.print_table_locale_opt(Some(locale), Some(precision));
+    }
+
+    fn print_table_locale_opt(&self, locale: Option<&num_format::Locale>, precision: Option<usize>) {
+        let time_type = if self.iter().all(|entry| entry.time.fract() == 0.0) { "i" } else { "f" };
+        let columns = columns_with_strings(&[("period", time_type, true), ("type", "s", true), ("amount", "f", true), ("discount_factor", "r", true), ("present_value", "f", true)]);
+        let data = self.iter()
+            .map(|entry| vec![entry.time.to_string(), entry.cashflow_type.to_string(), entry.amount.to_string(), entry.discount_factor.to_string(), entry.present_value.to_string()])
+            .collect::<Vec<_>>();
+        print_table_locale_opt(&columns, data, locale, precision);
+    }
+}
+
+impl Deref for BondSeries {
+    type Target = Vec<BondCashflow>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// One coupon or redemption payment within a [`BondSeries`].
+#[derive(Clone, Debug)]
+pub struct BondCashflow {
+    time: f64,
+    cashflow_type: BondCashflowType,
+    amount: f64,
+    discount_factor: f64,
+    present_value: f64,
+}
+
+impl BondCashflow {
+    pub(crate) fn new(time: f64, cashflow_type: BondCashflowType, amount: f64, discount_factor: f64) -> Self {
+        let present_value = amount * discount_factor;
+        assert!(present_value.is_finite());
+        Self {
+            time,
+            cashflow_type,
+            amount,
+            discount_factor,
+            present_value,
+        }
+    }
+
+    /// Returns the time of the payment measured in coupon periods.
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    /// Returns whether the payment is a coupon or the redemption of the face value.
+    pub fn cashflow_type(&self) -> &BondCashflowType {
+        &self.cashflow_type
+    }
+
+    /// Returns the amount of the payment.
+    pub fn amount(&self) -> f64 {
+        self.amount
+    }
+
+    /// Returns the factor by which the payment is multiplied to find its present value.
+    pub fn discount_factor(&self) -> f64 {
+        self.discount_factor
+    }
+
+    /// Returns the present value of the payment.
+    pub fn present_value(&self) -> f64 {
+        self.present_value
+    }
+}
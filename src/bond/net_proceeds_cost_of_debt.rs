@@ -0,0 +1,291 @@
+//! **Cost of debt net of issuance costs.** When a company issues bonds it pays underwriting, legal,
+//! and other fees, so the cash it actually receives is less than the face value. The coupons and the
+//! redemption are still based on the face value, which makes the effective cost of the debt higher
+//! than the coupon rate.
+//!
+//! The effective cost per period is the rate r that solves:
+//!
+//! face_value - issuance_cost = sum( coupon_payment / (1 + r)<sup>k</sup> ) + redemption / (1 + r)<sup>n</sup>
+//!
+//! where k runs from 1 to n. This is the issuer's view of the same calculation as a yield to
+//! maturity, with the net proceeds in place of the price.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // A $1,000 bond paying a $30 coupon every six months for ten years, issued with $20 of costs.
+//! let solution = net_proceeds_cost_of_debt_solution(1_000, 20, 30, 20, 1_000);
+//! dbg!(&solution);
+//! ```
+
+use crate::*;
+
+/// Returns the **effective cost of debt per period** when the issuer receives the face value less
+/// the issuance costs but pays coupons and the redemption based on the face value.
+///
+/// Related functions:
+/// * To see the net proceeds and the work done by the solver use
+/// [`net_proceeds_cost_of_debt_solution`].
+///
+/// See the [net_proceeds_cost_of_debt](./index.html) module page for the equation that's solved.
+///
+/// # Arguments
+/// * `face_value` - The face value of the debt issued.
+/// * `issuance_cost` - The fees paid to issue the debt, which are deducted from the proceeds.
+/// * `coupon_payment` - The interest paid each period, based on the face value.
+/// * `periods` - The number of coupon periods until the debt is redeemed.
+/// * `redemption` - The amount repaid at the end of the last period, usually the face value.
+///
+/// # Panics
+/// The call will fail if `face_value` or `redemption` is not positive, if `issuance_cost` is
+/// negative or not less than `face_value`, if `coupon_payment` is negative, if `periods` is zero,
+/// or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // A 6% semiannual bond issued with 2% of its face value going to fees costs the issuer about
+/// // 3.14% per half year rather than 3%.
+/// let rate = net_proceeds_cost_of_debt(1_000, 20, 30, 20, 1_000);
+/// assert_rounded_6!(0.031361, rate);
+///
+/// // With no issuance costs the effective cost is the coupon rate.
+/// assert_rounded_6!(0.03, net_proceeds_cost_of_debt(1_000, 0, 30, 20, 1_000));
+/// ```
+pub fn net_proceeds_cost_of_debt<F, I, C, R>(face_value: F, issuance_cost: I, coupon_payment: C, periods: u32, redemption: R) -> f64
+    where
+        F: Into<f64> + Copy,
+        I: Into<f64> + Copy,
+        C: Into<f64> + Copy,
+        R: Into<f64> + Copy
+{
+    net_proceeds_cost_of_debt_solution(face_value, issuance_cost, coupon_payment, periods, redemption).effective_rate()
+}
+
+/// Returns the **effective cost of debt per period** along with the net proceeds, the inputs, and
+/// the work done by the solver.
+///
+/// Related functions:
+/// * To calculate only the effective rate as an `f64` use [`net_proceeds_cost_of_debt`].
+/// * To control the precision of the solver use
+/// [`net_proceeds_cost_of_debt_solution_with_config`].
+///
+/// # Arguments
+/// * `face_value` - The face value of the debt issued.
+/// * `issuance_cost` - The fees paid to issue the debt, which are deducted from the proceeds.
+/// * `coupon_payment` - The interest paid each period, based on the face value.
+/// * `periods` - The number of coupon periods until the debt is redeemed.
+/// * `redemption` - The amount repaid at the end of the last period, usually the face value.
+///
+/// # Panics
+/// The call will fail if `face_value` or `redemption` is not positive, if `issuance_cost` is
+/// negative or not less than `face_value`, if `coupon_payment` is negative, if `periods` is zero,
+/// or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // $10 million of five-year notes paying 5% annually with $150,000 of issuance costs.
+/// let solution = net_proceeds_cost_of_debt_solution(10_000_000, 150_000, 500_000, 5, 10_000_000);
+/// assert_rounded_4!(9_850_000.0, solution.net_proceeds());
+/// assert_rounded_4!(0.05, solution.coupon_rate());
+/// assert_rounded_6!(0.053498, solution.effective_rate());
+/// assert!(solution.converged());
+/// ```
+pub fn net_proceeds_cost_of_debt_solution<F, I, C, R>(face_value: F, issuance_cost: I, coupon_payment: C, periods: u32, redemption: R) -> NetProceedsSolution
+    where
+        F: Into<f64> + Copy,
+        I: Into<f64> + Copy,
+        C: Into<f64> + Copy,
+        R: Into<f64> + Copy
+{
+    net_proceeds_cost_of_debt_solution_with_config(face_value, issuance_cost, coupon_payment, periods, redemption, SolverConfig::default())
+}
+
+/// Returns the **effective cost of debt per period** using the given tolerance and maximum number
+/// of iterations.
+///
+/// Related functions:
+/// * To solve to the full precision of an `f64` use [`net_proceeds_cost_of_debt_solution`].
+///
+/// # Arguments
+/// * `face_value` - The face value of the debt issued.
+/// * `issuance_cost` - The fees paid to issue the debt, which are deducted from the proceeds.
+/// * `coupon_payment` - The interest paid each period, based on the face value.
+/// * `periods` - The number of coupon periods until the debt is redeemed.
+/// * `redemption` - The amount repaid at the end of the last period, usually the face value.
+/// * `config` - The tolerance and maximum number of iterations for the solver.
+///
+/// # Panics
+/// The call will fail if `face_value` or `redemption` is not positive, if `issuance_cost` is
+/// negative or not less than `face_value`, if `coupon_payment` is negative, if `periods` is zero,
+/// or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let config = SolverConfig::new(0.000_001, 100);
+/// let solution = net_proceeds_cost_of_debt_solution_with_config(1_000, 20, 30, 20, 1_000, config);
+/// assert!(solution.converged());
+/// assert_rounded_4!(0.0314, solution.effective_rate());
+/// ```
+pub fn net_proceeds_cost_of_debt_solution_with_config<F, I, C, R>(face_value: F, issuance_cost: I, coupon_payment: C, periods: u32, redemption: R, config: SolverConfig) -> NetProceedsSolution
+    where
+        F: Into<f64> + Copy,
+        I: Into<f64> + Copy,
+        C: Into<f64> + Copy,
+        R: Into<f64> + Copy
+{
+    let face_value = face_value.into();
+    let issuance_cost = issuance_cost.into();
+    let coupon_payment = coupon_payment.into();
+    let redemption = redemption.into();
+    assert!(face_value.is_finite(), "The face value must be finite (not NaN or infinity)");
+    assert!(issuance_cost.is_finite(), "The issuance cost must be finite (not NaN or infinity)");
+    assert!(coupon_payment.is_finite(), "The coupon payment must be finite (not NaN or infinity)");
+    assert!(redemption.is_finite(), "The redemption must be finite (not NaN or infinity)");
+    assert!(face_value > 0.0, "The face value must be greater than zero.");
+    assert!(issuance_cost >= 0.0 && issuance_cost < face_value, "The issuance cost must be zero or positive and less than the face value.");
+    assert!(coupon_payment >= 0.0, "The coupon payment must be zero or positive.");
+    assert!(redemption > 0.0, "The redemption must be greater than zero.");
+    assert!(periods > 0, "The number of periods must be greater than zero.");
+
+    let net_proceeds = face_value - issuance_cost;
+    let value = |rate: f64| debt_value(rate, coupon_payment, periods, redemption);
+
+    // The value of the payments falls as the rate rises, from far above the net proceeds near -100%
+    // toward zero, so there's always a rate to find.
+    let outcome = solver::bracket_and_bisect(&config, |rate| value(rate) > net_proceeds)
+        .expect("Unable to find the effective rate.");
+
+    NetProceedsSolution::new(face_value, issuance_cost, coupon_payment, periods, redemption, outcome)
+}
+
+fn debt_value(rate: f64, coupon_payment: f64, periods: u32, redemption: f64) -> f64 {
+    (1..=periods).map(|period| coupon_payment / (1.0 + rate).powf(period as f64)).sum::<f64>()
+        + redemption / (1.0 + rate).powf(periods as f64)
+}
+
+/// A record of the effective cost of debt net of issuance costs. Created by calling
+/// [`net_proceeds_cost_of_debt_solution`].
+#[derive(Clone, Debug)]
+pub struct NetProceedsSolution {
+    face_value: f64,
+    issuance_cost: f64,
+    coupon_payment: f64,
+    periods: u32,
+    redemption: f64,
+    net_proceeds: f64,
+    effective_rate: f64,
+    iterations: u32,
+    converged: bool,
+    formula: String,
+    symbolic_formula: String,
+}
+
+impl NetProceedsSolution {
+    pub(crate) fn new(face_value: f64, issuance_cost: f64, coupon_payment: f64, periods: u32, redemption: f64, outcome: solver::SolverOutcome) -> Self {
+        let solver::SolverOutcome { value: effective_rate, iterations, converged } = outcome;
+        assert!(effective_rate.is_finite());
+        let net_proceeds = face_value - issuance_cost;
+        let formula = format!("{:.4} - {:.4} = sum({:.4} / {:.6}^k) + {:.4} / {:.6}^{}",
+                              face_value, issuance_cost, coupon_payment, 1.0 + effective_rate, redemption, 1.0 + effective_rate, periods);
+        let symbolic_formula = "face_value - issuance_cost = sum(coupon / (1 + r)^k) + redemption / (1 + r)^n".to_string();
+        Self {
+            face_value,
+            issuance_cost,
+            coupon_payment,
+            periods,
+            redemption,
+            net_proceeds,
+            effective_rate,
+            iterations,
+            converged,
+            formula,
+            symbolic_formula,
+        }
+    }
+
+    /// Returns the face value of the debt issued.
+    pub fn face_value(&self) -> f64 {
+        self.face_value
+    }
+
+    /// Returns the fees paid to issue the debt.
+    pub fn issuance_cost(&self) -> f64 {
+        self.issuance_cost
+    }
+
+    /// Returns the interest paid each period.
+    pub fn coupon_payment(&self) -> f64 {
+        self.coupon_payment
+    }
+
+    /// Returns the number of coupon periods until the debt is redeemed.
+    pub fn periods(&self) -> u32 {
+        self.periods
+    }
+
+    /// Returns the amount repaid at the end of the last period.
+    pub fn redemption(&self) -> f64 {
+        self.redemption
+    }
+
+    /// Returns the cash received by the issuer, which is the face value less the issuance costs.
+    pub fn net_proceeds(&self) -> f64 {
+        self.net_proceeds
+    }
+
+    /// Returns the coupon payment as a fraction of the face value.
+    pub fn coupon_rate(&self) -> f64 {
+        self.coupon_payment / self.face_value
+    }
+
+    /// Returns the effective cost of the debt per period after the issuance costs.
+    pub fn effective_rate(&self) -> f64 {
+        self.effective_rate
+    }
+
+    /// Returns the number of iterations the solver used to find the effective rate.
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    /// Returns true if the effective rate was found to within the solver's tolerance before it ran
+    /// out of iterations.
+    pub fn converged(&self) -> bool {
+        self.converged
+    }
+
+    /// Returns a text version of the equation that was solved with the actual values.
+    pub fn formula(&self) -> &str {
+        &self.formula
+    }
+
+    /// Returns a text version of the equation that was solved using variables such as r for the
+    /// effective rate.
+    pub fn symbolic_formula(&self) -> &str {
+        &self.symbolic_formula
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_net_proceeds_cost_of_debt_exceeds_coupon_rate() {
+        let solution = net_proceeds_cost_of_debt_solution(1_000, 25, 40, 10, 1_000);
+        assert!(solution.effective_rate() > solution.coupon_rate());
+        // Pricing the bond at the effective rate gives the net proceeds.
+        let price = bond_price(1_000, 0.04, 1, 10, solution.effective_rate());
+        assert_approx_equal!(solution.net_proceeds(), price);
+    }
+
+    #[test]
+    fn test_net_proceeds_cost_of_debt_zero_coupon() {
+        // With no coupons the effective rate is the growth from the net proceeds to the redemption.
+        let rate = net_proceeds_cost_of_debt(1_000, 30, 0, 10, 1_000);
+        assert_approx_equal!((1_000.0_f64 / 970.0).powf(0.1) - 1.0, rate);
+    }
+}
@@ -0,0 +1,280 @@
+//! **Break-even analysis.** How many units must be sold to cover the fixed costs of a project? Each
+//! unit sold contributes its price less its variable cost, called the contribution margin, toward
+//! the fixed costs and then toward profit.
+//!
+//! break_even_units = (fixed_cost + target_profit) / (price_per_unit - variable_cost_per_unit)
+//!
+//! The target profit is zero for the break-even point itself.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // A product that sells for $25, costs $15 per unit to make, and has $50,000 of fixed costs.
+//! let solution = break_even_units_solution(50_000, 25, 15);
+//! dbg!(&solution);
+//! ```
+
+/// Returns the **number of units that must be sold to cover the fixed costs**.
+///
+/// Related functions:
+/// * To see the contribution margin and the break-even revenue use [`break_even_units_solution`].
+/// * To include a profit goal use [`break_even_units_with_target_profit`].
+///
+/// The formula is:
+///
+/// break_even_units = fixed_cost / (price_per_unit - variable_cost_per_unit)
+///
+/// The result is not rounded so it may include a fraction of a unit.
+///
+/// # Arguments
+/// * `fixed_cost` - The costs that don't depend on the number of units sold, such as rent.
+/// * `price_per_unit` - The selling price of each unit.
+/// * `variable_cost_per_unit` - The cost of making or buying each unit.
+///
+/// # Panics
+/// The call will fail if `fixed_cost` is negative, if the price is not greater than the variable
+/// cost so that each unit sold loses money or breaks even, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // Each unit contributes $10 toward $50,000 of fixed costs.
+/// assert_rounded_4!(5_000.0, break_even_units(50_000, 25, 15));
+/// ```
+pub fn break_even_units<F, P, V>(fixed_cost: F, price_per_unit: P, variable_cost_per_unit: V) -> f64
+    where
+        F: Into<f64> + Copy,
+        P: Into<f64> + Copy,
+        V: Into<f64> + Copy
+{
+    break_even_units_solution(fixed_cost, price_per_unit, variable_cost_per_unit).units()
+}
+
+/// Returns the **number of units that must be sold to cover the fixed costs** along with the
+/// contribution margin, the break-even revenue, and the formula.
+///
+/// Related functions:
+/// * To calculate only the number of units as an `f64` use [`break_even_units`].
+///
+/// # Arguments
+/// * `fixed_cost` - The costs that don't depend on the number of units sold, such as rent.
+/// * `price_per_unit` - The selling price of each unit.
+/// * `variable_cost_per_unit` - The cost of making or buying each unit.
+///
+/// # Panics
+/// The call will fail if `fixed_cost` is negative, if the price is not greater than the variable
+/// cost so that each unit sold loses money or breaks even, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = break_even_units_solution(50_000, 25, 15);
+/// assert_rounded_4!(10.0, solution.contribution_margin());
+/// assert_rounded_4!(0.4, solution.contribution_margin_ratio());
+/// assert_rounded_4!(5_000.0, solution.units());
+/// assert_rounded_4!(125_000.0, solution.revenue());
+/// assert_eq!("5000.0000 = (50000.0000 + 0.0000) / (25.0000 - 15.0000)", solution.formula());
+/// ```
+pub fn break_even_units_solution<F, P, V>(fixed_cost: F, price_per_unit: P, variable_cost_per_unit: V) -> BreakEvenSolution
+    where
+        F: Into<f64> + Copy,
+        P: Into<f64> + Copy,
+        V: Into<f64> + Copy
+{
+    break_even_units_with_target_profit_solution(fixed_cost, price_per_unit, variable_cost_per_unit, 0.0)
+}
+
+/// Returns the **number of units that must be sold to cover the fixed costs and earn a target
+/// profit**.
+///
+/// Related functions:
+/// * To see the contribution margin and the required revenue use
+/// [`break_even_units_with_target_profit_solution`].
+///
+/// The formula is:
+///
+/// units = (fixed_cost + target_profit) / (price_per_unit - variable_cost_per_unit)
+///
+/// # Arguments
+/// * `fixed_cost` - The costs that don't depend on the number of units sold, such as rent.
+/// * `price_per_unit` - The selling price of each unit.
+/// * `variable_cost_per_unit` - The cost of making or buying each unit.
+/// * `target_profit` - The profit to be earned after covering the fixed costs.
+///
+/// # Panics
+/// The call will fail if `fixed_cost` or `target_profit` is negative, if the price is not greater
+/// than the variable cost, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // Earning $20,000 of profit takes another 2,000 units beyond the break-even point.
+/// assert_rounded_4!(7_000.0, break_even_units_with_target_profit(50_000, 25, 15, 20_000));
+/// ```
+pub fn break_even_units_with_target_profit<F, P, V, T>(fixed_cost: F, price_per_unit: P, variable_cost_per_unit: V, target_profit: T) -> f64
+    where
+        F: Into<f64> + Copy,
+        P: Into<f64> + Copy,
+        V: Into<f64> + Copy,
+        T: Into<f64> + Copy
+{
+    break_even_units_with_target_profit_solution(fixed_cost, price_per_unit, variable_cost_per_unit, target_profit).units()
+}
+
+/// Returns the **number of units that must be sold to cover the fixed costs and earn a target
+/// profit** along with the contribution margin, the required revenue, and the formula.
+///
+/// Related functions:
+/// * To calculate only the number of units as an `f64` use
+/// [`break_even_units_with_target_profit`].
+///
+/// # Arguments
+/// * `fixed_cost` - The costs that don't depend on the number of units sold, such as rent.
+/// * `price_per_unit` - The selling price of each unit.
+/// * `variable_cost_per_unit` - The cost of making or buying each unit.
+/// * `target_profit` - The profit to be earned after covering the fixed costs.
+///
+/// # Panics
+/// The call will fail if `fixed_cost` or `target_profit` is negative, if the price is not greater
+/// than the variable cost, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = break_even_units_with_target_profit_solution(50_000, 25, 15, 20_000);
+/// assert_rounded_4!(7_000.0, solution.units());
+/// assert_rounded_4!(175_000.0, solution.revenue());
+/// ```
+pub fn break_even_units_with_target_profit_solution<F, P, V, T>(fixed_cost: F, price_per_unit: P, variable_cost_per_unit: V, target_profit: T) -> BreakEvenSolution
+    where
+        F: Into<f64> + Copy,
+        P: Into<f64> + Copy,
+        V: Into<f64> + Copy,
+        T: Into<f64> + Copy
+{
+    let fixed_cost = fixed_cost.into();
+    let price_per_unit = price_per_unit.into();
+    let variable_cost_per_unit = variable_cost_per_unit.into();
+    let target_profit = target_profit.into();
+    assert!(fixed_cost.is_finite(), "The fixed cost must be finite (not NaN or infinity)");
+    assert!(price_per_unit.is_finite(), "The price per unit must be finite (not NaN or infinity)");
+    assert!(variable_cost_per_unit.is_finite(), "The variable cost per unit must be finite (not NaN or infinity)");
+    assert!(target_profit.is_finite(), "The target profit must be finite (not NaN or infinity)");
+    assert!(fixed_cost >= 0.0, "The fixed cost must be zero or positive.");
+    assert!(target_profit >= 0.0, "The target profit must be zero or positive.");
+    assert!(price_per_unit > variable_cost_per_unit, "The price per unit must be greater than the variable cost per unit or no number of units will break even.");
+    BreakEvenSolution::new(fixed_cost, price_per_unit, variable_cost_per_unit, target_profit)
+}
+
+/// A record of a break-even calculation. Created by calling [`break_even_units_solution`] or
+/// [`break_even_units_with_target_profit_solution`].
+#[derive(Clone, Debug)]
+pub struct BreakEvenSolution {
+    fixed_cost: f64,
+    price_per_unit: f64,
+    variable_cost_per_unit: f64,
+    target_profit: f64,
+    contribution_margin: f64,
+    units: f64,
+    formula: String,
+    symbolic_formula: String,
+}
+
+impl BreakEvenSolution {
+    pub(crate) fn new(fixed_cost: f64, price_per_unit: f64, variable_cost_per_unit: f64, target_profit: f64) -> Self {
+        let contribution_margin = price_per_unit - variable_cost_per_unit;
+        let units = (fixed_cost + target_profit) / contribution_margin;
+        assert!(units.is_finite());
+        let formula = format!("{:.4} = ({:.4} + {:.4}) / ({:.4} - {:.4})", units, fixed_cost, target_profit, price_per_unit, variable_cost_per_unit);
+        let symbolic_formula = "units = (fixed_cost + target_profit) / (price - variable_cost)".to_string();
+        Self {
+            fixed_cost,
+            price_per_unit,
+            variable_cost_per_unit,
+            target_profit,
+            contribution_margin,
+            units,
+            formula,
+            symbolic_formula,
+        }
+    }
+
+    /// Returns the costs that don't depend on the number of units sold.
+    pub fn fixed_cost(&self) -> f64 {
+        self.fixed_cost
+    }
+
+    /// Returns the selling price of each unit.
+    pub fn price_per_unit(&self) -> f64 {
+        self.price_per_unit
+    }
+
+    /// Returns the cost of making or buying each unit.
+    pub fn variable_cost_per_unit(&self) -> f64 {
+        self.variable_cost_per_unit
+    }
+
+    /// Returns the profit to be earned after covering the fixed costs, which is zero for the
+    /// break-even point itself.
+    pub fn target_profit(&self) -> f64 {
+        self.target_profit
+    }
+
+    /// Returns the amount each unit sold contributes toward the fixed costs and profit, which is
+    /// the price less the variable cost.
+    pub fn contribution_margin(&self) -> f64 {
+        self.contribution_margin
+    }
+
+    /// Returns the contribution margin as a fraction of the price, or zero if the price is zero.
+    pub fn contribution_margin_ratio(&self) -> f64 {
+        if self.price_per_unit == 0.0 {
+            0.0
+        } else {
+            self.contribution_margin / self.price_per_unit
+        }
+    }
+
+    /// Returns the number of units that must be sold, which may include a fraction of a unit.
+    pub fn units(&self) -> f64 {
+        self.units
+    }
+
+    /// Returns the revenue from selling the required number of units.
+    pub fn revenue(&self) -> f64 {
+        self.units * self.price_per_unit
+    }
+
+    /// Returns a text version of the formula used to calculate the number of units with the actual
+    /// values.
+    pub fn formula(&self) -> &str {
+        &self.formula
+    }
+
+    /// Returns a text version of the formula used to calculate the number of units using
+    /// variables such as price for the price per unit.
+    pub fn symbolic_formula(&self) -> &str {
+        &self.symbolic_formula
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn test_break_even_units_profit() {
+        // Selling the break-even number of units covers the costs and the target profit exactly.
+        let solution = break_even_units_with_target_profit_solution(12_500.0, 8.75, 3.4, 4_000.0);
+        let profit = solution.units() * (solution.price_per_unit() - solution.variable_cost_per_unit()) - solution.fixed_cost();
+        assert_approx_equal!(4_000.0, profit);
+        assert_approx_equal!(break_even_units(16_500.0, 8.75, 3.4), solution.units());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_break_even_units_negative_margin() {
+        break_even_units(50_000, 15, 25);
+    }
+}
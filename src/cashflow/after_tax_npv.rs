@@ -0,0 +1,167 @@
+//! **After-tax net present value.** The standard capital budgeting calculation: operating cashflows
+//! are reduced by taxes, depreciation adds back a tax shield, and the resulting after-tax
+//! cashflows are discounted along with the initial investment.
+//!
+//! This combines [`net_present_value_schedule`](./fn.net_present_value_schedule.html) with the tax
+//! shield from [`depreciation_tax_shield_pv`](./fn.depreciation_tax_shield_pv.html).
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // A $100,000 machine that brings in $30,000 a year before taxes, depreciated with MACRS.
+//! let pre_tax_flows = [-100_000, 30_000, 30_000, 30_000, 30_000, 30_000, 30_000];
+//! let depreciation = [20_000, 32_000, 19_200, 11_520, 11_520, 5_760];
+//! let solution = after_tax_npv_solution(&pre_tax_flows, &depreciation, 0.25, 0.1);
+//! solution.print_table();
+//! ```
+//! Outputs to terminal:
+//! ```text
+//! period      rate      cashflow  discount_factor  present_value
+//! ------  --------  ------------  ---------------  -------------
+//!      0  0.100000  -100_000.0000         1.000000  -100_000.0000
+//!      1  0.100000    27_500.0000         0.909091    25_000.0000
+//!      2  0.100000    30_500.0000         0.826446    25_206.6116
+//!      3  0.100000    27_300.0000         0.751315    20_510.8941
+//!      4  0.100000    25_380.0000         0.683013    17_334.8815
+//!      5  0.100000    25_380.0000         0.620921    15_758.9832
+//!      6  0.100000    23_940.0000         0.564474    13_513.5058
+//! ```
+
+use crate::*;
+
+/// Returns the **after-tax net present value** of a project.
+///
+/// Related functions:
+/// * To see the after-tax cashflow for each period use [`after_tax_npv_solution`].
+/// * For the net present value before taxes use [`net_present_value_schedule`].
+///
+/// The first entry in `pre_tax_flows` is the initial investment at period 0, which is not taxed,
+/// as with [`net_present_value_schedule`]. For each later period the after-tax cashflow is:
+///
+/// after_tax_flow = pre_tax_flow * (1 - tax_rate) + depreciation * tax_rate
+///
+/// # Arguments
+/// * `pre_tax_flows` - The initial investment followed by the operating cashflow before taxes for
+/// each period.
+/// * `depreciation` - The depreciation for each period starting with period 1, so there's one
+/// fewer entry than in `pre_tax_flows`.
+/// * `tax_rate` - The tax rate expressed as a floating point number. For instance 0.25 would mean
+/// 25%.
+/// * `discount_rate` - The periodic rate used to discount the cashflows.
+///
+/// # Panics
+/// The call will fail if `pre_tax_flows` is empty, if `depreciation` doesn't have exactly one
+/// fewer entry than `pre_tax_flows`, if `tax_rate` is outside the range 0.0 to 1.0, if
+/// `discount_rate` is less than or equal to -1.0, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let pre_tax_flows = [-100_000, 30_000, 30_000, 30_000, 30_000, 30_000, 30_000];
+/// let depreciation = [20_000, 32_000, 19_200, 11_520, 11_520, 5_760];
+/// let npv = after_tax_npv(&pre_tax_flows, &depreciation, 0.25, 0.1);
+/// assert_rounded_4!(17_324.8762, npv);
+///
+/// // Taxes take a large part of the value that the project would have before taxes.
+/// let rates = [0.1; 6];
+/// assert_rounded_4!(30_657.8210, net_present_value_schedule(&rates, &pre_tax_flows));
+/// ```
+pub fn after_tax_npv<C, D>(pre_tax_flows: &[C], depreciation: &[D], tax_rate: f64, discount_rate: f64) -> f64
+    where
+        C: Into<f64> + Copy,
+        D: Into<f64> + Copy
+{
+    after_tax_npv_solution(pre_tax_flows, depreciation, tax_rate, discount_rate).present_value()
+}
+
+/// Returns the **after-tax net present value** of a project along with the after-tax cashflow
+/// for each period and its present value.
+///
+/// In the solution's series the `cashflow` is the after-tax cashflow for the period and the
+/// solution's `present_value` is the after-tax net present value.
+///
+/// Related functions:
+/// * To calculate only the net present value as an `f64` use [`after_tax_npv`].
+///
+/// # Arguments
+/// * `pre_tax_flows` - The initial investment followed by the operating cashflow before taxes for
+/// each period.
+/// * `depreciation` - The depreciation for each period starting with period 1, so there's one
+/// fewer entry than in `pre_tax_flows`.
+/// * `tax_rate` - The tax rate expressed as a floating point number. For instance 0.25 would mean
+/// 25%.
+/// * `discount_rate` - The periodic rate used to discount the cashflows.
+///
+/// # Panics
+/// The call will fail if `pre_tax_flows` is empty, if `depreciation` doesn't have exactly one
+/// fewer entry than `pre_tax_flows`, if `tax_rate` is outside the range 0.0 to 1.0, if
+/// `discount_rate` is less than or equal to -1.0, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let pre_tax_flows = [-100_000, 30_000, 30_000, 30_000, 30_000, 30_000, 30_000];
+/// let depreciation = [20_000, 32_000, 19_200, 11_520, 11_520, 5_760];
+/// let solution = after_tax_npv_solution(&pre_tax_flows, &depreciation, 0.25, 0.1);
+///
+/// let series = solution.series();
+/// assert_eq!(7, series.len());
+/// // The initial investment isn't taxed.
+/// assert_rounded_4!(-100_000.0, series[0].cashflow());
+/// // $30,000 less 25% tax plus a tax shield of 25% of $20,000.
+/// assert_rounded_4!(27_500.0, series[1].cashflow());
+/// ```
+pub fn after_tax_npv_solution<C, D>(pre_tax_flows: &[C], depreciation: &[D], tax_rate: f64, discount_rate: f64) -> DiscountedCashflowSolution
+    where
+        C: Into<f64> + Copy,
+        D: Into<f64> + Copy
+{
+    assert!(!pre_tax_flows.is_empty(), "There must be at least an initial investment in the pre-tax flows.");
+    assert_eq!(pre_tax_flows.len() - 1, depreciation.len(), "There must be one depreciation amount for each pre-tax flow after the initial investment.");
+    assert!(tax_rate.is_finite() && (0.0..=1.0).contains(&tax_rate), "The tax rate must be from 0.0 to 1.0.");
+    assert!(discount_rate.is_finite(), "The discount rate must be finite (not NaN or infinity)");
+    assert!(discount_rate > -1.0, "The discount rate must be greater than -1.0 (-100%).");
+
+    let initial_investment = pre_tax_flows[0].into();
+    assert!(initial_investment.is_finite(), "The pre-tax flows must be finite (not NaN or infinity)");
+    let formula = format!("{:.4} = {:.4}", initial_investment, initial_investment);
+    let mut series = vec![DiscountedCashflow::new(0.0, discount_rate, initial_investment, 1.0, &formula)];
+    for (index, (pre_tax_flow, depreciation)) in pre_tax_flows[1..].iter().zip(depreciation.iter()).enumerate() {
+        let pre_tax_flow = (*pre_tax_flow).into();
+        let depreciation = (*depreciation).into();
+        assert!(pre_tax_flow.is_finite(), "The pre-tax flows must be finite (not NaN or infinity)");
+        assert!(depreciation.is_finite(), "The depreciation must be finite (not NaN or infinity)");
+        let period = index as u32 + 1;
+        let after_tax_flow = pre_tax_flow * (1.0 - tax_rate) + depreciation * tax_rate;
+        let discount_factor = 1.0 / (1.0 + discount_rate).powi(period as i32);
+        let formula = format!("{:.4} = ({:.4} * (1 - {:.6}) + {:.4} * {:.6}) / ({:.6} ^ {})",
+                              after_tax_flow * discount_factor, pre_tax_flow, tax_rate, depreciation, tax_rate, 1.0 + discount_rate, period);
+        series.push(DiscountedCashflow::new(period as f64, discount_rate, after_tax_flow, discount_factor, &formula));
+    }
+    DiscountedCashflowSolution::new(series, "npv = cashflow_0 + sum((cashflow_t * (1 - tax_rate) + depreciation_t * tax_rate) / (1 + r)^t)")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_after_tax_npv_matches_pieces() {
+        // The after-tax NPV is the NPV of the taxed operating flows plus the PV of the tax shield.
+        let pre_tax_flows = [-50_000.0, 12_000.0, 15_000.0, 18_000.0, 14_000.0];
+        let depreciation = [12_500.0, 12_500.0, 12_500.0, 12_500.0];
+        let (tax_rate, discount_rate) = (0.3, 0.09);
+        let taxed_flows = pre_tax_flows.iter().enumerate()
+            .map(|(period, flow)| if period == 0 { *flow } else { flow * (1.0 - tax_rate) })
+            .collect::<Vec<_>>();
+        let expected = net_present_value_schedule(&[discount_rate; 4], &taxed_flows)
+            + depreciation_tax_shield_pv(&depreciation, tax_rate, discount_rate);
+        assert_approx_equal!(expected, after_tax_npv(&pre_tax_flows, &depreciation, tax_rate, discount_rate));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_after_tax_npv_depreciation_length() {
+        after_tax_npv(&[-1_000, 500, 500], &[500, 500, 500], 0.25, 0.1);
+    }
+}
@@ -0,0 +1,467 @@
+//! **Crossover rate of two projects.** When two mutually exclusive projects are compared by net
+//! present value the better choice can depend on the discount rate. A project with most of its
+//! cashflows early loses less value as the rate rises than one with most of its cashflows late, so
+//! their NPV profiles may cross. The crossover rate is the discount rate where the two projects have
+//! the same net present value.
+//!
+//! The crossover rate is the internal rate of return of the difference between the projects'
+//! cashflows, which is the rate r that solves:
+//!
+//! 0 = sum( (cashflow_a<sub>t</sub> - cashflow_b<sub>t</sub>) / (1 + r)<sup>t</sup> )
+//!
+//! where the first cashflow of each project is at period 0.
+//!
+//! The same rate is the incremental IRR used to choose between a larger and a smaller project.
+//! [`incremental_irr`] compares it to a hurdle rate and reports which project to choose.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! let project_a = [-1_000.0, 500.0, 400.0, 300.0, 100.0];
+//! let project_b = [-1_000.0, 100.0, 300.0, 400.0, 675.0];
+//! let solution = crossover_rate_solution(&project_a, &project_b).unwrap();
+//! dbg!(&solution);
+//! ```
+
+use crate::*;
+
+// The highest periodic rate searched for a crossover. Projects whose NPV profiles only cross above
+// 1,000% per period are treated as not crossing.
+const MAX_CROSSOVER_RATE: f64 = 10.0;
+
+// The width of each step when scanning for the lowest rate where the NPV profiles cross.
+const CROSSOVER_SCAN_STEP: f64 = 0.01;
+
+/// Returns the **discount rate where two projects have the same net present value**, or `None` if
+/// their NPV profiles don't cross at any rate from zero to 1,000% per period or the projects have
+/// the same cashflows.
+///
+/// Related functions:
+/// * To see the net present value where the projects cross use [`crossover_rate_solution`].
+///
+/// See the [crossover_rate](./index.html) module page for the equation that's solved. If the
+/// projects cross more than once the lowest crossover rate is returned.
+///
+/// # Arguments
+/// * `project_a` - The cashflows of the first project, one per period starting with period 0.
+/// * `project_b` - The cashflows of the second project, one per period starting with period 0. The
+/// projects may have different numbers of periods.
+///
+/// # Panics
+/// The call will fail if either project has no cashflows or if any cashflow is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // Project A pays back early and project B pays back late.
+/// let project_a = [-1_000.0, 500.0, 400.0, 300.0, 100.0];
+/// let project_b = [-1_000.0, 100.0, 300.0, 400.0, 675.0];
+/// let rate = crossover_rate(&project_a, &project_b).unwrap();
+/// assert_rounded_6!(0.119748, rate);
+///
+/// // Below the crossover rate project B is worth more and above it project A is.
+/// let npv = |rate: f64, cashflows: &[f64]| cashflows.iter().enumerate()
+///     .map(|(period, cashflow)| cashflow / (1.0 + rate).powi(period as i32))
+///     .sum::<f64>();
+/// assert!(npv(0.10, &project_b) > npv(0.10, &project_a));
+/// assert!(npv(0.15, &project_a) > npv(0.15, &project_b));
+///
+/// // One project that's always better than the other never crosses.
+/// assert_eq!(None, crossover_rate(&[-1_000.0, 600.0, 600.0], &[-1_000.0, 500.0, 500.0]));
+/// ```
+pub fn crossover_rate<C>(project_a: &[C], project_b: &[C]) -> Option<f64>
+    where C: Into<f64> + Copy
+{
+    crossover_rate_solution(project_a, project_b).map(|solution| solution.rate())
+}
+
+/// Returns the **discount rate where two projects have the same net present value** along with
+/// the common net present value at that rate, or `None` if their NPV profiles don't cross at any
+/// rate from zero to 1,000% per period or the projects have the same cashflows.
+///
+/// Related functions:
+/// * To calculate only the rate as an `f64` use [`crossover_rate`].
+/// * To control the precision of the solver use [`crossover_rate_solution_with_config`].
+///
+/// # Arguments
+/// * `project_a` - The cashflows of the first project, one per period starting with period 0.
+/// * `project_b` - The cashflows of the second project, one per period starting with period 0. The
+/// projects may have different numbers of periods.
+///
+/// # Panics
+/// The call will fail if either project has no cashflows or if any cashflow is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let project_a = [-1_000.0, 500.0, 400.0, 300.0, 100.0];
+/// let project_b = [-1_000.0, 100.0, 300.0, 400.0, 675.0];
+/// let solution = crossover_rate_solution(&project_a, &project_b).unwrap();
+/// assert_rounded_6!(0.119748, solution.rate());
+/// assert_rounded_4!(42.8382, solution.net_present_value());
+/// assert_eq!(vec![0.0, 400.0, 100.0, -100.0, -575.0], solution.incremental_cashflows());
+/// ```
+pub fn crossover_rate_solution<C>(project_a: &[C], project_b: &[C]) -> Option<CrossoverSolution>
+    where C: Into<f64> + Copy
+{
+    crossover_rate_solution_with_config(project_a, project_b, SolverConfig::default())
+}
+
+/// Returns the **discount rate where two projects have the same net present value** using the
+/// given tolerance and maximum number of iterations, or `None` if their NPV profiles don't cross at
+/// any rate from zero to 1,000% per period or the projects have the same cashflows.
+///
+/// Related functions:
+/// * To solve to the full precision of an `f64` use [`crossover_rate_solution`].
+///
+/// # Arguments
+/// * `project_a` - The cashflows of the first project, one per period starting with period 0.
+/// * `project_b` - The cashflows of the second project, one per period starting with period 0. The
+/// projects may have different numbers of periods.
+/// * `config` - The tolerance and maximum number of iterations for the solver.
+///
+/// # Panics
+/// The call will fail if either project has no cashflows or if any cashflow is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let config = SolverConfig::new(0.000_01, 100);
+/// let solution = crossover_rate_solution_with_config(&[-500.0, 300.0, 300.0], &[-900.0, 500.0, 550.0], config).unwrap();
+/// assert!(solution.converged());
+/// assert_rounded_4!(0.0792, solution.rate());
+/// ```
+pub fn crossover_rate_solution_with_config<C>(project_a: &[C], project_b: &[C], config: SolverConfig) -> Option<CrossoverSolution>
+    where C: Into<f64> + Copy
+{
+    assert!(!project_a.is_empty() && !project_b.is_empty(), "Each project must have at least one cashflow.");
+    let project_a = project_a.iter().map(|cashflow| (*cashflow).into()).collect::<Vec<f64>>();
+    let project_b = project_b.iter().map(|cashflow| (*cashflow).into()).collect::<Vec<f64>>();
+    assert!(project_a.iter().chain(project_b.iter()).all(|cashflow| cashflow.is_finite()), "The cashflows must be finite (not NaN or infinity)");
+
+    let periods = project_a.len().max(project_b.len());
+    let incremental_cashflows = (0..periods)
+        .map(|period| project_a.get(period).unwrap_or(&0.0) - project_b.get(period).unwrap_or(&0.0))
+        .collect::<Vec<f64>>();
+    if incremental_cashflows.iter().all(|cashflow| *cashflow == 0.0) {
+        // The projects have the same cashflows so their NPV profiles never separate.
+        return None;
+    }
+    let npv = |rate: f64| discounted_sum(rate, &incremental_cashflows);
+
+    // Scan upward from zero for the first step where the difference in NPVs changes sign, then
+    // narrow that step down to the crossover rate.
+    let mut low = 0.0;
+    let mut low_npv = npv(low);
+    let solver::SolverOutcome { value: rate, iterations, converged } = loop {
+        if low_npv == 0.0 {
+            break solver::SolverOutcome { value: low, iterations: 0, converged: true };
+        }
+        let high = low + CROSSOVER_SCAN_STEP;
+        if high > MAX_CROSSOVER_RATE {
+            return None;
+        }
+        let high_npv = npv(high);
+        if high_npv.signum() != low_npv.signum() {
+            let low_sign = low_npv.signum();
+            break solver::bisect(low, high, &config, |rate| npv(rate).signum() == low_sign);
+        }
+        low = high;
+        low_npv = high_npv;
+    };
+
+    let net_present_value = discounted_sum(rate, &project_a);
+    Some(CrossoverSolution::new(project_a, project_b, incremental_cashflows, rate, net_present_value, iterations, converged))
+}
+
+/// Returns the **choice between a larger and a smaller project** found by comparing the internal
+/// rate of return of the difference in their cashflows to a hurdle rate.
+///
+/// Related functions:
+/// * To control the precision of the solver use [`incremental_irr_with_config`].
+/// * To find only the rate where the projects have the same net present value use
+/// [`crossover_rate`].
+///
+/// The extra investment in the larger project is worthwhile if the incremental cashflows earn more
+/// than the hurdle rate. The choice is made on the sign of the incremental net present value at
+/// the hurdle rate, so the larger project is chosen if it's positive and the smaller project
+/// otherwise. When the incremental cashflows are an investment, with outflows first and inflows
+/// later, this is the same as choosing the larger project if the incremental IRR is greater than
+/// the hurdle rate. When they're a borrowing, with an inflow first and outflows later, the IRR
+/// rule is reversed since a higher incremental IRR means a more expensive loan, and the net
+/// present value still gives the right answer.
+///
+/// # Arguments
+/// * `larger` - The cashflows of the larger project, one per period starting with period 0.
+/// * `smaller` - The cashflows of the smaller project, one per period starting with period 0.
+/// * `hurdle_rate` - The minimum acceptable periodic rate of return, such as the cost of capital.
+///
+/// # Panics
+/// The call will fail if either project has no cashflows, if any cashflow is not finite, or if
+/// `hurdle_rate` is not finite or is less than or equal to -1.0.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // The larger project costs another $5,000 up front and pays more in later years.
+/// let larger = [-10_000.0, 3_000.0, 4_000.0, 5_000.0, 3_000.0];
+/// let smaller = [-5_000.0, 2_000.0, 2_500.0, 2_000.0, 1_000.0];
+///
+/// // The extra investment earns about 16% so it's worth making at a 10% hurdle rate.
+/// let decision = incremental_irr(&larger, &smaller, 0.10);
+/// assert_eq!(ProjectChoice::Larger, decision.choice());
+/// assert_rounded_6!(0.160294, decision.incremental_irr().unwrap());
+/// assert_rounded_4!(768.7316, decision.incremental_npv());
+///
+/// // But not at a 20% hurdle rate.
+/// let decision = incremental_irr(&larger, &smaller, 0.20);
+/// assert_eq!(ProjectChoice::Smaller, decision.choice());
+/// assert_rounded_4!(-424.3827, decision.incremental_npv());
+/// ```
+pub fn incremental_irr(larger: &[f64], smaller: &[f64], hurdle_rate: f64) -> IncrementalDecision {
+    incremental_irr_with_config(larger, smaller, hurdle_rate, SolverConfig::default())
+}
+
+/// Returns the **choice between a larger and a smaller project** using the given tolerance and
+/// maximum number of iterations to find the incremental IRR.
+///
+/// Related functions:
+/// * To solve to the full precision of an `f64` use [`incremental_irr`].
+///
+/// # Arguments
+/// * `larger` - The cashflows of the larger project, one per period starting with period 0.
+/// * `smaller` - The cashflows of the smaller project, one per period starting with period 0.
+/// * `hurdle_rate` - The minimum acceptable periodic rate of return, such as the cost of capital.
+/// * `config` - The tolerance and maximum number of iterations for the solver.
+///
+/// # Panics
+/// The call will fail if either project has no cashflows, if any cashflow is not finite, or if
+/// `hurdle_rate` is not finite or is less than or equal to -1.0.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let config = SolverConfig::new(0.000_01, 100);
+/// let decision = incremental_irr_with_config(&[-900.0, 500.0, 550.0], &[-500.0, 300.0, 300.0], 0.05, config);
+/// assert_eq!(ProjectChoice::Larger, decision.choice());
+/// assert_rounded_4!(0.0792, decision.incremental_irr().unwrap());
+/// ```
+pub fn incremental_irr_with_config(larger: &[f64], smaller: &[f64], hurdle_rate: f64, config: SolverConfig) -> IncrementalDecision {
+    assert!(hurdle_rate.is_finite(), "The hurdle rate must be finite (not NaN or infinity)");
+    assert!(hurdle_rate > -1.0, "The hurdle rate must be greater than -1.0 (-100%).");
+    let incremental_irr = crossover_rate_solution_with_config(larger, smaller, config).map(|solution| solution.rate());
+    let periods = larger.len().max(smaller.len());
+    let incremental_cashflows = (0..periods)
+        .map(|period| larger.get(period).unwrap_or(&0.0) - smaller.get(period).unwrap_or(&0.0))
+        .collect::<Vec<f64>>();
+    let incremental_npv = discounted_sum(hurdle_rate, &incremental_cashflows);
+    let choice = if incremental_npv > 0.0 { ProjectChoice::Larger } else { ProjectChoice::Smaller };
+    IncrementalDecision::new(choice, hurdle_rate, incremental_irr, incremental_npv)
+}
+
+/// A record of the rate where two projects have the same net present value. Created by calling
+/// [`crossover_rate_solution`].
+#[derive(Clone, Debug)]
+pub struct CrossoverSolution {
+    project_a: Vec<f64>,
+    project_b: Vec<f64>,
+    incremental_cashflows: Vec<f64>,
+    rate: f64,
+    net_present_value: f64,
+    iterations: u32,
+    converged: bool,
+    formula: String,
+    symbolic_formula: String,
+}
+
+impl CrossoverSolution {
+    pub(crate) fn new(project_a: Vec<f64>, project_b: Vec<f64>, incremental_cashflows: Vec<f64>, rate: f64, net_present_value: f64, iterations: u32, converged: bool) -> Self {
+        assert!(rate.is_finite());
+        assert!(net_present_value.is_finite());
+        let formula = format!("0 = {}", incremental_cashflows.iter().enumerate()
+            .map(|(period, cashflow)| format!("{:.4} / {:.6}^{}", cashflow, 1.0 + rate, period))
+            .collect::<Vec<_>>()
+            .join(" + "));
+        let symbolic_formula = "0 = sum((cashflow_a - cashflow_b) / (1 + r)^t)".to_string();
+        Self {
+            project_a,
+            project_b,
+            incremental_cashflows,
+            rate,
+            net_present_value,
+            iterations,
+            converged,
+            formula,
+            symbolic_formula,
+        }
+    }
+
+    /// Returns the cashflows of the first project.
+    pub fn project_a(&self) -> &[f64] {
+        &self.project_a
+    }
+
+    /// Returns the cashflows of the second project.
+    pub fn project_b(&self) -> &[f64] {
+        &self.project_b
+    }
+
+    /// Returns the cashflows of the first project minus those of the second project for each
+    /// period. The shorter project is treated as having cashflows of zero after its last period.
+    pub fn incremental_cashflows(&self) -> Vec<f64> {
+        self.incremental_cashflows.clone()
+    }
+
+    /// Returns the discount rate where the two projects have the same net present value.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns true if the incremental cashflows change sign exactly once, in which case the
+    /// projects' NPV profiles cross at no more than one rate. If this is false there may be other
+    /// crossover rates above the one returned by [`CrossoverSolution::rate`]. See
+    /// [`is_conventional_cash_flow`].
+    pub fn is_conventional(&self) -> bool {
+        is_conventional_cash_flow(&self.incremental_cashflows)
+    }
+
+    /// Returns the net present value of each project at the crossover rate.
+    pub fn net_present_value(&self) -> f64 {
+        self.net_present_value
+    }
+
+    /// Returns the number of iterations the solver used to find the rate.
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    /// Returns true if the rate was found to within the solver's tolerance before it ran out of
+    /// iterations.
+    pub fn converged(&self) -> bool {
+        self.converged
+    }
+
+    /// Returns a text version of the equation that was solved with the actual values.
+    pub fn formula(&self) -> &str {
+        &self.formula
+    }
+
+    /// Returns a text version of the equation that was solved using variables such as r for the
+    /// rate.
+    pub fn symbolic_formula(&self) -> &str {
+        &self.symbolic_formula
+    }
+}
+
+/// The project chosen by [`incremental_irr`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProjectChoice {
+    /// The incremental cashflows have a positive net present value at the hurdle rate so the extra
+    /// investment in the larger project is worthwhile.
+    Larger,
+    /// The incremental cashflows don't have a positive net present value at the hurdle rate so the
+    /// smaller project is better.
+    Smaller,
+}
+
+/// A record of the choice between a larger and a smaller project. Created by calling
+/// [`incremental_irr`].
+#[derive(Clone, Debug)]
+pub struct IncrementalDecision {
+    choice: ProjectChoice,
+    hurdle_rate: f64,
+    incremental_irr: Option<f64>,
+    incremental_npv: f64,
+}
+
+impl IncrementalDecision {
+    pub(crate) fn new(choice: ProjectChoice, hurdle_rate: f64, incremental_irr: Option<f64>, incremental_npv: f64) -> Self {
+        assert!(incremental_npv.is_finite());
+        Self {
+            choice,
+            hurdle_rate,
+            incremental_irr,
+            incremental_npv,
+        }
+    }
+
+    /// Returns the project to choose.
+    pub fn choice(&self) -> ProjectChoice {
+        self.choice.clone()
+    }
+
+    /// Returns the minimum acceptable rate of return that was passed to the function.
+    pub fn hurdle_rate(&self) -> f64 {
+        self.hurdle_rate
+    }
+
+    /// Returns the internal rate of return of the larger project's cashflows minus the smaller
+    /// project's, or `None` if there's no such rate from zero to 1,000% per period.
+    pub fn incremental_irr(&self) -> Option<f64> {
+        self.incremental_irr
+    }
+
+    /// Returns the net present value of the larger project's cashflows minus the smaller project's
+    /// at the hurdle rate.
+    pub fn incremental_npv(&self) -> f64 {
+        self.incremental_npv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crossover_rate_equal_npvs() {
+        // Projects of different lengths and sizes.
+        let project_a = [-20_000.0, 9_000.0, 9_000.0, 9_000.0];
+        let project_b = [-30_000.0, 8_000.0, 8_000.0, 8_000.0, 8_000.0, 8_000.0];
+        let solution = crossover_rate_solution(&project_a, &project_b).unwrap();
+        let npv_a = discounted_sum(solution.rate(), &project_a);
+        let npv_b = discounted_sum(solution.rate(), &project_b);
+        assert_approx_equal!(npv_a, npv_b);
+        assert_approx_equal!(npv_a, solution.net_present_value());
+        assert!(solution.is_conventional());
+        // Swapping the projects gives the same rate.
+        assert_approx_equal!(solution.rate(), crossover_rate(&project_b, &project_a).unwrap());
+    }
+
+    #[test]
+    fn test_incremental_irr_matches_npv() {
+        // For a conventional incremental stream the IRR rule and the NPV rule agree.
+        let larger = [-50_000.0, 14_000.0, 14_000.0, 14_000.0, 14_000.0, 14_000.0];
+        let smaller = [-30_000.0, 9_000.0, 9_000.0, 9_000.0, 9_000.0, 9_000.0];
+        let rate = crossover_rate(&larger, &smaller).unwrap();
+        for hurdle_rate in [0.0, 0.04, 0.07, 0.12].iter() {
+            let decision = incremental_irr(&larger, &smaller, *hurdle_rate);
+            assert_approx_equal!(rate, decision.incremental_irr().unwrap());
+            let choice = if decision.incremental_npv() > 0.0 { ProjectChoice::Larger } else { ProjectChoice::Smaller };
+            assert_eq!(choice, decision.choice());
+        }
+    }
+
+    #[test]
+    fn test_incremental_irr_borrowing_stream() {
+        // The incremental cashflows are a $1,000 inflow repaid with two $600 outflows, which is
+        // borrowing at about 13%. That's a bad deal at a 5% hurdle rate and a good one at 20%.
+        let larger = [-4_000.0, 2_400.0, 2_400.0];
+        let smaller = [-5_000.0, 3_000.0, 3_000.0];
+        let rate = crossover_rate(&larger, &smaller).unwrap();
+        assert_rounded_6!(0.130662, rate);
+        let decision = incremental_irr(&larger, &smaller, 0.05);
+        assert!(decision.incremental_npv() < 0.0);
+        assert_eq!(ProjectChoice::Smaller, decision.choice());
+        let decision = incremental_irr(&larger, &smaller, 0.20);
+        assert!(decision.incremental_npv() > 0.0);
+        assert_eq!(ProjectChoice::Larger, decision.choice());
+    }
+
+    #[test]
+    fn test_crossover_rate_identical_projects() {
+        // Projects with the same cashflows have the same NPV at every rate so there's no single
+        // crossover rate. A trailing zero cashflow doesn't make a difference.
+        let project = [-1_000.0, 400.0, 400.0, 400.0];
+        assert_eq!(None, crossover_rate(&project, &[-1_000.0, 400.0, 400.0, 400.0, 0.0]));
+    }
+}
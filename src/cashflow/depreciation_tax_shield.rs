@@ -0,0 +1,134 @@
+//! **Depreciation tax shield.** Depreciation isn't a cash expense but it reduces taxable income, so
+//! each period's depreciation saves the business the depreciation times the tax rate. The present
+//! value of those savings is part of the value of a capital project.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // Straight-line depreciation of a $50,000 machine over five years with a 25% tax rate.
+//! let depreciation = [10_000, 10_000, 10_000, 10_000, 10_000];
+//! let solution = depreciation_tax_shield_pv_solution(&depreciation, 0.25, 0.08);
+//! solution.print_table();
+//! ```
+//! Outputs to terminal:
+//! ```text
+//! period      rate    cashflow  discount_factor  present_value
+//! ------  --------  ----------  ---------------  -------------
+//!      1  0.080000  2_500.0000         0.925926     2_314.8148
+//!      2  0.080000  2_500.0000         0.857339     2_143.3471
+//!      3  0.080000  2_500.0000         0.793832     1_984.5806
+//!      4  0.080000  2_500.0000         0.735030     1_837.5746
+//!      5  0.080000  2_500.0000         0.680583     1_701.4580
+//! ```
+
+use crate::*;
+
+/// Returns the **present value of the tax savings from depreciation**.
+///
+/// Related functions:
+/// * To see the tax shield and its present value for each period use
+/// [`depreciation_tax_shield_pv_solution`].
+///
+/// The formula is:
+///
+/// pv = sum(depreciation<sub>t</sub> * tax_rate / (1 + discount_rate)<sup>t</sup>)
+///
+/// # Arguments
+/// * `depreciation` - The depreciation for each period, starting with period 1.
+/// * `tax_rate` - The tax rate expressed as a floating point number. For instance 0.25 would mean
+/// 25%.
+/// * `discount_rate` - The periodic rate used to discount the tax savings.
+///
+/// # Panics
+/// The call will fail if `tax_rate` is outside the range 0.0 to 1.0, if `discount_rate` is less
+/// than or equal to -1.0, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let depreciation = [10_000, 10_000, 10_000, 10_000, 10_000];
+/// assert_rounded_4!(9_981.7751, depreciation_tax_shield_pv(&depreciation, 0.25, 0.08));
+/// ```
+pub fn depreciation_tax_shield_pv<D>(depreciation: &[D], tax_rate: f64, discount_rate: f64) -> f64
+    where D: Into<f64> + Copy
+{
+    depreciation_tax_shield_pv_solution(depreciation, tax_rate, discount_rate).present_value()
+}
+
+/// Returns the **present value of the tax savings from depreciation** along with the tax shield
+/// and its present value for each period.
+///
+/// In the solution's series the `cashflow` is the tax shield for the period, which is the
+/// depreciation times the tax rate.
+///
+/// Related functions:
+/// * To calculate only the present value as an `f64` use [`depreciation_tax_shield_pv`].
+///
+/// # Arguments
+/// * `depreciation` - The depreciation for each period, starting with period 1.
+/// * `tax_rate` - The tax rate expressed as a floating point number. For instance 0.25 would mean
+/// 25%.
+/// * `discount_rate` - The periodic rate used to discount the tax savings.
+///
+/// # Panics
+/// The call will fail if `tax_rate` is outside the range 0.0 to 1.0, if `discount_rate` is less
+/// than or equal to -1.0, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // MACRS depreciation of the same $50,000 machine over six tax years.
+/// let depreciation = [10_000, 16_000, 9_600, 5_760, 5_760, 2_880];
+/// let solution = depreciation_tax_shield_pv_solution(&depreciation, 0.25, 0.08);
+///
+/// // The total tax saved is the same as with straight-line depreciation but it comes sooner so
+/// // it's worth more.
+/// assert_rounded_4!(12_500.0, solution.sum_of_cashflows());
+/// assert_rounded_4!(10_141.5724, solution.present_value());
+///
+/// let series = solution.series();
+/// assert_rounded_4!(2_500.0, series[0].cashflow());
+/// assert_rounded_4!(2_314.8148, series[0].present_value());
+/// ```
+pub fn depreciation_tax_shield_pv_solution<D>(depreciation: &[D], tax_rate: f64, discount_rate: f64) -> DiscountedCashflowSolution
+    where D: Into<f64> + Copy
+{
+    assert!(tax_rate.is_finite() && (0.0..=1.0).contains(&tax_rate), "The tax rate must be from 0.0 to 1.0.");
+    assert!(discount_rate.is_finite(), "The discount rate must be finite (not NaN or infinity)");
+    assert!(discount_rate > -1.0, "The discount rate must be greater than -1.0 (-100%).");
+    let mut series = vec![];
+    for (index, depreciation) in depreciation.iter().enumerate() {
+        let depreciation = (*depreciation).into();
+        assert!(depreciation.is_finite(), "The depreciation must be finite (not NaN or infinity)");
+        let period = index as u32 + 1;
+        let tax_shield = depreciation * tax_rate;
+        let discount_factor = 1.0 / (1.0 + discount_rate).powi(period as i32);
+        let formula = format!("{:.4} = {:.4} * {:.6} / ({:.6} ^ {})", tax_shield * discount_factor, depreciation, tax_rate, 1.0 + discount_rate, period);
+        series.push(DiscountedCashflow::new(period as f64, discount_rate, tax_shield, discount_factor, &formula));
+    }
+    DiscountedCashflowSolution::new(series, "pv = sum(depreciation_t * tax_rate / (1 + r)^t)")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_depreciation_tax_shield_pv_discount_rate() {
+        let depreciation = [12_000.0, 9_000.0, 6_000.0, 3_000.0];
+        let mut previous = depreciation_tax_shield_pv(&depreciation, 0.3, 0.0);
+        // With no discounting the present value is the total tax saved.
+        assert_approx_equal!(9_000.0, previous);
+        for discount_rate in [0.02, 0.05, 0.1, 0.2].iter() {
+            let pv = depreciation_tax_shield_pv(&depreciation, 0.3, *discount_rate);
+            assert!(pv < previous);
+            previous = pv;
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_depreciation_tax_shield_pv_tax_rate_out_of_range() {
+        depreciation_tax_shield_pv(&[1_000], 1.5, 0.05);
+    }
+}
@@ -0,0 +1,191 @@
+//! **Equated time.** Given a series of payments due at different times, when could a single payment
+//! of the total amount be made instead so that its value is the same as the value of the separate
+//! payments?
+//!
+//! The exact answer depends on the interest rate. It's the time at which the total amount has the
+//! same present value as the series of payments:
+//!
+//! t = ln(sum(cashflow<sub>t</sub>) / sum(cashflow<sub>t</sub> / (1 + r)<sup>t</sup>)) / ln(1 + r)
+//!
+//! The method of equated time is an older approximation that ignores the rate and takes the
+//! average of the times weighted by the payments:
+//!
+//! t ≈ sum(t * cashflow<sub>t</sub>) / sum(cashflow<sub>t</sub>)
+//!
+//! With a positive rate the approximation is always a little later than the exact time.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // $1,000 due in one year and $2,000 due in four years.
+//! let solution = equated_time_solution(0.05, &[1_000, 0, 0, 2_000]);
+//! dbg!(&solution);
+//! ```
+
+use crate::*;
+
+/// Returns the **exact equated time** of a series of payments, the period at which a single
+/// payment equal to the sum of the payments would have the same present value.
+///
+/// Related functions:
+/// * To also calculate the approximation from the method of equated time use
+/// [`equated_time_solution`].
+///
+/// See the [equated_time](./index.html) module page for the formulas.
+///
+/// # Arguments
+/// * `rate` - The periodic rate, expressed as a floating point number. For instance 0.05 would
+/// mean 5%.
+/// * `cash_flows` - The payments, one per period starting with period 1. Periods without a payment
+/// should have a zero.
+///
+/// # Panics
+/// The call will fail if `rate` is less than or equal to -1.0, if any of the payments is not
+/// finite, if the payments don't all have the same sign, or if they add up to zero.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // $1,000 due in one year and $2,000 due in four years can be replaced by $3,000 due in just
+/// // under three years.
+/// assert_rounded_6!(2.950447, equated_time(0.05, &[1_000, 0, 0, 2_000]));
+/// ```
+pub fn equated_time<C>(rate: f64, cash_flows: &[C]) -> f64
+    where C: Into<f64> + Copy
+{
+    equated_time_solution(rate, cash_flows).exact_time()
+}
+
+/// Returns the **exact equated time** of a series of payments along with the approximation from
+/// the method of equated time.
+///
+/// Related functions:
+/// * To calculate only the exact equated time as an `f64` use [`equated_time`].
+///
+/// # Arguments
+/// * `rate` - The periodic rate, expressed as a floating point number. For instance 0.05 would
+/// mean 5%.
+/// * `cash_flows` - The payments, one per period starting with period 1. Periods without a payment
+/// should have a zero.
+///
+/// # Panics
+/// The call will fail if `rate` is less than or equal to -1.0, if any of the payments is not
+/// finite, if the payments don't all have the same sign, or if they add up to zero.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = equated_time_solution(0.05, &[1_000, 0, 0, 2_000]);
+/// assert_rounded_4!(3_000.0, solution.sum_of_cashflows());
+/// assert_rounded_4!(2_597.7859, solution.present_value());
+/// assert_rounded_6!(2.950447, solution.exact_time());
+///
+/// // The method of equated time gives the weighted average of one year and four years.
+/// assert_rounded_6!(3.0, solution.approximate_time());
+/// ```
+pub fn equated_time_solution<C>(rate: f64, cash_flows: &[C]) -> EquatedTimeSolution
+    where C: Into<f64> + Copy
+{
+    assert!(rate.is_finite(), "The rate must be finite (not NaN or infinity)");
+    assert!(rate > -1.0, "The rate must be greater than -1.0 (-100%).");
+    let cash_flows = cash_flows.iter().map(|cash_flow| (*cash_flow).into()).collect::<Vec<f64>>();
+    for cash_flow in cash_flows.iter() {
+        assert!(cash_flow.is_finite(), "The cash flows must be finite (not NaN or infinity)");
+    }
+    assert!(cash_flows.iter().all(|cash_flow| *cash_flow >= 0.0) || cash_flows.iter().all(|cash_flow| *cash_flow <= 0.0),
+            "The cash flows must all have the same sign.");
+    let sum_of_cashflows: f64 = cash_flows.iter().sum();
+    assert!(sum_of_cashflows != 0.0, "The cash flows must not add up to zero.");
+
+    let present_value: f64 = cash_flows.iter().enumerate()
+        .map(|(index, cash_flow)| cash_flow / (1.0 + rate).powi(index as i32 + 1))
+        .sum();
+    let approximate_time = cash_flows.iter().enumerate()
+        .map(|(index, cash_flow)| (index + 1) as f64 * cash_flow)
+        .sum::<f64>() / sum_of_cashflows;
+    // With a rate of zero every time has the same value so use the limit as the rate approaches
+    // zero, which is the weighted average time.
+    let exact_time = if rate == 0.0 {
+        approximate_time
+    } else {
+        (sum_of_cashflows / present_value).ln() / (1.0 + rate).ln()
+    };
+    EquatedTimeSolution::new(rate, sum_of_cashflows, present_value, exact_time, approximate_time)
+}
+
+/// A record of the equated time of a series of payments. Created by calling
+/// [`equated_time_solution`].
+#[derive(Clone, Debug)]
+pub struct EquatedTimeSolution {
+    rate: f64,
+    sum_of_cashflows: f64,
+    present_value: f64,
+    exact_time: f64,
+    approximate_time: f64,
+}
+
+impl EquatedTimeSolution {
+    pub(crate) fn new(rate: f64, sum_of_cashflows: f64, present_value: f64, exact_time: f64, approximate_time: f64) -> Self {
+        assert!(exact_time.is_finite());
+        assert!(approximate_time.is_finite());
+        Self {
+            rate,
+            sum_of_cashflows,
+            present_value,
+            exact_time,
+            approximate_time,
+        }
+    }
+
+    /// Returns the periodic rate.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the total of the payments, which is the amount of the single equivalent payment.
+    pub fn sum_of_cashflows(&self) -> f64 {
+        self.sum_of_cashflows
+    }
+
+    /// Returns the present value of the payments. Unlike most of the crate this has the same sign
+    /// as the payments.
+    pub fn present_value(&self) -> f64 {
+        self.present_value
+    }
+
+    /// Returns the period at which a single payment of the total has the same present value as
+    /// the separate payments.
+    pub fn exact_time(&self) -> f64 {
+        self.exact_time
+    }
+
+    /// Returns the approximate equated time from the method of equated time, which is the average
+    /// of the periods weighted by the payments.
+    pub fn approximate_time(&self) -> f64 {
+        self.approximate_time
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_equated_time_same_value() {
+        let cash_flows = [250.0, 400.0, 0.0, 900.0, 150.0];
+        let rate = 0.07;
+        let solution = equated_time_solution(rate, &cash_flows);
+        // A single payment of the total at the equated time has the same present value.
+        assert_approx_equal!(solution.present_value(), solution.sum_of_cashflows() / (1.0 + rate).powf(solution.exact_time()));
+        assert!(solution.exact_time() < solution.approximate_time());
+        // Without interest the exact and approximate times agree.
+        let solution = equated_time_solution(0.0, &cash_flows);
+        assert_approx_equal!(solution.approximate_time(), solution.exact_time());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_equated_time_mixed_signs() {
+        equated_time(0.05, &[-1_000, 2_000]);
+    }
+}
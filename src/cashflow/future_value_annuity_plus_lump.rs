@@ -0,0 +1,446 @@
+//! **Future value of a lump sum plus an annuity.** A common savings plan starts with a single
+//! deposit such as a rollover and then adds a fixed contribution every period. The balance at the
+//! end is the future value of the lump sum plus the future value of the annuity.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // A $50,000 rollover plus $500 a month for 25 years at 0.5% a month.
+//! let solution = future_value_annuity_plus_lump_solution(0.005, 300, 500, 50_000, false);
+//! dbg!(&solution);
+//! solution.print_table();
+//! ```
+
+use crate::*;
+
+/// Returns the **future value of a lump sum plus a series of constant payments** at a constant
+/// rate.
+///
+/// Related functions:
+/// * To see the combined balance in each period use [`future_value_annuity_plus_lump_solution`].
+/// * For the lump sum alone use [`future_value`] and for the payments alone use
+/// [`future_value_annuity`]. This function returns the sum of the two.
+///
+/// The formula is:
+///
+/// future_value = -(present_value * (1 + r)<sup>n</sup> + payment * (1 + r * due) * ((1 + r)<sup>n</sup> - 1) / r)
+///
+/// As with [`future_value`] and [`future_value_annuity`] the result is negative if the present
+/// value and payment are positive.
+///
+/// # Arguments
+/// * `rate` - The rate at which the investment grows or shrinks per period, expressed as a
+/// floating point number. For instance 0.05 would mean 5%.
+/// * `periods` - The number of periods such as quarters or years.
+/// * `payment` - The amount added in each period.
+/// * `present_value` - The lump sum deposited at the start.
+/// * `due_at_beginning` - True if the payments are made at the beginning of each period rather
+/// than at the end.
+///
+/// # Panics
+/// The call will fail if `rate` is less than or equal to -1.0, if `periods` is zero, or if the
+/// payment or present value are not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // A $50,000 rollover plus $500 a month for 25 years at 0.5% a month.
+/// let fv = future_value_annuity_plus_lump(0.005, 300, 500, 50_000, false);
+/// assert_rounded_4!(-569_745.4718, fv);
+///
+/// // The same as adding up the two pieces.
+/// let lump = future_value(0.005, 300, 50_000, false);
+/// let annuity = future_value_annuity(0.005, 300, 500, false);
+/// assert_rounded_4!(lump + annuity, fv);
+/// ```
+pub fn future_value_annuity_plus_lump<C, P>(rate: f64, periods: u32, payment: C, present_value: P, due_at_beginning: bool) -> f64
+    where
+        C: Into<f64> + Copy,
+        P: Into<f64> + Copy
+{
+    future_value_annuity_plus_lump_solution(rate, periods, payment, present_value, due_at_beginning).future_value()
+}
+
+/// Returns the **future value of a lump sum plus a series of constant payments** along with the
+/// inputs, the two parts of the balance, and a period-by-period schedule.
+///
+/// Related functions:
+/// * To calculate only the future value as an `f64` use [`future_value_annuity_plus_lump`].
+///
+/// # Arguments
+/// * `rate` - The rate at which the investment grows or shrinks per period, expressed as a
+/// floating point number. For instance 0.05 would mean 5%.
+/// * `periods` - The number of periods such as quarters or years.
+/// * `payment` - The amount added in each period.
+/// * `present_value` - The lump sum deposited at the start.
+/// * `due_at_beginning` - True if the payments are made at the beginning of each period rather
+/// than at the end.
+///
+/// # Panics
+/// The call will fail if `rate` is less than or equal to -1.0, if `periods` is zero, or if the
+/// payment or present value are not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = future_value_annuity_plus_lump_solution(0.04, 3, 1_000, 10_000, true);
+/// assert_rounded_4!(-14_495.1040, solution.future_value());
+/// assert_rounded_4!(-11_248.6400, solution.future_value_of_lump_sum());
+/// assert_rounded_4!(-3_246.4640, solution.future_value_of_annuity());
+///
+/// // The schedule starts with the lump sum and ends with the future value.
+/// let series = solution.series();
+/// assert_eq!(4, series.len());
+/// assert_rounded_4!(-10_000.0000, series[0].value());
+/// assert_rounded_4!(-11_440.0000, series[1].value());
+/// assert_rounded_4!(solution.future_value(), series[3].value());
+/// ```
+pub fn future_value_annuity_plus_lump_solution<C, P>(rate: f64, periods: u32, payment: C, present_value: P, due_at_beginning: bool) -> LumpSumAnnuitySolution
+    where
+        C: Into<f64> + Copy,
+        P: Into<f64> + Copy
+{
+    let payment = payment.into();
+    let present_value = present_value.into();
+    assert!(rate.is_finite(), "The rate must be finite (not NaN or infinity)");
+    assert!(rate > -1.0, "The rate must be greater than -1.0 (-100%).");
+    assert!(payment.is_finite(), "The payment must be finite (not NaN or infinity)");
+    assert!(present_value.is_finite(), "The present value must be finite (not NaN or infinity)");
+    assert!(periods > 0, "The number of periods must be greater than zero.");
+    LumpSumAnnuitySolution::new(rate, periods, payment, present_value, due_at_beginning)
+}
+
+/// Returns the **inflation-adjusted future value of a lump sum plus a series of constant
+/// payments**, meaning the balance at the end expressed in today's purchasing power.
+///
+/// Related functions:
+/// * To see both the nominal and real figures use [`future_value_real_solution`].
+/// * For the nominal balance alone use [`future_value_annuity_plus_lump`].
+///
+/// The nominal future value is deflated by the cumulative inflation over the same periods:
+///
+/// real_future_value = nominal_future_value / (1 + inflation_rate)<sup>n</sup>
+///
+/// This is the same as growing the savings at the real rate from the Fisher relation,
+/// (1 + nominal_rate) / (1 + inflation_rate) - 1, except that the payments are treated as fixed in
+/// nominal terms. As with [`future_value_annuity_plus_lump`] the result is negative if the present
+/// value and payment are positive.
+///
+/// # Arguments
+/// * `nominal_rate` - The rate at which the savings grow per period before inflation, expressed as
+/// a floating point number. For instance 0.05 would mean 5%.
+/// * `inflation_rate` - The rate of inflation per period.
+/// * `periods` - The number of periods such as months or years.
+/// * `present_value` - The lump sum deposited at the start.
+/// * `payment` - The amount added in each period.
+/// * `due_at_beginning` - True if the payments are made at the beginning of each period rather
+/// than at the end.
+///
+/// # Panics
+/// The call will fail if `nominal_rate` or `inflation_rate` is less than or equal to -1.0, if
+/// `periods` is zero, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // A $50,000 rollover plus $500 a month for 25 years at 0.5% a month with 0.2% monthly inflation.
+/// let real = future_value_real(0.005, 0.002, 300, 50_000, 500, false);
+/// assert_rounded_4!(-312_870.3607, real);
+///
+/// // Without inflation the real and nominal values are the same.
+/// let nominal = future_value_annuity_plus_lump(0.005, 300, 500, 50_000, false);
+/// assert_rounded_4!(nominal, future_value_real(0.005, 0.0, 300, 50_000, 500, false));
+/// ```
+pub fn future_value_real<P, C>(nominal_rate: f64, inflation_rate: f64, periods: u32, present_value: P, payment: C, due_at_beginning: bool) -> f64
+    where
+        P: Into<f64> + Copy,
+        C: Into<f64> + Copy
+{
+    future_value_real_solution(nominal_rate, inflation_rate, periods, present_value, payment, due_at_beginning).real_future_value()
+}
+
+/// Returns the **inflation-adjusted future value of a lump sum plus a series of constant
+/// payments** along with the nominal future value and the inputs.
+///
+/// Related functions:
+/// * To calculate only the real future value as an `f64` use [`future_value_real`].
+///
+/// # Arguments
+/// * `nominal_rate` - The rate at which the savings grow per period before inflation, expressed as
+/// a floating point number. For instance 0.05 would mean 5%.
+/// * `inflation_rate` - The rate of inflation per period.
+/// * `periods` - The number of periods such as months or years.
+/// * `present_value` - The lump sum deposited at the start.
+/// * `payment` - The amount added in each period.
+/// * `due_at_beginning` - True if the payments are made at the beginning of each period rather
+/// than at the end.
+///
+/// # Panics
+/// The call will fail if `nominal_rate` or `inflation_rate` is less than or equal to -1.0, if
+/// `periods` is zero, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = future_value_real_solution(0.05, 0.03, 3, 10_000, 1_000, false);
+/// assert_rounded_4!(-14_728.7500, solution.nominal_future_value());
+/// assert_rounded_4!(-13_478.8927, solution.real_future_value());
+/// assert_rounded_6!(0.019417, solution.real_rate());
+///
+/// // The nominal side is a complete lump sum plus annuity solution.
+/// assert_rounded_4!(-11_576.2500, solution.nominal_solution().future_value_of_lump_sum());
+/// ```
+pub fn future_value_real_solution<P, C>(nominal_rate: f64, inflation_rate: f64, periods: u32, present_value: P, payment: C, due_at_beginning: bool) -> RealFutureValueSolution
+    where
+        P: Into<f64> + Copy,
+        C: Into<f64> + Copy
+{
+    assert!(inflation_rate.is_finite(), "The inflation rate must be finite (not NaN or infinity)");
+    assert!(inflation_rate > -1.0, "The inflation rate must be greater than -1.0 (-100%).");
+    let nominal_solution = future_value_annuity_plus_lump_solution(nominal_rate, periods, payment, present_value, due_at_beginning);
+    RealFutureValueSolution::new(inflation_rate, nominal_solution)
+}
+
+// The two parts of the balance after the given number of periods, following the same sign convention as
+// future_value() and future_value_annuity().
+fn lump_sum_balance(rate: f64, periods: u32, present_value: f64) -> f64 {
+    -present_value * (1.0 + rate).powf(periods as f64)
+}
+
+fn annuity_balance(rate: f64, periods: u32, payment: f64, due_at_beginning: bool) -> f64 {
+    if rate == 0.0 {
+        return -payment * periods as f64;
+    }
+    let due_factor = if due_at_beginning { 1.0 + rate } else { 1.0 };
+    -payment * due_factor * ((1.0 + rate).powf(periods as f64) - 1.0) / rate
+}
+
+/// A record of the future value of a lump sum plus an annuity. Created by calling
+/// [`future_value_annuity_plus_lump_solution`].
+#[derive(Clone, Debug)]
+pub struct LumpSumAnnuitySolution {
+    rate: f64,
+    periods: u32,
+    payment: f64,
+    present_value: f64,
+    due_at_beginning: bool,
+    future_value_of_lump_sum: f64,
+    future_value_of_annuity: f64,
+    future_value: f64,
+    formula: String,
+    symbolic_formula: String,
+}
+
+impl LumpSumAnnuitySolution {
+    pub(crate) fn new(rate: f64, periods: u32, payment: f64, present_value: f64, due_at_beginning: bool) -> Self {
+        let future_value_of_lump_sum = lump_sum_balance(rate, periods, present_value);
+        let future_value_of_annuity = annuity_balance(rate, periods, payment, due_at_beginning);
+        let future_value = future_value_of_lump_sum + future_value_of_annuity;
+        assert!(future_value.is_finite());
+        let due = if due_at_beginning { 1.0 } else { 0.0 };
+        let formula = format!("-({:.4} * {:.6}^{} + {:.4} * (1 + {:.6} * {}) * ({:.6}^{} - 1) / {:.6})",
+                              present_value, 1.0 + rate, periods, payment, rate, due, 1.0 + rate, periods, rate);
+        let symbolic_formula = "fv = -(pv * (1 + r)^n + pmt * (1 + r * due) * ((1 + r)^n - 1) / r)".to_string();
+        Self {
+            rate,
+            periods,
+            payment,
+            present_value,
+            due_at_beginning,
+            future_value_of_lump_sum,
+            future_value_of_annuity,
+            future_value,
+            formula,
+            symbolic_formula,
+        }
+    }
+
+    /// Returns the periodic rate.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the number of periods.
+    pub fn periods(&self) -> u32 {
+        self.periods
+    }
+
+    /// Returns the amount added in each period.
+    pub fn payment(&self) -> f64 {
+        self.payment
+    }
+
+    /// Returns the lump sum deposited at the start.
+    pub fn present_value(&self) -> f64 {
+        self.present_value
+    }
+
+    /// Returns true if the payments are made at the beginning of each period.
+    pub fn due_at_beginning(&self) -> bool {
+        self.due_at_beginning
+    }
+
+    /// Returns the part of the future value that comes from the lump sum. This is the same as
+    /// [`future_value`].
+    pub fn future_value_of_lump_sum(&self) -> f64 {
+        self.future_value_of_lump_sum
+    }
+
+    /// Returns the part of the future value that comes from the payments. This is the same as
+    /// [`future_value_annuity`].
+    pub fn future_value_of_annuity(&self) -> f64 {
+        self.future_value_of_annuity
+    }
+
+    /// Returns the combined balance at the end of the last period.
+    pub fn future_value(&self) -> f64 {
+        self.future_value
+    }
+
+    /// Returns a text version of the formula used to calculate the future value with the actual
+    /// values.
+    pub fn formula(&self) -> &str {
+        &self.formula
+    }
+
+    /// Returns a text version of the formula used to calculate the future value using variables
+    /// such as r for the rate.
+    pub fn symbolic_formula(&self) -> &str {
+        &self.symbolic_formula
+    }
+
+    /// Calculates the combined balance at the end of each period. Period 0 holds the lump sum and
+    /// the last period holds the future value.
+    pub fn series(&self) -> TvmSeries {
+        let symbolic_formula = "-(pv * (1 + r)^t + pmt * (1 + r * due) * ((1 + r)^t - 1) / r)";
+        let series = (0..=self.periods)
+            .map(|period| {
+                let lump_sum = lump_sum_balance(self.rate, period, self.present_value);
+                let annuity = annuity_balance(self.rate, period, self.payment, self.due_at_beginning);
+                let formula = format!("{:.4} + {:.4}", lump_sum, annuity);
+                TvmPeriod::new(period, self.rate, lump_sum + annuity, &formula, symbolic_formula)
+            })
+            .collect();
+        TvmSeries::new(series)
+    }
+
+    pub fn print_table(&self) {
+        self.series().print_table();
+    }
+
+    pub fn print_table_locale(&self, locale: &num_format::Locale, precision: usize) {
+        self.series().print_table_locale(locale, precision);
+    }
+}
+
+/// A record of the nominal and inflation-adjusted future value of a lump sum plus an annuity.
+/// Created by calling [`future_value_real_solution`].
+#[derive(Clone, Debug)]
+pub struct RealFutureValueSolution {
+    inflation_rate: f64,
+    real_rate: f64,
+    deflator: f64,
+    real_future_value: f64,
+    nominal_solution: LumpSumAnnuitySolution,
+}
+
+impl RealFutureValueSolution {
+    pub(crate) fn new(inflation_rate: f64, nominal_solution: LumpSumAnnuitySolution) -> Self {
+        let real_rate = (1.0 + nominal_solution.rate()) / (1.0 + inflation_rate) - 1.0;
+        let deflator = (1.0 + inflation_rate).powf(nominal_solution.periods() as f64);
+        let real_future_value = nominal_solution.future_value() / deflator;
+        assert!(real_future_value.is_finite());
+        Self {
+            inflation_rate,
+            real_rate,
+            deflator,
+            real_future_value,
+            nominal_solution,
+        }
+    }
+
+    /// Returns the periodic rate before inflation.
+    pub fn nominal_rate(&self) -> f64 {
+        self.nominal_solution.rate()
+    }
+
+    /// Returns the periodic rate of inflation.
+    pub fn inflation_rate(&self) -> f64 {
+        self.inflation_rate
+    }
+
+    /// Returns the periodic real rate from the Fisher relation,
+    /// (1 + nominal_rate) / (1 + inflation_rate) - 1.
+    pub fn real_rate(&self) -> f64 {
+        self.real_rate
+    }
+
+    /// Returns the number of periods.
+    pub fn periods(&self) -> u32 {
+        self.nominal_solution.periods()
+    }
+
+    /// Returns the cumulative price level at the end relative to the start,
+    /// (1 + inflation_rate)<sup>n</sup>.
+    pub fn deflator(&self) -> f64 {
+        self.deflator
+    }
+
+    /// Returns the balance at the end of the last period in nominal terms.
+    pub fn nominal_future_value(&self) -> f64 {
+        self.nominal_solution.future_value()
+    }
+
+    /// Returns the balance at the end of the last period in today's purchasing power.
+    pub fn real_future_value(&self) -> f64 {
+        self.real_future_value
+    }
+
+    /// Returns the purchasing power lost to inflation, as the nominal future value minus the real
+    /// future value.
+    pub fn inflation_loss(&self) -> f64 {
+        self.nominal_future_value() - self.real_future_value
+    }
+
+    /// Returns the nominal lump sum plus annuity calculation including its series.
+    pub fn nominal_solution(&self) -> &LumpSumAnnuitySolution {
+        &self.nominal_solution
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_future_value_annuity_plus_lump_matches_pieces() {
+        for due_at_beginning in [false, true].iter() {
+            let (rate, periods, payment, present_value) = (0.0065, 180, 750.0, 42_000.0);
+            let fv = future_value_annuity_plus_lump(rate, periods, payment, present_value, *due_at_beginning);
+            let pieces = future_value(rate, periods, present_value, false)
+                + future_value_annuity(rate, periods, payment, *due_at_beginning);
+            assert_approx_equal!(pieces, fv);
+        }
+    }
+
+    #[test]
+    fn test_future_value_annuity_plus_lump_zero_rate() {
+        let solution = future_value_annuity_plus_lump_solution(0.0, 12, 100, 1_000, false);
+        assert_approx_equal!(-2_200.0, solution.future_value());
+        assert_approx_equal!(-1_100.0, solution.series()[1].value());
+    }
+
+    #[test]
+    fn test_future_value_real() {
+        // No inflation leaves the nominal value alone.
+        let solution = future_value_real_solution(0.004, 0.0, 240, 20_000, 300, true);
+        assert_approx_equal!(solution.nominal_future_value(), solution.real_future_value());
+        assert_approx_equal!(0.004, solution.real_rate());
+
+        // Positive inflation shrinks the real value toward zero without changing its sign.
+        let solution = future_value_real_solution(0.004, 0.0025, 240, 20_000, 300, true);
+        assert!(solution.real_future_value() < 0.0);
+        assert!(solution.real_future_value().abs() < solution.nominal_future_value().abs());
+        assert_approx_equal!(solution.nominal_future_value() / 1.0025_f64.powi(240), solution.real_future_value());
+    }
+}
@@ -0,0 +1,325 @@
+//! **Future value of an annuity with an annual step-up.** Retirement plan contributions are often
+//! raised by a fixed percentage once a year, such as with each annual salary increase, while the
+//! contributions themselves are made every month. The payment stays level within each year and
+//! steps up at each year boundary, unlike a growing annuity where the payment grows every period.
+//!
+//! The payment in period t, counting from 1, is:
+//!
+//! payment<sub>t</sub> = initial_payment * (1 + annual_stepup)<sup>floor((t - 1) / periods_per_year)</sup>
+//!
+//! and each payment is made at the end of its period.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // $500 a month for 30 years at 0.5% a month, with the contribution raised 3% each year.
+//! let solution = future_value_annuity_stepup_solution(0.005, 360, 500, 0.03, 12);
+//! dbg!(&solution);
+//! solution.series().filter(|entry| entry.period() % 12 == 1).print_table();
+//! ```
+
+use crate::*;
+use std::ops::Deref;
+
+/// Returns the **future value of a series of payments that step up once a year**.
+///
+/// Related functions:
+/// * To see the payment and balance in each period use [`future_value_annuity_stepup_solution`].
+/// * For a payment that never changes use [`future_value_annuity`].
+///
+/// See the [future_value_annuity_stepup](./index.html) module page for how the payments are
+/// calculated. As with [`future_value_annuity`] the result is negative if the payment is positive.
+///
+/// # Arguments
+/// * `rate` - The rate at which the investment grows per period, expressed as a floating point
+/// number. For instance 0.005 would mean 0.5%.
+/// * `periods` - The number of payments.
+/// * `initial_payment` - The payment in each period of the first year.
+/// * `annual_stepup` - The rate at which the payment is raised at the start of each year after the
+/// first, such as 0.03 for 3%.
+/// * `periods_per_year` - The number of periods in a year, such as 12 for monthly payments.
+///
+/// # Panics
+/// The call will fail if `periods_per_year` is zero, if `rate` or `annual_stepup` is less than or
+/// equal to -1.0, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // $500 a month for 30 years at 0.5% a month, with the contribution raised 3% each year.
+/// let fv = future_value_annuity_stepup(0.005, 360, 500, 0.03, 12);
+/// assert_rounded_4!(-700_020.0133, fv);
+///
+/// // Without the step-up it's an ordinary annuity.
+/// let level = future_value_annuity_stepup(0.005, 360, 500, 0.0, 12);
+/// assert_rounded_4!(future_value_annuity(0.005, 360, 500, false), level);
+/// ```
+pub fn future_value_annuity_stepup<P>(rate: f64, periods: u32, initial_payment: P, annual_stepup: f64, periods_per_year: u32) -> f64
+    where P: Into<f64> + Copy
+{
+    future_value_annuity_stepup_solution(rate, periods, initial_payment, annual_stepup, periods_per_year).future_value()
+}
+
+/// Returns the **future value of a series of payments that step up once a year** along with the
+/// inputs and a period-by-period schedule.
+///
+/// Related functions:
+/// * To calculate only the future value as an `f64` use [`future_value_annuity_stepup`].
+///
+/// # Arguments
+/// * `rate` - The rate at which the investment grows per period, expressed as a floating point
+/// number. For instance 0.005 would mean 0.5%.
+/// * `periods` - The number of payments.
+/// * `initial_payment` - The payment in each period of the first year.
+/// * `annual_stepup` - The rate at which the payment is raised at the start of each year after the
+/// first, such as 0.03 for 3%.
+/// * `periods_per_year` - The number of periods in a year, such as 12 for monthly payments.
+///
+/// # Panics
+/// The call will fail if `periods_per_year` is zero, if `rate` or `annual_stepup` is less than or
+/// equal to -1.0, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = future_value_annuity_stepup_solution(0.005, 24, 500, 0.03, 12);
+/// assert_rounded_4!(-12_901.0111, solution.future_value());
+///
+/// // The payment steps up in the first period of the second year.
+/// let series = solution.series();
+/// assert_eq!(24, series.len());
+/// assert_rounded_4!(500.0, series[11].payment());
+/// assert_rounded_4!(6_167.7812, series[11].balance());
+/// assert_eq!(2, series[12].year());
+/// assert_rounded_4!(515.0, series[12].payment());
+/// assert_rounded_4!(6_713.6201, series[12].balance());
+/// ```
+pub fn future_value_annuity_stepup_solution<P>(rate: f64, periods: u32, initial_payment: P, annual_stepup: f64, periods_per_year: u32) -> StepUpAnnuitySolution
+    where P: Into<f64> + Copy
+{
+    let initial_payment = initial_payment.into();
+    assert!(rate.is_finite(), "The rate must be finite (not NaN or infinity)");
+    assert!(rate > -1.0, "The rate must be greater than -1.0 (-100%).");
+    assert!(initial_payment.is_finite(), "The initial payment must be finite (not NaN or infinity)");
+    assert!(annual_stepup.is_finite(), "The annual step-up must be finite (not NaN or infinity)");
+    assert!(annual_stepup > -1.0, "The annual step-up must be greater than -1.0 (-100%).");
+    assert!(periods_per_year > 0, "The number of periods per year must be greater than zero.");
+
+    let mut balance = 0.0;
+    let series = (1..=periods)
+        .map(|period| {
+            let year = (period - 1) / periods_per_year + 1;
+            let payment = initial_payment * (1.0 + annual_stepup).powi(year as i32 - 1);
+            let interest = balance * rate;
+            balance += interest + payment;
+            StepUpAnnuityPeriod::new(period, year, payment, interest, balance)
+        })
+        .collect();
+    StepUpAnnuitySolution::new(rate, periods, initial_payment, annual_stepup, periods_per_year, -balance, series)
+}
+
+/// A record of the future value of an annuity with an annual step-up. Created by calling
+/// [`future_value_annuity_stepup_solution`].
+#[derive(Clone, Debug)]
+pub struct StepUpAnnuitySolution {
+    rate: f64,
+    periods: u32,
+    initial_payment: f64,
+    annual_stepup: f64,
+    periods_per_year: u32,
+    future_value: f64,
+    series: StepUpAnnuitySeries,
+    symbolic_formula: String,
+}
+
+impl StepUpAnnuitySolution {
+    pub(crate) fn new(rate: f64, periods: u32, initial_payment: f64, annual_stepup: f64, periods_per_year: u32, future_value: f64, series: Vec<StepUpAnnuityPeriod>) -> Self {
+        assert!(future_value.is_finite());
+        let symbolic_formula = "fv = -sum(pmt * (1 + g)^floor((t - 1) / m) * (1 + r)^(n - t))".to_string();
+        Self {
+            rate,
+            periods,
+            initial_payment,
+            annual_stepup,
+            periods_per_year,
+            future_value,
+            series: StepUpAnnuitySeries::new(series),
+            symbolic_formula,
+        }
+    }
+
+    /// Returns the periodic rate.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the number of payments.
+    pub fn periods(&self) -> u32 {
+        self.periods
+    }
+
+    /// Returns the payment in each period of the first year.
+    pub fn initial_payment(&self) -> f64 {
+        self.initial_payment
+    }
+
+    /// Returns the rate at which the payment is raised at the start of each year.
+    pub fn annual_stepup(&self) -> f64 {
+        self.annual_stepup
+    }
+
+    /// Returns the number of periods in a year.
+    pub fn periods_per_year(&self) -> u32 {
+        self.periods_per_year
+    }
+
+    /// Returns the sum of the payments before any growth.
+    pub fn sum_of_payments(&self) -> f64 {
+        self.series.iter().map(|entry| entry.payment).sum()
+    }
+
+    /// Returns the balance at the end of the last period, which is negative if the payments are
+    /// positive.
+    pub fn future_value(&self) -> f64 {
+        self.future_value
+    }
+
+    /// Returns a text version of the formula used to calculate the future value with variables
+    /// such as g for the annual step-up and m for the periods per year.
+    pub fn symbolic_formula(&self) -> &str {
+        &self.symbolic_formula
+    }
+
+    /// Returns the payment, interest, and balance in each period. The amounts have the same sign as
+    /// the payments.
+    pub fn series(&self) -> StepUpAnnuitySeries {
+        self.series.clone()
+    }
+
+    /// Prints a formatted table with the details of each period.
+    pub fn print_table(&self) {
+        self.series.print_table();
+    }
+
+    /// Prints a formatted table with the details of each period using the given locale and
+    /// precision for money amounts.
+    pub fn print_table_locale(&self, locale: &num_format::Locale, precision: usize) {
+        self.series.print_table_locale(locale, precision);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct StepUpAnnuitySeries(Vec<StepUpAnnuityPeriod>);
+
+impl StepUpAnnuitySeries {
+    pub(crate) fn new(series: Vec<StepUpAnnuityPeriod>) -> Self {
+        Self(series)
+    }
+
+    pub fn filter<P>(&self, predicate: P) -> Self
+        where P: Fn(&&StepUpAnnuityPeriod) -> bool
+    {
+        Self(self.iter().filter(|x| predicate(x)).cloned().collect())
+    }
+
+    pub fn print_table(&self) {
+        self.print_table_locale_opt(None, None);
+    }
+
+    pub fn print_table_locale(&self, locale: &num_format::Locale, precision: usize) {
+        self.print_table_locale_opt(Some(locale), Some(precision));
+    }
+
+    fn print_table_locale_opt(&self, locale: Option<&num_format::Locale>, precision: Option<usize>) {
+        let columns = columns_with_strings(&[("period", "i", true), ("year", "i", true), ("payment", "f", true), ("interest", "f", true), ("balance", "f", true)]);
+        let data = self.iter()
+            .map(|entry| vec![entry.period.to_string(), entry.year.to_string(), entry.payment.to_string(), entry.interest.to_string(), entry.balance.to_string()])
+            .collect::<Vec<_>>();
+        print_table_locale_opt(&columns, data, locale, precision);
+    }
+}
+
+impl Deref for StepUpAnnuitySeries {
+    type Target = Vec<StepUpAnnuityPeriod>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// One period within a [`StepUpAnnuitySeries`].
+#[derive(Clone, Debug)]
+pub struct StepUpAnnuityPeriod {
+    period: u32,
+    year: u32,
+    payment: f64,
+    interest: f64,
+    balance: f64,
+}
+
+impl StepUpAnnuityPeriod {
+    pub(crate) fn new(period: u32, year: u32, payment: f64, interest: f64, balance: f64) -> Self {
+        assert!(balance.is_finite());
+        Self {
+            period,
+            year,
+            payment,
+            interest,
+            balance,
+        }
+    }
+
+    /// Returns the period number, starting with 1.
+    pub fn period(&self) -> u32 {
+        self.period
+    }
+
+    /// Returns the year the period falls in, starting with 1.
+    pub fn year(&self) -> u32 {
+        self.year
+    }
+
+    /// Returns the payment made at the end of the period.
+    pub fn payment(&self) -> f64 {
+        self.payment
+    }
+
+    /// Returns the growth on the balance from the end of the previous period.
+    pub fn interest(&self) -> f64 {
+        self.interest
+    }
+
+    /// Returns the balance at the end of the period including this period's payment.
+    pub fn balance(&self) -> f64 {
+        self.balance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_future_value_annuity_stepup_by_year() {
+        // Each year's payments are an ordinary annuity that then grows for the remaining years.
+        let (rate, initial_payment, annual_stepup, periods_per_year, years): (f64, f64, f64, u32, i32) = (0.004, 250.0, 0.025, 12, 10);
+        let expected = (0..years)
+            .map(|year| {
+                let payment = initial_payment * (1.0 + annual_stepup).powi(year);
+                let year_end = future_value_annuity(rate, periods_per_year, payment, false);
+                year_end * (1.0 + rate).powi((years - year - 1) * periods_per_year as i32)
+            })
+            .sum::<f64>();
+        let solution = future_value_annuity_stepup_solution(rate, years as u32 * periods_per_year, initial_payment, annual_stepup, periods_per_year);
+        assert_approx_equal!(expected, solution.future_value());
+        assert_approx_equal!(-solution.future_value(), solution.series().last().unwrap().balance());
+    }
+
+    #[test]
+    fn test_future_value_annuity_stepup_partial_year() {
+        // A final partial year still gets the step-up.
+        let series = future_value_annuity_stepup_solution(0.01, 5, 100, 0.10, 4).series();
+        assert_eq!(2, series[4].year());
+        assert_approx_equal!(110.0, series[4].payment());
+    }
+}
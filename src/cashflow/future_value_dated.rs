@@ -0,0 +1,324 @@
+//! **Future value of irregular, dated deposits.** Given a series of deposits made on arbitrary
+//! dates, what will the account be worth on a later date?
+//!
+//! Each deposit is compounded at an annual rate from its own date to the horizon. The fraction of a
+//! year between the dates follows a [`DayCountBasis`] such as actual/365 or 30/360.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! use chrono::NaiveDate;
+//! let deposits = [
+//!     (NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(), 1_000.0),
+//!     (NaiveDate::from_ymd_opt(2020, 7, 15).unwrap(), 250.0),
+//!     (NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(), 1_000.0),
+//! ];
+//! let solution = future_value_dated_solution(0.05, &deposits, NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(), DayCountBasis::Actual365);
+//! solution.print_table();
+//! ```
+
+use chrono::NaiveDate;
+use std::ops::Deref;
+
+use crate::*;
+
+/// Returns the **future value of a series of dated deposits** on a horizon date. Each deposit
+/// grows at the annual rate from its date to the horizon, with the time between the dates measured
+/// by the given day count convention.
+///
+/// Related functions:
+/// * To see how much each deposit grows use [`future_value_dated_solution`].
+/// * For level deposits made every period use [`future_value_annuity`].
+///
+/// The formula is:
+///
+/// future_value = sum( deposit<sub>i</sub> * (1 + annual_rate)<sup>t<sub>i</sub></sup> )
+///
+/// where t<sub>i</sub> is the fraction of a year from the deposit to the horizon as calculated by
+/// [`year_fraction`].
+///
+/// Unlike [`future_value`] the result has the same sign as the deposits since this is the balance
+/// of the account rather than the cashflow needed to settle it.
+///
+/// # Arguments
+/// * `annual_rate` - The annual rate, expressed as a floating point number. For instance 0.05
+/// would mean 5%.
+/// * `deposits` - The date and amount of each deposit. The deposits don't need to be in date order.
+/// * `horizon` - The date on which to find the value of the account.
+/// * `basis` - The day count convention used to turn the days between a deposit and the horizon
+/// into a fraction of a year.
+///
+/// # Panics
+/// The call will fail if any deposit is dated after `horizon`, if the rate is less than -1.0, or if
+/// any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// use chrono::NaiveDate;
+/// // Two deposits of $1,000 a year apart grow at 5% until 2022.
+/// let deposits = [(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(), 1_000.0), (NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(), 1_000.0)];
+/// let future_value = future_value_dated(0.05, &deposits, NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(), DayCountBasis::Actual365);
+/// // The first deposit is held for 731 days since 2020 is a leap year.
+/// assert_rounded_4!(2_152.6474, future_value);
+///
+/// // Counting 30 days per month and 360 days per year each deposit is held for whole years.
+/// let future_value = future_value_dated(0.05, &deposits, NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(), DayCountBasis::Thirty360);
+/// assert_rounded_4!(2_152.5000, future_value);
+/// ```
+pub fn future_value_dated(annual_rate: f64, deposits: &[(NaiveDate, f64)], horizon: NaiveDate, basis: DayCountBasis) -> f64 {
+    future_value_dated_solution(annual_rate, deposits, horizon, basis).future_value()
+}
+
+/// Returns the **future value of a series of dated deposits** on a horizon date along with the
+/// grown value of each deposit.
+///
+/// Related functions:
+/// * To calculate only the future value as an `f64` use [`future_value_dated`].
+///
+/// # Arguments
+/// * `annual_rate` - The annual rate, expressed as a floating point number. For instance 0.05
+/// would mean 5%.
+/// * `deposits` - The date and amount of each deposit. The deposits don't need to be in date order.
+/// * `horizon` - The date on which to find the value of the account.
+/// * `basis` - The day count convention used to turn the days between a deposit and the horizon
+/// into a fraction of a year.
+///
+/// # Panics
+/// The call will fail if any deposit is dated after `horizon`, if the rate is less than -1.0, or if
+/// any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// use chrono::NaiveDate;
+/// let deposits = [(NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(), 1_000.0), (NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(), 1_000.0)];
+/// let solution = future_value_dated_solution(0.05, &deposits, NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(), DayCountBasis::Actual365);
+/// dbg!(&solution);
+///
+/// // The series is sorted by date.
+/// let series = solution.series();
+/// assert_eq!(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(), series[0].date());
+/// assert_rounded_4!(1_102.6474, series[0].future_value());
+///
+/// // The deposit made exactly one year before the horizon grows by exactly 5%.
+/// assert_rounded_4!(1_050.0000, series[1].future_value());
+/// ```
+pub fn future_value_dated_solution(annual_rate: f64, deposits: &[(NaiveDate, f64)], horizon: NaiveDate, basis: DayCountBasis) -> DatedFutureValueSolution {
+    assert!(annual_rate.is_finite(), "The rate must be finite (not NaN or infinity)");
+    assert!(annual_rate >= -1.0, "The rate must be greater than or equal to -1.0 because a rate lower than -100% would mean the investment loses more than its full value in a period.");
+    let mut deposits = deposits.to_vec();
+    deposits.sort_by_key(|(date, _)| *date);
+    let series = deposits.iter()
+        .map(|(date, amount)| {
+            assert!(amount.is_finite(), "The deposits must be finite (not NaN or infinity)");
+            assert!(*date <= horizon, "The deposit on {} is after the horizon of {}.", date, horizon);
+            let years = year_fraction(*date, horizon, basis);
+            let growth_factor = (1.0 + annual_rate).powf(years);
+            DatedDeposit::new(*date, years, *amount, growth_factor)
+        })
+        .collect::<Vec<_>>();
+    DatedFutureValueSolution::new(annual_rate, horizon, basis, series)
+}
+
+/// A record of a call to [`future_value_dated_solution`].
+#[derive(Clone, Debug)]
+pub struct DatedFutureValueSolution {
+    annual_rate: f64,
+    horizon: NaiveDate,
+    basis: DayCountBasis,
+    sum_of_deposits: f64,
+    future_value: f64,
+    series: DatedDepositSeries,
+    symbolic_formula: String,
+}
+
+impl DatedFutureValueSolution {
+    pub(crate) fn new(annual_rate: f64, horizon: NaiveDate, basis: DayCountBasis, series: Vec<DatedDeposit>) -> Self {
+        let sum_of_deposits = series.iter().map(|deposit| deposit.amount).sum();
+        let future_value = series.iter().map(|deposit| deposit.future_value).sum::<f64>();
+        assert!(future_value.is_finite());
+        Self {
+            annual_rate,
+            horizon,
+            basis,
+            sum_of_deposits,
+            future_value,
+            series: DatedDepositSeries::new(series),
+            symbolic_formula: "fv = sum(deposit_i * (1 + r)^t_i)".to_string(),
+        }
+    }
+
+    /// Returns the annual rate.
+    pub fn annual_rate(&self) -> f64 {
+        self.annual_rate
+    }
+
+    /// Returns the date on which the future value is measured.
+    pub fn horizon(&self) -> NaiveDate {
+        self.horizon
+    }
+
+    /// Returns the day count convention used to measure the time from each deposit to the horizon.
+    pub fn basis(&self) -> DayCountBasis {
+        self.basis
+    }
+
+    /// Returns the sum of the deposits before any growth.
+    pub fn sum_of_deposits(&self) -> f64 {
+        self.sum_of_deposits
+    }
+
+    /// Returns the value of all of the deposits on the horizon date.
+    pub fn future_value(&self) -> f64 {
+        self.future_value
+    }
+
+    /// Returns the growth on the deposits, that is the future value minus the sum of the deposits.
+    pub fn total_growth(&self) -> f64 {
+        self.future_value - self.sum_of_deposits
+    }
+
+    /// Returns a text version of the formula used to calculate the future value with variables
+    /// such as r for the rate.
+    pub fn symbolic_formula(&self) -> &str {
+        &self.symbolic_formula
+    }
+
+    /// Returns each deposit in date order along with its value on the horizon date.
+    pub fn series(&self) -> DatedDepositSeries {
+        self.series.clone()
+    }
+
+    /// Prints a formatted table with the details of each deposit.
+    pub fn print_table(&self) {
+        self.series.print_table();
+    }
+
+    /// Prints a formatted table with the details of each deposit using the given locale and
+    /// precision for money amounts.
+    pub fn print_table_locale(&self, locale: &num_format::Locale, precision: usize) {
+        self.series.print_table_locale(locale, precision);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DatedDepositSeries(Vec<DatedDeposit>);
+
+impl DatedDepositSeries {
+    pub(crate) fn new(series: Vec<DatedDeposit>) -> Self {
+        Self(series)
+    }
+
+    pub fn filter<P>(&self, predicate: P) -> Self
+        where P: Fn(&&DatedDeposit) -> bool
+    {
+        Self(self.iter().filter(|x| predicate(x)).cloned().collect())
+    }
+
+    pub fn print_table(&self) {
+        self.print_table_locale_opt(None, None);
+    }
+
+    pub fn print_table_locale(&self, locale: &num_format::Locale, precision: usize) {
+        self.print_table_locale_opt(Some(locale), Some(precision));
+    }
+
+    fn print_table_locale_opt(&self, locale: Option<&num_format::Locale>, precision: Option<usize>) {
+        let columns = columns_with_strings(&[("date", "s", true), ("years", "r", true), ("deposit", "f", true), ("growth_factor", "r", true), ("future_value", "f", true)]);
+        let data = self.iter()
+            .map(|entry| vec![entry.date.to_string(), entry.years.to_string(), entry.amount.to_string(), entry.growth_factor.to_string(), entry.future_value.to_string()])
+            .collect::<Vec<_>>();
+        print_table_locale_opt(&columns, data, locale, precision);
+    }
+}
+
+impl Deref for DatedDepositSeries {
+    type Target = Vec<DatedDeposit>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// One deposit within a [`DatedDepositSeries`] along with its value on the horizon date.
+#[derive(Clone, Debug)]
+pub struct DatedDeposit {
+    date: NaiveDate,
+    years: f64,
+    amount: f64,
+    growth_factor: f64,
+    future_value: f64,
+}
+
+impl DatedDeposit {
+    pub(crate) fn new(date: NaiveDate, years: f64, amount: f64, growth_factor: f64) -> Self {
+        let future_value = amount * growth_factor;
+        assert!(future_value.is_finite());
+        Self {
+            date,
+            years,
+            amount,
+            growth_factor,
+            future_value,
+        }
+    }
+
+    /// Returns the date of the deposit.
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    /// Returns the time from the deposit to the horizon in years as measured by the solution's day
+    /// count convention.
+    pub fn years(&self) -> f64 {
+        self.years
+    }
+
+    /// Returns the amount of the deposit.
+    pub fn amount(&self) -> f64 {
+        self.amount
+    }
+
+    /// Returns the factor by which the deposit grows between its date and the horizon.
+    pub fn growth_factor(&self) -> f64 {
+        self.growth_factor
+    }
+
+    /// Returns the value of the deposit on the horizon date.
+    pub fn future_value(&self) -> f64 {
+        self.future_value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_future_value_dated_two_deposits() {
+        let deposits = [(NaiveDate::from_ymd_opt(2019, 1, 1).unwrap(), 500.0), (NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(), 500.0)];
+        let solution = future_value_dated_solution(0.08, &deposits, NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(), DayCountBasis::Actual365);
+        // 2019 has 365 days so the first deposit grows for exactly one year more than it would
+        // from 2020. 2020 is a leap year so the second deposit is held for 366 days.
+        let second = 500.0 * 1.08_f64.powf(366.0 / 365.0);
+        let first = 500.0 * 1.08 * 1.08_f64.powf(366.0 / 365.0);
+        assert_approx_equal!(first + second, solution.future_value());
+        assert_approx_equal!(1_000.0, solution.sum_of_deposits());
+        assert_approx_equal!(solution.future_value() - 1_000.0, solution.total_growth());
+    }
+
+    #[test]
+    fn test_future_value_dated_on_horizon() {
+        let horizon = NaiveDate::from_ymd_opt(2021, 6, 30).unwrap();
+        let future_value = future_value_dated(0.10, &[(horizon, 750.0)], horizon, DayCountBasis::ActualActual);
+        assert_approx_equal!(750.0, future_value);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_future_value_dated_deposit_after_horizon() {
+        let deposits = [(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(), 100.0), (NaiveDate::from_ymd_opt(2022, 1, 2).unwrap(), 100.0)];
+        future_value_dated(0.05, &deposits, NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(), DayCountBasis::Actual365);
+    }
+}
@@ -0,0 +1,298 @@
+//! **Internal rate of return by Newton's method.** The internal rate of return (IRR) is the periodic
+//! rate at which the net present value of a series of cashflows is zero. Starting from a guess,
+//! each iteration moves the rate to where the tangent of the net present value curve crosses zero:
+//!
+//! rate<sub>k+1</sub> = rate<sub>k</sub> - npv(rate<sub>k</sub>) / npv'(rate<sub>k</sub>)
+//!
+//! This usually converges in a handful of iterations from a reasonable guess, but a poor guess can
+//! take many more iterations, wander far from the answer, or land on a different IRR when the
+//! cashflows have more than one. The solution keeps the rate after every iteration so the path
+//! can be examined. If an iteration would jump to a rate at or below -100% the rate is instead
+//! moved halfway to -100%.
+//!
+//! A series of cashflows that changes sign more than once may have several IRRs or none, so the
+//! solver logs a warning for such a series and [`IrrSolution::is_conventional`] reports whether
+//! the series has a single sign change. To find every IRR within a range use
+//! [`all_irrs`](./fn.all_irrs.html).
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! let cash_flows = [-10_000.0, 3_000.0, 4_200.0, 6_800.0];
+//! let solution = irr_solution_with_guess(&cash_flows, 0.9);
+//! dbg!(solution.iteration_trace());
+//! ```
+
+use crate::*;
+use log::warn;
+
+/// The starting rate used by [`irr`] and [`irr_solution`].
+pub const DEFAULT_IRR_GUESS: f64 = 0.1;
+
+/// Returns the **internal rate of return** of a series of cashflows.
+///
+/// Related functions:
+/// * To see the number of iterations and the rate after each one use [`irr_solution`].
+/// * To start from a different rate use [`irr_solution_with_guess`].
+///
+/// The search starts from [`DEFAULT_IRR_GUESS`]. See the [irr](./index.html) module page for the
+/// method. If the solver doesn't converge this is the last rate it reached, so check
+/// [`IrrSolution::converged`] when the cashflows are unusual.
+///
+/// # Arguments
+/// * `cash_flows` - The cashflows starting with period 0, which is not discounted.
+///
+/// # Panics
+/// The call will fail if there are fewer than two cashflows or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let rate = irr(&[-10_000.0, 3_000.0, 4_200.0, 6_800.0]);
+/// assert_rounded_6!(0.163406, rate);
+/// ```
+pub fn irr(cash_flows: &[f64]) -> f64 {
+    irr_solution(cash_flows).rate()
+}
+
+/// Returns the **internal rate of return** of a series of cashflows along with the number of
+/// iterations and the rate after each one.
+///
+/// Related functions:
+/// * To calculate only the rate as an `f64` use [`irr`].
+/// * To start from a different rate use [`irr_solution_with_guess`].
+///
+/// # Arguments
+/// * `cash_flows` - The cashflows starting with period 0, which is not discounted.
+///
+/// # Panics
+/// The call will fail if there are fewer than two cashflows or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = irr_solution(&[-10_000.0, 3_000.0, 4_200.0, 6_800.0]);
+/// assert!(solution.converged());
+/// assert_eq!(0.1, solution.guess());
+/// assert_eq!(5, solution.iterations());
+/// ```
+pub fn irr_solution(cash_flows: &[f64]) -> IrrSolution {
+    irr_solution_with_guess(cash_flows, DEFAULT_IRR_GUESS)
+}
+
+/// Returns the **internal rate of return** of a series of cashflows starting the search from the
+/// given rate.
+///
+/// Related functions:
+/// * To start from [`DEFAULT_IRR_GUESS`] use [`irr_solution`].
+/// * To control the precision of the solver use [`irr_solution_with_guess_and_config`].
+///
+/// # Arguments
+/// * `cash_flows` - The cashflows starting with period 0, which is not discounted.
+/// * `guess` - The rate used for the first iteration.
+///
+/// # Panics
+/// The call will fail if there are fewer than two cashflows, if `guess` is less than or equal to
+/// -1.0, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let cash_flows = [-10_000.0, 3_000.0, 4_200.0, 6_800.0];
+///
+/// // A guess close to the answer needs fewer iterations than the default.
+/// let close = irr_solution_with_guess(&cash_flows, 0.16);
+/// assert_eq!(4, close.iterations());
+///
+/// // A guess of 90% overshoots to about -83% on the first iteration and takes a while to recover.
+/// let far = irr_solution_with_guess(&cash_flows, 0.9);
+/// assert!(far.converged());
+/// assert_eq!(14, far.iterations());
+/// assert_rounded_4!(-0.8305, far.iteration_trace()[0]);
+/// assert_rounded_6!(close.rate(), far.rate());
+/// ```
+pub fn irr_solution_with_guess(cash_flows: &[f64], guess: f64) -> IrrSolution {
+    irr_solution_with_guess_and_config(cash_flows, guess, SolverConfig::default())
+}
+
+/// Returns the **internal rate of return** of a series of cashflows starting the search from the
+/// given rate and using the given tolerance and maximum number of iterations.
+///
+/// Related functions:
+/// * To solve to the full precision of an `f64` use [`irr_solution_with_guess`].
+///
+/// The solver stops when an iteration changes the rate by no more than the tolerance. With a
+/// tolerance of zero it stops when the change is too small to matter at the precision of an `f64`.
+///
+/// # Arguments
+/// * `cash_flows` - The cashflows starting with period 0, which is not discounted.
+/// * `guess` - The rate used for the first iteration.
+/// * `config` - The tolerance and maximum number of iterations for the solver.
+///
+/// # Panics
+/// The call will fail if there are fewer than two cashflows, if `guess` is less than or equal to
+/// -1.0, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let cash_flows = [-10_000.0, 3_000.0, 4_200.0, 6_800.0];
+///
+/// // Not enough iterations to get there from a poor guess.
+/// let solution = irr_solution_with_guess_and_config(&cash_flows, 0.9, SolverConfig::new(0.0, 5));
+/// assert!(!solution.converged());
+/// assert_eq!(5, solution.iteration_trace().len());
+/// ```
+pub fn irr_solution_with_guess_and_config(cash_flows: &[f64], guess: f64, config: SolverConfig) -> IrrSolution {
+    assert!(cash_flows.len() >= 2, "There must be at least two cashflows.");
+    assert!(cash_flows.iter().all(|cash_flow| cash_flow.is_finite()), "The cashflows must be finite (not NaN or infinity)");
+    assert!(guess.is_finite(), "The guess must be finite (not NaN or infinity)");
+    assert!(guess > -1.0, "The guess must be greater than -1.0 (-100%).");
+    if !is_conventional_cash_flow(cash_flows) {
+        warn!("The cashflows change sign {} times so there may be more than one internal rate of return, or none. Consider all_irrs().", sign_change_count(cash_flows));
+    }
+
+    let mut rate = guess;
+    let mut iteration_trace = vec![];
+    let mut converged = false;
+    while (iteration_trace.len() as u32) < config.max_iterations {
+        let slope = npv_derivative(cash_flows, rate);
+        if slope == 0.0 || !slope.is_finite() {
+            break;
+        }
+        let mut next_rate = rate - discounted_sum(rate, cash_flows) / slope;
+        if !next_rate.is_finite() {
+            break;
+        }
+        if next_rate <= -1.0 {
+            next_rate = (rate - 1.0) / 2.0;
+        }
+        let step = (next_rate - rate).abs();
+        rate = next_rate;
+        iteration_trace.push(rate);
+        if step <= config.tolerance.max(f64::EPSILON * rate.abs().max(1.0)) {
+            converged = true;
+            break;
+        }
+    }
+    IrrSolution::new(cash_flows, guess, rate, converged, iteration_trace)
+}
+
+fn npv_derivative(cash_flows: &[f64], rate: f64) -> f64 {
+    cash_flows.iter().enumerate()
+        .map(|(period, cash_flow)| -(period as f64) * cash_flow / (1.0 + rate).powf(period as f64 + 1.0))
+        .sum()
+}
+
+/// A record of an internal rate of return found by Newton's method. Created by calling
+/// [`irr_solution`] or [`irr_solution_with_guess`].
+#[derive(Clone, Debug)]
+pub struct IrrSolution {
+    cash_flows: Vec<f64>,
+    guess: f64,
+    rate: f64,
+    converged: bool,
+    iteration_trace: Vec<f64>,
+}
+
+impl IrrSolution {
+    pub(crate) fn new(cash_flows: &[f64], guess: f64, rate: f64, converged: bool, iteration_trace: Vec<f64>) -> Self {
+        Self {
+            cash_flows: cash_flows.to_vec(),
+            guess,
+            rate,
+            converged,
+            iteration_trace,
+        }
+    }
+
+    /// Returns the cashflows starting with period 0.
+    pub fn cash_flows(&self) -> &[f64] {
+        &self.cash_flows
+    }
+
+    /// Returns the rate used for the first iteration.
+    pub fn guess(&self) -> f64 {
+        self.guess
+    }
+
+    /// Returns the internal rate of return, or the last rate reached if the solver didn't
+    /// converge.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns true if the cashflows change sign exactly once so that the rate is the only internal
+    /// rate of return. If this is false the solver may have found one of several rates, or none.
+    /// See [`is_conventional_cash_flow`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use finance_solution::*;
+    /// assert!(irr_solution(&[-10_000.0, 3_000.0, 4_200.0, 6_800.0]).is_conventional());
+    ///
+    /// // A project with a cleanup cost at the end has two rates.
+    /// assert!(!irr_solution(&[-1_600.0, 10_000.0, -10_000.0]).is_conventional());
+    /// ```
+    pub fn is_conventional(&self) -> bool {
+        is_conventional_cash_flow(&self.cash_flows)
+    }
+
+    /// Returns the net present value of the cashflows at the reported rate, which is close to zero
+    /// if the solver converged.
+    pub fn net_present_value(&self) -> f64 {
+        discounted_sum(self.rate, &self.cash_flows)
+    }
+
+    /// Returns the number of iterations the solver used.
+    pub fn iterations(&self) -> u32 {
+        self.iteration_trace.len() as u32
+    }
+
+    /// Returns true if the rate stopped changing by more than the solver's tolerance before it ran
+    /// out of iterations.
+    pub fn converged(&self) -> bool {
+        self.converged
+    }
+
+    /// Returns the rate after each iteration, not including the guess. The last entry is the
+    /// reported rate unless the solver stopped before its first iteration because the net present
+    /// value curve was flat at the guess.
+    pub fn iteration_trace(&self) -> &[f64] {
+        &self.iteration_trace
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_irr_solution_with_guess_trace() {
+        let cash_flows = [-25_000.0, 4_000.0, 6_500.0, 8_000.0, 9_000.0, 5_500.0];
+        let default = irr_solution(&cash_flows);
+        let close = irr_solution_with_guess(&cash_flows, default.rate() + 0.001);
+        assert!(default.converged() && close.converged());
+        assert!(close.iterations() < default.iterations());
+        for solution in [default, close].iter() {
+            assert_eq!(solution.rate(), *solution.iteration_trace().last().unwrap());
+            assert_approx_equal!(0.0, solution.net_present_value());
+        }
+    }
+
+    #[test]
+    fn test_irr_solution_with_guess_multiple_irrs() {
+        // Each guess leads to the nearest of the pump project's two rates.
+        let cash_flows = [-1_600.0, 10_000.0, -10_000.0];
+        assert_rounded_6!(0.25, irr_solution_with_guess(&cash_flows, 0.1).rate());
+        assert_rounded_6!(4.0, irr_solution_with_guess(&cash_flows, 3.0).rate());
+    }
+
+    #[test]
+    fn test_irr_solution_is_conventional() {
+        assert!(!irr_solution(&[-1_600.0, 10_000.0, -10_000.0]).is_conventional());
+        // Zero cashflows don't count as a sign change.
+        assert!(irr_solution(&[-1_600.0, 0.0, 2_000.0]).is_conventional());
+        assert!(irr_solution_with_guess(&[1_000.0, -300.0, -400.0, -500.0], 0.05).is_conventional());
+    }
+}
@@ -0,0 +1,234 @@
+//! **Lease payments with a money factor.** Auto leases are usually priced with a residual value
+//! and a money factor rather than an interest rate. The monthly payment has two parts:
+//!
+//! depreciation_fee = (capitalized_cost - residual_value) / periods
+//!
+//! finance_fee = (capitalized_cost + residual_value) * money_factor
+//!
+//! The finance fee is charged on the sum of the capitalized cost and the residual, which
+//! approximates the average balance of the lease, so the payment doesn't match an annuity at any
+//! rate. By convention the money factor is turned into an annual percentage rate by multiplying it
+//! by 2,400.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // A $32,000 vehicle with a 58% residual after 36 months.
+//! let solution = lease_payment_solution(32_000, 32_000.0 * 0.58, 0.00125, 36);
+//! dbg!(&solution);
+//! ```
+
+use crate::*;
+
+/// Returns the **monthly payment on a lease priced with a money factor**. Taxes and fees are not
+/// included.
+///
+/// Related functions:
+/// * To see the depreciation and finance parts of the payment use [`lease_payment_solution`].
+///
+/// See the [lease_payment](./index.html) module page for the formula.
+///
+/// # Arguments
+/// * `capitalized_cost` - The price of the vehicle being leased after any down payment or trade-in,
+/// often called the net or adjusted capitalized cost.
+/// * `residual_value` - The value of the vehicle at the end of the lease as set by the lessor.
+/// * `money_factor` - The finance charge per period, such as 0.00125.
+/// * `periods` - The number of payments, usually months.
+///
+/// # Panics
+/// The call will fail if `periods` is zero, if `capitalized_cost` is not positive, if
+/// `residual_value` is negative or greater than `capitalized_cost`, if `money_factor` is negative,
+/// or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // A $30,000 capitalized cost with an $18,000 residual over 36 months.
+/// let pmt = lease_payment(30_000, 18_000, 0.00125, 36);
+/// assert_rounded_4!(393.3333, pmt);
+/// ```
+pub fn lease_payment<C, R>(capitalized_cost: C, residual_value: R, money_factor: f64, periods: u32) -> f64
+    where
+        C: Into<f64> + Copy,
+        R: Into<f64> + Copy
+{
+    lease_payment_solution(capitalized_cost, residual_value, money_factor, periods).payment()
+}
+
+/// Returns the **monthly payment on a lease priced with a money factor** along with its
+/// depreciation and finance parts and the equivalent annual percentage rate.
+///
+/// Related functions:
+/// * To calculate only the payment as an `f64` use [`lease_payment`].
+///
+/// # Arguments
+/// * `capitalized_cost` - The price of the vehicle being leased after any down payment or trade-in,
+/// often called the net or adjusted capitalized cost.
+/// * `residual_value` - The value of the vehicle at the end of the lease as set by the lessor.
+/// * `money_factor` - The finance charge per period, such as 0.00125.
+/// * `periods` - The number of payments, usually months.
+///
+/// # Panics
+/// The call will fail if `periods` is zero, if `capitalized_cost` is not positive, if
+/// `residual_value` is negative or greater than `capitalized_cost`, if `money_factor` is negative,
+/// or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = lease_payment_solution(30_000, 18_000, 0.00125, 36);
+/// assert_rounded_4!(333.3333, solution.depreciation_fee());
+/// assert_rounded_4!(60.0, solution.finance_fee());
+/// assert_rounded_4!(393.3333, solution.payment());
+///
+/// // A money factor of 0.00125 is quoted as a 3% APR.
+/// assert_rounded_6!(0.03, solution.apr());
+///
+/// // Over the whole lease.
+/// assert_rounded_4!(14_160.0, solution.sum_of_payments());
+/// assert_rounded_4!(2_160.0, solution.sum_of_finance_fees());
+/// ```
+pub fn lease_payment_solution<C, R>(capitalized_cost: C, residual_value: R, money_factor: f64, periods: u32) -> LeaseSolution
+    where
+        C: Into<f64> + Copy,
+        R: Into<f64> + Copy
+{
+    let capitalized_cost = capitalized_cost.into();
+    let residual_value = residual_value.into();
+    assert!(capitalized_cost.is_finite(), "The capitalized cost must be finite (not NaN or infinity)");
+    assert!(capitalized_cost > 0.0, "The capitalized cost must be greater than zero.");
+    assert!(residual_value.is_finite(), "The residual value must be finite (not NaN or infinity)");
+    assert!(residual_value >= 0.0, "The residual value must not be negative.");
+    assert!(residual_value <= capitalized_cost, "The residual value must not be greater than the capitalized cost.");
+    assert!(money_factor.is_finite(), "The money factor must be finite (not NaN or infinity)");
+    assert!(money_factor >= 0.0, "The money factor must not be negative.");
+    assert!(periods > 0, "The number of periods must be greater than zero.");
+    LeaseSolution::new(capitalized_cost, residual_value, money_factor, periods)
+}
+
+/// A record of a lease payment calculated from a money factor. Created by calling
+/// [`lease_payment_solution`].
+#[derive(Clone, Debug)]
+pub struct LeaseSolution {
+    capitalized_cost: f64,
+    residual_value: f64,
+    money_factor: f64,
+    periods: u32,
+    depreciation_fee: f64,
+    finance_fee: f64,
+    payment: f64,
+    formula: String,
+    symbolic_formula: String,
+}
+
+impl LeaseSolution {
+    pub(crate) fn new(capitalized_cost: f64, residual_value: f64, money_factor: f64, periods: u32) -> Self {
+        let depreciation_fee = (capitalized_cost - residual_value) / periods as f64;
+        let finance_fee = (capitalized_cost + residual_value) * money_factor;
+        let payment = depreciation_fee + finance_fee;
+        assert!(payment.is_finite());
+        let formula = format!("({:.4} - {:.4}) / {} + ({:.4} + {:.4}) * {:.6}",
+                              capitalized_cost, residual_value, periods, capitalized_cost, residual_value, money_factor);
+        let symbolic_formula = "pmt = (cap_cost - residual) / n + (cap_cost + residual) * mf".to_string();
+        Self {
+            capitalized_cost,
+            residual_value,
+            money_factor,
+            periods,
+            depreciation_fee,
+            finance_fee,
+            payment,
+            formula,
+            symbolic_formula,
+        }
+    }
+
+    /// Returns the capitalized cost of the vehicle.
+    pub fn capitalized_cost(&self) -> f64 {
+        self.capitalized_cost
+    }
+
+    /// Returns the value of the vehicle at the end of the lease.
+    pub fn residual_value(&self) -> f64 {
+        self.residual_value
+    }
+
+    /// Returns the money factor.
+    pub fn money_factor(&self) -> f64 {
+        self.money_factor
+    }
+
+    /// Returns the number of payments.
+    pub fn periods(&self) -> u32 {
+        self.periods
+    }
+
+    /// Returns the annual percentage rate conventionally quoted for the money factor, which is the
+    /// money factor times 2,400 as a percentage, or times 24 as a floating point number. For
+    /// instance a money factor of 0.00125 gives 0.03, meaning 3%.
+    pub fn apr(&self) -> f64 {
+        self.money_factor * 24.0
+    }
+
+    /// Returns the part of each payment that covers the loss in the vehicle's value.
+    pub fn depreciation_fee(&self) -> f64 {
+        self.depreciation_fee
+    }
+
+    /// Returns the part of each payment that is the finance charge.
+    pub fn finance_fee(&self) -> f64 {
+        self.finance_fee
+    }
+
+    /// Returns the payment per period before taxes and fees.
+    pub fn payment(&self) -> f64 {
+        self.payment
+    }
+
+    /// Returns the total of the payments over the lease.
+    pub fn sum_of_payments(&self) -> f64 {
+        self.payment * self.periods as f64
+    }
+
+    /// Returns the total of the depreciation fees, which is the capitalized cost minus the residual
+    /// value.
+    pub fn sum_of_depreciation_fees(&self) -> f64 {
+        self.depreciation_fee * self.periods as f64
+    }
+
+    /// Returns the total of the finance charges over the lease.
+    pub fn sum_of_finance_fees(&self) -> f64 {
+        self.finance_fee * self.periods as f64
+    }
+
+    /// Returns a text version of the formula used to calculate the payment with the actual values.
+    pub fn formula(&self) -> &str {
+        &self.formula
+    }
+
+    /// Returns a text version of the formula used to calculate the payment using variables such as
+    /// mf for the money factor.
+    pub fn symbolic_formula(&self) -> &str {
+        &self.symbolic_formula
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lease_payment_parts() {
+        let solution = lease_payment_solution(41_250.0, 24_750.0, 0.00210, 39);
+        assert_approx_equal!(solution.depreciation_fee() + solution.finance_fee(), solution.payment());
+        assert_approx_equal!(16_500.0, solution.sum_of_depreciation_fees());
+        assert_approx_equal!(138.6, solution.finance_fee());
+        assert_approx_equal!(0.0504, solution.apr());
+    }
+
+    #[test]
+    fn test_lease_payment_no_finance_charge() {
+        // With a money factor of zero the payment only covers depreciation.
+        assert_approx_equal!(250.0, lease_payment(20_000, 11_000, 0.0, 36));
+    }
+}
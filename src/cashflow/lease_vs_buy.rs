@@ -0,0 +1,255 @@
+//! **Leasing versus buying.** Leasing an asset such as a car means a series of lease payments and
+//! nothing to sell at the end. Buying it with a loan means loan payments, paying off whatever is
+//! still owed when the lease would have ended, and then selling the asset. The cheaper option is
+//! the one with the lower present value of its costs over the lease term.
+//!
+//! Both options are discounted at the financing rate and compared over the lease term:
+//!
+//! lease_cost = present value of the lease payments, made at the start of each period
+//!
+//! buy_cost = present value of the loan payments made during the lease term
+//! \+ present value of the loan balance at the end of the lease term
+//! \- present value of the resale value at the end of the lease term
+//!
+//! Since the loan is discounted at its own rate the first two parts of the buy cost always add up
+//! to the purchase price, whatever the length of the loan. The decision comes down to whether the
+//! lease payments cost more than the loss in value of the asset.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // Lease for $450 a month for 36 months or buy for $35,000 with a 60-month loan at 0.5% a month
+//! // and sell for $18,000 after 36 months.
+//! let decision = lease_vs_buy(450, 36, 35_000, 0.005, 18_000, 60);
+//! dbg!(&decision);
+//! ```
+
+use crate::*;
+
+/// The cheaper of leasing and buying as found by [`lease_vs_buy`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum LeaseOrBuy {
+    Lease,
+    Buy,
+}
+
+/// Compares the **present value of leasing an asset with the present value of buying it** with a
+/// loan and selling it at the end of the lease term.
+///
+/// See the [lease_vs_buy](./index.html) module page for how the costs are calculated. The lease
+/// payments are made at the start of each period as is usual for a lease and the loan payments at
+/// the end of each period. All amounts are entered as positive numbers and the costs in the result
+/// are positive.
+///
+/// # Arguments
+/// * `lease_payment` - The lease payment per period.
+/// * `lease_periods` - The number of lease payments, which is also the length of the comparison.
+/// * `purchase_price` - The price of the asset, all of which is financed.
+/// * `financing_rate` - The periodic rate on the loan, which is also used to discount both options.
+/// * `resale_value` - The amount the asset can be sold for at the end of the lease term.
+/// * `financing_periods` - The number of loan payments. This may be longer or shorter than the
+/// lease term.
+///
+/// # Panics
+/// The call will fail if `lease_periods` or `financing_periods` is zero, if `financing_rate` is
+/// less than or equal to -1.0, if any amount is negative, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let decision = lease_vs_buy(450, 36, 35_000, 0.005, 18_000, 60);
+/// assert_rounded_4!(676.6481, decision.loan_payment());
+/// assert_rounded_4!(14_865.9171, decision.lease_cost());
+/// assert_rounded_4!(19_958.3915, decision.buy_cost());
+/// assert_eq!(LeaseOrBuy::Lease, decision.cheaper());
+/// assert_rounded_4!(5_092.4744, decision.savings());
+///
+/// // If the asset holds its value better buying comes out ahead.
+/// assert_rounded_4!(24_094.0649, decision.breakeven_resale_value());
+/// let decision = lease_vs_buy(450, 36, 35_000, 0.005, 25_000, 60);
+/// assert_eq!(LeaseOrBuy::Buy, decision.cheaper());
+/// assert_rounded_4!(757.0401, decision.savings());
+/// ```
+pub fn lease_vs_buy<L, P, R>(lease_payment: L, lease_periods: u32, purchase_price: P, financing_rate: f64, resale_value: R, financing_periods: u32) -> LeaseVsBuyDecision
+    where
+        L: Into<f64> + Copy,
+        P: Into<f64> + Copy,
+        R: Into<f64> + Copy
+{
+    let lease_payment = lease_payment.into();
+    let purchase_price = purchase_price.into();
+    let resale_value = resale_value.into();
+    assert!(lease_payment.is_finite(), "The lease payment must be finite (not NaN or infinity)");
+    assert!(lease_payment >= 0.0, "The lease payment must not be negative.");
+    assert!(lease_periods > 0, "The number of lease periods must be greater than zero.");
+    assert!(purchase_price.is_finite(), "The purchase price must be finite (not NaN or infinity)");
+    assert!(purchase_price >= 0.0, "The purchase price must not be negative.");
+    assert!(financing_rate.is_finite(), "The financing rate must be finite (not NaN or infinity)");
+    assert!(financing_rate > -1.0, "The financing rate must be greater than -1.0 (-100%).");
+    assert!(resale_value.is_finite(), "The resale value must be finite (not NaN or infinity)");
+    assert!(resale_value >= 0.0, "The resale value must not be negative.");
+    assert!(financing_periods > 0, "The number of financing periods must be greater than zero.");
+
+    LeaseVsBuyDecision::new(lease_payment, lease_periods, purchase_price, financing_rate, resale_value, financing_periods)
+}
+
+// The present value of a series of positive payments as a positive amount.
+fn annuity_cost(rate: f64, periods: u32, payment: f64, due_at_beginning: bool) -> f64 {
+    if rate == 0.0 {
+        payment * periods as f64
+    } else {
+        -present_value_annuity(rate, periods, payment, due_at_beginning)
+    }
+}
+
+// The present value of a single positive amount as a positive amount.
+fn discount(rate: f64, periods: u32, amount: f64) -> f64 {
+    if amount == 0.0 {
+        0.0
+    } else {
+        -present_value(rate, periods, amount, false)
+    }
+}
+
+/// The result of comparing leasing with buying. Created by calling [`lease_vs_buy`].
+#[derive(Clone, Debug)]
+pub struct LeaseVsBuyDecision {
+    lease_payment: f64,
+    lease_periods: u32,
+    purchase_price: f64,
+    financing_rate: f64,
+    resale_value: f64,
+    financing_periods: u32,
+    loan_payment: f64,
+    lease_cost: f64,
+    buy_cost: f64,
+    breakeven_resale_value: f64,
+}
+
+impl LeaseVsBuyDecision {
+    pub(crate) fn new(lease_payment: f64, lease_periods: u32, purchase_price: f64, financing_rate: f64, resale_value: f64, financing_periods: u32) -> Self {
+        let lease_cost = annuity_cost(financing_rate, lease_periods, lease_payment, true);
+        let loan_payment = -payment(financing_rate, financing_periods, purchase_price, 0, false);
+        let loan_cost = annuity_cost(financing_rate, lease_periods.min(financing_periods), loan_payment, false);
+        let loan_balance = if financing_periods > lease_periods {
+            annuity_cost(financing_rate, financing_periods - lease_periods, loan_payment, false)
+        } else {
+            0.0
+        };
+        let balance_cost = discount(financing_rate, lease_periods, loan_balance);
+        let resale_credit = discount(financing_rate, lease_periods, resale_value);
+        let breakeven_resale_value = (loan_cost + balance_cost - lease_cost) * (1.0 + financing_rate).powi(lease_periods as i32);
+        let buy_cost = loan_cost + balance_cost - resale_credit;
+        assert!(lease_cost.is_finite());
+        assert!(buy_cost.is_finite());
+        Self {
+            lease_payment,
+            lease_periods,
+            purchase_price,
+            financing_rate,
+            resale_value,
+            financing_periods,
+            loan_payment,
+            lease_cost,
+            buy_cost,
+            breakeven_resale_value,
+        }
+    }
+
+    /// Returns the lease payment per period.
+    pub fn lease_payment(&self) -> f64 {
+        self.lease_payment
+    }
+
+    /// Returns the number of lease payments.
+    pub fn lease_periods(&self) -> u32 {
+        self.lease_periods
+    }
+
+    /// Returns the price of the asset.
+    pub fn purchase_price(&self) -> f64 {
+        self.purchase_price
+    }
+
+    /// Returns the periodic rate on the loan, which is also the discount rate.
+    pub fn financing_rate(&self) -> f64 {
+        self.financing_rate
+    }
+
+    /// Returns the amount the asset is sold for at the end of the lease term.
+    pub fn resale_value(&self) -> f64 {
+        self.resale_value
+    }
+
+    /// Returns the number of loan payments.
+    pub fn financing_periods(&self) -> u32 {
+        self.financing_periods
+    }
+
+    /// Returns the loan payment per period as a positive amount.
+    pub fn loan_payment(&self) -> f64 {
+        self.loan_payment
+    }
+
+    /// Returns the present value of the lease payments.
+    pub fn lease_cost(&self) -> f64 {
+        self.lease_cost
+    }
+
+    /// Returns the present value of the loan payments and the payoff of the loan at the end of the
+    /// lease term, less the present value of the resale value.
+    pub fn buy_cost(&self) -> f64 {
+        self.buy_cost
+    }
+
+    /// Returns the lease cost less the buy cost. This is positive if buying is cheaper.
+    pub fn present_value_difference(&self) -> f64 {
+        self.lease_cost - self.buy_cost
+    }
+
+    /// Returns the cheaper option. If the costs are the same this is [`LeaseOrBuy::Buy`].
+    pub fn cheaper(&self) -> LeaseOrBuy {
+        if self.lease_cost < self.buy_cost {
+            LeaseOrBuy::Lease
+        } else {
+            LeaseOrBuy::Buy
+        }
+    }
+
+    /// Returns how much less the cheaper option costs in present value terms.
+    pub fn savings(&self) -> f64 {
+        self.present_value_difference().abs()
+    }
+
+    /// Returns the resale value at which leasing and buying cost the same. With a higher resale
+    /// value buying is cheaper.
+    pub fn breakeven_resale_value(&self) -> f64 {
+        self.breakeven_resale_value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lease_vs_buy_breakeven_resale_value() {
+        let decision = lease_vs_buy(525.0, 24, 41_000.0, 0.004, 0.0, 48);
+        let breakeven = decision.breakeven_resale_value();
+        let at_breakeven = lease_vs_buy(525.0, 24, 41_000.0, 0.004, breakeven, 48);
+        assert_approx_equal!(0.0, at_breakeven.present_value_difference());
+        assert_eq!(LeaseOrBuy::Lease, lease_vs_buy(525.0, 24, 41_000.0, 0.004, breakeven - 1.0, 48).cheaper());
+        assert_eq!(LeaseOrBuy::Buy, lease_vs_buy(525.0, 24, 41_000.0, 0.004, breakeven + 1.0, 48).cheaper());
+    }
+
+    #[test]
+    fn test_lease_vs_buy_loan_length() {
+        // Discounted at the loan's own rate the loan costs the purchase price however long it is,
+        // including a loan paid off before the lease would end and a loan at 0%.
+        for &(financing_rate, financing_periods) in [(0.006, 72), (0.006, 36), (0.006, 12), (0.0, 60)].iter() {
+            let decision = lease_vs_buy(400.0, 36, 30_000.0, financing_rate, 15_000.0, financing_periods);
+            let resale_credit = 15_000.0 / (1.0 + financing_rate as f64).powi(36);
+            assert_approx_equal!(30_000.0 - resale_credit, decision.buy_cost());
+        }
+    }
+}
@@ -0,0 +1,237 @@
+//! **Yield on a loan bought at a premium or discount.** When a loan is sold on the secondary market
+//! for more or less than its remaining balance the buyer's yield differs from the note rate. It's
+//! the internal rate of return of paying the purchase price now and receiving the remaining
+//! payments, which is the rate y that solves:
+//!
+//! 0 = -purchase_price + sum( payment<sub>t</sub> / (1 + y)<sup>t</sup> )
+//!
+//! where the first remaining payment is at the end of period 1. Buying below the balance gives a
+//! yield above the note rate and buying above it gives a yield below the note rate.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // Twelve more payments of $1,500 followed by twelve of $1,000, bought for $20,000.
+//! let mut remaining_payments = vec![1_500.0; 12];
+//! remaining_payments.extend(vec![1_000.0; 12]);
+//! let solution = loan_purchase_yield_solution(&remaining_payments, 20_000.0);
+//! dbg!(&solution);
+//! ```
+
+use crate::*;
+
+/// Returns the **periodic yield from buying the remaining payments of a loan** for a given price.
+///
+/// Related functions:
+/// * To see the number of iterations used by the solver use [`loan_purchase_yield_solution`].
+/// * To find the rate of a new loan from its payment use [`rate_from_payment`].
+///
+/// See the [loan_purchase_yield](./index.html) module page for the equation that's solved.
+///
+/// # Arguments
+/// * `remaining_payments` - The payments still to be received, one per period starting with the end
+/// of period 1.
+/// * `purchase_price` - The amount paid for the loan.
+///
+/// # Panics
+/// The call will fail if `purchase_price` is not positive, if any payment is negative, if the
+/// payments are all zero, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // A $100,000 loan at 0.5% a month for 60 months has 48 payments left.
+/// let payment = -payment(0.005, 60, 100_000, 0, false);
+/// let remaining_payments = vec![payment; 48];
+/// let balance = -present_value_annuity(0.005, 48, payment, false);
+/// assert_rounded_4!(82_319.6833, balance);
+///
+/// // Buying it at 95% of the balance yields more than the note rate.
+/// let rate = loan_purchase_yield(&remaining_payments, balance * 0.95);
+/// assert_rounded_6!(0.007212, rate);
+///
+/// // Buying it at the balance yields the note rate.
+/// assert_rounded_6!(0.005, loan_purchase_yield(&remaining_payments, balance));
+/// ```
+pub fn loan_purchase_yield(remaining_payments: &[f64], purchase_price: f64) -> f64 {
+    loan_purchase_yield_solution(remaining_payments, purchase_price).rate()
+}
+
+/// Returns the **periodic yield from buying the remaining payments of a loan** along with the
+/// purchase price, the formula, and the work done by the solver.
+///
+/// Related functions:
+/// * To calculate only the yield as an `f64` use [`loan_purchase_yield`].
+/// * To control the precision of the solver use [`loan_purchase_yield_solution_with_config`].
+///
+/// # Arguments
+/// * `remaining_payments` - The payments still to be received, one per period starting with the end
+/// of period 1.
+/// * `purchase_price` - The amount paid for the loan.
+///
+/// # Panics
+/// The call will fail if `purchase_price` is not positive, if any payment is negative, if the
+/// payments are all zero, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let mut remaining_payments = vec![1_500.0; 12];
+/// remaining_payments.extend(vec![1_000.0; 12]);
+/// let solution = loan_purchase_yield_solution(&remaining_payments, 20_000.0);
+/// assert_rounded_6!(0.039671, solution.rate());
+/// assert_rounded_4!(30_000.0, solution.sum_of_payments());
+/// assert!(solution.converged());
+/// ```
+pub fn loan_purchase_yield_solution(remaining_payments: &[f64], purchase_price: f64) -> LoanPurchaseYieldSolution {
+    loan_purchase_yield_solution_with_config(remaining_payments, purchase_price, SolverConfig::default())
+}
+
+/// Returns the **periodic yield from buying the remaining payments of a loan** using the given
+/// tolerance and maximum number of iterations.
+///
+/// Related functions:
+/// * To solve to the full precision of an `f64` use [`loan_purchase_yield_solution`].
+///
+/// # Arguments
+/// * `remaining_payments` - The payments still to be received, one per period starting with the end
+/// of period 1.
+/// * `purchase_price` - The amount paid for the loan.
+/// * `config` - The tolerance and maximum number of iterations for the solver.
+///
+/// # Panics
+/// The call will fail if `purchase_price` is not positive, if any payment is negative, if the
+/// payments are all zero, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let config = SolverConfig::new(0.000_001, 100);
+/// let solution = loan_purchase_yield_solution_with_config(&[400.0; 36], 12_000.0, config);
+/// assert!(solution.converged());
+/// assert_rounded_4!(0.0102, solution.rate());
+/// ```
+pub fn loan_purchase_yield_solution_with_config(remaining_payments: &[f64], purchase_price: f64, config: SolverConfig) -> LoanPurchaseYieldSolution {
+    assert!(purchase_price.is_finite(), "The purchase price must be finite (not NaN or infinity)");
+    assert!(purchase_price > 0.0, "The purchase price must be greater than zero.");
+    for payment in remaining_payments.iter() {
+        assert!(payment.is_finite(), "The payments must be finite (not NaN or infinity)");
+        assert!(*payment >= 0.0, "The payments must be zero or positive.");
+    }
+    assert!(remaining_payments.iter().any(|payment| *payment > 0.0), "At least one payment must be greater than zero.");
+
+    let net_present_value = |rate: f64| -purchase_price + remaining_payments.iter().enumerate()
+        .map(|(index, payment)| payment / (1.0 + rate).powi(index as i32 + 1))
+        .sum::<f64>();
+
+    let solver::SolverOutcome { value: rate, iterations, converged } = if net_present_value(0.0) == 0.0 {
+        // The payments add up to exactly the purchase price.
+        solver::SolverOutcome { value: 0.0, iterations: 0, converged: true }
+    } else {
+        // The net present value falls as the rate rises from far above zero near -100% to minus
+        // the purchase price, so there's always a yield to find.
+        solver::bracket_and_bisect(&config, |rate| net_present_value(rate) > 0.0)
+            .expect("Unable to find the yield.")
+    };
+
+    LoanPurchaseYieldSolution::new(remaining_payments.to_vec(), purchase_price, rate, iterations, converged)
+}
+
+/// A record of the yield from buying the remaining payments of a loan. Created by calling
+/// [`loan_purchase_yield_solution`].
+#[derive(Clone, Debug)]
+pub struct LoanPurchaseYieldSolution {
+    remaining_payments: Vec<f64>,
+    purchase_price: f64,
+    rate: f64,
+    iterations: u32,
+    converged: bool,
+    formula: String,
+    symbolic_formula: String,
+}
+
+impl LoanPurchaseYieldSolution {
+    pub(crate) fn new(remaining_payments: Vec<f64>, purchase_price: f64, rate: f64, iterations: u32, converged: bool) -> Self {
+        assert!(rate.is_finite());
+        let formula = format!("0 = -{:.4} + {}", purchase_price, remaining_payments.iter().enumerate()
+            .map(|(index, payment)| format!("{:.4} / {:.6}^{}", payment, 1.0 + rate, index + 1))
+            .collect::<Vec<_>>()
+            .join(" + "));
+        let symbolic_formula = "0 = -purchase_price + sum(payment_t / (1 + y)^t)".to_string();
+        Self {
+            remaining_payments,
+            purchase_price,
+            rate,
+            iterations,
+            converged,
+            formula,
+            symbolic_formula,
+        }
+    }
+
+    /// Returns the payments still to be received.
+    pub fn remaining_payments(&self) -> &[f64] {
+        &self.remaining_payments
+    }
+
+    /// Returns the amount paid for the loan.
+    pub fn purchase_price(&self) -> f64 {
+        self.purchase_price
+    }
+
+    /// Returns the total of the remaining payments without any discounting.
+    pub fn sum_of_payments(&self) -> f64 {
+        self.remaining_payments.iter().sum()
+    }
+
+    /// Returns the periodic yield earned by the buyer.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the number of iterations the solver used to find the yield. This is zero if the
+    /// payments add up to exactly the purchase price.
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    /// Returns true if the yield was found to within the solver's tolerance before it ran out of
+    /// iterations.
+    pub fn converged(&self) -> bool {
+        self.converged
+    }
+
+    /// Returns a text version of the equation that was solved with the actual values.
+    pub fn formula(&self) -> &str {
+        &self.formula
+    }
+
+    /// Returns a text version of the equation that was solved using variables such as y for the
+    /// yield.
+    pub fn symbolic_formula(&self) -> &str {
+        &self.symbolic_formula
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_loan_purchase_yield_premium_and_discount() {
+        let note_rate = 0.0075;
+        let payment = -payment(note_rate, 120, 50_000, 0, false);
+        let remaining_payments = vec![payment; 90];
+        let balance = -present_value_annuity(note_rate, 90, payment, false);
+        assert_rounded_8!(note_rate, loan_purchase_yield(&remaining_payments, balance));
+        assert!(loan_purchase_yield(&remaining_payments, balance * 0.9) > note_rate);
+        assert!(loan_purchase_yield(&remaining_payments, balance * 1.1) < note_rate);
+    }
+
+    #[test]
+    fn test_loan_purchase_yield_zero() {
+        let solution = loan_purchase_yield_solution(&[250.0, 250.0, 0.0, 500.0], 1_000.0);
+        assert_eq!(0.0, solution.rate());
+        assert_eq!(0, solution.iterations());
+    }
+}
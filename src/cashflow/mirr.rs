@@ -0,0 +1,373 @@
+//! **Modified internal rate of return.** The internal rate of return assumes that every cashflow
+//! received is reinvested at the IRR itself. The modified internal rate of return (MIRR) instead
+//! discounts the outflows to the start at a finance rate and grows the inflows to the end at a
+//! reinvestment rate:
+//!
+//! mirr = (fv_of_inflows / pv_of_outflows)<sup>1/n</sup> - 1
+//!
+//! where n is the number of periods after period 0.
+//!
+//! [`net_present_value_with_reinvestment`] uses the same terminal value in a net present value,
+//! discounting it to the start at the discount rate and subtracting the outflows. The result is
+//! positive exactly when the MIRR is above the discount rate.
+//!
+//! [`mirr_equals_irr_reinvestment_rate`] finds the reinvestment rate at which the two measures
+//! agree. When the only outflow is at the start this is the IRR itself, which is the assumption
+//! built into the IRR.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! let cash_flows = [-1_000.0, -500.0, 800.0, 900.0];
+//! let rate = mirr(&cash_flows, 0.06, 0.10);
+//! dbg!(rate, mirr_equals_irr_reinvestment_rate(&cash_flows, 0.06));
+//! ```
+
+use crate::*;
+
+/// Returns the **modified internal rate of return** of a series of cashflows.
+///
+/// See the [mirr](./index.html) module page for the formula.
+///
+/// # Arguments
+/// * `cash_flows` - The cashflows starting with period 0. Negative values are outflows and
+/// positive values are inflows.
+/// * `finance_rate` - The periodic rate at which the outflows are discounted to period 0.
+/// * `reinvestment_rate` - The periodic rate at which the inflows grow until the last period.
+///
+/// # Panics
+/// The call will fail if there are fewer than two cashflows, if there isn't at least one inflow
+/// and one outflow, if either rate is less than or equal to -1.0, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let rate = mirr(&[-10_000.0, 3_000.0, 4_200.0, 6_800.0], 0.10, 0.12);
+/// assert_rounded_6!(0.151471, rate);
+/// ```
+pub fn mirr(cash_flows: &[f64], finance_rate: f64, reinvestment_rate: f64) -> f64 {
+    check_mirr_parameters(cash_flows, finance_rate);
+    assert!(reinvestment_rate.is_finite(), "The reinvestment rate must be finite (not NaN or infinity)");
+    assert!(reinvestment_rate > -1.0, "The reinvestment rate must be greater than -1.0 (-100%).");
+    let periods = (cash_flows.len() - 1) as f64;
+    (future_value_of_inflows(cash_flows, reinvestment_rate) / present_value_of_outflows(cash_flows, finance_rate)).powf(1.0 / periods) - 1.0
+}
+
+/// Returns the **net present value of a series of cashflows where the inflows are reinvested** at
+/// a given rate until the last period.
+///
+/// Related functions:
+/// * To see the terminal value and the outflows separately use
+/// [`net_present_value_with_reinvestment_solution`].
+/// * To discount every cashflow at the same rate use [`npv_profile`].
+///
+/// The inflows are grown to the last period at the reinvestment rate to form a single terminal
+/// value which is discounted back at the discount rate. The outflows are discounted at the discount
+/// rate as well:
+///
+/// npv = fv_of_inflows / (1 + discount_rate)<sup>n</sup> - pv_of_outflows
+///
+/// This is the [`mirr`] expressed as a net present value. With the discount rate used as the
+/// finance rate the net present value is positive when the MIRR is above the discount rate, zero
+/// when they're equal, and negative when it's below.
+///
+/// # Arguments
+/// * `cash_flows` - The cashflows starting with period 0. Negative values are outflows and
+/// positive values are inflows.
+/// * `discount_rate` - The periodic rate at which the terminal value and the outflows are
+/// discounted to period 0.
+/// * `reinvestment_rate` - The periodic rate at which the inflows grow until the last period.
+///
+/// # Panics
+/// The call will fail if there are no cashflows, if either rate is less than or equal to -1.0, or
+/// if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let cash_flows = [-10_000.0, 3_000.0, 4_200.0, 6_800.0];
+/// let npv = net_present_value_with_reinvestment(&cash_flows, 0.10, 0.12);
+/// assert_rounded_4!(1_470.4733, npv);
+///
+/// // Reinvesting at the discount rate gives the ordinary net present value.
+/// assert_rounded_4!(npv_profile(&cash_flows, &[0.10])[0].1, net_present_value_with_reinvestment(&cash_flows, 0.10, 0.10));
+/// ```
+pub fn net_present_value_with_reinvestment(cash_flows: &[f64], discount_rate: f64, reinvestment_rate: f64) -> f64 {
+    net_present_value_with_reinvestment_solution(cash_flows, discount_rate, reinvestment_rate).net_present_value()
+}
+
+/// Returns the **net present value of a series of cashflows where the inflows are reinvested** at
+/// a given rate until the last period, along with the terminal value and the outflows.
+///
+/// Related functions:
+/// * To calculate only the net present value as an `f64` use
+/// [`net_present_value_with_reinvestment`].
+///
+/// # Arguments
+/// * `cash_flows` - The cashflows starting with period 0. Negative values are outflows and
+/// positive values are inflows.
+/// * `discount_rate` - The periodic rate at which the terminal value and the outflows are
+/// discounted to period 0.
+/// * `reinvestment_rate` - The periodic rate at which the inflows grow until the last period.
+///
+/// # Panics
+/// The call will fail if there are no cashflows, if either rate is less than or equal to -1.0, or
+/// if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let cash_flows = [-10_000.0, 3_000.0, 4_200.0, 6_800.0];
+/// let solution = net_present_value_with_reinvestment_solution(&cash_flows, 0.10, 0.12);
+/// assert_eq!(3, solution.periods());
+///
+/// // The inflows grown to period 3 at 12% and discounted back at 10%.
+/// assert_rounded_4!(15_267.2, solution.terminal_value());
+/// assert_rounded_4!(11_470.4733, solution.present_value_of_terminal_value());
+///
+/// // Less the outflows discounted at 10%.
+/// assert_rounded_4!(10_000.0, solution.initial_outlay());
+/// assert_rounded_4!(1_470.4733, solution.net_present_value());
+///
+/// // The same terminal value and outflows give the MIRR.
+/// let rate = mirr(&cash_flows, 0.10, 0.12);
+/// assert_rounded_4!(solution.initial_outlay() * (1.0 + rate).powi(3), solution.terminal_value());
+/// ```
+pub fn net_present_value_with_reinvestment_solution(cash_flows: &[f64], discount_rate: f64, reinvestment_rate: f64) -> ReinvestmentNpvSolution {
+    assert!(!cash_flows.is_empty(), "There must be at least one cashflow.");
+    for cash_flow in cash_flows.iter() {
+        assert!(cash_flow.is_finite(), "The cashflows must be finite (not NaN or infinity)");
+    }
+    assert!(discount_rate.is_finite(), "The discount rate must be finite (not NaN or infinity)");
+    assert!(discount_rate > -1.0, "The discount rate must be greater than -1.0 (-100%).");
+    assert!(reinvestment_rate.is_finite(), "The reinvestment rate must be finite (not NaN or infinity)");
+    assert!(reinvestment_rate > -1.0, "The reinvestment rate must be greater than -1.0 (-100%).");
+    let periods = (cash_flows.len() - 1) as u32;
+    let terminal_value = future_value_of_inflows(cash_flows, reinvestment_rate);
+    let initial_outlay = present_value_of_outflows(cash_flows, discount_rate);
+    ReinvestmentNpvSolution::new(cash_flows, discount_rate, reinvestment_rate, periods, terminal_value, initial_outlay)
+}
+
+/// A record of a net present value with the inflows reinvested until the last period. Created by
+/// calling [`net_present_value_with_reinvestment_solution`].
+#[derive(Clone, Debug)]
+pub struct ReinvestmentNpvSolution {
+    cash_flows: Vec<f64>,
+    discount_rate: f64,
+    reinvestment_rate: f64,
+    periods: u32,
+    terminal_value: f64,
+    present_value_of_terminal_value: f64,
+    initial_outlay: f64,
+}
+
+impl ReinvestmentNpvSolution {
+    pub(crate) fn new(cash_flows: &[f64], discount_rate: f64, reinvestment_rate: f64, periods: u32, terminal_value: f64, initial_outlay: f64) -> Self {
+        let present_value_of_terminal_value = terminal_value / (1.0 + discount_rate).powf(periods as f64);
+        assert!(present_value_of_terminal_value.is_finite());
+        Self {
+            cash_flows: cash_flows.to_vec(),
+            discount_rate,
+            reinvestment_rate,
+            periods,
+            terminal_value,
+            present_value_of_terminal_value,
+            initial_outlay,
+        }
+    }
+
+    /// Returns the cashflows starting with period 0.
+    pub fn cash_flows(&self) -> &[f64] {
+        &self.cash_flows
+    }
+
+    /// Returns the rate at which the terminal value and the outflows are discounted.
+    pub fn discount_rate(&self) -> f64 {
+        self.discount_rate
+    }
+
+    /// Returns the rate at which the inflows grow until the last period.
+    pub fn reinvestment_rate(&self) -> f64 {
+        self.reinvestment_rate
+    }
+
+    /// Returns the number of periods after period 0, which is when the terminal value is received.
+    pub fn periods(&self) -> u32 {
+        self.periods
+    }
+
+    /// Returns the inflows grown to the last period at the reinvestment rate.
+    pub fn terminal_value(&self) -> f64 {
+        self.terminal_value
+    }
+
+    /// Returns the terminal value discounted to period 0 at the discount rate.
+    pub fn present_value_of_terminal_value(&self) -> f64 {
+        self.present_value_of_terminal_value
+    }
+
+    /// Returns the outflows discounted to period 0 at the discount rate, as a positive amount.
+    pub fn initial_outlay(&self) -> f64 {
+        self.initial_outlay
+    }
+
+    /// Returns the present value of the terminal value less the initial outlay.
+    pub fn net_present_value(&self) -> f64 {
+        self.present_value_of_terminal_value - self.initial_outlay
+    }
+}
+
+/// Returns the **reinvestment rate at which the modified internal rate of return equals the
+/// internal rate of return**, or `None` if there's no such rate.
+///
+/// Related functions:
+/// * To control the precision of the solver use [`mirr_equals_irr_reinvestment_rate_with_config`].
+///
+/// The result is `None` if the cashflows aren't conventional, since then the IRR may not be unique
+/// (see [`is_conventional_cash_flow`]), or if all of the inflows are in the last period so that the
+/// reinvestment rate has no effect.
+///
+/// # Arguments
+/// * `cash_flows` - The cashflows starting with period 0. Negative values are outflows and
+/// positive values are inflows.
+/// * `finance_rate` - The periodic rate at which the outflows are discounted to period 0.
+///
+/// # Panics
+/// The call will fail if there are fewer than two cashflows, if there isn't at least one inflow
+/// and one outflow, if `finance_rate` is less than or equal to -1.0, or if any value is not
+/// finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // With a single outflow at the start the answer is the IRR, whatever the finance rate.
+/// let cash_flows = [-1_000.0, 300.0, 400.0, 500.0];
+/// let rate = mirr_equals_irr_reinvestment_rate(&cash_flows, 0.08).unwrap();
+/// assert_rounded_6!(0.088963, rate);
+/// assert_rounded_6!(all_irrs(&cash_flows, (0.0, 1.0), 10)[0], rate);
+///
+/// // With a later outflow it depends on the finance rate.
+/// let cash_flows = [-1_000.0, -500.0, 800.0, 900.0];
+/// let rate = mirr_equals_irr_reinvestment_rate(&cash_flows, 0.10).unwrap();
+/// assert_rounded_6!(0.032354, rate);
+/// assert_rounded_6!(0.058672, mirr(&cash_flows, 0.10, rate));
+/// ```
+pub fn mirr_equals_irr_reinvestment_rate(cash_flows: &[f64], finance_rate: f64) -> Option<f64> {
+    mirr_equals_irr_reinvestment_rate_with_config(cash_flows, finance_rate, SolverConfig::default())
+}
+
+/// Returns the **reinvestment rate at which the modified internal rate of return equals the
+/// internal rate of return** using the given tolerance and maximum number of iterations, or `None`
+/// if there's no such rate.
+///
+/// Related functions:
+/// * To solve to the full precision of an `f64` use [`mirr_equals_irr_reinvestment_rate`].
+///
+/// # Arguments
+/// * `cash_flows` - The cashflows starting with period 0. Negative values are outflows and
+/// positive values are inflows.
+/// * `finance_rate` - The periodic rate at which the outflows are discounted to period 0.
+/// * `config` - The tolerance and maximum number of iterations for the solver.
+///
+/// # Panics
+/// The call will fail if there are fewer than two cashflows, if there isn't at least one inflow
+/// and one outflow, if `finance_rate` is less than or equal to -1.0, or if any value is not
+/// finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let config = SolverConfig::new(0.000_001, 100);
+/// let rate = mirr_equals_irr_reinvestment_rate_with_config(&[-1_000.0, -500.0, 800.0, 900.0], 0.06, config).unwrap();
+/// assert_rounded_4!(0.0578, rate);
+/// ```
+pub fn mirr_equals_irr_reinvestment_rate_with_config(cash_flows: &[f64], finance_rate: f64, config: SolverConfig) -> Option<f64> {
+    check_mirr_parameters(cash_flows, finance_rate);
+    if !is_conventional_cash_flow(cash_flows) {
+        return None;
+    }
+    let last_index = cash_flows.len() - 1;
+    if cash_flows[..last_index].iter().all(|cash_flow| *cash_flow <= 0.0) {
+        return None;
+    }
+
+    // A conventional series has exactly one IRR. Below it the net present value has the sign of
+    // the last nonzero cashflow and above it the sign of the first.
+    let last_sign = cash_flows.iter().rev().find(|cash_flow| **cash_flow != 0.0).unwrap().signum();
+    let npv = |rate: f64| discounted_sum(rate, cash_flows);
+    let irr = solver::bracket_and_bisect(&config, |rate| npv(rate).signum() == last_sign)?.value;
+
+    // The MIRR equals the IRR when the inflows grow to the outflows compounded at the IRR. The
+    // future value of the inflows rises with the reinvestment rate.
+    let target = present_value_of_outflows(cash_flows, finance_rate) * (1.0 + irr).powf(last_index as f64);
+    solver::bracket_and_bisect(&config, |rate| future_value_of_inflows(cash_flows, rate) < target)
+        .map(|outcome| outcome.value)
+}
+
+fn check_mirr_parameters(cash_flows: &[f64], finance_rate: f64) {
+    assert!(cash_flows.len() >= 2, "There must be at least two cashflows.");
+    for cash_flow in cash_flows.iter() {
+        assert!(cash_flow.is_finite(), "The cashflows must be finite (not NaN or infinity)");
+    }
+    assert!(cash_flows.iter().any(|cash_flow| *cash_flow > 0.0), "There must be at least one inflow.");
+    assert!(cash_flows.iter().any(|cash_flow| *cash_flow < 0.0), "There must be at least one outflow.");
+    assert!(finance_rate.is_finite(), "The finance rate must be finite (not NaN or infinity)");
+    assert!(finance_rate > -1.0, "The finance rate must be greater than -1.0 (-100%).");
+}
+
+// The outflows discounted to period 0, as a positive amount.
+fn present_value_of_outflows(cash_flows: &[f64], finance_rate: f64) -> f64 {
+    let outflows = cash_flows.iter().map(|cash_flow| (-cash_flow).max(0.0)).collect::<Vec<_>>();
+    discounted_sum(finance_rate, &outflows)
+}
+
+// The inflows grown to the last period.
+fn future_value_of_inflows(cash_flows: &[f64], reinvestment_rate: f64) -> f64 {
+    let last_index = cash_flows.len() - 1;
+    cash_flows.iter().enumerate()
+        .filter(|(_, cash_flow)| **cash_flow > 0.0)
+        .map(|(period, cash_flow)| cash_flow * (1.0 + reinvestment_rate).powf((last_index - period) as f64))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mirr_equals_irr_with_single_outflow() {
+        let cash_flows = [-25_000.0, 4_000.0, 7_500.0, 0.0, 9_000.0, 11_000.0];
+        let irr = all_irrs(&cash_flows, (-0.5, 1.0), 100)[0];
+        for finance_rate in [0.0, 0.05, 0.2].iter() {
+            let rate = mirr_equals_irr_reinvestment_rate(&cash_flows, *finance_rate).unwrap();
+            assert_approx_equal!(irr, rate);
+            assert_approx_equal!(irr, mirr(&cash_flows, *finance_rate, rate));
+        }
+    }
+
+    #[test]
+    fn test_mirr_equals_irr_reinvestment_rate_none() {
+        // Two sign changes.
+        assert_eq!(None, mirr_equals_irr_reinvestment_rate(&[-1_000.0, 3_000.0, -1_500.0], 0.05));
+        // Only one inflow at the end, which the reinvestment rate doesn't affect.
+        assert_eq!(None, mirr_equals_irr_reinvestment_rate(&[-1_000.0, 0.0, 1_300.0], 0.05));
+    }
+
+    #[test]
+    fn test_net_present_value_with_reinvestment_sign_follows_mirr() {
+        let cash_flows = [-8_000.0, -2_000.0, 2_500.0, 4_000.0, 3_000.0, 3_500.0];
+        for &(discount_rate, reinvestment_rate) in [(0.05, 0.08), (0.12, 0.04), (0.09, 0.09)].iter() {
+            let solution = net_present_value_with_reinvestment_solution(&cash_flows, discount_rate, reinvestment_rate);
+            let rate = mirr(&cash_flows, discount_rate, reinvestment_rate);
+            let expected = solution.initial_outlay() * ((1.0 + rate) / (1.0 + discount_rate)).powi(5) - solution.initial_outlay();
+            assert_approx_equal!(expected, solution.net_present_value());
+            assert_eq!(rate > discount_rate, solution.net_present_value() > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_net_present_value_with_reinvestment_equal_rates() {
+        let cash_flows = [-5_000.0, 1_200.0, -300.0, 2_600.0, 2_900.0];
+        assert_approx_equal!(npv_profile(&cash_flows, &[0.07])[0].1, net_present_value_with_reinvestment(&cash_flows, 0.07, 0.07));
+    }
+}
@@ -11,26 +11,134 @@ use crate::*;
 use std::cmp::max;
 use std::ops::Deref;
 
+pub mod after_tax_npv;
+#[doc(inline)]
+pub use after_tax_npv::*;
+
+pub mod depreciation_tax_shield;
+#[doc(inline)]
+pub use depreciation_tax_shield::*;
+
+pub mod equated_time;
+#[doc(inline)]
+pub use equated_time::*;
+
 pub mod future_value_annuity;
 #[doc(inline)]
 pub use future_value_annuity::*;
 
+pub mod future_value_annuity_plus_lump;
+#[doc(inline)]
+pub use future_value_annuity_plus_lump::*;
+
+pub mod future_value_annuity_stepup;
+#[doc(inline)]
+pub use future_value_annuity_stepup::*;
+
+pub mod future_value_dated;
+#[doc(inline)]
+pub use future_value_dated::*;
+
 pub mod payment;
 #[doc(inline)]
 pub use payment::*;
 
+pub mod payment_for_future_value;
+#[doc(inline)]
+pub use payment_for_future_value::*;
+
+pub mod payment_balloon;
+#[doc(inline)]
+pub use payment_balloon::*;
+
+pub mod payment_interest_only;
+#[doc(inline)]
+pub use payment_interest_only::*;
+
+pub mod lease_payment;
+#[doc(inline)]
+pub use lease_payment::*;
+
+pub mod lease_vs_buy;
+#[doc(inline)]
+pub use lease_vs_buy::*;
+
+pub mod rate_from_payment;
+#[doc(inline)]
+pub use rate_from_payment::*;
+
+pub mod loan_purchase_yield;
+#[doc(inline)]
+pub use loan_purchase_yield::*;
+
 pub mod present_value_annuity;
 #[doc(inline)]
 pub use present_value_annuity::*;
 
+pub mod present_value_annuity_growing;
+#[doc(inline)]
+pub use present_value_annuity_growing::*;
+
+pub mod present_value_perpetuity;
+#[doc(inline)]
+pub use present_value_perpetuity::*;
+
+pub mod present_value_h_model;
+#[doc(inline)]
+pub use present_value_h_model::*;
+
+pub mod present_value_step_annuity;
+#[doc(inline)]
+pub use present_value_step_annuity::*;
+
+pub mod present_value_with_salvage;
+#[doc(inline)]
+pub use present_value_with_salvage::*;
+
+pub mod present_value_with_stub;
+#[doc(inline)]
+pub use present_value_with_stub::*;
+
+pub mod present_value_cashflows;
+#[doc(inline)]
+pub use present_value_cashflows::*;
+
+pub mod modified_dietz;
+#[doc(inline)]
+pub use modified_dietz::*;
+
+pub mod time_weighted_return;
+#[doc(inline)]
+pub use time_weighted_return::*;
+
 pub mod net_present_value;
 #[doc(inline)]
 pub use net_present_value::*;
 
+pub mod irr;
+#[doc(inline)]
+pub use irr::*;
+
+pub mod crossover_rate;
+#[doc(inline)]
+pub use crossover_rate::*;
+
+pub mod mirr;
+#[doc(inline)]
+pub use mirr::*;
+
 pub mod nper;
 #[doc(inline)]
 pub use nper::*;
 
+pub mod periods_until_depleted;
+#[doc(inline)]
+pub use periods_until_depleted::*;
+
+pub mod required_rate_for_goal;
+#[doc(inline)]
+pub use required_rate_for_goal::*;
+
 #[derive(Debug, Clone)]
 pub enum CashflowVariable {
     PresentValueAnnuity,
@@ -0,0 +1,202 @@
+//! **Modified Dietz return.** The return of a portfolio over a period in which money was added or
+//! withdrawn. Each external flow is weighted by the fraction of the period it was invested, so a
+//! contribution made halfway through the period counts as half as much capital as one made at the
+//! start.
+//!
+//! return = (end_value - begin_value - sum(flow)) / (begin_value + sum(weight * flow))
+//!
+//! The weight of a flow made on day D of a period of CD days is (CD - D) / CD. Contributions are
+//! positive and withdrawals are negative.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // A $100,000 portfolio receives a $10,000 contribution halfway through the quarter and ends the
+//! // quarter worth $115,000.
+//! let solution = modified_dietz_return_solution(100_000, 115_000, &[(0.5, 10_000.0)]);
+//! dbg!(&solution);
+//! ```
+
+/// Returns the **Modified Dietz return** for a period with external flows such as contributions
+/// and withdrawals.
+///
+/// Related functions:
+/// * To see the gain and the weighted capital use [`modified_dietz_return_solution`].
+///
+/// See the [modified_dietz](./index.html) module page for the formula.
+///
+/// # Arguments
+/// * `begin_value` - The value of the portfolio at the start of the period.
+/// * `end_value` - The value of the portfolio at the end of the period.
+/// * `flows` - Pairs of the weight and the amount of each external flow. The weight is the fraction
+/// of the period remaining when the flow was made, from 1.0 for a flow at the very start to 0.0 for
+/// a flow at the very end. Contributions are positive and withdrawals are negative.
+///
+/// # Panics
+/// The call will fail if any weight is outside the range 0.0 to 1.0, if any value is not finite,
+/// or if the weighted capital is zero.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // A $10,000 contribution halfway through the quarter.
+/// let quarterly_return = modified_dietz_return(100_000, 115_000, &[(0.5, 10_000.0)]);
+/// assert_rounded_6!(0.047619, quarterly_return);
+///
+/// // Without any flows this is the simple return.
+/// assert_rounded_6!(0.15, modified_dietz_return(100_000, 115_000, &[]));
+/// ```
+pub fn modified_dietz_return<B, E>(begin_value: B, end_value: E, flows: &[(f64, f64)]) -> f64
+    where
+        B: Into<f64> + Copy,
+        E: Into<f64> + Copy
+{
+    modified_dietz_return_solution(begin_value, end_value, flows).modified_dietz_return()
+}
+
+/// Returns the **Modified Dietz return** for a period with external flows along with the gain and
+/// the weighted capital used as the denominator.
+///
+/// Related functions:
+/// * To calculate only the return as an `f64` use [`modified_dietz_return`].
+///
+/// # Arguments
+/// * `begin_value` - The value of the portfolio at the start of the period.
+/// * `end_value` - The value of the portfolio at the end of the period.
+/// * `flows` - Pairs of the weight and the amount of each external flow. The weight is the fraction
+/// of the period remaining when the flow was made, from 1.0 for a flow at the very start to 0.0 for
+/// a flow at the very end. Contributions are positive and withdrawals are negative.
+///
+/// # Panics
+/// The call will fail if any weight is outside the range 0.0 to 1.0, if any value is not finite,
+/// or if the weighted capital is zero.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // A contribution two thirds of the way through a 90-day quarter and a withdrawal at the very
+/// // end.
+/// let flows = [(30.0 / 90.0, 20_000.0), (0.0, -5_000.0)];
+/// let solution = modified_dietz_return_solution(250_000, 272_000, &flows);
+/// assert_rounded_4!(15_000.0, solution.net_flows());
+/// assert_rounded_4!(7_000.0, solution.gain());
+/// assert_rounded_4!(256_666.6667, solution.weighted_capital());
+/// assert_rounded_6!(0.027273, solution.modified_dietz_return());
+/// ```
+pub fn modified_dietz_return_solution<B, E>(begin_value: B, end_value: E, flows: &[(f64, f64)]) -> DietzSolution
+    where
+        B: Into<f64> + Copy,
+        E: Into<f64> + Copy
+{
+    let begin_value = begin_value.into();
+    let end_value = end_value.into();
+    assert!(begin_value.is_finite(), "The beginning value must be finite (not NaN or infinity)");
+    assert!(end_value.is_finite(), "The ending value must be finite (not NaN or infinity)");
+    for (weight, amount) in flows.iter() {
+        assert!(weight.is_finite(), "The weights must be finite (not NaN or infinity)");
+        assert!(amount.is_finite(), "The flows must be finite (not NaN or infinity)");
+        assert!(*weight >= 0.0 && *weight <= 1.0, "The weights must be between 0.0 and 1.0.");
+    }
+    let net_flows: f64 = flows.iter().map(|(_, amount)| amount).sum();
+    let weighted_flows: f64 = flows.iter().map(|(weight, amount)| weight * amount).sum();
+    let weighted_capital = begin_value + weighted_flows;
+    assert!(weighted_capital != 0.0, "The weighted capital must not be zero.");
+    DietzSolution::new(begin_value, end_value, net_flows, weighted_capital)
+}
+
+/// A record of a Modified Dietz return calculation. Created by calling
+/// [`modified_dietz_return_solution`].
+#[derive(Clone, Debug)]
+pub struct DietzSolution {
+    begin_value: f64,
+    end_value: f64,
+    net_flows: f64,
+    gain: f64,
+    weighted_capital: f64,
+    modified_dietz_return: f64,
+    formula: String,
+    symbolic_formula: String,
+}
+
+impl DietzSolution {
+    pub(crate) fn new(begin_value: f64, end_value: f64, net_flows: f64, weighted_capital: f64) -> Self {
+        let gain = end_value - begin_value - net_flows;
+        let modified_dietz_return = gain / weighted_capital;
+        assert!(modified_dietz_return.is_finite());
+        let formula = format!("{:.6} = ({:.4} - {:.4} - {:.4}) / {:.4}", modified_dietz_return, end_value, begin_value, net_flows, weighted_capital);
+        let symbolic_formula = "return = (end_value - begin_value - sum(flow)) / (begin_value + sum(weight * flow))".to_string();
+        Self {
+            begin_value,
+            end_value,
+            net_flows,
+            gain,
+            weighted_capital,
+            modified_dietz_return,
+            formula,
+            symbolic_formula,
+        }
+    }
+
+    /// Returns the value of the portfolio at the start of the period.
+    pub fn begin_value(&self) -> f64 {
+        self.begin_value
+    }
+
+    /// Returns the value of the portfolio at the end of the period.
+    pub fn end_value(&self) -> f64 {
+        self.end_value
+    }
+
+    /// Returns the sum of the external flows, with contributions positive and withdrawals
+    /// negative.
+    pub fn net_flows(&self) -> f64 {
+        self.net_flows
+    }
+
+    /// Returns the investment gain, which is the change in value not explained by the flows.
+    pub fn gain(&self) -> f64 {
+        self.gain
+    }
+
+    /// Returns the denominator of the return, the beginning value plus each flow weighted by the
+    /// fraction of the period it was invested.
+    pub fn weighted_capital(&self) -> f64 {
+        self.weighted_capital
+    }
+
+    /// Returns the Modified Dietz return for the period.
+    pub fn modified_dietz_return(&self) -> f64 {
+        self.modified_dietz_return
+    }
+
+    /// Returns a text version of the formula used to calculate the return with the actual values.
+    pub fn formula(&self) -> &str {
+        &self.formula
+    }
+
+    /// Returns a text version of the formula used to calculate the return using variables such as
+    /// weight for the fraction of the period each flow was invested.
+    pub fn symbolic_formula(&self) -> &str {
+        &self.symbolic_formula
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn test_modified_dietz_flow_timing() {
+        // A contribution at the start counts fully toward the capital and one at the end doesn't
+        // count at all.
+        assert_approx_equal!(5_000.0 / 110_000.0, modified_dietz_return(100_000, 115_000, &[(1.0, 10_000.0)]));
+        assert_approx_equal!(5_000.0 / 100_000.0, modified_dietz_return(100_000, 115_000, &[(0.0, 10_000.0)]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_modified_dietz_weight_out_of_range() {
+        modified_dietz_return(100_000, 115_000, &[(1.5, 10_000.0)]);
+    }
+}
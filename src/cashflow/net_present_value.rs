@@ -296,6 +296,802 @@ where C: Into<f64> + Copy
     NpvSolution::new(rates, periods, initial_investment, cashflows, sum_of_cashflows, sum_of_discounted_cashflows, net_present_value)
 }
 
+/// Returns the periodic rate at which the net present value of an initial investment followed by
+/// a constant cashflow is zero, or `None` if no positive rate does so. This is the internal rate of
+/// return for loan-shaped cashflows.
+///
+/// Related functions:
+/// * To keep the inputs and the formula in a struct use [`npv_zero_rate_solution`].
+///
+/// The rate is found by bisection on the closed-form annuity formula:
+///
+/// 0 = initial_investment + cashflow * ((1 - (1 + rate)<sup>-periods</sup>) / rate)
+///
+/// # Arguments
+/// * `initial_investment` - The initial investment at period 0. As with [`net_present_value`] this
+/// is typically negative.
+/// * `cashflow` - The constant cashflow received at the end of each period. This must have the
+/// opposite sign from `initial_investment`.
+/// * `periods` - The number of cashflows after the initial investment.
+///
+/// # Panics
+/// The call will fail if `periods` is zero or if `initial_investment` and `cashflow` are not
+/// nonzero values with opposite signs.
+///
+/// # Examples
+/// ```
+/// use finance_solution::*;
+///
+/// // Lend $10,000 and receive $1,200 per year for 10 years.
+/// let rate = npv_zero_rate(-10_000, 1_200, 10).unwrap();
+/// assert_rounded_6!(0.034602, rate);
+///
+/// // At that rate the net present value is zero.
+/// assert_rounded_4!(0.0, net_present_value(rate, 10, -10_000, 1_200));
+///
+/// // If the cashflows don't add up to more than the investment no positive rate will work.
+/// assert_eq!(None, npv_zero_rate(-10_000, 1_000, 10));
+/// ```
+pub fn npv_zero_rate<I, C>(initial_investment: I, cashflow: C, periods: u32) -> Option<f64>
+    where I: Into<f64> + Copy, C: Into<f64> + Copy
+{
+    npv_zero_rate_solution(initial_investment, cashflow, periods).map(|solution| solution.rate())
+}
+
+/// Returns the periodic rate at which the net present value of an initial investment followed by
+/// a constant cashflow is zero along with the inputs and the formula, or `None` if no positive
+/// rate does so.
+///
+/// Related functions:
+/// * To calculate only the rate as an `f64` use [`npv_zero_rate`].
+///
+/// # Arguments
+/// * `initial_investment` - The initial investment at period 0. As with [`net_present_value`] this
+/// is typically negative.
+/// * `cashflow` - The constant cashflow received at the end of each period. This must have the
+/// opposite sign from `initial_investment`.
+/// * `periods` - The number of cashflows after the initial investment.
+///
+/// # Panics
+/// The call will fail if `periods` is zero or if `initial_investment` and `cashflow` are not
+/// nonzero values with opposite signs.
+///
+/// # Examples
+/// ```
+/// use finance_solution::*;
+/// let solution = npv_zero_rate_solution(-10_000, 1_200, 10).unwrap();
+/// dbg!(&solution);
+/// assert_rounded_6!(0.034602, solution.rate());
+/// ```
+pub fn npv_zero_rate_solution<I, C>(initial_investment: I, cashflow: C, periods: u32) -> Option<NpvZeroRateSolution>
+    where I: Into<f64> + Copy, C: Into<f64> + Copy
+{
+    npv_zero_rate_solution_with_config(initial_investment, cashflow, periods, SolverConfig::default())
+}
+
+/// Returns the periodic rate at which the net present value of an initial investment followed by
+/// a constant cashflow is zero using the given tolerance and maximum number of iterations, or
+/// `None` if no positive rate does so.
+///
+/// Related functions:
+/// * To solve to the full precision of an `f64` use [`npv_zero_rate_solution`].
+///
+/// # Arguments
+/// * `initial_investment` - The initial investment at period 0. As with [`net_present_value`] this
+/// is typically negative.
+/// * `cashflow` - The constant cashflow received at the end of each period. This must have the
+/// opposite sign from `initial_investment`.
+/// * `periods` - The number of cashflows after the initial investment.
+/// * `config` - The tolerance and maximum number of iterations for the solver.
+///
+/// # Panics
+/// The call will fail if `periods` is zero or if `initial_investment` and `cashflow` are not
+/// nonzero values with opposite signs.
+///
+/// # Examples
+/// ```
+/// use finance_solution::*;
+/// let config = SolverConfig::new(0.000_1, 100);
+/// let solution = npv_zero_rate_solution_with_config(-10_000, 1_200, 10, config).unwrap();
+/// assert!(solution.converged());
+/// assert!((solution.rate() - 0.034602).abs() <= 0.000_1);
+/// ```
+pub fn npv_zero_rate_solution_with_config<I, C>(initial_investment: I, cashflow: C, periods: u32, config: SolverConfig) -> Option<NpvZeroRateSolution>
+    where I: Into<f64> + Copy, C: Into<f64> + Copy
+{
+    let initial_investment = initial_investment.into();
+    let cashflow = cashflow.into();
+    assert!(initial_investment.is_finite(), "The initial investment must be finite (not NaN or infinity)");
+    assert!(cashflow.is_finite(), "The cashflow must be finite (not NaN or infinity)");
+    assert!(periods > 0, "The number of periods must be greater than zero.");
+    assert!(initial_investment * cashflow < 0.0, "The initial investment and the cashflow must be nonzero and have opposite signs.");
+
+    // With a rate of zero the net present value is simply the sum of the cashflows. If they don't
+    // more than repay the initial investment, any positive rate would only make things worse.
+    if (cashflow * periods as f64).abs() <= initial_investment.abs() {
+        return None;
+    }
+
+    let npv = |rate: f64| initial_investment + cashflow * annuity_factor(rate, periods);
+
+    // Find an upper bound where the net present value has the same sign as the initial investment,
+    // then narrow the range until the rate is known to within the solver's tolerance.
+    let mut low = 0.0;
+    let mut high = 1.0;
+    while npv(high).signum() != initial_investment.signum() {
+        low = high;
+        high *= 2.0;
+        if !high.is_finite() {
+            return None;
+        }
+    }
+    let outcome = solver::bisect(low, high, &config, |rate| npv(rate).signum() != initial_investment.signum());
+    let rate = outcome.value;
+
+    let formula = format!("0 = {:.4} + {:.4} * ((1 - {:.6}^-{}) / {:.6})", initial_investment, cashflow, 1.0 + rate, periods, rate);
+    let symbolic_formula = "0 = initial_investment + cashflow * ((1 - (1 + r)^-n) / r)";
+    Some(NpvZeroRateSolution::new(initial_investment, cashflow, periods, outcome, &formula, symbolic_formula))
+}
+
+/// Returns the number of times a series of cashflows changes sign, such as from the outflow of an
+/// initial investment to the inflows that follow it. Cashflows of zero are skipped, so going to or
+/// from zero is not a sign change.
+///
+/// By Descartes' rule of signs the net present value as a function of the rate can have no more
+/// roots than there are sign changes, so this is an upper limit on the number of internal rates of
+/// return. See [`is_conventional_cash_flow`] for the common case of a single sign change.
+///
+/// # Arguments
+/// * `cash_flows` - The cashflows, one per period.
+///
+/// # Examples
+/// ```
+/// use finance_solution::*;
+///
+/// assert_eq!(1, sign_change_count(&[-1_000.0, 300.0, 400.0, 500.0]));
+///
+/// // A mine that costs money to close at the end.
+/// assert_eq!(2, sign_change_count(&[-1_000.0, 800.0, 800.0, 0.0, -500.0]));
+/// ```
+pub fn sign_change_count(cash_flows: &[f64]) -> u32 {
+    let signs = cash_flows.iter()
+        .filter(|cash_flow| **cash_flow != 0.0)
+        .map(|cash_flow| cash_flow.signum())
+        .collect::<Vec<f64>>();
+    signs.windows(2).filter(|pair| pair[0] != pair[1]).count() as u32
+}
+
+/// Returns true if a series of cashflows changes sign exactly once, as with an investment followed
+/// by returns or a loan followed by repayments.
+///
+/// A conventional series has exactly one internal rate of return, so it's safe to show an IRR for
+/// it. A series with more than one sign change may have several rates where the net present value
+/// is zero, or none at all, and an IRR reported for it should be treated with caution. A series
+/// with no sign change has no internal rate of return.
+///
+/// # Arguments
+/// * `cash_flows` - The cashflows, one per period.
+///
+/// # Examples
+/// ```
+/// use finance_solution::*;
+///
+/// assert!(is_conventional_cash_flow(&[-10_000.0, 3_000.0, 4_200.0, 6_800.0]));
+/// assert!(!is_conventional_cash_flow(&[-1_000.0, 800.0, 800.0, -500.0]));
+/// assert!(!is_conventional_cash_flow(&[100.0, 200.0]));
+/// ```
+pub fn is_conventional_cash_flow(cash_flows: &[f64]) -> bool {
+    sign_change_count(cash_flows) == 1
+}
+
+/// Returns **every internal rate of return** of a series of cashflows that can be found within a
+/// range of rates, sorted from lowest to highest.
+///
+/// A series that changes sign more than once, such as a project with a large cleanup cost at the
+/// end, can have several rates where the net present value is zero. Reporting only one of them is
+/// misleading, so this function divides the range into `steps` equal parts, looks for each part
+/// where the net present value changes sign, and narrows each of those down to a rate. Use
+/// [`sign_change_count`] to see the most rates there can be.
+///
+/// Related functions:
+/// * To control the precision of the solver use [`all_irrs_with_config`].
+///
+/// A rate where the net present value touches zero without crossing it is only found if it falls
+/// exactly on the boundary of a step. Two rates within the same step cancel out and neither is
+/// found, so use more steps for a finer search.
+///
+/// # Arguments
+/// * `cash_flows` - The cashflows starting with period 0, which is not discounted.
+/// * `search_range` - The lowest and highest periodic rates to search.
+/// * `steps` - The number of equal parts the range is divided into.
+///
+/// # Panics
+/// The call will fail if `steps` is zero, if the low end of the range is less than or equal to
+/// -1.0 or is not below the high end, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// use finance_solution::*;
+///
+/// // The classic pump project, where spending more now to pump the oil out sooner gives up a year
+/// // of later income. It has internal rates of return of both 25% and 400%.
+/// let cash_flows = [-1_600.0, 10_000.0, -10_000.0];
+/// assert_eq!(2, sign_change_count(&cash_flows));
+/// let irrs = all_irrs(&cash_flows, (-0.5, 10.0), 1_000);
+/// assert_eq!(2, irrs.len());
+/// assert_rounded_6!(0.25, irrs[0]);
+/// assert_rounded_6!(4.0, irrs[1]);
+/// ```
+pub fn all_irrs(cash_flows: &[f64], search_range: (f64, f64), steps: usize) -> Vec<f64> {
+    all_irrs_with_config(cash_flows, search_range, steps, SolverConfig::default())
+}
+
+/// Returns **every internal rate of return** of a series of cashflows that can be found within a
+/// range of rates, sorted from lowest to highest, using the given tolerance and maximum number of
+/// iterations for each rate.
+///
+/// Related functions:
+/// * To solve to the full precision of an `f64` use [`all_irrs`].
+///
+/// # Arguments
+/// * `cash_flows` - The cashflows starting with period 0, which is not discounted.
+/// * `search_range` - The lowest and highest periodic rates to search.
+/// * `steps` - The number of equal parts the range is divided into.
+/// * `config` - The tolerance and maximum number of iterations for the solver.
+///
+/// # Panics
+/// The call will fail if `steps` is zero, if the low end of the range is less than or equal to
+/// -1.0 or is not below the high end, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// use finance_solution::*;
+///
+/// let config = SolverConfig::new(0.000_001, 100);
+/// let irrs = all_irrs_with_config(&[-1_600.0, 10_000.0, -10_000.0], (0.0, 1.0), 10, config);
+/// assert_eq!(1, irrs.len());
+/// assert_rounded_4!(0.25, irrs[0]);
+/// ```
+pub fn all_irrs_with_config(cash_flows: &[f64], search_range: (f64, f64), steps: usize, config: SolverConfig) -> Vec<f64> {
+    let (low, high) = search_range;
+    assert!(steps > 0, "The number of steps must be greater than zero.");
+    assert!(low.is_finite() && high.is_finite(), "The search range must be finite (not NaN or infinity)");
+    assert!(low > -1.0, "The low end of the search range must be greater than -1.0 (-100%).");
+    assert!(low < high, "The low end of the search range must be below the high end.");
+    for cash_flow in cash_flows.iter() {
+        assert!(cash_flow.is_finite(), "The cashflows must be finite (not NaN or infinity)");
+    }
+    let npv = |rate: f64| discounted_sum(rate, cash_flows);
+
+    let step = (high - low) / steps as f64;
+    let mut irrs = vec![];
+    let mut step_low = low;
+    let mut step_low_npv = npv(step_low);
+    if step_low_npv == 0.0 {
+        irrs.push(step_low);
+    }
+    for step_index in 1..=steps {
+        let step_high = if step_index == steps { high } else { low + step * step_index as f64 };
+        let step_high_npv = npv(step_high);
+        if step_high_npv == 0.0 {
+            irrs.push(step_high);
+        } else if step_low_npv != 0.0 && step_high_npv.signum() != step_low_npv.signum() {
+            let low_sign = step_low_npv.signum();
+            irrs.push(solver::bisect(step_low, step_high, &config, |rate| npv(rate).signum() == low_sign).value);
+        }
+        step_low = step_high;
+        step_low_npv = step_high_npv;
+    }
+    irrs
+}
+
+/// Returns the net present value of each consecutive window of a series of cashflows. Each window
+/// is valued as if its first cashflow were at period 0, so the first cashflow is not discounted
+/// and the last one is discounted for `window - 1` periods.
+///
+/// This is useful for watching how the economics of a long-running project change over time. The
+/// result has one entry for each starting position, which is `cash_flows.len() - window + 1`
+/// entries.
+///
+/// # Arguments
+/// * `rate` - The periodic discount rate, expressed as a floating point number. For instance 0.05
+/// would mean 5%.
+/// * `cash_flows` - The cashflows, one per period.
+/// * `window` - The number of cashflows in each window.
+///
+/// # Panics
+/// The call will fail if `window` is zero or greater than the number of cashflows, if `rate` is
+/// less than or equal to -1.0, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// use finance_solution::*;
+///
+/// let cash_flows = [-1_000, 400, 400, 400, 300, 200];
+/// let npvs = rolling_npv(0.1, &cash_flows, 4);
+/// assert_eq!(3, npvs.len());
+///
+/// // The first window is the same as the usual net present value of its four cashflows.
+/// assert_rounded_4!(net_present_value_schedule(&[0.1; 3], &cash_flows[..4]), npvs[0]);
+///
+/// // Later windows have no investment so they're worth more.
+/// assert_rounded_4!(1_319.6093, npvs[1]);
+/// assert_rounded_4!(1_161.8332, npvs[2]);
+/// ```
+pub fn rolling_npv<C>(rate: f64, cash_flows: &[C], window: usize) -> Vec<f64>
+    where C: Into<f64> + Copy
+{
+    assert!(window > 0, "The window must contain at least one cashflow.");
+    assert!(window <= cash_flows.len(), "The window must not be longer than the series of cashflows.");
+    assert!(rate.is_finite(), "The rate must be finite (not NaN or infinity)");
+    assert!(rate > -1.0, "The rate must be greater than -1.0 (-100%).");
+    let cash_flows = cash_flows.iter().map(|cash_flow| (*cash_flow).into()).collect::<Vec<f64>>();
+    for cash_flow in cash_flows.iter() {
+        assert!(cash_flow.is_finite(), "The cashflows must be finite (not NaN or infinity)");
+    }
+
+    // Every window uses the same discount factors so calculate them once.
+    let mut discount_factors = Vec::with_capacity(window);
+    let mut discount_factor = 1.0;
+    for _ in 0..window {
+        discount_factors.push(discount_factor);
+        discount_factor /= 1.0 + rate;
+    }
+
+    cash_flows.windows(window)
+        .map(|cash_flows| cash_flows.iter().zip(discount_factors.iter()).map(|(cash_flow, discount_factor)| cash_flow * discount_factor).sum())
+        .collect()
+}
+
+/// Returns a set of what-if scenarios showing the net present value of a series of cashflows at
+/// each of several discount rates. This shows how sensitive the value of a project is to the
+/// discount rate, and the internal rate of return lies between the rates where the net present
+/// value changes sign.
+///
+/// Each entry in the resulting [`ScenarioList`] has the rate as its input and the net present
+/// value as its output.
+///
+/// # Arguments
+/// * `cash_flows` - The cashflows starting with the initial investment at period 0, which is not
+/// discounted.
+/// * `rates` - The periodic discount rates to try.
+///
+/// # Panics
+/// The call will fail if `cash_flows` is empty, if any rate is less than or equal to -1.0, or if any
+/// value is not finite.
+///
+/// # Examples
+/// ```
+/// use finance_solution::*;
+///
+/// let cash_flows = [-10_000, 3_000, 4_200, 6_800];
+/// let scenarios = net_present_value_vary_rate(&cash_flows, &[0.05, 0.08, 0.10, 0.12, 0.20]);
+/// scenarios.print_table();
+///
+/// assert_rounded_4!(1_776.6601, scenarios.entries[1].output);
+///
+/// // The net present value turns negative somewhere between 12% and 20%.
+/// assert!(scenarios.entries[3].output > 0.0);
+/// assert!(scenarios.entries[4].output < 0.0);
+/// ```
+/// Output:
+/// ```text
+///     Rate  Present Value
+/// --------  -------------
+/// 0.050000     2_540.7623
+/// 0.080000     1_776.6601
+/// 0.100000     1_307.2878
+/// 0.120000       866.8914
+/// 0.200000      -648.1481
+/// ```
+pub fn net_present_value_vary_rate<C>(cash_flows: &[C], rates: &[f64]) -> ScenarioList
+    where C: Into<f64> + Copy
+{
+    assert!(!cash_flows.is_empty(), "There must be at least one cashflow.");
+    let cash_flows = cash_flows.iter().map(|cash_flow| (*cash_flow).into()).collect::<Vec<f64>>();
+    for cash_flow in cash_flows.iter() {
+        assert!(cash_flow.is_finite(), "The cashflows must be finite (not NaN or infinity)");
+    }
+    let entries = npv_profile(&cash_flows, rates);
+    let setup = format!("Compare net present values with different discount rates where the sum of the cashflows is {}.", format_float(cash_flows.iter().sum::<f64>()));
+    ScenarioList::new(setup, TvmVariable::Rate, TvmVariable::PresentValue, entries)
+}
+
+/// Returns the **net present value profile** of a series of cashflows as (rate, net present value)
+/// pairs, one for each of the given rates. The values are not rounded so they can be plotted
+/// directly.
+///
+/// The internal rate of return is where the profile crosses zero. To find it from a set of rates
+/// use [`net_present_value_vary_rate`] and [`ScenarioList::interpolate_input_for`] or solve for it
+/// with [`all_irrs`].
+///
+/// Related functions:
+/// * To use evenly spaced rates use [`npv_profile_range`].
+///
+/// # Arguments
+/// * `cash_flows` - The cashflows starting with period 0, which is not discounted.
+/// * `rates` - The periodic discount rates to value the cashflows at.
+///
+/// # Panics
+/// The call will fail if any rate is less than or equal to -1.0 or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// use finance_solution::*;
+///
+/// let points = npv_profile(&[-10_000.0, 3_000.0, 4_200.0, 6_800.0], &[0.0, 0.1, 0.2]);
+/// assert_eq!(3, points.len());
+/// assert_eq!((0.0, 4_000.0), points[0]);
+/// assert_rounded_4!(1_307.2878, points[1].1);
+/// assert_rounded_4!(-648.1481, points[2].1);
+/// ```
+pub fn npv_profile(cash_flows: &[f64], rates: &[f64]) -> Vec<(f64, f64)> {
+    for cash_flow in cash_flows.iter() {
+        assert!(cash_flow.is_finite(), "The cashflows must be finite (not NaN or infinity)");
+    }
+    rates.iter()
+        .map(|rate| {
+            assert!(rate.is_finite(), "The rates must be finite (not NaN or infinity)");
+            assert!(*rate > -1.0, "The rates must be greater than -1.0 (-100%).");
+            (*rate, discounted_sum(*rate, cash_flows))
+        })
+        .collect()
+}
+
+/// Returns the **net present value profile** of a series of cashflows as (rate, net present value)
+/// pairs at evenly spaced rates from `min_rate` to `max_rate`. The range is divided into `steps`
+/// equal parts so there are `steps + 1` points including both ends.
+///
+/// Related functions:
+/// * To choose the rates yourself use [`npv_profile`].
+///
+/// # Arguments
+/// * `cash_flows` - The cashflows starting with period 0, which is not discounted.
+/// * `min_rate` - The lowest periodic discount rate.
+/// * `max_rate` - The highest periodic discount rate.
+/// * `steps` - The number of equal parts the range is divided into.
+///
+/// # Panics
+/// The call will fail if `steps` is zero, if `min_rate` is less than or equal to -1.0 or is greater
+/// than `max_rate`, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// use finance_solution::*;
+///
+/// let cash_flows = [-10_000.0, 3_000.0, 4_200.0, 6_800.0];
+/// let points = npv_profile_range(&cash_flows, 0.0, 0.3, 30);
+/// assert_eq!(31, points.len());
+/// assert_rounded_6!(0.3, points[30].0);
+///
+/// // The internal rate of return is between the last positive point and the first negative one.
+/// let crossing = points.windows(2).position(|pair| pair[0].1 > 0.0 && pair[1].1 < 0.0).unwrap();
+/// assert_rounded_6!(0.16, points[crossing].0);
+/// ```
+pub fn npv_profile_range(cash_flows: &[f64], min_rate: f64, max_rate: f64, steps: usize) -> Vec<(f64, f64)> {
+    assert!(steps > 0, "The number of steps must be greater than zero.");
+    assert!(min_rate.is_finite() && max_rate.is_finite(), "The rates must be finite (not NaN or infinity)");
+    assert!(min_rate <= max_rate, "The minimum rate must not be greater than the maximum rate.");
+    let step = (max_rate - min_rate) / steps as f64;
+    let rates = (0..=steps)
+        .map(|step_index| if step_index == steps { max_rate } else { min_rate + step * step_index as f64 })
+        .collect::<Vec<f64>>();
+    npv_profile(cash_flows, &rates)
+}
+
+/// Returns the **net present value of each category of cashflows**, such as revenue, operating
+/// costs, and capital spending, in the order the categories were given. Since discounting is linear
+/// the category values add up to the net present value of the whole project.
+///
+/// Related functions:
+/// * To get the total as well and print the breakdown as a table use
+/// [`npv_by_category_solution`].
+///
+/// # Arguments
+/// * `rate` - The periodic discount rate, expressed as a floating point number. For instance 0.10
+/// would mean 10%.
+/// * `categorized_flows` - The name of each category and its cashflows starting with period 0,
+/// which is not discounted. The categories may have different numbers of periods.
+///
+/// # Panics
+/// The call will fail if `rate` is less than or equal to -1.0 or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let flows = vec![
+///     ("revenue".to_string(), vec![0.0, 5_000.0, 6_000.0, 7_000.0]),
+///     ("opex".to_string(), vec![0.0, -2_000.0, -2_200.0, -2_400.0]),
+///     ("capex".to_string(), vec![-8_000.0, 0.0, 0.0, 1_000.0]),
+/// ];
+/// let npvs = npv_by_category(0.10, &flows);
+/// assert_eq!("revenue", npvs[0].0);
+/// assert_rounded_4!(14_763.3358, npvs[0].1);
+/// assert_rounded_4!(-5_439.5192, npvs[1].1);
+/// assert_rounded_4!(-7_248.6852, npvs[2].1);
+/// ```
+pub fn npv_by_category(rate: f64, categorized_flows: &[(String, Vec<f64>)]) -> Vec<(String, f64)> {
+    npv_by_category_solution(rate, categorized_flows).categories().to_vec()
+}
+
+/// Returns the **net present value of each category of cashflows** along with the total, which
+/// can be printed as a table showing which categories drive the project's value.
+///
+/// Related functions:
+/// * To get only the category values use [`npv_by_category`].
+///
+/// # Arguments
+/// * `rate` - The periodic discount rate, expressed as a floating point number. For instance 0.10
+/// would mean 10%.
+/// * `categorized_flows` - The name of each category and its cashflows starting with period 0,
+/// which is not discounted. The categories may have different numbers of periods.
+///
+/// # Panics
+/// The call will fail if `rate` is less than or equal to -1.0 or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let flows = vec![
+///     ("revenue".to_string(), vec![0.0, 5_000.0, 6_000.0, 7_000.0]),
+///     ("opex".to_string(), vec![0.0, -2_000.0, -2_200.0, -2_400.0]),
+///     ("capex".to_string(), vec![-8_000.0, 0.0, 0.0, 1_000.0]),
+/// ];
+/// let solution = npv_by_category_solution(0.10, &flows);
+/// assert_rounded_4!(2_075.1315, solution.net_present_value());
+/// solution.print_table();
+/// ```
+/// Output:
+/// ```text
+/// category  net_present_value
+/// --------  -----------------
+///  revenue        14_763.3358
+///     opex        -5_439.5192
+///    capex        -7_248.6852
+///    total         2_075.1315
+/// ```
+pub fn npv_by_category_solution(rate: f64, categorized_flows: &[(String, Vec<f64>)]) -> CategoryNpvSolution {
+    assert!(rate.is_finite(), "The rate must be finite (not NaN or infinity)");
+    assert!(rate > -1.0, "The rate must be greater than -1.0 (-100%).");
+    let categories = categorized_flows.iter()
+        .map(|(category, cash_flows)| {
+            for cash_flow in cash_flows.iter() {
+                assert!(cash_flow.is_finite(), "The cashflows must be finite (not NaN or infinity)");
+            }
+            (category.clone(), discounted_sum(rate, cash_flows))
+        })
+        .collect();
+    CategoryNpvSolution::new(rate, categories)
+}
+
+/// A breakdown of a project's net present value by category of cashflow. Created by calling
+/// [`npv_by_category_solution`].
+#[derive(Clone, Debug)]
+pub struct CategoryNpvSolution {
+    rate: f64,
+    categories: Vec<(String, f64)>,
+    net_present_value: f64,
+}
+
+impl CategoryNpvSolution {
+    pub(crate) fn new(rate: f64, categories: Vec<(String, f64)>) -> Self {
+        let net_present_value = categories.iter().map(|(_, npv)| npv).sum::<f64>();
+        assert!(net_present_value.is_finite());
+        Self {
+            rate,
+            categories,
+            net_present_value,
+        }
+    }
+
+    /// Returns the periodic discount rate.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the name and net present value of each category in the order they were given.
+    pub fn categories(&self) -> &[(String, f64)] {
+        &self.categories
+    }
+
+    /// Returns the net present value of all of the categories together.
+    pub fn net_present_value(&self) -> f64 {
+        self.net_present_value
+    }
+
+    /// Prints a formatted table with the net present value of each category followed by the
+    /// total.
+    pub fn print_table(&self) {
+        self.print_table_locale_opt(None, None);
+    }
+
+    /// Prints a formatted table with the net present value of each category followed by the total
+    /// using the given locale and precision.
+    pub fn print_table_locale(&self, locale: &num_format::Locale, precision: usize) {
+        self.print_table_locale_opt(Some(locale), Some(precision));
+    }
+
+    fn print_table_locale_opt(&self, locale: Option<&num_format::Locale>, precision: Option<usize>) {
+        let columns = columns_with_strings(&[("category", "s", true), ("net_present_value", "f", true)]);
+        let data = self.categories.iter()
+            .map(|(category, npv)| vec![category.clone(), npv.to_string()])
+            .chain(std::iter::once(vec!["total".to_string(), self.net_present_value.to_string()]))
+            .collect::<Vec<_>>();
+        print_table_locale_opt(&columns, data, locale, precision);
+    }
+}
+
+/// Discount factors for a single rate calculated once and reused for many net present value
+/// calculations, such as the trials of a Monte Carlo simulation that all use the same discount
+/// rate and horizon.
+///
+/// The factor for period t is 1 / (1 + rate)<sup>t</sup> starting with a factor of 1.0 for period
+/// 0, so the cashflows passed to [`DiscountFactors::npv`] start with the initial investment as
+/// with [`net_present_value_schedule`].
+///
+/// The `npv trials` benchmarks in `benches/my_benchmark.rs` compare this with calling
+/// [`net_present_value_schedule`] once per trial.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let discount_factors = DiscountFactors::new(0.034, 3);
+/// assert_eq!(4, discount_factors.factors().len());
+///
+/// // Each trial reuses the same factors.
+/// let trials = [[-1_000.0, 300.0, 400.0, 500.0], [-1_000.0, 250.0, 450.0, 480.0]];
+/// let npvs = trials.iter().map(|cash_flows| discount_factors.npv(cash_flows)).collect::<Vec<_>>();
+/// assert_rounded_4!(116.5433, npvs[0]);
+/// assert_rounded_4!(net_present_value_schedule(&[0.034; 3], &trials[1]), npvs[1]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct DiscountFactors {
+    rate: f64,
+    factors: Vec<f64>,
+}
+
+impl DiscountFactors {
+    /// Calculates the discount factors for periods 0 through `periods` at the given rate.
+    ///
+    /// # Arguments
+    /// * `rate` - The periodic discount rate, expressed as a floating point number. For instance
+    /// 0.05 would mean 5%.
+    /// * `periods` - The last period that will have a cashflow.
+    ///
+    /// # Panics
+    /// The call will fail if `rate` is less than or equal to -1.0 or is not finite.
+    pub fn new(rate: f64, periods: u32) -> Self {
+        assert!(rate.is_finite(), "The rate must be finite (not NaN or infinity)");
+        assert!(rate > -1.0, "The rate must be greater than -1.0 (-100%).");
+        let mut factors = Vec::with_capacity(periods as usize + 1);
+        let mut factor = 1.0;
+        for _ in 0..=periods {
+            factors.push(factor);
+            factor /= 1.0 + rate;
+        }
+        Self {
+            rate,
+            factors,
+        }
+    }
+
+    /// Returns the periodic discount rate.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the last period that has a discount factor.
+    pub fn periods(&self) -> u32 {
+        self.factors.len() as u32 - 1
+    }
+
+    /// Returns the discount factors starting with 1.0 for period 0.
+    pub fn factors(&self) -> &[f64] {
+        &self.factors
+    }
+
+    /// Returns the net present value of a series of cashflows starting with the initial investment
+    /// at period 0. There may be fewer cashflows than discount factors.
+    ///
+    /// # Panics
+    /// The call will fail if there are more cashflows than discount factors. The cashflows aren't
+    /// checked for NaN or infinity so that each call is as fast as possible.
+    pub fn npv(&self, cash_flows: &[f64]) -> f64 {
+        assert!(cash_flows.len() <= self.factors.len(), "There must be no more cashflows than discount factors.");
+        cash_flows.iter().zip(self.factors.iter()).map(|(cash_flow, factor)| cash_flow * factor).sum()
+    }
+}
+
+// The net present value of cashflows starting with the initial investment at period 0. The
+// functions that search for a rate or evaluate many rates call this after checking the cashflows
+// once, so it does no checking of its own.
+pub(crate) fn discounted_sum(rate: f64, cash_flows: &[f64]) -> f64 {
+    cash_flows.iter().enumerate()
+        .map(|(period, cash_flow)| cash_flow / (1.0 + rate).powf(period as f64))
+        .sum()
+}
+
+fn annuity_factor(rate: f64, periods: u32) -> f64 {
+    if rate == 0.0 {
+        periods as f64
+    } else {
+        (1. - (1. / (1. + rate)).powf(periods as f64)) / rate
+    }
+}
+
+/// A record of the rate at which the net present value of an initial investment followed by a
+/// constant cashflow is zero. Created by calling [`npv_zero_rate_solution`].
+#[derive(Clone, Debug)]
+pub struct NpvZeroRateSolution {
+    initial_investment: f64,
+    cashflow: f64,
+    periods: u32,
+    rate: f64,
+    iterations: u32,
+    converged: bool,
+    formula: String,
+    symbolic_formula: String,
+}
+
+impl NpvZeroRateSolution {
+    pub(crate) fn new(initial_investment: f64, cashflow: f64, periods: u32, outcome: solver::SolverOutcome, formula: &str, symbolic_formula: &str) -> Self {
+        let solver::SolverOutcome { value: rate, iterations, converged } = outcome;
+        assert!(rate.is_finite());
+        Self {
+            initial_investment,
+            cashflow,
+            periods,
+            rate,
+            iterations,
+            converged,
+            formula: formula.to_string(),
+            symbolic_formula: symbolic_formula.to_string(),
+        }
+    }
+
+    /// Returns the initial investment at period 0.
+    pub fn initial_investment(&self) -> f64 {
+        self.initial_investment
+    }
+
+    /// Returns the constant cashflow received at the end of each period.
+    pub fn cashflow(&self) -> f64 {
+        self.cashflow
+    }
+
+    /// Returns the number of cashflows after the initial investment.
+    pub fn periods(&self) -> u32 {
+        self.periods
+    }
+
+    /// Returns the periodic rate at which the net present value is zero.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the number of bisection iterations the solver used to narrow down the rate.
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    /// Returns true if the rate was found to within the solver's tolerance before it ran out of
+    /// iterations.
+    pub fn converged(&self) -> bool {
+        self.converged
+    }
+
+    /// Returns a text version of the equation that was solved with the actual values.
+    pub fn formula(&self) -> &str {
+        &self.formula
+    }
+
+    /// Returns a text version of the equation that was solved using variables such as r for the
+    /// rate.
+    pub fn symbolic_formula(&self) -> &str {
+        &self.symbolic_formula
+    }
+}
+
 /// The custom solution information of a NPV scenario. 
 /// The struct values are immutable by the user of the library.
 #[derive(Debug)]
@@ -582,6 +1378,22 @@ mod tests {
     use super::*;
     //use crate::*;
 
+    #[test]
+    fn test_discount_factors_npv() {
+        let discount_factors = DiscountFactors::new(0.0725, 12);
+        assert_eq!(12, discount_factors.periods());
+        let cash_flows = [-5_000.0, 800.0, 650.0, 900.0, -120.0, 1_100.0, 700.0, 700.0, 700.0, 450.0, 300.0, 250.0, 2_000.0];
+        assert_approx_equal!(net_present_value_schedule(&[0.0725; 12], &cash_flows), discount_factors.npv(&cash_flows));
+        // A shorter series uses only the factors it needs.
+        assert_approx_equal!(net_present_value(0.0725, 3, -5_000, 800), discount_factors.npv(&[-5_000.0, 800.0, 800.0, 800.0]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_discount_factors_too_many_cash_flows() {
+        DiscountFactors::new(0.05, 2).npv(&[-100.0, 50.0, 50.0, 50.0]);
+    }
+
     #[test]
     fn test_net_present_value_1() {
         let rate = 0.034;
@@ -626,4 +1438,129 @@ mod tests {
         let npv = net_present_value_schedule_solution(&rates, &cashflows);
         assert_eq!(98.950922304, (10_000_000_000. * npv.npv()).round() / 10_000_000_000.);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_npv_zero_rate_matches_payment() {
+        // A loan payment calculated at a known rate should give back that rate.
+        let (rate, periods, present_value) = (0.0075, 360, 250_000.0);
+        let pmt = payment(rate, periods, present_value, 0.0, false);
+        let calculated_rate = npv_zero_rate(-present_value, -pmt, periods).unwrap();
+        assert_rounded_8!(rate, calculated_rate);
+
+        // The same with the signs reversed, as for a borrower.
+        let calculated_rate = npv_zero_rate(present_value, pmt, periods).unwrap();
+        assert_rounded_8!(rate, calculated_rate);
+    }
+
+    #[test]
+    fn test_npv_zero_rate_no_solution() {
+        assert!(npv_zero_rate(-1_000, 100, 10).is_none());
+        assert!(npv_zero_rate(-1_000, 99, 10).is_none());
+        assert!(npv_zero_rate_solution(-1_000, 101, 10).is_some());
+    }
+
+    #[test]
+    fn test_net_present_value_vary_rate() {
+        let cash_flows = [-1_000.0, 300.0, 400.0, 500.0];
+        let rates = [0.0, 0.034, 0.089];
+        let scenarios = net_present_value_vary_rate(&cash_flows, &rates);
+        assert_eq!(TvmVariable::Rate, scenarios.input_variable);
+        assert_eq!(rates.len(), scenarios.entries.len());
+        for (entry, rate) in scenarios.entries.iter().zip(rates.iter()) {
+            assert_eq!(*rate, entry.input);
+            assert_approx_equal!(net_present_value_schedule(&[*rate; 3], &cash_flows), entry.output);
+        }
+    }
+
+    #[test]
+    fn test_npv_zero_rate_max_iterations() {
+        let config = SolverConfig::new(0.0, 1);
+        let solution = npv_zero_rate_solution_with_config(-10_000, 1_200, 10, config).unwrap();
+        assert!(!solution.converged());
+        assert_eq!(1, solution.iterations());
+        assert!(npv_zero_rate_solution(-10_000, 1_200, 10).unwrap().converged());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_npv_zero_rate_same_signs() {
+        npv_zero_rate(1_000, 200, 10);
+    }
+
+    #[test]
+    fn test_rolling_npv_matches_schedule() {
+        let cash_flows = [-5_000.0, 1_200.0, 1_500.0, 900.0, 2_000.0, 1_100.0, -300.0, 1_800.0];
+        let rate = 0.065;
+        for window in 2..=cash_flows.len() {
+            let npvs = rolling_npv(rate, &cash_flows, window);
+            assert_eq!(cash_flows.len() - window + 1, npvs.len());
+            for (start, npv) in npvs.iter().enumerate() {
+                let expected = cash_flows[start] + present_value_per_flow_rate(&cash_flows[start + 1..start + window], &vec![rate; window - 1]);
+                assert_approx_equal!(expected, *npv);
+            }
+        }
+        // A window of one is just the cashflows themselves.
+        assert_eq!(cash_flows.to_vec(), rolling_npv(rate, &cash_flows, 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rolling_npv_window_too_long() {
+        rolling_npv(0.05, &[-100, 50, 60], 4);
+    }
+
+    #[test]
+    fn test_sign_change_count_skips_zeros() {
+        assert_eq!(0, sign_change_count(&[]));
+        assert_eq!(0, sign_change_count(&[0.0, 0.0]));
+        assert_eq!(1, sign_change_count(&[0.0, -500.0, 0.0, 0.0, 700.0]));
+        assert_eq!(3, sign_change_count(&[-1.0, 2.0, -3.0, 4.0]));
+        assert!(is_conventional_cash_flow(&[0.0, -500.0, 0.0, 0.0, 700.0]));
+        assert!(!is_conventional_cash_flow(&[-1.0, 2.0, -3.0, 4.0]));
+    }
+
+    #[test]
+    fn test_all_irrs() {
+        // Three sign changes with rates of 10%, 20%, and 30% built from (1.1 - x)(1.2 - x)(1.3 - x)
+        // where x = 1 + r.
+        let cash_flows = [-1.0, 3.6, -4.31, 1.716];
+        let irrs = all_irrs(&cash_flows, (0.0, 1.0), 200);
+        assert_eq!(3, irrs.len());
+        assert_rounded_6!(0.1, irrs[0]);
+        assert_rounded_6!(0.2, irrs[1]);
+        assert_rounded_6!(0.3, irrs[2]);
+        for irr in irrs.iter() {
+            assert_approx_equal!(0.0, net_present_value_schedule(&[*irr; 3], &cash_flows));
+        }
+        // A conventional series has one rate, and none outside the range.
+        assert_eq!(1, all_irrs(&[-1_000.0, 600.0, 600.0], (-0.5, 1.0), 50).len());
+        assert!(all_irrs(&[-1_000.0, 600.0, 600.0], (0.2, 1.0), 50).is_empty());
+    }
+
+    #[test]
+    fn test_npv_profile_range_matches_vary_rate() {
+        let cash_flows = [-2_500.0, 700.0, 900.0, 1_100.0, 400.0];
+        let points = npv_profile_range(&cash_flows, -0.05, 0.25, 12);
+        let rates = points.iter().map(|point| point.0).collect::<Vec<f64>>();
+        let scenarios = net_present_value_vary_rate(&cash_flows, &rates);
+        assert_eq!(13, points.len());
+        assert_eq!(-0.05, points[0].0);
+        assert_eq!(0.25, points[12].0);
+        for (point, entry) in points.iter().zip(scenarios.entries.iter()) {
+            assert_eq!(point.1, entry.output);
+        }
+    }
+
+    #[test]
+    fn test_npv_by_category_sums_to_total() {
+        let flows = vec![
+            ("revenue".to_string(), vec![0.0, 1_200.0, 1_500.0, 1_800.0, 2_000.0]),
+            ("costs".to_string(), vec![0.0, -400.0, -450.0, -500.0]),
+            ("investment".to_string(), vec![-4_000.0]),
+        ];
+        let solution = npv_by_category_solution(0.08, &flows);
+        let combined = [-4_000.0, 800.0, 1_050.0, 1_300.0, 2_000.0];
+        assert_approx_equal!(net_present_value_schedule(&[0.08; 4], &combined), solution.net_present_value());
+        assert_approx_equal!(-4_000.0, solution.categories()[2].1);
+    }
+}
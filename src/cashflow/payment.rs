@@ -173,22 +173,291 @@ use std::ops::Deref;
 
 const RUN_PAYMENT_INVARIANTS: bool = false;
 
+/// The sign convention used for the payment and the other money amounts in a [`PaymentSolution`]
+/// and its series.
+///
+/// The default is `CashFlowNegativePayment`, which follows the same convention as Excel and Google
+/// Sheets: money leaving the borrower's hands is negative, so a loan with a positive present value
+/// has a negative payment and negative principal and interest amounts.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub enum PaymentSignConvention {
+    /// A positive present value produces a negative payment, principal, and interest, as with the
+    /// `PMT` function in a spreadsheet.
+    #[default]
+    CashFlowNegativePayment,
+    /// The payment is shown as a positive amount. The loan is viewed from the other side, so the
+    /// present value, future value, sums, and every amount in the series are flipped along with
+    /// the payment. Principal plus interest still equals the payment.
+    AbsolutePayment,
+}
+
+/// A column of the payment schedule table, used to choose which columns are shown by
+/// [`PaymentSolution::print_table_columns`] and [`PaymentSeries::print_table_columns`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum PaymentColumn {
+    Period,
+    Payment,
+    PaymentsToDate,
+    PaymentsRemaining,
+    Principal,
+    PrincipalToDate,
+    /// The principal still owed at the end of the period, that is the balance of the loan.
+    PrincipalRemaining,
+    Interest,
+    InterestToDate,
+    InterestRemaining,
+}
+
+const PAYMENT_COLUMNS: [PaymentColumn; 10] = [PaymentColumn::Period, PaymentColumn::Payment, PaymentColumn::PaymentsToDate,
+    PaymentColumn::PaymentsRemaining, PaymentColumn::Principal, PaymentColumn::PrincipalToDate, PaymentColumn::PrincipalRemaining,
+    PaymentColumn::Interest, PaymentColumn::InterestToDate, PaymentColumn::InterestRemaining];
+
+// The first field follows the sign convention and is what the solution derefs to. The last field
+// is the solution as calculated, using the cash flow sign convention.
 #[derive(Clone, Debug)]
-pub struct PaymentSolution(CashflowSolution);
+pub struct PaymentSolution(CashflowSolution, PaymentSignConvention, CashflowSolution);
 
 #[derive(Clone, Debug)]
 pub struct PaymentSeries(CashflowSeries);
 
 impl PaymentSolution {
     pub(crate) fn new(solution: CashflowSolution) -> Self {
-        Self {
-            0: solution,
-        }
+        Self::new_with_sign_convention(solution, PaymentSignConvention::default())
+    }
+
+    fn new_with_sign_convention(solution: CashflowSolution, sign_convention: PaymentSignConvention) -> Self {
+        let flip = sign_convention == PaymentSignConvention::AbsolutePayment && solution.payment() < 0.0;
+        let shown = if flip {
+            // The same loan from the other side, with every money amount negated.
+            let (rate, periods, due_at_beginning) = (solution.rate(), solution.periods(), solution.due_at_beginning());
+            let (present_value, future_value, payment) = (-solution.present_value(), -solution.future_value(), -solution.payment());
+            let (formula, symbolic_formula) = payment_formula(rate, periods, present_value, future_value, due_at_beginning, payment);
+            CashflowSolution::new(CashflowVariable::Payment, rate, periods, present_value, future_value, due_at_beginning, payment, &formula, &symbolic_formula)
+        } else {
+            solution.clone()
+        };
+        Self(shown, sign_convention, solution)
+    }
+
+    /// Returns a copy of this solution using the given sign convention for the payment and for the
+    /// other money amounts, including those in the series. See [`PaymentSignConvention`].
+    ///
+    /// # Examples
+    /// ```
+    /// use finance_solution::*;
+    /// let solution = payment_solution(0.01, 12, 10_000, 0, false)
+    ///     .with_sign_convention(PaymentSignConvention::AbsolutePayment);
+    /// assert!(solution.payment() > 0.0);
+    ///
+    /// // The loan is seen from the lender's side so the present value is flipped too.
+    /// assert_rounded_4!(-10_000.0, solution.present_value());
+    /// ```
+    pub fn with_sign_convention(&self, sign_convention: PaymentSignConvention) -> Self {
+        Self::new_with_sign_convention(self.2.clone(), sign_convention)
     }
+
+    /// Returns a copy of this solution where the payment is a positive amount. This is the same as
+    /// calling `with_sign_convention(PaymentSignConvention::AbsolutePayment)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use finance_solution::*;
+    ///
+    /// // A $10,000 loan at 1% per month for 12 months.
+    /// let solution = payment_solution(0.01, 12, 10_000, 0, false);
+    ///
+    /// // By default the payment is negative since the present value is positive.
+    /// assert_rounded_4!(-888.4879, solution.payment());
+    ///
+    /// // With the absolute convention the payment, principal, and interest are all positive.
+    /// let solution = solution.with_abs_payments();
+    /// assert_rounded_4!(888.4879, solution.payment());
+    /// assert_rounded_2!(10_661.85, solution.sum_of_payments());
+    /// let series = solution.series();
+    /// assert_rounded_4!(100.0000, series[0].interest());
+    /// assert_rounded_4!(788.4879, series[0].principal());
+    /// solution.print_table();
+    /// ```
+    pub fn with_abs_payments(&self) -> Self {
+        self.with_sign_convention(PaymentSignConvention::AbsolutePayment)
+    }
+
+    /// Returns the sign convention used for the payment and the money amounts in the series.
+    pub fn sign_convention(&self) -> &PaymentSignConvention {
+        &self.1
+    }
+
     pub fn print_table(&self) {
         self.series().print_table(true, true)
     }
 
+    /// Prints the payment schedule showing only the given columns. The columns always appear in the
+    /// same order as in [`print_table`](./struct.PaymentSolution.html#method.print_table)
+    /// regardless of their order in `columns`.
+    ///
+    /// # Examples
+    /// Show a narrow table with the period, payment, principal, interest, and balance.
+    /// ```
+    /// # use finance_solution::*;
+    /// let solution = payment_solution(0.005, 24, 10_000, 0, false);
+    /// solution.print_table_columns(&[PaymentColumn::Period, PaymentColumn::Payment, PaymentColumn::Principal,
+    ///     PaymentColumn::Interest, PaymentColumn::PrincipalRemaining]);
+    /// ```
+    pub fn print_table_columns(&self, columns: &[PaymentColumn]) {
+        self.series().print_table_columns(columns)
+    }
+
+    /// Returns the payment schedule as a GitHub-flavored markdown table with every column, for
+    /// pasting into a README or an issue comment. See [`PaymentSeries::to_markdown`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use finance_solution::*;
+    /// let markdown = payment_solution(0.01, 12, 10_000, 0, false).to_markdown();
+    /// let lines = markdown.lines().collect::<Vec<_>>();
+    /// assert_eq!(14, lines.len());
+    /// assert!(lines[0].starts_with("| period | payment | payments_to_date |"));
+    /// assert!(lines[2].starts_with("| 1 | -888.4879 |"));
+    /// ```
+    pub fn to_markdown(&self) -> String {
+        self.series().to_markdown()
+    }
+
+    /// Returns the maturity in periods of a zero coupon bond with the same Macaulay duration as the
+    /// loan's payments when they're discounted at `rate`. A zero coupon bond's duration is its
+    /// maturity so this is simply the loan's duration, the average time until the payments are
+    /// made weighted by their present values. Any future value is included as a final payment at
+    /// the end of the last period.
+    ///
+    /// Payments made at the beginning of each period count from period 0, so the result is one
+    /// period shorter than for the same payments made at the end of each period.
+    ///
+    /// # Arguments
+    /// * `rate` - The periodic rate used to discount the payments. Pass the loan's own rate for the
+    /// duration at the loan's yield.
+    ///
+    /// # Panics
+    /// The call will fail if `rate` is less than or equal to -1.0 or is not finite.
+    ///
+    /// # Examples
+    /// ```
+    /// # use finance_solution::*;
+    /// // A 30-year mortgage at 0.5% per month behaves like a zero coupon bond maturing in just under
+    /// // 11 years.
+    /// let solution = payment_solution(0.005, 360, 200_000, 0, false);
+    /// assert_rounded_4!(129.3236, solution.duration_equivalent_maturity(0.005));
+    ///
+    /// // With payments at the start of each month the loan's cashflows all arrive a month sooner.
+    /// let solution = payment_solution(0.01, 12, 10_000, 0, true);
+    /// assert_rounded_4!(5.3815, solution.duration_equivalent_maturity(0.01));
+    /// ```
+    pub fn duration_equivalent_maturity(&self, rate: f64) -> f64 {
+        assert!(rate.is_finite(), "The rate must be finite (not NaN or infinity)");
+        assert!(rate > -1.0, "The rate must be greater than -1.0 (-100%).");
+        let periods = self.periods();
+        let offset = if self.due_at_beginning() { 1 } else { 0 };
+        let mut present_value_sum = 0.0;
+        let mut weighted_time = 0.0;
+        for period in 1..=periods {
+            let time = period - offset;
+            let mut cash_flow = self.payment();
+            if period == periods {
+                cash_flow += self.future_value();
+            }
+            let present_value = cash_flow / (1.0 + rate).powi(time as i32);
+            present_value_sum += present_value;
+            weighted_time += time as f64 * present_value;
+        }
+        if present_value_sum == 0.0 {
+            return 0.0;
+        }
+        weighted_time / present_value_sum
+    }
+
+    /// Returns the amount still owed on the loan at the end of a period, after that period's
+    /// interest and payment. This includes the part of any balloon payment that hasn't been paid
+    /// down yet, so at the end of the last period it's the future value. The result follows the
+    /// same sign convention as [`CashflowPeriod::principal_remaining`] in the payment schedule, so
+    /// by default it has the opposite sign from the present value.
+    ///
+    /// Related functions:
+    /// * To value the remaining payments at a different rate use
+    /// [`market_value`](./struct.PaymentSolution.html#method.market_value).
+    ///
+    /// # Arguments
+    /// * `after_period` - The number of periods that have passed. Zero gives the present value.
+    ///
+    /// # Panics
+    /// The call will fail if `after_period` is greater than the number of periods.
+    ///
+    /// # Examples
+    /// ```
+    /// # use finance_solution::*;
+    /// // A $100,000 loan at 0.5% per month with a $40,000 balloon after five years.
+    /// let solution = payment_solution(0.005, 60, 100_000, -40_000, false);
+    /// assert_rounded_4!(-78_129.3300, solution.remaining_balance(24));
+    /// assert_rounded_4!(-40_000.0, solution.remaining_balance(60));
+    ///
+    /// // For a fully amortizing loan this is the balance in the schedule.
+    /// let solution = payment_solution(0.005, 60, 100_000, 0, false);
+    /// assert_rounded_4!(solution.series()[23].principal_remaining(), solution.remaining_balance(24));
+    /// ```
+    pub fn remaining_balance(&self, after_period: u32) -> f64 {
+        assert!(after_period <= self.periods(), "The period must not be greater than the number of periods.");
+        let rate = self.rate();
+        let payments_made = after_period as f64;
+        let growth = (1.0 + rate).powi(after_period as i32);
+        let accumulated_payments = if rate == 0.0 {
+            self.payment() * payments_made
+        } else {
+            self.payment() * (1.0 + rate * self.due_at_beginning() as u32 as f64) * (growth - 1.0) / rate
+        };
+        -(self.present_value() * growth + accumulated_payments)
+    }
+
+    /// Returns the value at the end of a period of the loan's remaining payments, including any
+    /// balloon payment, discounted at a market rate rather than the loan's own rate. This is what a
+    /// buyer would pay for the loan if they wanted to earn the market rate. The result has the same
+    /// sign as [`remaining_balance`](./struct.PaymentSolution.html#method.remaining_balance).
+    ///
+    /// With the loan's own rate this is the same as the remaining balance. A higher market rate
+    /// shrinks the value and a lower one enlarges it.
+    ///
+    /// If the payments are due at the beginning of each period the next payment is due right away
+    /// so it isn't discounted.
+    ///
+    /// # Arguments
+    /// * `after_period` - The number of periods that have passed, so the payments from the next
+    /// period onward are included.
+    /// * `market_rate` - The periodic rate used to discount the remaining payments.
+    ///
+    /// # Panics
+    /// The call will fail if `after_period` is greater than the number of periods or if
+    /// `market_rate` is less than or equal to -1.0 or is not finite.
+    ///
+    /// # Examples
+    /// ```
+    /// # use finance_solution::*;
+    /// // A $100,000 loan at 0.5% per month with a $40,000 balloon is sold after two years when
+    /// // similar loans pay 0.75% per month.
+    /// let solution = payment_solution(0.005, 60, 100_000, -40_000, false);
+    /// assert_rounded_4!(-73_332.6102, solution.market_value(24, 0.0075));
+    ///
+    /// // At the loan's own rate the value is the remaining balance.
+    /// assert_rounded_4!(solution.remaining_balance(24), solution.market_value(24, 0.005));
+    /// ```
+    pub fn market_value(&self, after_period: u32, market_rate: f64) -> f64 {
+        assert!(after_period <= self.periods(), "The period must not be greater than the number of periods.");
+        assert!(market_rate.is_finite(), "The market rate must be finite (not NaN or infinity)");
+        assert!(market_rate > -1.0, "The market rate must be greater than -1.0 (-100%).");
+        let offset = if self.due_at_beginning() { 1 } else { 0 };
+        let payments = (after_period + 1..=self.periods())
+            .map(|period| self.payment() / (1.0 + market_rate).powi((period - after_period - offset) as i32))
+            .sum::<f64>();
+        let balloon = self.future_value() / (1.0 + market_rate).powi((self.periods() - after_period) as i32);
+        payments + balloon
+    }
+
     /// Calculates the period-by-period details of a payment calculation including how the payment
     /// is broken down between principal and interest.
     ///
@@ -244,6 +513,7 @@ impl PaymentSolution {
         if self.future_value() != 0.0 {
             return PaymentSeries::new(CashflowSeries::new(series));
         }
+        let payment = self.payment();
         let mut payments_to_date = 0.0;
         let mut principal_to_date = 0.0;
         let mut interest_to_date = 0.0;
@@ -254,8 +524,8 @@ impl PaymentSolution {
             } else {
                 -principal_remaining_at_start_of_period * self.rate()
             };
-            let principal = self.payment() - interest;
-            payments_to_date += self.payment();
+            let principal = payment - interest;
+            payments_to_date += payment;
             principal_to_date += principal;
             interest_to_date += interest;
             let payments_remaining = self.sum_of_payments() - payments_to_date;
@@ -268,7 +538,7 @@ impl PaymentSolution {
                 let symbolic_formula = "interest = -(principal * rate)".to_string();
                 (formula, symbolic_formula)
             };
-            let entry = CashflowPeriod::new(period, self.rate(), self.due_at_beginning(), self.payment(), payments_to_date,
+            let entry = CashflowPeriod::new(period, self.rate(), self.due_at_beginning(), payment, payments_to_date,
                                                payments_remaining, principal, principal_to_date, principal_remaining, interest,
                                                interest_to_date, interest_remaining, formula, symbolic_formula);
             series.push(entry);
@@ -280,6 +550,299 @@ impl PaymentSolution {
         payment_series
     }
 
+    /// Returns the first period in which more of the payment goes to principal than to interest,
+    /// or `None` if that never happens, as with an interest-only or negatively amortizing loan.
+    /// The periods are numbered starting at 1.
+    ///
+    /// If the payments are due at the beginning of each period, the first payment is all principal
+    /// so the result is period 1.
+    ///
+    /// Unlike [`PaymentSolution::series`] this works for any future value, such as for a balloon
+    /// loan.
+    ///
+    /// # Examples
+    /// A 30-year mortgage of $200,000 at 6% compounded monthly.
+    /// ```
+    /// # use finance_solution::*;
+    /// let solution = payment_solution(0.06 / 12.0, 360, 200_000, 0, false);
+    ///
+    /// // Starting in the 223rd month, more than 18 years into the loan, most of the payment goes to
+    /// // principal.
+    /// assert_eq!(Some(223), solution.crossover_period());
+    ///
+    /// // With a $50,000 balloon at the end the payment is smaller so the crossover comes later.
+    /// let balloon = payment_solution(0.06 / 12.0, 360, 200_000, -50_000, false);
+    /// assert_eq!(Some(272), balloon.crossover_period());
+    ///
+    /// // If the payment only covers interest the principal is never paid down.
+    /// let interest_only = payment_solution(0.06 / 12.0, 360, 200_000, -200_000, false);
+    /// assert_eq!(None, interest_only.crossover_period());
+    /// ```
+    pub fn crossover_period(&self) -> Option<u32> {
+        let payment = self.payment();
+        self.principal_and_interest().iter()
+            .position(|(principal, interest)| principal * payment > 0.0 && principal.abs() > interest.abs())
+            .map(|index| index as u32 + 1)
+    }
+
+    /// Returns true if in any period the payment doesn't cover the interest so that the balance
+    /// owed grows rather than shrinks. This happens when the future value is a larger balance
+    /// than the present value grows to on its own, so the payments add to the debt.
+    ///
+    /// Unlike [`PaymentSolution::series`] this works for any future value.
+    ///
+    /// # Examples
+    /// ```
+    /// # use finance_solution::*;
+    /// // Borrow $10,000 at 1% a month and owe $12,000 after a year. The interest alone would bring
+    /// // the balance to about $11,268 so the payments have to increase the balance.
+    /// let solution = payment_solution(0.01, 12, 10_000, -12_000, false);
+    /// assert!(solution.is_negatively_amortizing());
+    /// assert!(!solution.is_fully_amortizing());
+    ///
+    /// // An interest-only loan never reduces the balance but it doesn't grow either.
+    /// let interest_only = payment_solution(0.01, 12, 10_000, -10_000, false);
+    /// assert!(!interest_only.is_negatively_amortizing());
+    /// ```
+    pub fn is_negatively_amortizing(&self) -> bool {
+        // Principal that goes in the same direction as the original loan adds to the balance.
+        self.principal_and_interest().iter().any(|(principal, _)| principal * self.present_value() > 0.0)
+    }
+
+    /// Returns true if the payments pay off the entire balance by the end of the last period
+    /// without the balance growing along the way. This is false for an interest-only or balloon
+    /// loan, or one that's negatively amortizing.
+    ///
+    /// # Examples
+    /// ```
+    /// # use finance_solution::*;
+    /// assert!(payment_solution(0.06 / 12.0, 360, 200_000, 0, false).is_fully_amortizing());
+    ///
+    /// // A balloon payment is still owed at the end.
+    /// assert!(!payment_solution(0.06 / 12.0, 360, 200_000, -50_000, false).is_fully_amortizing());
+    /// ```
+    pub fn is_fully_amortizing(&self) -> bool {
+        self.future_value() == 0.0 && !self.is_negatively_amortizing()
+    }
+
+    /// Returns the principal part of each payment, one entry per period starting with period 1.
+    /// The amounts follow the solution's [`PaymentSignConvention`].
+    ///
+    /// Unlike [`PaymentSolution::series`] this works for any future value, such as for a balloon
+    /// loan.
+    ///
+    /// # Examples
+    /// ```
+    /// # use finance_solution::*;
+    /// let solution = payment_solution(0.01, 12, 10_000, 0, false);
+    /// let principal = solution.principal_series();
+    /// let interest = solution.interest_series();
+    /// assert_eq!(12, principal.len());
+    /// assert_rounded_4!(-788.4879, principal[0]);
+    /// assert_rounded_4!(-100.0, interest[0]);
+    ///
+    /// // Each payment is the sum of its principal and interest.
+    /// for (payment, (principal, interest)) in solution.payment_series().iter().zip(principal.iter().zip(interest.iter())) {
+    ///     assert_rounded_8!(*payment, principal + interest);
+    /// }
+    /// ```
+    pub fn principal_series(&self) -> Vec<f64> {
+        self.principal_and_interest().iter().map(|(principal, _)| *principal).collect()
+    }
+
+    /// Returns the interest part of each payment, one entry per period starting with period 1.
+    /// The amounts follow the solution's [`PaymentSignConvention`]. See
+    /// [`PaymentSolution::principal_series`] for an example.
+    pub fn interest_series(&self) -> Vec<f64> {
+        self.principal_and_interest().iter().map(|(_, interest)| *interest).collect()
+    }
+
+    /// Returns the payment for each period starting with period 1. Every entry is the same as
+    /// [`CashflowSolution::payment`] but this lines up with [`PaymentSolution::principal_series`]
+    /// and [`PaymentSolution::interest_series`].
+    pub fn payment_series(&self) -> Vec<f64> {
+        vec![self.payment(); self.periods() as usize]
+    }
+
+    // Returns the principal and interest parts of each payment.
+    fn principal_and_interest(&self) -> Vec<(f64, f64)> {
+        let payment = self.payment();
+        let mut principal_to_date = 0.0;
+        let mut parts = Vec::with_capacity(self.periods() as usize);
+        for period in 1..=self.periods() {
+            let interest = if self.due_at_beginning() && period == 1 {
+                0.0
+            } else {
+                -(self.present_value() + principal_to_date) * self.rate()
+            };
+            let principal = payment - interest;
+            principal_to_date += principal;
+            parts.push((principal, interest));
+        }
+        parts
+    }
+
+    /// Returns the internal rate of return of the loan after an upfront fee, which is the true
+    /// periodic yield to the lender. The fee is taken out of the amount disbursed at the start so
+    /// the borrower receives less but makes the same payments.
+    ///
+    /// The net cashflows are the present value less the fee at period 0, then the payments, then
+    /// any future value such as a balloon payment at the end of the last period. With no fee the
+    /// result is the loan's periodic rate and with a fee it's higher.
+    ///
+    /// The rate is found by bisection using the default [`SolverConfig`].
+    ///
+    /// # Arguments
+    /// * `upfront_fee` - The fee charged when the loan is made, as a positive amount.
+    ///
+    /// # Panics
+    /// The call will fail if the present value is zero, if `upfront_fee` is negative or not finite,
+    /// or if the fee is at least as large as the present value.
+    ///
+    /// # Examples
+    /// ```
+    /// # use finance_solution::*;
+    /// // A $10,000 loan at 1% per month for 12 months.
+    /// let solution = payment_solution(0.01, 12, 10_000, 0, false);
+    /// assert_rounded_6!(0.01, solution.internal_rate_of_return(0.0));
+    ///
+    /// // A $200 origination fee pushes the lender's yield above the note rate.
+    /// assert_rounded_6!(0.013212, solution.internal_rate_of_return(200.0));
+    /// ```
+    pub fn internal_rate_of_return(&self, upfront_fee: f64) -> f64 {
+        let present_value = self.present_value();
+        assert!(present_value != 0.0, "The present value must not be zero.");
+        assert!(upfront_fee.is_finite(), "The upfront fee must be finite (not NaN or infinity)");
+        assert!(upfront_fee >= 0.0, "The upfront fee must be zero or positive.");
+        assert!(upfront_fee < present_value.abs(), "The upfront fee must be less than the present value.");
+
+        // Build the net cashflows from the borrower's point of view. The lender's cashflows are the
+        // same with the signs reversed so they have the same internal rate of return.
+        let periods = self.periods() as usize;
+        let mut cashflows = vec![0.0; periods + 1];
+        cashflows[0] = present_value - upfront_fee * present_value.signum();
+        let first_payment_index = if self.due_at_beginning() { 0 } else { 1 };
+        for cashflow in cashflows.iter_mut().skip(first_payment_index).take(periods) {
+            *cashflow += self.payment();
+        }
+        cashflows[periods] += self.future_value();
+        let npv = |rate: f64| discounted_sum(rate, &cashflows);
+
+        // Charging a fee can only raise the yield so the answer is at or above the loan's rate.
+        // Widen the range until the net present value has the same sign as the amount disbursed.
+        let low = self.rate();
+        let mut high = self.rate().max(0.0) + 1.0;
+        while npv(high) * present_value < 0.0 {
+            high *= 2.0;
+            assert!(high.is_finite(), "Unable to find the internal rate of return.");
+        }
+        solver::bisect(low, high, &SolverConfig::default(), |rate| npv(rate) * present_value < 0.0).value
+    }
+
+    /// Returns the weighted average life (WAL) of the loan, the average time until each dollar of
+    /// principal is repaid. The result is measured in periods, so for monthly payments divide by 12
+    /// to get years.
+    ///
+    /// Each principal repayment is weighted by the time at which it's made. Payments due at the
+    /// beginning of a period are made at the end of the previous period, so the first one is at
+    /// time zero. If the loan has a nonzero future value, as with an interest-only or balloon
+    /// loan, that amount is repaid at the end of the last period. If there's no principal to repay
+    /// the result is zero.
+    ///
+    /// The formula is:
+    ///
+    /// WAL = sum( t * principal<sub>t</sub> ) / sum( principal<sub>t</sub> )
+    ///
+    /// # Examples
+    /// ```
+    /// # use finance_solution::*;
+    /// // A bullet loan pays only interest until the full principal is repaid at the end, so the
+    /// // weighted average life is the full term.
+    /// let bullet = payment_solution(0.05, 10, 100_000, -100_000, false);
+    /// assert_rounded_4!(10.0, bullet.weighted_average_life());
+    ///
+    /// // An amortizing loan repays principal along the way.
+    /// let amortizing = payment_solution(0.05, 10, 100_000, 0, false);
+    /// assert_rounded_4!(5.9009, amortizing.weighted_average_life());
+    /// ```
+    pub fn weighted_average_life(&self) -> f64 {
+        let rate = self.rate();
+        let periods = self.periods();
+        let payment = self.payment();
+        let mut principal_to_date = 0.0;
+        let mut weighted_principal = 0.0;
+        for period in 1..=periods {
+            let interest = if self.due_at_beginning() && period == 1 {
+                0.0
+            } else {
+                -(self.present_value() + principal_to_date) * rate
+            };
+            let principal = payment - interest;
+            let time = if self.due_at_beginning() { period - 1 } else { period };
+            principal_to_date += principal;
+            weighted_principal += time as f64 * principal;
+        }
+        // Whatever principal hasn't been repaid by the regular payments is repaid at the end.
+        let principal_remaining = -(self.present_value() + principal_to_date);
+        weighted_principal += periods as f64 * principal_remaining;
+        let total_principal = -self.present_value();
+        if total_principal == 0.0 {
+            0.0
+        } else {
+            weighted_principal / total_principal
+        }
+    }
+
+    /// Returns the loan viewed as a bond held by the lender so that bond analytics can be applied
+    /// to it.
+    ///
+    /// The mapping is:
+    /// * Each period is one coupon period, so there's one coupon per year and the yield per year
+    /// is the loan's periodic rate.
+    /// * The face value is the amount of the loan and each payment is a coupon, so the coupon rate
+    /// is the payment as a fraction of the amount of the loan.
+    /// * Any future value such as a balloon payment is the redemption at the end of the last
+    /// period. A fully amortizing loan has no redemption.
+    /// * Payments due at the beginning of each period are made at the end of the previous period,
+    /// so the first one is at time zero and isn't discounted.
+    ///
+    /// The amounts are from the lender's point of view so they're positive whatever the sign of
+    /// the present value, and the bond's price equals the amount of the loan.
+    ///
+    /// # Examples
+    /// ```
+    /// # use finance_solution::*;
+    /// // A $100,000 loan at 0.5% a month for 60 months.
+    /// let bond = payment_solution(0.005, 60, 100_000, 0, false).as_bond();
+    /// assert_rounded_4!(100_000.0, bond.clean_price());
+    /// assert_rounded_4!(1_933.2802, bond.coupon_payment());
+    /// assert_eq!(0.0, bond.redemption_pv());
+    ///
+    /// // The Macaulay duration in months weights the time of each payment by its present value.
+    /// let duration = bond.series().iter().map(|entry| entry.time() * entry.present_value()).sum::<f64>() / bond.clean_price();
+    /// assert_rounded_4!(29.0064, duration);
+    /// ```
+    pub fn as_bond(&self) -> BondSolution {
+        let rate = self.rate();
+        let periods = self.periods();
+        let sign = if self.present_value() < 0.0 { -1.0 } else { 1.0 };
+        let face_value = self.present_value().abs();
+        let coupon = -self.payment() * sign;
+        let redemption = -self.future_value() * sign;
+        let coupon_rate = if face_value == 0.0 { 0.0 } else { coupon / face_value };
+        let mut series = (1..=periods)
+            .map(|period| {
+                let time = if self.due_at_beginning() { period - 1 } else { period } as f64;
+                BondCashflow::new(time, BondCashflowType::Coupon, coupon, 1.0 / (1.0 + rate).powf(time))
+            })
+            .collect::<Vec<_>>();
+        if redemption != 0.0 {
+            let time = periods as f64;
+            series.push(BondCashflow::new(time, BondCashflowType::Redemption, redemption, 1.0 / (1.0 + rate).powf(time)));
+        }
+        BondSolution::new(face_value, coupon_rate, 1, rate, series, "price = sum(payment / (1 + r)^t) + future_value / (1 + r)^n")
+    }
+
     pub fn print_ab_comparison(
         &self,
         other: &PaymentSolution,
@@ -324,18 +887,20 @@ impl PaymentSolution {
         self.series().print_ab_comparison_locale_opt(&other.series(), include_running_totals, include_remaining_amounts, locale, precision);
     }
 
+    // Checks the solution as calculated, before any change to the sign convention.
     fn invariant(&self) {
-        let rate = self.rate();
-        let periods = self.periods();
-        let present_value = self.present_value();
-        let future_value = self.future_value();
-        let payment = self.payment();
-        let sum_of_payments = self.sum_of_payments();
-        let sum_of_interest = self.sum_of_interest();
-        let formula = self.formula();
-        let symbolic_formula = self.symbolic_formula();
+        let solution = &self.2;
+        let rate = solution.rate();
+        let periods = solution.periods();
+        let present_value = solution.present_value();
+        let future_value = solution.future_value();
+        let payment = solution.payment();
+        let sum_of_payments = solution.sum_of_payments();
+        let sum_of_interest = solution.sum_of_interest();
+        let formula = solution.formula();
+        let symbolic_formula = solution.symbolic_formula();
         let present_and_future_value= present_value + future_value;
-        assert!(self.calculated_field().is_payment());
+        assert!(solution.calculated_field().is_payment());
         assert!(rate.is_finite());
         assert!(present_value.is_finite());
         assert!(future_value.is_finite());
@@ -379,6 +944,62 @@ impl PaymentSeries {
         }
     }
 
+    /// Prints the series showing only the given columns. The columns always appear in the same
+    /// order as in the full table regardless of their order in `columns`.
+    pub fn print_table_columns(&self, columns: &[PaymentColumn]) {
+        self.print_table_columns_locale_opt(columns, None, None);
+    }
+
+    /// Prints the series showing only the given columns using the given locale and precision for
+    /// money amounts.
+    pub fn print_table_columns_locale(&self, columns: &[PaymentColumn], locale: &num_format::Locale, precision: usize) {
+        self.print_table_columns_locale_opt(columns, Some(locale), Some(precision));
+    }
+
+    /// Prints the series showing only the given columns using the grouping and decimal places of
+    /// the given style rather than a locale.
+    ///
+    /// # Examples
+    /// ```
+    /// # use finance_solution::*;
+    /// // Ungrouped numbers for another program to read.
+    /// payment_solution(0.01, 12, 25_000, 0, false)
+    ///     .series()
+    ///     .print_table_columns_styled(&[PaymentColumn::Payment, PaymentColumn::PrincipalRemaining], &NumberStyle::new(false, 2));
+    /// ```
+    pub fn print_table_columns_styled(&self, columns: &[PaymentColumn], style: &NumberStyle) {
+        let (columns, data) = self.table_columns_and_data(columns);
+        print_table_styled(&columns, data, style);
+    }
+
+    /// Returns the series as a GitHub-flavored markdown table with every column, for pasting into a
+    /// README or an issue comment. The numbers are formatted as in the printed tables.
+    pub fn to_markdown(&self) -> String {
+        let (columns, data) = self.table_columns_and_data(&PAYMENT_COLUMNS);
+        markdown_table(&columns, data)
+    }
+
+    fn print_table_columns_locale_opt(&self, columns: &[PaymentColumn], locale: Option<&num_format::Locale>, precision: Option<usize>) {
+        let (columns, data) = self.table_columns_and_data(columns);
+        print_table_locale_opt(&columns, data, locale, precision);
+    }
+
+    fn table_columns_and_data(&self, columns: &[PaymentColumn]) -> TableColumnsAndData {
+        let visible = |column: PaymentColumn| columns.contains(&column);
+        let columns = columns_with_strings(&[
+            ("period", "i", visible(PaymentColumn::Period)),
+            ("payment", "f", visible(PaymentColumn::Payment)), ("payments_to_date", "f", visible(PaymentColumn::PaymentsToDate)), ("payments_remaining", "f", visible(PaymentColumn::PaymentsRemaining)),
+            ("principal", "f", visible(PaymentColumn::Principal)), ("principal_to_date", "f", visible(PaymentColumn::PrincipalToDate)), ("principal_remaining", "f", visible(PaymentColumn::PrincipalRemaining)),
+            ("interest", "f", visible(PaymentColumn::Interest)), ("interest_to_date", "f", visible(PaymentColumn::InterestToDate)), ("interest_remaining", "f", visible(PaymentColumn::InterestRemaining))]);
+        let data = self.iter()
+            .map(|entry| vec![entry.period().to_string(),
+                              entry.payment().to_string(), entry.payments_to_date().to_string(), entry.payments_remaining().to_string(),
+                              entry.principal().to_string(), entry.principal_to_date().to_string(), entry.principal_remaining().to_string(),
+                              entry.interest().to_string(), entry.interest_to_date().to_string(), entry.interest_remaining().to_string()])
+            .collect::<Vec<_>>();
+        (columns, data)
+    }
+
     fn invariant(&self, solution: &CashflowSolution) {
         let periods = solution.periods();
         if solution.future_value() != 0.0 {
@@ -1008,6 +1629,145 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_crossover_period() {
+        let solution = payment_solution(0.045 / 12.0, 360, 200_000, 0, false);
+        assert_eq!(Some(176), solution.crossover_period());
+        // The crossover doesn't depend on the sign convention.
+        assert_eq!(Some(176), solution.with_abs_payments().crossover_period());
+        assert_eq!(Some(176), payment_solution(0.045 / 12.0, 360, -200_000, 0, false).crossover_period());
+        // With payments due at the beginning the first payment has no interest.
+        assert_eq!(Some(1), payment_solution(0.045 / 12.0, 360, 200_000, 0, true).crossover_period());
+    }
+
+    #[test]
+    fn test_crossover_period_balloon() {
+        let solution = payment_solution(0.005, 360, 200_000, -50_000, false);
+        assert_eq!(Some(272), solution.crossover_period());
+        let principal = solution.principal_series();
+        let interest = solution.interest_series();
+        assert!(principal[270].abs() < interest[270].abs());
+        assert!(principal[271].abs() > interest[271].abs());
+        // A balloon as large as the loan leaves nothing to amortize.
+        assert_eq!(None, payment_solution(0.005, 360, 200_000, -200_000, false).crossover_period());
+    }
+
+    #[test]
+    fn test_weighted_average_life() {
+        // With no interest the principal is repaid evenly so the average is the midpoint.
+        assert_approx_equal!(6.5, payment_solution(0.0, 12, 1_200, 0, false).weighted_average_life());
+        assert_approx_equal!(5.5, payment_solution(0.0, 12, 1_200, 0, true).weighted_average_life());
+        // A balloon loan falls between an amortizing loan and a bullet loan.
+        let amortizing = payment_solution(0.01, 60, 10_000, 0, false).weighted_average_life();
+        let balloon = payment_solution(0.01, 60, 10_000, -5_000, false).weighted_average_life();
+        assert!(amortizing < balloon && balloon < 60.0);
+        // The sign of the loan doesn't matter.
+        assert_approx_equal!(balloon, payment_solution(0.01, 60, -10_000, 5_000, false).weighted_average_life());
+    }
+
+    #[test]
+    fn test_negative_amortization() {
+        // The sign of the loan doesn't matter.
+        for present_value in [25_000.0_f64, -25_000.0].iter() {
+            let growing = payment_solution(0.005, 48, *present_value, -present_value * 1.5, false);
+            assert!(growing.is_negatively_amortizing());
+            assert!(!growing.is_fully_amortizing());
+            let amortizing = payment_solution(0.005, 48, *present_value, 0.0, true);
+            assert!(!amortizing.is_negatively_amortizing());
+            assert!(amortizing.is_fully_amortizing());
+        }
+    }
+
+    #[test]
+    fn test_principal_and_interest_series() {
+        for due_at_beginning in [false, true].iter() {
+            let solution = payment_solution(0.0055, 48, 18_000, 0, *due_at_beginning).with_abs_payments();
+            let series = solution.series();
+            let principal = solution.principal_series();
+            let interest = solution.interest_series();
+            assert_eq!(series.len(), principal.len());
+            for (index, entry) in series.iter().enumerate() {
+                assert_approx_equal!(entry.principal(), principal[index]);
+                assert_approx_equal!(entry.interest(), interest[index]);
+                assert_approx_equal!(solution.payment_series()[index], principal[index] + interest[index]);
+            }
+        }
+        // A balloon loan has no series but the principal and interest are still available.
+        let balloon = payment_solution(0.0055, 48, 18_000, -6_000, false);
+        assert_eq!(48, balloon.principal_series().len());
+        assert_approx_equal!(-12_000.0, balloon.principal_series().iter().sum::<f64>());
+    }
+
+    #[test]
+    fn test_internal_rate_of_return() {
+        for due_at_beginning in [false, true].iter() {
+            for future_value in [0.0, -20_000.0].iter() {
+                let solution = payment_solution(0.004, 120, 80_000, *future_value, *due_at_beginning);
+                assert_approx_equal!(0.004, solution.internal_rate_of_return(0.0));
+                assert!(solution.internal_rate_of_return(1_500.0) > 0.004);
+                // The sign of the loan doesn't matter.
+                let reversed = payment_solution(0.004, 120, -80_000, -future_value, *due_at_beginning);
+                assert_approx_equal!(solution.internal_rate_of_return(1_500.0), reversed.internal_rate_of_return(1_500.0));
+            }
+        }
+    }
+
+    #[test]
+    fn test_as_bond() {
+        for due_at_beginning in [false, true].iter() {
+            for (present_value, future_value) in [(45_000.0, 0.0_f64), (45_000.0, -15_000.0), (-45_000.0, 15_000.0)].iter() {
+                let solution = payment_solution(0.0065, 84, *present_value, *future_value, *due_at_beginning);
+                let bond = solution.as_bond();
+                assert_approx_equal!(45_000.0, bond.clean_price());
+                assert_approx_equal!(solution.payment().abs(), bond.coupon_payment());
+                assert_approx_equal!(future_value.abs() * 1.0065_f64.powi(-84), bond.redemption_pv());
+            }
+        }
+    }
+
+    #[test]
+    fn test_sign_convention_default() {
+        let solution = payment_solution(0.05, 10, 25_000, 0, false);
+        assert_eq!(&PaymentSignConvention::CashFlowNegativePayment, solution.sign_convention());
+        assert!(solution.payment() < 0.0);
+        run_payment_invariants(&solution, &solution.series());
+    }
+
+    #[test]
+    fn test_sign_convention_absolute_payment() {
+        for due_at_beginning in [false, true].iter() {
+            for present_value in [25_000.0_f64, -25_000.0].iter() {
+                let solution = payment_solution(0.05, 10, *present_value, 0, *due_at_beginning);
+                let abs_solution = solution.with_abs_payments();
+                assert_eq!(&PaymentSignConvention::AbsolutePayment, abs_solution.sign_convention());
+                assert_approx_equal!(solution.payment().abs(), abs_solution.payment());
+                assert_approx_equal!(solution.sum_of_payments().abs(), abs_solution.sum_of_payments());
+                assert_approx_equal!(solution.sum_of_interest().abs(), abs_solution.sum_of_interest());
+                let series = solution.series();
+                let abs_series = abs_solution.series();
+                assert_eq!(series.len(), abs_series.len());
+                for (entry, abs_entry) in series.iter().zip(abs_series.iter()) {
+                    assert_approx_equal!(entry.payment().abs(), abs_entry.payment());
+                    assert_approx_equal!(entry.principal().abs(), abs_entry.principal());
+                    assert_approx_equal!(entry.interest().abs(), abs_entry.interest());
+                    assert_approx_equal!(entry.principal_remaining().abs(), abs_entry.principal_remaining());
+                    assert_approx_equal!(abs_entry.payment(), abs_entry.principal() + abs_entry.interest());
+                }
+                // The accessors reached through the CashflowSolution agree with the series.
+                let cashflow_solution: &CashflowSolution = &abs_solution;
+                assert_approx_equal!(abs_solution.payment(), cashflow_solution.payment());
+                assert_approx_equal!(-present_value.abs(), cashflow_solution.present_value());
+                assert_approx_equal!(abs_series[0].principal_remaining() + abs_series[0].principal(), -cashflow_solution.present_value());
+                assert_approx_equal!(cashflow_solution.sum_of_interest(), cashflow_solution.sum_of_payments() + cashflow_solution.present_value());
+                run_payment_invariants(&abs_solution, &abs_series);
+                // Switching back gives the original solution.
+                let solution_again = abs_solution.with_sign_convention(PaymentSignConvention::CashFlowNegativePayment);
+                assert_eq!(solution.payment(), solution_again.payment());
+                assert_eq!(solution.present_value(), solution_again.present_value());
+            }
+        }
+    }
+
     #[test]
     fn test_against_excel_ipmt_month_1() {
         // Payments at the end of the period.
@@ -1875,5 +2635,44 @@ mod tests {
         assert_approx_equal!(-2839.5041f64, payment(0.23, 250, 12345.67, 123.4567, false));
     }
 
-}
+    #[test]
+    fn test_duration_equivalent_maturity_shorter_than_term() {
+        let solution = payment_solution(0.004, 180, 150_000, 0, false);
+        let maturity = solution.duration_equivalent_maturity(0.004);
+        assert!(maturity > 1.0 && maturity < 180.0);
+        // Discounting at a higher rate gives more weight to the early payments.
+        assert!(solution.duration_equivalent_maturity(0.01) < maturity);
+    }
+
+    #[test]
+    fn test_duration_equivalent_maturity_single_payment() {
+        // If the whole loan is repaid at the end the payment is zero and the loan is a zero coupon bond.
+        let future_value = -10_000.0 * 1.01f64.powi(12);
+        let solution = payment_solution(0.01, 12, 10_000, future_value, false);
+        assert_approx_equal!(0.0, solution.payment());
+        assert_approx_equal!(12.0, solution.duration_equivalent_maturity(0.01));
+    }
+
+    #[test]
+    fn test_market_value_at_note_rate() {
+        for &(due_at_beginning, future_value) in [(false, 0.0), (true, 0.0), (false, -15_000.0), (true, 7_500.0)].iter() {
+            let solution = payment_solution(0.0065, 84, 45_000.0, future_value, due_at_beginning);
+            for &after_period in [0, 1, 40, 83, 84].iter() {
+                assert_approx_equal!(solution.remaining_balance(after_period), solution.market_value(after_period, 0.0065));
+            }
+            assert_approx_equal!(-45_000.0, solution.remaining_balance(0));
+            assert!(solution.market_value(40, 0.009).abs() < solution.market_value(40, 0.0065).abs());
+        }
+    }
+
+    #[test]
+    fn test_remaining_balance_matches_series() {
+        let solution = payment_solution(0.004, 36, 12_000, 0, false);
+        for solution in [solution.clone(), solution.with_abs_payments()].iter() {
+            let series = solution.series();
+            assert_approx_equal!(series[11].principal_remaining(), solution.remaining_balance(12));
+            assert_approx_equal!(series[35].principal_remaining(), solution.remaining_balance(36));
+        }
+    }
 
+}
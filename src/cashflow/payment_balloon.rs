@@ -0,0 +1,277 @@
+//! **Balloon payment calculations.** Many commercial loans have payments calculated as if the loan
+//! would be paid off over a long amortization period, but the loan matures much earlier and the
+//! remaining balance is due all at once as a balloon payment.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // A $1 million loan at 6% per year amortized over 30 years with a balloon after 7 years.
+//! let solution = payment_balloon_solution(0.06 / 12.0, 360, 84, 1_000_000, false);
+//! dbg!(&solution);
+//! ```
+
+use crate::*;
+
+/// Returns the regular payment and the final balloon payment for a loan that's amortized over one
+/// number of periods but matures after a shorter number of periods.
+///
+/// The regular payment is the same as from [`payment_solution`] over the full amortization period.
+/// At maturity the remaining balance is due as the balloon payment, in addition to the last regular
+/// payment. As with [`payment`] the payments are negative if the present value is positive.
+///
+/// # Arguments
+/// * `rate` - The periodic interest rate, expressed as a floating point number. For instance 0.01
+/// would mean 1%.
+/// * `amortization_periods` - The number of periods used to calculate the regular payment.
+/// * `maturity_periods` - The number of periods until the loan matures and the balloon is due.
+/// * `present_value` - The amount of the loan.
+/// * `due_at_beginning` - True if the payments are due at the beginning of each period rather than
+/// at the end.
+///
+/// # Panics
+/// The call will fail if `maturity_periods` is zero or greater than `amortization_periods`, or for
+/// the same reasons as [`payment`].
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // A $1 million loan at 6% per year amortized over 30 years with a balloon after 7 years.
+/// let solution = payment_balloon_solution(0.06 / 12.0, 360, 84, 1_000_000, false);
+///
+/// // The monthly payment is the same as for a 30-year loan.
+/// assert_rounded_4!(-5_995.5053, solution.payment());
+///
+/// // After 7 years most of the loan is still outstanding.
+/// assert_rounded_4!(-896_393.8589, solution.balloon_amount());
+///
+/// // The balloon is the balance remaining after the last regular payment.
+/// let series = solution.series();
+/// assert_eq!(84, series.len());
+/// assert_rounded_4!(solution.balloon_amount(), series[83].principal_remaining());
+///
+/// // The total paid including the balloon, and the part of it that's interest.
+/// assert_rounded_4!(-1_400_016.3000, solution.sum_of_payments());
+/// assert_rounded_4!(-400_016.3000, solution.sum_of_interest());
+/// ```
+pub fn payment_balloon_solution<T>(rate: f64, amortization_periods: u32, maturity_periods: u32, present_value: T, due_at_beginning: bool) -> BalloonPaymentSolution
+    where T: Into<f64> + Copy
+{
+    assert!(maturity_periods > 0, "The number of periods until maturity must be greater than zero.");
+    assert!(maturity_periods <= amortization_periods, "The number of periods until maturity must not be greater than the number of amortization periods.");
+    let amortized = payment_solution(rate, amortization_periods, present_value, 0.0, due_at_beginning);
+    let balloon_amount = amortized.series()
+        .get(maturity_periods as usize - 1)
+        .map(|entry| entry.principal_remaining())
+        .unwrap();
+    BalloonPaymentSolution::new(amortized, maturity_periods, balloon_amount)
+}
+
+/// Returns the number of payments after which the balance of a loan falls to a target amount,
+/// such as a planned balloon payment, or `None` if the payments never bring the balance down to
+/// that amount.
+///
+/// This solves the amortization equation for the number of periods. The result may be fractional
+/// in which case the balance reaches the target partway between two payments. Interest-only and
+/// negatively amortizing loans never reach a target below the starting balance so the result is
+/// `None`.
+///
+/// # Arguments
+/// * `rate` - The periodic interest rate, expressed as a floating point number. For instance 0.01
+/// would mean 1%.
+/// * `payment` - The regular payment. As with [`payment`] it's negative if the present value is
+/// positive.
+/// * `present_value` - The amount of the loan.
+/// * `target_balance` - The balance remaining after the last payment. This has the same sign as
+/// the payment, as with [`BalloonPaymentSolution::balloon_amount`] and the `principal_remaining`
+/// values in a payment series.
+/// * `due_at_beginning` - True if the payments are due at the beginning of each period rather than
+/// at the end.
+///
+/// # Panics
+/// The call will fail if `rate` is less than or equal to -1.0 or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // A $1 million loan at 6% per year with the payment of a 30-year loan. How long until the
+/// // balance is down to $800,000?
+/// let payment = payment(0.06 / 12.0, 360, 1_000_000, 0, false);
+/// let periods = periods_until_balance(0.06 / 12.0, payment, 1_000_000, -800_000, false).unwrap();
+/// assert_rounded_4!(139.4278, periods);
+///
+/// // Paying only the interest leaves the balance where it started.
+/// assert_eq!(None, periods_until_balance(0.005, -5_000, 1_000_000, -800_000, false));
+/// ```
+pub fn periods_until_balance<C, P, B>(rate: f64, payment: C, present_value: P, target_balance: B, due_at_beginning: bool) -> Option<f64>
+    where
+        C: Into<f64> + Copy,
+        P: Into<f64> + Copy,
+        B: Into<f64> + Copy
+{
+    let payment = payment.into();
+    let present_value = present_value.into();
+    // The balance owed has the same sign as the present value.
+    let balance = -target_balance.into();
+    assert!(rate.is_finite(), "The rate must be finite (not NaN or infinity)");
+    assert!(rate > -1.0, "The rate must be greater than -1.0 (-100%).");
+    assert!(payment.is_finite(), "The payment must be finite (not NaN or infinity)");
+    assert!(present_value.is_finite(), "The present value must be finite (not NaN or infinity)");
+    assert!(balance.is_finite(), "The target balance must be finite (not NaN or infinity)");
+
+    if balance == present_value {
+        return Some(0.0);
+    }
+    let periods = if rate == 0.0 {
+        (balance - present_value) / payment
+    } else if due_at_beginning {
+        // The balance after n payments is (1 + r)^(n - 1) * (pv + pmt * (1 + r) / r) - pmt / r.
+        1.0 + ((balance + payment / rate) / (present_value + payment * (1.0 + rate) / rate)).ln() / (1.0 + rate).ln()
+    } else {
+        // The balance after n payments is (1 + r)^n * (pv + pmt / r) - pmt / r.
+        ((balance + payment / rate) / (present_value + payment / rate)).ln() / (1.0 + rate).ln()
+    };
+    if periods.is_finite() && periods >= 0.0 {
+        Some(periods)
+    } else {
+        None
+    }
+}
+
+/// A record of a loan with a balloon payment, created by calling [`payment_balloon_solution`].
+#[derive(Clone, Debug)]
+pub struct BalloonPaymentSolution {
+    amortized: PaymentSolution,
+    maturity_periods: u32,
+    balloon_amount: f64,
+    sum_of_payments: f64,
+    sum_of_interest: f64,
+}
+
+impl BalloonPaymentSolution {
+    pub(crate) fn new(amortized: PaymentSolution, maturity_periods: u32, balloon_amount: f64) -> Self {
+        assert!(balloon_amount.is_finite());
+        let sum_of_payments = amortized.payment() * maturity_periods as f64 + balloon_amount;
+        let sum_of_interest = sum_of_payments + amortized.present_value();
+        Self {
+            amortized,
+            maturity_periods,
+            balloon_amount,
+            sum_of_payments,
+            sum_of_interest,
+        }
+    }
+
+    /// Returns the periodic interest rate.
+    pub fn rate(&self) -> f64 {
+        self.amortized.rate()
+    }
+
+    /// Returns the number of periods used to calculate the regular payment.
+    pub fn amortization_periods(&self) -> u32 {
+        self.amortized.periods()
+    }
+
+    /// Returns the number of periods until the loan matures and the balloon is due.
+    pub fn maturity_periods(&self) -> u32 {
+        self.maturity_periods
+    }
+
+    /// Returns the amount of the loan.
+    pub fn present_value(&self) -> f64 {
+        self.amortized.present_value()
+    }
+
+    /// Returns true if the payments are due at the beginning of each period.
+    pub fn due_at_beginning(&self) -> bool {
+        self.amortized.due_at_beginning()
+    }
+
+    /// Returns the regular payment made each period until maturity.
+    pub fn payment(&self) -> f64 {
+        self.amortized.payment()
+    }
+
+    /// Returns the balloon payment due at maturity in addition to the last regular payment. This
+    /// is the balance of the loan remaining after the last regular payment.
+    pub fn balloon_amount(&self) -> f64 {
+        self.balloon_amount
+    }
+
+    /// Returns the sum of the regular payments and the balloon payment.
+    pub fn sum_of_payments(&self) -> f64 {
+        self.sum_of_payments
+    }
+
+    /// Returns the total interest paid until maturity.
+    pub fn sum_of_interest(&self) -> f64 {
+        self.sum_of_interest
+    }
+
+    /// Returns the solution for the regular payment as if the loan were held for the full
+    /// amortization period.
+    pub fn amortized_solution(&self) -> &PaymentSolution {
+        &self.amortized
+    }
+
+    /// Calculates the period-by-period details of the regular payments until maturity. The
+    /// remaining principal in the last entry is the balloon payment.
+    pub fn series(&self) -> CashflowSeries {
+        let maturity_periods = self.maturity_periods;
+        self.amortized.series().filter(|entry| entry.period() <= maturity_periods)
+    }
+
+    pub fn print_table(&self) {
+        self.series().print_table(true, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payment_balloon_matches_remaining_balance() {
+        let (rate, amortization_periods, maturity_periods, present_value) = (0.004, 300, 60, 250_000.0);
+        let solution = payment_balloon_solution(rate, amortization_periods, maturity_periods, present_value, false);
+        // The remaining balance is the future value of the loan less the future value of the
+        // payments made so far.
+        let remaining_balance = future_value(rate, maturity_periods, present_value, false)
+            + future_value_annuity(rate, maturity_periods, solution.payment(), false);
+        assert_approx_equal!(remaining_balance, solution.balloon_amount());
+    }
+
+    #[test]
+    fn test_payment_balloon_at_full_term() {
+        // With maturity at the end of the amortization period there's nothing left for a balloon.
+        let solution = payment_balloon_solution(0.004, 120, 120, 50_000, true);
+        assert_rounded_4!(0.0, solution.balloon_amount());
+        assert_approx_equal!(solution.amortized_solution().sum_of_interest(), solution.sum_of_interest());
+    }
+
+    #[test]
+    fn test_periods_until_balance_matches_schedule() {
+        for due_at_beginning in [false, true].iter() {
+            let solution = payment_solution(0.004, 300, 250_000, 0, *due_at_beginning);
+            for entry in solution.series().iter().filter(|entry| entry.period() % 50 == 0) {
+                let periods = periods_until_balance(0.004, solution.payment(), 250_000, entry.principal_remaining(), *due_at_beginning).unwrap();
+                assert_rounded_6!(entry.period() as f64, periods);
+            }
+        }
+        let periods = periods_until_balance(0.0, -1_000, 50_000, -20_000, false).unwrap();
+        assert_approx_equal!(30.0, periods);
+    }
+
+    #[test]
+    fn test_periods_until_balance_unreachable() {
+        // The payment doesn't cover the interest so the balance grows.
+        assert_eq!(None, periods_until_balance(0.004, -500, 250_000, -200_000, false));
+        // The target is above the starting balance of an amortizing loan.
+        assert_eq!(None, periods_until_balance(0.004, -1_500, 250_000, -300_000, true));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_payment_balloon_maturity_after_amortization() {
+        payment_balloon_solution(0.004, 120, 121, 50_000, false);
+    }
+}
@@ -0,0 +1,269 @@
+//! **Sinking fund payments.** Rather than paying off a loan, a sinking fund sets aside a fixed
+//! amount each period so that the balance reaches a target on a given date, such as money for
+//! replacing equipment or retiring a bond. There may already be a balance at the start.
+//!
+//! The payment is found from the future value of the starting balance plus the future value of the
+//! payments:
+//!
+//! future_value = present_value * (1 + r)<sup>n</sup> + payment * (1 + r * due) * ((1 + r)<sup>n</sup> - 1) / r
+//!
+//! Unlike [`payment`], where money paid in and money paid out have opposite signs, all of the
+//! amounts here are seen from the saver's side: a positive target and starting balance give a
+//! positive payment to deposit.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // Save $100,000 over 10 years at 5% a year.
+//! let solution = payment_for_future_value_solution(0.05, 10, 100_000, 0, false);
+//! dbg!(&solution);
+//! solution.print_table();
+//! ```
+
+use crate::*;
+
+/// Returns the **payment needed each period to reach a future value**, allowing for a balance that
+/// is already saved.
+///
+/// Related functions:
+/// * To see the balance in each period use [`payment_for_future_value_solution`].
+/// * For a loan paid down to zero use [`payment`].
+///
+/// See the [payment_for_future_value](./index.html) module page for the formula. If there's no
+/// interest the payment is (future_value - present_value) / periods.
+///
+/// # Arguments
+/// * `rate` - The rate at which the balance grows per period, expressed as a floating point number.
+/// For instance 0.05 would mean 5%.
+/// * `periods` - The number of payments.
+/// * `future_value` - The balance to reach by the end of the last period.
+/// * `present_value` - The balance at the start.
+/// * `due_at_beginning` - True if the payments are made at the beginning of each period rather
+/// than at the end.
+///
+/// # Panics
+/// The call will fail if `rate` is less than or equal to -1.0, if `periods` is zero, or if any value
+/// is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // Save $100,000 over 10 years at 5% a year.
+/// let pmt = payment_for_future_value(0.05, 10, 100_000, 0, false);
+/// assert_rounded_4!(7_950.4575, pmt);
+///
+/// // With $20,000 already saved the deposits are smaller.
+/// let pmt = payment_for_future_value(0.05, 10, 100_000, 20_000, false);
+/// assert_rounded_4!(5_360.3660, pmt);
+///
+/// // This is the same as the payment function with the cash flow sign convention.
+/// assert_rounded_4!(-payment(0.05, 10, -20_000, 100_000, false), pmt);
+/// ```
+pub fn payment_for_future_value<F, P>(rate: f64, periods: u32, future_value: F, present_value: P, due_at_beginning: bool) -> f64
+    where
+        F: Into<f64> + Copy,
+        P: Into<f64> + Copy
+{
+    payment_for_future_value_solution(rate, periods, future_value, present_value, due_at_beginning).payment()
+}
+
+/// Returns the **payment needed each period to reach a future value** along with the inputs and
+/// the balance in each period.
+///
+/// Related functions:
+/// * To calculate only the payment as an `f64` use [`payment_for_future_value`].
+///
+/// # Arguments
+/// * `rate` - The rate at which the balance grows per period, expressed as a floating point number.
+/// For instance 0.05 would mean 5%.
+/// * `periods` - The number of payments.
+/// * `future_value` - The balance to reach by the end of the last period.
+/// * `present_value` - The balance at the start.
+/// * `due_at_beginning` - True if the payments are made at the beginning of each period rather
+/// than at the end.
+///
+/// # Panics
+/// The call will fail if `rate` is less than or equal to -1.0, if `periods` is zero, or if any value
+/// is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = payment_for_future_value_solution(0.05, 10, 100_000, 0, true);
+/// assert_rounded_4!(7_571.8643, solution.payment());
+/// assert_rounded_4!(75_718.6428, solution.sum_of_payments());
+/// assert_rounded_4!(24_281.3572, solution.sum_of_interest());
+///
+/// // The balance starts at zero and reaches the target at the end of the last period.
+/// let series = solution.series();
+/// assert_eq!(11, series.len());
+/// assert_rounded_4!(0.0, series[0].value());
+/// assert_rounded_4!(100_000.0, series[10].value());
+/// ```
+pub fn payment_for_future_value_solution<F, P>(rate: f64, periods: u32, future_value: F, present_value: P, due_at_beginning: bool) -> SinkingFundSolution
+    where
+        F: Into<f64> + Copy,
+        P: Into<f64> + Copy
+{
+    let future_value = future_value.into();
+    let present_value = present_value.into();
+    assert!(rate.is_finite(), "The rate must be finite (not NaN or infinity)");
+    assert!(rate > -1.0, "The rate must be greater than -1.0 (-100%).");
+    assert!(future_value.is_finite(), "The future value must be finite (not NaN or infinity)");
+    assert!(present_value.is_finite(), "The present value must be finite (not NaN or infinity)");
+    assert!(periods > 0, "The number of periods must be greater than zero.");
+    let payment = if rate == 0.0 {
+        (future_value - present_value) / periods as f64
+    } else {
+        let growth = (1.0 + rate).powf(periods as f64);
+        let due_factor = if due_at_beginning { 1.0 + rate } else { 1.0 };
+        (future_value - present_value * growth) * rate / ((growth - 1.0) * due_factor)
+    };
+    SinkingFundSolution::new(rate, periods, future_value, present_value, due_at_beginning, payment)
+}
+
+/// A record of the payment needed to reach a future value. Created by calling
+/// [`payment_for_future_value_solution`].
+#[derive(Clone, Debug)]
+pub struct SinkingFundSolution {
+    rate: f64,
+    periods: u32,
+    future_value: f64,
+    present_value: f64,
+    due_at_beginning: bool,
+    payment: f64,
+    formula: String,
+    symbolic_formula: String,
+}
+
+impl SinkingFundSolution {
+    pub(crate) fn new(rate: f64, periods: u32, future_value: f64, present_value: f64, due_at_beginning: bool, payment: f64) -> Self {
+        assert!(payment.is_finite());
+        let (formula, symbolic_formula) = if rate == 0.0 {
+            (format!("({:.4} - {:.4}) / {}", future_value, present_value, periods), "pmt = (fv - pv) / n".to_string())
+        } else {
+            let due = if due_at_beginning { 1.0 } else { 0.0 };
+            (format!("({:.4} - {:.4} * {:.6}^{}) * {:.6} / (({:.6}^{} - 1) * (1 + {:.6} * {}))",
+                     future_value, present_value, 1.0 + rate, periods, rate, 1.0 + rate, periods, rate, due),
+             "pmt = (fv - pv * (1 + r)^n) * r / (((1 + r)^n - 1) * (1 + r * due))".to_string())
+        };
+        Self {
+            rate,
+            periods,
+            future_value,
+            present_value,
+            due_at_beginning,
+            payment,
+            formula,
+            symbolic_formula,
+        }
+    }
+
+    /// Returns the periodic rate.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the number of payments.
+    pub fn periods(&self) -> u32 {
+        self.periods
+    }
+
+    /// Returns the balance to reach by the end of the last period.
+    pub fn future_value(&self) -> f64 {
+        self.future_value
+    }
+
+    /// Returns the balance at the start.
+    pub fn present_value(&self) -> f64 {
+        self.present_value
+    }
+
+    /// Returns true if the payments are made at the beginning of each period.
+    pub fn due_at_beginning(&self) -> bool {
+        self.due_at_beginning
+    }
+
+    /// Returns the amount to deposit each period.
+    pub fn payment(&self) -> f64 {
+        self.payment
+    }
+
+    /// Returns the total of the payments over all periods.
+    pub fn sum_of_payments(&self) -> f64 {
+        self.payment * self.periods as f64
+    }
+
+    /// Returns the growth earned over all periods, which is the part of the future value that
+    /// didn't come from the starting balance or the payments.
+    pub fn sum_of_interest(&self) -> f64 {
+        self.future_value - self.present_value - self.sum_of_payments()
+    }
+
+    /// Returns a text version of the formula used to calculate the payment with the actual values.
+    pub fn formula(&self) -> &str {
+        &self.formula
+    }
+
+    /// Returns a text version of the formula used to calculate the payment using variables such as
+    /// r for the rate.
+    pub fn symbolic_formula(&self) -> &str {
+        &self.symbolic_formula
+    }
+
+    /// Calculates the balance at the end of each period after that period's growth and payment.
+    /// Period 0 holds the starting balance and the last period holds the future value.
+    pub fn series(&self) -> TvmSeries {
+        let symbolic_formula = if self.due_at_beginning {
+            "balance = (prev_balance + pmt) * (1 + r)"
+        } else {
+            "balance = prev_balance * (1 + r) + pmt"
+        };
+        let mut series = vec![TvmPeriod::new(0, 0.0, self.present_value, &format!("{:.4}", self.present_value), "balance = pv")];
+        let mut balance = self.present_value;
+        for period in 1..=self.periods {
+            let previous_balance = balance;
+            let formula = if self.due_at_beginning {
+                balance = (balance + self.payment) * (1.0 + self.rate);
+                format!("({:.4} + {:.4}) * {:.6}", previous_balance, self.payment, 1.0 + self.rate)
+            } else {
+                balance = balance * (1.0 + self.rate) + self.payment;
+                format!("{:.4} * {:.6} + {:.4}", previous_balance, 1.0 + self.rate, self.payment)
+            };
+            series.push(TvmPeriod::new(period, self.rate, balance, &formula, symbolic_formula));
+        }
+        TvmSeries::new(series)
+    }
+
+    pub fn print_table(&self) {
+        self.series().print_table();
+    }
+
+    pub fn print_table_locale(&self, locale: &num_format::Locale, precision: usize) {
+        self.series().print_table_locale(locale, precision);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payment_for_future_value_reaches_target() {
+        for due_at_beginning in [false, true].iter() {
+            let solution = payment_for_future_value_solution(0.004, 120, 50_000, 5_000, *due_at_beginning);
+            let series = solution.series();
+            assert_approx_equal!(5_000.0, series[0].value());
+            assert_approx_equal!(50_000.0, series.last().unwrap().value());
+            assert_approx_equal!(-payment(0.004, 120, -5_000, 50_000, *due_at_beginning), solution.payment());
+        }
+    }
+
+    #[test]
+    fn test_payment_for_future_value_zero_rate() {
+        let solution = payment_for_future_value_solution(0.0, 8, 10_000, 2_000, false);
+        assert_approx_equal!(1_000.0, solution.payment());
+        assert_approx_equal!(0.0, solution.sum_of_interest());
+        assert_approx_equal!(6_000.0, solution.series()[4].value());
+    }
+}
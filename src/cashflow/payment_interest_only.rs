@@ -0,0 +1,221 @@
+//! **Interest-only periods followed by amortization.** Construction loans, student loans, and some
+//! mortgages start with a number of periods where the payment only covers the interest so the
+//! balance stays the same. After that the loan is paid off with level payments over the remaining
+//! periods.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // A $300,000 loan at 6% per year, interest-only for two years, then amortized over 30 years.
+//! let solution = payment_interest_only_then_amortize(0.06 / 12.0, 24, 360, 300_000, false);
+//! dbg!(&solution);
+//! ```
+
+use crate::*;
+
+/// Returns the interest-only payment and the amortizing payment for a loan that pays only interest
+/// for a number of periods and is then paid off over a further number of periods.
+///
+/// During the interest-only periods the balance doesn't change. The amortizing payment is the same
+/// as from [`payment_solution`] for the original amount over the amortization periods. As with
+/// [`payment`] the payments are negative if the present value is positive.
+///
+/// If the payments are due at the beginning of each period the interest is paid in advance, so the
+/// interest-only payment is discounted by one period.
+///
+/// # Arguments
+/// * `rate` - The periodic interest rate, expressed as a floating point number. For instance 0.01
+/// would mean 1%.
+/// * `io_periods` - The number of interest-only periods at the start of the loan. This may be zero.
+/// * `amortization_periods` - The number of periods over which the loan is paid off after the
+/// interest-only periods.
+/// * `present_value` - The amount of the loan.
+/// * `due_at_beginning` - True if the payments are due at the beginning of each period rather than
+/// at the end.
+///
+/// # Panics
+/// The call will fail if `amortization_periods` is zero, or for the same reasons as [`payment`].
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // A $300,000 loan at 6% per year, interest-only for two years, then amortized over 30 years.
+/// let solution = payment_interest_only_then_amortize(0.06 / 12.0, 24, 360, 300_000, false);
+/// assert_rounded_4!(-1_500.0, solution.io_payment());
+/// assert_rounded_4!(-1_798.6516, solution.amortizing_payment());
+/// assert_rounded_4!(-383_514.5672, solution.sum_of_interest());
+///
+/// // The balance is unchanged through the interest-only periods and paid off at the end.
+/// let series = solution.series();
+/// assert_eq!(384, series.len());
+/// assert_rounded_4!(-300_000.0, series[23].principal_remaining());
+/// assert_rounded_4!(0.0, series[383].principal_remaining());
+/// ```
+pub fn payment_interest_only_then_amortize<T>(rate: f64, io_periods: u32, amortization_periods: u32, present_value: T, due_at_beginning: bool) -> InterestOnlyPaymentSolution
+    where T: Into<f64> + Copy
+{
+    assert!(amortization_periods > 0, "The number of amortization periods must be greater than zero.");
+    let present_value = present_value.into();
+    let amortizing = payment_solution(rate, amortization_periods, present_value, 0.0, due_at_beginning);
+    InterestOnlyPaymentSolution::new(amortizing, io_periods)
+}
+
+/// A record of a loan with interest-only periods followed by amortization, created by calling
+/// [`payment_interest_only_then_amortize`].
+#[derive(Clone, Debug)]
+pub struct InterestOnlyPaymentSolution {
+    amortizing: PaymentSolution,
+    io_periods: u32,
+    io_payment: f64,
+    sum_of_payments: f64,
+    sum_of_interest: f64,
+}
+
+impl InterestOnlyPaymentSolution {
+    pub(crate) fn new(amortizing: PaymentSolution, io_periods: u32) -> Self {
+        let rate = amortizing.rate();
+        let io_payment = if amortizing.due_at_beginning() {
+            -amortizing.present_value() * rate / (1.0 + rate)
+        } else {
+            -amortizing.present_value() * rate
+        };
+        assert!(io_payment.is_finite());
+        let sum_of_payments = io_payment * io_periods as f64 + amortizing.sum_of_payments();
+        let sum_of_interest = sum_of_payments + amortizing.present_value();
+        Self {
+            amortizing,
+            io_periods,
+            io_payment,
+            sum_of_payments,
+            sum_of_interest,
+        }
+    }
+
+    /// Returns the periodic interest rate.
+    pub fn rate(&self) -> f64 {
+        self.amortizing.rate()
+    }
+
+    /// Returns the number of interest-only periods.
+    pub fn io_periods(&self) -> u32 {
+        self.io_periods
+    }
+
+    /// Returns the number of periods over which the loan is paid off after the interest-only
+    /// periods.
+    pub fn amortization_periods(&self) -> u32 {
+        self.amortizing.periods()
+    }
+
+    /// Returns the total number of periods including the interest-only periods.
+    pub fn periods(&self) -> u32 {
+        self.io_periods + self.amortizing.periods()
+    }
+
+    /// Returns the amount of the loan.
+    pub fn present_value(&self) -> f64 {
+        self.amortizing.present_value()
+    }
+
+    /// Returns true if the payments are due at the beginning of each period.
+    pub fn due_at_beginning(&self) -> bool {
+        self.amortizing.due_at_beginning()
+    }
+
+    /// Returns the payment made in each of the interest-only periods.
+    pub fn io_payment(&self) -> f64 {
+        self.io_payment
+    }
+
+    /// Returns the level payment made in each period after the interest-only periods.
+    pub fn amortizing_payment(&self) -> f64 {
+        self.amortizing.payment()
+    }
+
+    /// Returns the sum of the interest-only and amortizing payments.
+    pub fn sum_of_payments(&self) -> f64 {
+        self.sum_of_payments
+    }
+
+    /// Returns the total interest paid over the life of the loan.
+    pub fn sum_of_interest(&self) -> f64 {
+        self.sum_of_interest
+    }
+
+    /// Returns the solution for the amortizing payments as if the loan started when the
+    /// interest-only periods end.
+    pub fn amortizing_solution(&self) -> &PaymentSolution {
+        &self.amortizing
+    }
+
+    /// Calculates the period-by-period details of the interest-only payments followed by the
+    /// amortizing payments.
+    pub fn series(&self) -> CashflowSeries {
+        let rate = self.rate();
+        let present_value = self.present_value();
+        let due_at_beginning = self.due_at_beginning();
+        let mut series = vec![];
+        let mut payments_to_date = 0.0;
+        let mut principal_to_date = 0.0;
+        let mut interest_to_date = 0.0;
+        let mut push_entry = |period: u32, payment: f64, principal: f64, interest: f64, formula: String, symbolic_formula: String| {
+            payments_to_date += payment;
+            principal_to_date += principal;
+            interest_to_date += interest;
+            series.push(CashflowPeriod::new(period, rate, due_at_beginning, payment, payments_to_date, self.sum_of_payments - payments_to_date,
+                                            principal, principal_to_date, -(present_value + principal_to_date), interest,
+                                            interest_to_date, self.sum_of_interest - interest_to_date, formula, symbolic_formula));
+        };
+        for period in 1..=self.io_periods {
+            let (formula, symbolic_formula) = if due_at_beginning {
+                (format!("{:.4} = -({:.4} * {:.6} / {:.6})", self.io_payment, present_value, rate, 1.0 + rate), "interest = -(principal * rate / (1 + rate))".to_string())
+            } else {
+                (format!("{:.4} = -({:.4} * {:.6})", self.io_payment, present_value, rate), "interest = -(principal * rate)".to_string())
+            };
+            push_entry(period, self.io_payment, 0.0, self.io_payment, formula, symbolic_formula);
+        }
+        for entry in self.amortizing.series().iter() {
+            push_entry(self.io_periods + entry.period(), entry.payment(), entry.principal(), entry.interest(),
+                       entry.formula().to_string(), entry.symbolic_formula().to_string());
+        }
+        CashflowSeries::new(series)
+    }
+
+    pub fn print_table(&self) {
+        self.series().print_table(true, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payment_interest_only_balance() {
+        for due_at_beginning in [false, true].iter() {
+            let (rate, io_periods, amortization_periods, loan) = (0.0045, 36, 120, 40_000.0);
+            let solution = payment_interest_only_then_amortize(rate, io_periods, amortization_periods, loan, *due_at_beginning);
+            let series = solution.series();
+            assert_eq!((io_periods + amortization_periods) as usize, series.len());
+            for entry in series.iter().take(io_periods as usize) {
+                assert_approx_equal!(-loan, entry.principal_remaining());
+                assert_approx_equal!(solution.io_payment(), entry.interest());
+            }
+            assert_rounded_4!(0.0, series.last().unwrap().principal_remaining());
+            assert_rounded_4!(0.0, series.last().unwrap().interest_remaining());
+            // The present value of all of the payments is the amount of the loan. The value of the
+            // amortizing payments is discounted back over the interest-only periods.
+            let amortizing_value = present_value_annuity(rate, amortization_periods, solution.amortizing_payment(), *due_at_beginning);
+            let present_value_of_payments = present_value_annuity(rate, io_periods, solution.io_payment(), *due_at_beginning)
+                - present_value(rate, io_periods, amortizing_value, false);
+            assert_approx_equal!(loan, present_value_of_payments);
+        }
+    }
+
+    #[test]
+    fn test_payment_interest_only_no_io_periods() {
+        let solution = payment_interest_only_then_amortize(0.01, 0, 24, 5_000, false);
+        assert_approx_equal!(payment(0.01, 24, 5_000, 0, false), solution.amortizing_payment());
+        assert_approx_equal!(solution.amortizing_solution().sum_of_interest(), solution.sum_of_interest());
+    }
+}
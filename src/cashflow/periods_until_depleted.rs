@@ -0,0 +1,504 @@
+//! **How long will savings last?** A retirement balance earns a return each period while a
+//! withdrawal is taken at the end of each period, optionally raised by inflation every period. The
+//! balance runs out when the present value of the withdrawals equals the starting balance:
+//!
+//! balance = withdrawal / (r - g) * (1 - ((1 + g) / (1 + r))<sup>n</sup>)
+//!
+//! so the number of withdrawals is:
+//!
+//! n = ln(1 - balance * (r - g) / withdrawal) / ln((1 + g) / (1 + r))
+//!
+//! where g is the inflation adjustment, or zero if the withdrawal stays level. When the rate
+//! equals the inflation adjustment every withdrawal has the same present value and
+//! n = balance * (1 + r) / withdrawal.
+//!
+//! If the return covers the withdrawals, that is if balance * (r - g) is at least as large as the
+//! first withdrawal, the balance never runs out.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // $1,000,000 earning 0.4% a month with $5,000 withdrawn each month, raised 0.2% a month.
+//! let solution = periods_until_depleted_solution(0.004, 1_000_000, 5_000, Some(0.002)).unwrap();
+//! dbg!(&solution);
+//! solution.series().filter(|entry| entry.period() % 12 == 0).print_table();
+//! ```
+
+use crate::*;
+use std::ops::Deref;
+
+/// Returns the **number of withdrawals before a balance is used up**, or `None` if the
+/// withdrawals can go on forever.
+///
+/// Related functions:
+/// * To see the withdrawal and balance in each period use [`periods_until_depleted_solution`].
+/// * To find the number of periods to pay off a loan or reach a savings goal use [`nper`].
+///
+/// See the [periods_until_depleted](./index.html) module page for the formula. The result may
+/// include a fraction of a period, which stands for a last withdrawal smaller than the rest.
+///
+/// # Arguments
+/// * `rate` - The return earned on the balance per period, expressed as a floating point number.
+/// For instance 0.004 would mean 0.4%.
+/// * `balance` - The starting balance.
+/// * `withdrawal` - The first withdrawal, taken at the end of the first period.
+/// * `inflation_adjust` - The rate at which the withdrawal is raised each period, or `None` for a
+/// level withdrawal.
+///
+/// # Panics
+/// The call will fail if `balance` or `withdrawal` is not positive, if `rate` or the inflation
+/// adjustment is less than or equal to -1.0, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // $1,000,000 earning 0.4% a month with $5,000 withdrawn each month lasts about 33.6 years.
+/// let periods = periods_until_depleted(0.004, 1_000_000, 5_000, None).unwrap();
+/// assert_rounded_4!(403.1637, periods);
+///
+/// // Raising the withdrawal by 0.2% a month uses it up in about 21.3 years.
+/// let periods = periods_until_depleted(0.004, 1_000_000, 5_000, Some(0.002)).unwrap();
+/// assert_rounded_4!(256.1790, periods);
+///
+/// // A $4,000 withdrawal is covered by the return so the balance never runs out.
+/// assert_eq!(None, periods_until_depleted(0.004, 1_000_000, 4_000, None));
+/// ```
+pub fn periods_until_depleted<B, W>(rate: f64, balance: B, withdrawal: W, inflation_adjust: Option<f64>) -> Option<f64>
+    where
+        B: Into<f64> + Copy,
+        W: Into<f64> + Copy
+{
+    let balance = balance.into();
+    let withdrawal = withdrawal.into();
+    check_depletion_parameters(rate, balance, withdrawal, inflation_adjust);
+    let growth = inflation_adjust.unwrap_or(0.0);
+    if rate == growth {
+        return Some(balance * (1.0 + rate) / withdrawal);
+    }
+    let remaining_fraction = 1.0 - balance * (rate - growth) / withdrawal;
+    if remaining_fraction <= 0.0 {
+        return None;
+    }
+    Some(remaining_fraction.ln() / ((1.0 + growth) / (1.0 + rate)).ln())
+}
+
+/// Returns the **number of withdrawals before a balance is used up** along with the withdrawal,
+/// interest, and balance in each period, or `None` if the withdrawals can go on forever.
+///
+/// Related functions:
+/// * To calculate only the number of periods as an `f64` use [`periods_until_depleted`].
+///
+/// The series has one entry for each withdrawal. The last withdrawal is whatever is left of the
+/// balance, so it's usually smaller than the scheduled withdrawal.
+///
+/// # Arguments
+/// * `rate` - The return earned on the balance per period, expressed as a floating point number.
+/// For instance 0.004 would mean 0.4%.
+/// * `balance` - The starting balance.
+/// * `withdrawal` - The first withdrawal, taken at the end of the first period.
+/// * `inflation_adjust` - The rate at which the withdrawal is raised each period, or `None` for a
+/// level withdrawal.
+///
+/// # Panics
+/// The call will fail if `balance` or `withdrawal` is not positive, if `rate` or the inflation
+/// adjustment is less than or equal to -1.0, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // $100,000 earning 0.5% a month with $2,000 withdrawn each month.
+/// let solution = periods_until_depleted_solution(0.005, 100_000, 2_000, None).unwrap();
+/// assert_rounded_4!(57.6801, solution.periods());
+///
+/// let series = solution.series();
+/// assert_eq!(58, series.len());
+/// assert_rounded_4!(500.0, series[0].interest());
+/// assert_rounded_4!(98_500.0, series[0].balance());
+///
+/// // The last withdrawal empties the account.
+/// let last = series.last().unwrap();
+/// assert!(last.withdrawal() < 2_000.0);
+/// assert_rounded_4!(0.0, last.balance());
+/// ```
+pub fn periods_until_depleted_solution<B, W>(rate: f64, balance: B, withdrawal: W, inflation_adjust: Option<f64>) -> Option<DepletionSolution>
+    where
+        B: Into<f64> + Copy,
+        W: Into<f64> + Copy
+{
+    let balance = balance.into();
+    let withdrawal = withdrawal.into();
+    let periods = periods_until_depleted(rate, balance, withdrawal, inflation_adjust)?;
+    let growth = inflation_adjust.unwrap_or(0.0);
+    let period_count = periods.ceil() as u32;
+    let mut remaining = balance;
+    let series = (1..=period_count)
+        .map(|period| {
+            let interest = remaining * rate;
+            let scheduled = withdrawal * (1.0 + growth).powi(period as i32 - 1);
+            let available = remaining + interest;
+            // Clear out any rounding left over after the last withdrawal.
+            let amount = if period == period_count { available } else { scheduled.min(available) };
+            remaining = available - amount;
+            DepletionPeriod::new(period, amount, interest, remaining)
+        })
+        .collect();
+    Some(DepletionSolution::new(rate, balance, withdrawal, inflation_adjust, periods, series))
+}
+
+fn check_depletion_parameters(rate: f64, balance: f64, withdrawal: f64, inflation_adjust: Option<f64>) {
+    assert!(rate.is_finite(), "The rate must be finite (not NaN or infinity)");
+    assert!(rate > -1.0, "The rate must be greater than -1.0 (-100%).");
+    assert!(balance.is_finite(), "The balance must be finite (not NaN or infinity)");
+    assert!(balance > 0.0, "The balance must be greater than zero.");
+    assert!(withdrawal.is_finite(), "The withdrawal must be finite (not NaN or infinity)");
+    assert!(withdrawal > 0.0, "The withdrawal must be greater than zero.");
+    if let Some(inflation_adjust) = inflation_adjust {
+        assert!(inflation_adjust.is_finite(), "The inflation adjustment must be finite (not NaN or infinity)");
+        assert!(inflation_adjust > -1.0, "The inflation adjustment must be greater than -1.0 (-100%).");
+    }
+}
+
+/// A record of how long a balance lasts with regular withdrawals. Created by calling
+/// [`periods_until_depleted_solution`].
+#[derive(Clone, Debug)]
+pub struct DepletionSolution {
+    rate: f64,
+    balance: f64,
+    withdrawal: f64,
+    inflation_adjust: Option<f64>,
+    periods: f64,
+    series: DepletionSeries,
+}
+
+impl DepletionSolution {
+    pub(crate) fn new(rate: f64, balance: f64, withdrawal: f64, inflation_adjust: Option<f64>, periods: f64, series: Vec<DepletionPeriod>) -> Self {
+        assert!(periods.is_finite());
+        Self {
+            rate,
+            balance,
+            withdrawal,
+            inflation_adjust,
+            periods,
+            series: DepletionSeries::new(series),
+        }
+    }
+
+    /// Returns the return earned on the balance per period.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the starting balance.
+    pub fn balance(&self) -> f64 {
+        self.balance
+    }
+
+    /// Returns the first withdrawal.
+    pub fn withdrawal(&self) -> f64 {
+        self.withdrawal
+    }
+
+    /// Returns the rate at which the withdrawal is raised each period, if any.
+    pub fn inflation_adjust(&self) -> Option<f64> {
+        self.inflation_adjust
+    }
+
+    /// Returns the number of withdrawals before the balance is used up, which may include a
+    /// fraction of a period.
+    pub fn periods(&self) -> f64 {
+        self.periods
+    }
+
+    /// Returns the sum of all of the withdrawals.
+    pub fn sum_of_withdrawals(&self) -> f64 {
+        self.series.iter().map(|entry| entry.withdrawal).sum()
+    }
+
+    /// Returns the withdrawal, interest, and balance in each period.
+    pub fn series(&self) -> DepletionSeries {
+        self.series.clone()
+    }
+
+    /// Prints a formatted table with the details of each period.
+    pub fn print_table(&self) {
+        self.series.print_table();
+    }
+
+    /// Prints a formatted table with the details of each period using the given locale and
+    /// precision for money amounts.
+    pub fn print_table_locale(&self, locale: &num_format::Locale, precision: usize) {
+        self.series.print_table_locale(locale, precision);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DepletionSeries(Vec<DepletionPeriod>);
+
+impl DepletionSeries {
+    pub(crate) fn new(series: Vec<DepletionPeriod>) -> Self {
+        Self(series)
+    }
+
+    pub fn filter<P>(&self, predicate: P) -> Self
+        where P: Fn(&&DepletionPeriod) -> bool
+    {
+        Self(self.iter().filter(|x| predicate(x)).cloned().collect())
+    }
+
+    pub fn print_table(&self) {
+        self.print_table_locale_opt(None, None);
+    }
+
+    pub fn print_table_locale(&self, locale: &num_format::Locale, precision: usize) {
+        self.print_table_locale_opt(Some(locale), Some(precision));
+    }
+
+    fn print_table_locale_opt(&self, locale: Option<&num_format::Locale>, precision: Option<usize>) {
+        let columns = columns_with_strings(&[("period", "i", true), ("withdrawal", "f", true), ("interest", "f", true), ("balance", "f", true)]);
+        let data = self.iter()
+            .map(|entry| vec![entry.period.to_string(), entry.withdrawal.to_string(), entry.interest.to_string(), entry.balance.to_string()])
+            .collect::<Vec<_>>();
+        print_table_locale_opt(&columns, data, locale, precision);
+    }
+}
+
+impl Deref for DepletionSeries {
+    type Target = Vec<DepletionPeriod>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// One period within a [`DepletionSeries`].
+#[derive(Clone, Debug)]
+pub struct DepletionPeriod {
+    period: u32,
+    withdrawal: f64,
+    interest: f64,
+    balance: f64,
+}
+
+impl DepletionPeriod {
+    pub(crate) fn new(period: u32, withdrawal: f64, interest: f64, balance: f64) -> Self {
+        assert!(balance.is_finite());
+        Self {
+            period,
+            withdrawal,
+            interest,
+            balance,
+        }
+    }
+
+    /// Returns the period number, starting with 1.
+    pub fn period(&self) -> u32 {
+        self.period
+    }
+
+    /// Returns the amount withdrawn at the end of the period.
+    pub fn withdrawal(&self) -> f64 {
+        self.withdrawal
+    }
+
+    /// Returns the return earned on the balance during the period.
+    pub fn interest(&self) -> f64 {
+        self.interest
+    }
+
+    /// Returns the balance at the end of the period after the withdrawal.
+    pub fn balance(&self) -> f64 {
+        self.balance
+    }
+}
+
+/// Returns the **largest withdrawal that can go on forever while keeping its purchasing power**,
+/// which is the real return on the balance.
+///
+/// Related functions:
+/// * To also see the withdrawal that keeps the balance intact without raising it for inflation
+/// use [`sustainable_withdrawal_solution`].
+/// * To see how long a larger withdrawal lasts use [`periods_until_depleted`].
+///
+/// The formula is:
+///
+/// withdrawal = balance * (rate - inflation_rate)
+///
+/// The first withdrawal is taken at the end of the first period and each later one is raised by
+/// the inflation rate, so the balance also grows with inflation.
+///
+/// # Arguments
+/// * `rate` - The return earned on the balance per period, expressed as a floating point number.
+/// For instance 0.05 would mean 5%.
+/// * `balance` - The starting balance.
+/// * `inflation_rate` - The rate at which the withdrawal is raised each period.
+///
+/// # Panics
+/// The call will fail if `rate` is not greater than `inflation_rate` since no withdrawal could be
+/// raised with inflation forever, if `balance` is negative, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // A $1,000,000 balance earning 6% a year with 2.5% inflation.
+/// let withdrawal = sustainable_withdrawal(0.06, 1_000_000, 0.025);
+/// assert_rounded_4!(35_000.0, withdrawal);
+///
+/// // Any more and the balance runs out eventually.
+/// assert_eq!(None, periods_until_depleted(0.06, 1_000_000, withdrawal, Some(0.025)));
+/// assert!(periods_until_depleted(0.06, 1_000_000, withdrawal + 1.0, Some(0.025)).is_some());
+/// ```
+pub fn sustainable_withdrawal<B>(rate: f64, balance: B, inflation_rate: f64) -> f64
+    where B: Into<f64> + Copy
+{
+    sustainable_withdrawal_solution(rate, balance, inflation_rate).inflation_protected_withdrawal()
+}
+
+/// Returns the **largest withdrawals that can go on forever** from a balance, both with and
+/// without raising them for inflation.
+///
+/// Related functions:
+/// * To calculate only the inflation-protected withdrawal as an `f64` use
+/// [`sustainable_withdrawal`].
+///
+/// The nominal withdrawal is the return on the balance, balance * rate, and leaves the balance
+/// unchanged so its purchasing power shrinks over time. The inflation-protected withdrawal is
+/// balance * (rate - inflation_rate) and is raised by inflation each period.
+///
+/// # Arguments
+/// * `rate` - The return earned on the balance per period, expressed as a floating point number.
+/// For instance 0.05 would mean 5%.
+/// * `balance` - The starting balance.
+/// * `inflation_rate` - The rate at which the withdrawal is raised each period.
+///
+/// # Panics
+/// The call will fail if `rate` is not greater than `inflation_rate`, if `balance` is negative, or
+/// if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = sustainable_withdrawal_solution(0.06, 1_000_000, 0.025);
+/// assert_rounded_4!(60_000.0, solution.nominal_withdrawal());
+/// assert_rounded_4!(35_000.0, solution.inflation_protected_withdrawal());
+///
+/// // As a fraction of the balance this is the safe withdrawal rate.
+/// assert_rounded_6!(0.035, solution.inflation_protected_withdrawal_rate());
+///
+/// // The nominal withdrawal is the payment on a perpetuity worth the balance.
+/// assert_rounded_4!(-1_000_000.0, present_value_perpetuity_growing(0.06, 0.0, solution.nominal_withdrawal()));
+/// ```
+pub fn sustainable_withdrawal_solution<B>(rate: f64, balance: B, inflation_rate: f64) -> SustainableWithdrawalSolution
+    where B: Into<f64> + Copy
+{
+    let balance = balance.into();
+    assert!(rate.is_finite(), "The rate must be finite (not NaN or infinity)");
+    assert!(balance.is_finite(), "The balance must be finite (not NaN or infinity)");
+    assert!(balance >= 0.0, "The balance must not be negative.");
+    assert!(inflation_rate.is_finite(), "The inflation rate must be finite (not NaN or infinity)");
+    assert!(rate > inflation_rate, "The rate must be greater than the inflation rate or no withdrawal can keep its purchasing power forever.");
+    SustainableWithdrawalSolution::new(rate, balance, inflation_rate)
+}
+
+/// A record of the withdrawals that can go on forever from a balance. Created by calling
+/// [`sustainable_withdrawal_solution`].
+#[derive(Clone, Debug)]
+pub struct SustainableWithdrawalSolution {
+    rate: f64,
+    balance: f64,
+    inflation_rate: f64,
+    nominal_withdrawal: f64,
+    inflation_protected_withdrawal: f64,
+}
+
+impl SustainableWithdrawalSolution {
+    pub(crate) fn new(rate: f64, balance: f64, inflation_rate: f64) -> Self {
+        Self {
+            rate,
+            balance,
+            inflation_rate,
+            nominal_withdrawal: balance * rate,
+            inflation_protected_withdrawal: balance * (rate - inflation_rate),
+        }
+    }
+
+    /// Returns the return earned on the balance per period.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the starting balance.
+    pub fn balance(&self) -> f64 {
+        self.balance
+    }
+
+    /// Returns the rate at which the inflation-protected withdrawal is raised each period.
+    pub fn inflation_rate(&self) -> f64 {
+        self.inflation_rate
+    }
+
+    /// Returns the level withdrawal that leaves the balance unchanged, balance * rate.
+    pub fn nominal_withdrawal(&self) -> f64 {
+        self.nominal_withdrawal
+    }
+
+    /// Returns the first withdrawal when each withdrawal is raised by inflation,
+    /// balance * (rate - inflation_rate).
+    pub fn inflation_protected_withdrawal(&self) -> f64 {
+        self.inflation_protected_withdrawal
+    }
+
+    /// Returns the inflation-protected withdrawal as a fraction of the balance, which is the real
+    /// return, or zero if the balance is zero.
+    pub fn inflation_protected_withdrawal_rate(&self) -> f64 {
+        if self.balance == 0.0 {
+            0.0
+        } else {
+            self.inflation_protected_withdrawal / self.balance
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_periods_until_depleted_whole_periods() {
+        // When the balance is exactly the present value of n withdrawals it lasts n periods.
+        let (rate, withdrawal, periods) = (0.0035, 3_200.0, 300);
+        let balance = -present_value_annuity(rate, periods, withdrawal, false);
+        assert_rounded_6!(periods as f64, periods_until_depleted(rate, balance, withdrawal, None).unwrap());
+
+        let solution = periods_until_depleted_solution(rate, balance, withdrawal, Some(0.0)).unwrap();
+        assert_eq!(periods as usize, solution.series().len());
+        assert_approx_equal!(withdrawal, solution.series().last().unwrap().withdrawal());
+    }
+
+    #[test]
+    fn test_periods_until_depleted_inflation_equals_rate() {
+        let periods = periods_until_depleted(0.003, 500_000, 2_500, Some(0.003)).unwrap();
+        assert_approx_equal!(500_000.0 * 1.003 / 2_500.0, periods);
+        // Withdrawals that grow faster than the return always run out.
+        assert!(periods_until_depleted(0.003, 500_000, 100, Some(0.01)).is_some());
+    }
+
+    #[test]
+    fn test_sustainable_withdrawal_keeps_real_balance() {
+        // After raising the withdrawal with inflation each period the balance has grown with
+        // inflation too.
+        let (rate, balance, inflation_rate) = (0.005, 250_000.0, 0.002);
+        let mut withdrawal = sustainable_withdrawal(rate, balance, inflation_rate);
+        let mut remaining = balance;
+        for _ in 0..120 {
+            remaining = remaining * (1.0 + rate) - withdrawal;
+            withdrawal *= 1.0 + inflation_rate;
+        }
+        assert_approx_equal!(balance * (1.0f64 + inflation_rate).powi(120), remaining);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sustainable_withdrawal_inflation_above_rate() {
+        sustainable_withdrawal(0.03, 100_000, 0.04);
+    }
+}
@@ -0,0 +1,330 @@
+//! **Present value of a growing annuity.** A fixed number of payments where each payment is larger
+//! than the one before by a constant growth rate, such as a pension with a cost-of-living
+//! adjustment or rent that rises every year. The payments may come at the end of each period or,
+//! for an annuity due, at the beginning.
+//!
+//! For payments at the end of each period:
+//!
+//! pv = -pmt / (r - g) * (1 - ((1 + g) / (1 + r))<sup>n</sup>)
+//!
+//! and when the rate equals the growth rate each payment has the same present value so:
+//!
+//! pv = -pmt * n / (1 + r)
+//!
+//! For an annuity due every payment arrives one period sooner so either result is multiplied by
+//! (1 + r).
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // A $10,000 pension payment at the start of each year for 20 years, growing 3% a year and
+//! // discounted at 6%.
+//! let solution = present_value_annuity_growing_solution(0.06, 0.03, 20, 10_000, true);
+//! dbg!(&solution);
+//! solution.print_table();
+//! ```
+
+use crate::*;
+use std::ops::Deref;
+
+/// Returns the **present value of a series of payments that grow at a constant rate**.
+///
+/// Related functions:
+/// * To see the payment and its present value in each period use
+/// [`present_value_annuity_growing_solution`].
+/// * For payments that never change use [`present_value_annuity`].
+/// * For growing payments that continue forever use [`present_value_perpetuity_growing`].
+///
+/// See the [present_value_annuity_growing](./index.html) module page for the formulas. As with
+/// [`present_value_annuity`] the result is negative if the payment is positive.
+///
+/// # Arguments
+/// * `rate` - The periodic discount rate, expressed as a floating point number. For instance 0.06
+/// would mean 6%. Often appears as `r` or `i` in formulas.
+/// * `growth` - The rate at which the payment grows each period. Often appears as `g`.
+/// * `periods` - The number of payments.
+/// * `first_payment` - The first payment. Each later payment is larger by the growth rate.
+/// * `due` - True if the payments are made at the beginning of each period so that the first
+/// payment is at period 0, false if they're made at the end of each period.
+///
+/// # Panics
+/// The call will fail if `rate` or `growth` is less than or equal to -1.0, or if any value is not
+/// finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // A $10,000 pension payment each year for 20 years, growing 3% a year and discounted at 6%.
+/// let ordinary = present_value_annuity_growing(0.06, 0.03, 20, 10_000, false);
+/// assert_rounded_4!(-145_615.3266, ordinary);
+///
+/// // If the payments come at the start of each year they're worth more by a factor of (1 + rate).
+/// let due = present_value_annuity_growing(0.06, 0.03, 20, 10_000, true);
+/// assert_rounded_4!(-154_352.2462, due);
+///
+/// // With no growth it's an ordinary annuity.
+/// let level = present_value_annuity_growing(0.06, 0.0, 20, 10_000, true);
+/// assert_rounded_4!(present_value_annuity(0.06, 20, 10_000, true), level);
+/// ```
+pub fn present_value_annuity_growing<T>(rate: f64, growth: f64, periods: u32, first_payment: T, due: bool) -> f64
+    where T: Into<f64> + Copy
+{
+    let first_payment = first_payment.into();
+    check_annuity_growing_parameters(rate, growth, first_payment);
+    let ordinary = if rate == growth {
+        -first_payment * periods as f64 / (1.0 + rate)
+    } else {
+        -first_payment / (rate - growth) * (1.0 - ((1.0 + growth) / (1.0 + rate)).powi(periods as i32))
+    };
+    if due {
+        ordinary * (1.0 + rate)
+    } else {
+        ordinary
+    }
+}
+
+/// Returns the **present value of a series of payments that grow at a constant rate** along with
+/// the inputs and the payment and present value for each period.
+///
+/// Related functions:
+/// * To calculate only the present value as an `f64` use [`present_value_annuity_growing`].
+///
+/// # Arguments
+/// * `rate` - The periodic discount rate, expressed as a floating point number. For instance 0.06
+/// would mean 6%. Often appears as `r` or `i` in formulas.
+/// * `growth` - The rate at which the payment grows each period. Often appears as `g`.
+/// * `periods` - The number of payments.
+/// * `first_payment` - The first payment. Each later payment is larger by the growth rate.
+/// * `due` - True if the payments are made at the beginning of each period so that the first
+/// payment is at period 0, false if they're made at the end of each period.
+///
+/// # Panics
+/// The call will fail if `rate` or `growth` is less than or equal to -1.0, or if any value is not
+/// finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = present_value_annuity_growing_solution(0.05, 0.02, 5, 1_000, true);
+/// assert_rounded_4!(-4_722.3330, solution.present_value());
+///
+/// // The first payment of an annuity due is at period 0 so it isn't discounted.
+/// let series = solution.series();
+/// assert_eq!(5, series.len());
+/// assert_eq!(0, series[0].period());
+/// assert_rounded_4!(1_000.0, series[0].present_value());
+/// assert_eq!(4, series[4].period());
+/// assert_rounded_4!(1_082.4322, series[4].payment());
+/// assert_rounded_4!(890.5196, series[4].present_value());
+/// ```
+pub fn present_value_annuity_growing_solution<T>(rate: f64, growth: f64, periods: u32, first_payment: T, due: bool) -> GrowingAnnuitySolution
+    where T: Into<f64> + Copy
+{
+    let first_payment = first_payment.into();
+    let present_value = present_value_annuity_growing(rate, growth, periods, first_payment, due);
+    let first_period = if due { 0 } else { 1 };
+    let series = (0..periods)
+        .map(|index| {
+            let period = index + first_period;
+            let payment = first_payment * (1.0 + growth).powi(index as i32);
+            let discount_factor = 1.0 / (1.0 + rate).powi(period as i32);
+            GrowingAnnuityPeriod::new(period, payment, discount_factor, payment * discount_factor)
+        })
+        .collect();
+    GrowingAnnuitySolution::new(rate, growth, periods, first_payment, due, present_value, series)
+}
+
+fn check_annuity_growing_parameters(rate: f64, growth: f64, first_payment: f64) {
+    assert!(rate.is_finite(), "The rate must be finite (not NaN or infinity)");
+    assert!(rate > -1.0, "The rate must be greater than -1.0 (-100%).");
+    assert!(growth.is_finite(), "The growth rate must be finite (not NaN or infinity)");
+    assert!(growth > -1.0, "The growth rate must be greater than -1.0 (-100%).");
+    assert!(first_payment.is_finite(), "The first payment must be finite (not NaN or infinity)");
+}
+
+/// A record of the present value of a growing annuity. Created by calling
+/// [`present_value_annuity_growing_solution`].
+#[derive(Clone, Debug)]
+pub struct GrowingAnnuitySolution {
+    rate: f64,
+    growth: f64,
+    periods: u32,
+    first_payment: f64,
+    due: bool,
+    present_value: f64,
+    series: GrowingAnnuitySeries,
+}
+
+impl GrowingAnnuitySolution {
+    pub(crate) fn new(rate: f64, growth: f64, periods: u32, first_payment: f64, due: bool, present_value: f64, series: Vec<GrowingAnnuityPeriod>) -> Self {
+        assert!(present_value.is_finite());
+        Self {
+            rate,
+            growth,
+            periods,
+            first_payment,
+            due,
+            present_value,
+            series: GrowingAnnuitySeries::new(series),
+        }
+    }
+
+    /// Returns the periodic discount rate.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the rate at which the payment grows each period.
+    pub fn growth(&self) -> f64 {
+        self.growth
+    }
+
+    /// Returns the number of payments.
+    pub fn periods(&self) -> u32 {
+        self.periods
+    }
+
+    /// Returns the first payment.
+    pub fn first_payment(&self) -> f64 {
+        self.first_payment
+    }
+
+    /// Returns true if the payments are made at the beginning of each period.
+    pub fn due(&self) -> bool {
+        self.due
+    }
+
+    /// Returns the sum of the payments before any discounting.
+    pub fn sum_of_payments(&self) -> f64 {
+        self.series.iter().map(|entry| entry.payment).sum()
+    }
+
+    /// Returns the present value of the payments, which is negative if the payments are positive.
+    pub fn present_value(&self) -> f64 {
+        self.present_value
+    }
+
+    /// Returns the payment and its present value for each period. The amounts have the same sign
+    /// as the payments.
+    pub fn series(&self) -> GrowingAnnuitySeries {
+        self.series.clone()
+    }
+
+    /// Prints a formatted table with the details of each period.
+    pub fn print_table(&self) {
+        self.series.print_table();
+    }
+
+    /// Prints a formatted table with the details of each period using the given locale and
+    /// precision for money amounts.
+    pub fn print_table_locale(&self, locale: &num_format::Locale, precision: usize) {
+        self.series.print_table_locale(locale, precision);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GrowingAnnuitySeries(Vec<GrowingAnnuityPeriod>);
+
+impl GrowingAnnuitySeries {
+    pub(crate) fn new(series: Vec<GrowingAnnuityPeriod>) -> Self {
+        Self(series)
+    }
+
+    pub fn filter<P>(&self, predicate: P) -> Self
+        where P: Fn(&&GrowingAnnuityPeriod) -> bool
+    {
+        Self(self.iter().filter(|x| predicate(x)).cloned().collect())
+    }
+
+    pub fn print_table(&self) {
+        self.print_table_locale_opt(None, None);
+    }
+
+    pub fn print_table_locale(&self, locale: &num_format::Locale, precision: usize) {
+        self.print_table_locale_opt(Some(locale), Some(precision));
+    }
+
+    fn print_table_locale_opt(&self, locale: Option<&num_format::Locale>, precision: Option<usize>) {
+        let columns = columns_with_strings(&[("period", "i", true), ("payment", "f", true), ("discount_factor", "r", true), ("present_value", "f", true)]);
+        let data = self.iter()
+            .map(|entry| vec![entry.period.to_string(), entry.payment.to_string(), entry.discount_factor.to_string(), entry.present_value.to_string()])
+            .collect::<Vec<_>>();
+        print_table_locale_opt(&columns, data, locale, precision);
+    }
+}
+
+impl Deref for GrowingAnnuitySeries {
+    type Target = Vec<GrowingAnnuityPeriod>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// One payment within a [`GrowingAnnuitySeries`].
+#[derive(Clone, Debug)]
+pub struct GrowingAnnuityPeriod {
+    period: u32,
+    payment: f64,
+    discount_factor: f64,
+    present_value: f64,
+}
+
+impl GrowingAnnuityPeriod {
+    pub(crate) fn new(period: u32, payment: f64, discount_factor: f64, present_value: f64) -> Self {
+        assert!(present_value.is_finite());
+        Self {
+            period,
+            payment,
+            discount_factor,
+            present_value,
+        }
+    }
+
+    /// Returns the period in which the payment is made, starting with 0 for an annuity due and 1
+    /// otherwise.
+    pub fn period(&self) -> u32 {
+        self.period
+    }
+
+    /// Returns the payment.
+    pub fn payment(&self) -> f64 {
+        self.payment
+    }
+
+    /// Returns the factor used to discount the payment back to period 0.
+    pub fn discount_factor(&self) -> f64 {
+        self.discount_factor
+    }
+
+    /// Returns the value of the payment at period 0, with the same sign as the payment.
+    pub fn present_value(&self) -> f64 {
+        self.present_value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_present_value_annuity_growing_due_factor() {
+        // The due version is larger by exactly (1 + rate), including when the rate equals growth.
+        for &(rate, growth) in [(0.06, 0.03), (0.04, 0.04), (0.02, 0.05)].iter() {
+            let ordinary = present_value_annuity_growing_solution(rate, growth, 15, 2_500.0, false);
+            let due = present_value_annuity_growing_solution(rate, growth, 15, 2_500.0, true);
+            assert_approx_equal!(ordinary.present_value() * (1.0 + rate), due.present_value());
+            let sum_due = due.series().iter().map(|entry| entry.present_value()).sum::<f64>();
+            assert_approx_equal!(-due.present_value(), sum_due);
+        }
+    }
+
+    #[test]
+    fn test_present_value_annuity_growing_rate_equals_growth() {
+        // Every payment is worth the same today so the series sum matches the formula.
+        let solution = present_value_annuity_growing_solution(0.05, 0.05, 10, 300.0, false);
+        assert_approx_equal!(-300.0 * 10.0 / 1.05, solution.present_value());
+        let sum = solution.series().iter().map(|entry| entry.present_value()).sum::<f64>();
+        assert_approx_equal!(-solution.present_value(), sum);
+    }
+}
@@ -0,0 +1,880 @@
+//! **Present value of a series of cashflows with their own discount factors.** Unlike the annuity
+//! and net present value functions, each cashflow here may be discounted in its own way, for
+//! instance at a rate reflecting its own risk.
+//!
+//! The functions in this module return a [`DiscountedCashflowSolution`] whose series shows the
+//! discount factor applied to each cashflow and its contribution to the present value. The present
+//! value of a series of cashflows has the same sign as the cashflows, while the functions for a
+//! payment made every period, [`present_value_risky`], [`present_value_annuity_indexed`], and
+//! [`present_value_annuity_window`], follow [`present_value_annuity`] so that a positive payment
+//! has a negative present value.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! let cashflows = [1_000, 1_000, 5_000];
+//! let rates = [0.04, 0.06, 0.12];
+//! let solution = present_value_per_flow_rate_solution(&cashflows, &rates);
+//! solution.print_table();
+//! ```
+//! Outputs to terminal:
+//! ```text
+//! period      rate    cashflow  discount_factor  present_value
+//! ------  --------  ----------  ---------------  -------------
+//!      1  0.040000  1_000.0000         0.961538       961.5385
+//!      2  0.060000  1_000.0000         0.889996       889.9964
+//!      3  0.120000  5_000.0000         0.711780     3_558.9012
+//! ```
+
+use crate::*;
+use std::ops::Deref;
+
+/// Returns the present value of a series of cashflows where each cashflow is discounted at its own
+/// rate.
+///
+/// Related functions:
+/// * To keep the discount factor and present value of each cashflow use
+/// [`present_value_per_flow_rate_solution`].
+/// * To chain varying rates across periods, so that the rate for period 1 affects every later
+/// cashflow, use [`present_value_schedule`] or [`net_present_value_schedule`].
+///
+/// The formula is:
+///
+/// present_value = sum( cashflow<sub>t</sub> / (1 + rate<sub>t</sub>)<sup>t</sup> )
+///
+/// where the first cashflow is at the end of period 1.
+///
+/// # Arguments
+/// * `cashflows` - The cashflows at the end of periods 1, 2, 3, and so on.
+/// * `rates` - The periodic discount rate for each cashflow, such as a rate adjusted for the risk
+/// of that cashflow. There must be one rate per cashflow.
+///
+/// # Panics
+/// The call will fail if `cashflows` and `rates` have different lengths or if any rate is less than
+/// -1.0.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // The later, riskier cashflow is discounted at a higher rate.
+/// let present_value = present_value_per_flow_rate(&[1_000, 1_000, 5_000], &[0.04, 0.06, 0.12]);
+/// assert_rounded_4!(5_410.4361, present_value);
+/// ```
+pub fn present_value_per_flow_rate<C>(cashflows: &[C], rates: &[f64]) -> f64
+    where C: Into<f64> + Copy
+{
+    present_value_per_flow_rate_solution(cashflows, rates).present_value()
+}
+
+/// Returns the present value of a series of cashflows where each cashflow is discounted at its own
+/// rate, along with the discount factor and contribution of each cashflow.
+///
+/// Related functions:
+/// * To calculate only the present value as an `f64` use [`present_value_per_flow_rate`].
+///
+/// # Arguments
+/// * `cashflows` - The cashflows at the end of periods 1, 2, 3, and so on.
+/// * `rates` - The periodic discount rate for each cashflow. There must be one rate per cashflow.
+///
+/// # Panics
+/// The call will fail if `cashflows` and `rates` have different lengths or if any rate is less than
+/// -1.0.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = present_value_per_flow_rate_solution(&[1_000, 1_000, 5_000], &[0.04, 0.06, 0.12]);
+/// dbg!(&solution);
+///
+/// // The last cashflow contributes the most to the present value.
+/// let series = solution.series();
+/// assert_rounded_4!(3_558.9012, series[2].present_value());
+/// assert_rounded_6!(0.711780, series[2].discount_factor());
+/// ```
+pub fn present_value_per_flow_rate_solution<C>(cashflows: &[C], rates: &[f64]) -> DiscountedCashflowSolution
+    where C: Into<f64> + Copy
+{
+    assert_eq!(cashflows.len(), rates.len(), "There must be one rate for each cashflow.");
+    let mut series = vec![];
+    for (index, (cashflow, rate)) in cashflows.iter().zip(rates.iter()).enumerate() {
+        let cashflow = (*cashflow).into();
+        let rate = *rate;
+        assert!(cashflow.is_finite(), "The cashflows must be finite (not NaN or infinity)");
+        assert!(rate.is_finite(), "The rates must be finite (not NaN or infinity)");
+        assert!(rate >= -1.0, "The rates must be greater than or equal to -1.0 because a rate lower than -100% would mean the investment loses more than its full value in a period.");
+        let period = index as u32 + 1;
+        let discount_factor = 1.0 / (1.0 + rate).powi(period as i32);
+        let formula = format!("{:.4} = {:.4} / ({:.6} ^ {})", cashflow * discount_factor, cashflow, 1.0 + rate, period);
+        series.push(DiscountedCashflow::new(period as f64, rate, cashflow, discount_factor, &formula));
+    }
+    DiscountedCashflowSolution::new(series, "pv = sum(cashflow_t / (1 + r_t)^t)")
+}
+
+/// Returns the present value of a series of cashflows that each occur at their own offset in
+/// periods, which need not be a whole number.
+///
+/// Related functions:
+/// * To keep the discount factor and present value of each cashflow use
+/// [`present_value_timed_solution`].
+/// * For cashflows at the end of each whole period use [`net_present_value_schedule`].
+///
+/// The formula is:
+///
+/// present_value = sum( amount<sub>i</sub> / (1 + rate)<sup>offset<sub>i</sub></sup> )
+///
+/// As with the other functions in this module the present value has the same sign as the
+/// cashflows.
+///
+/// # Arguments
+/// * `rate` - The periodic discount rate, expressed as a floating point number. For instance 0.05
+/// would mean 5%.
+/// * `flows` - Pairs of the offset in periods from now and the amount of the cashflow. An offset of
+/// zero is not discounted. The pairs may be in any order.
+///
+/// # Panics
+/// The call will fail if `rate` is less than or equal to -1.0 or if any offset or amount is not
+/// finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // An investment now followed by cashflows at six months, fifteen months, and 33 months, with
+/// // the periods measured in years.
+/// let flows = [(0.0, -5_000.0), (0.5, 1_200.0), (1.25, 2_000.0), (2.75, 2_500.0)];
+/// assert_rounded_4!(154.8987, present_value_timed(0.06, &flows));
+///
+/// // With whole-number offsets this is the usual net present value.
+/// let flows = [(0.0, -1_000.0), (1.0, 300.0), (2.0, 400.0), (3.0, 500.0)];
+/// let npv = net_present_value_schedule(&[0.034, 0.034, 0.034], &[-1_000, 300, 400, 500]);
+/// assert_rounded_4!(npv, present_value_timed(0.034, &flows));
+/// ```
+pub fn present_value_timed(rate: f64, flows: &[(f64, f64)]) -> f64 {
+    present_value_timed_solution(rate, flows).present_value()
+}
+
+/// Returns the present value of a series of cashflows that each occur at their own offset in
+/// periods, along with the discount factor and contribution of each cashflow.
+///
+/// Related functions:
+/// * To calculate only the present value as an `f64` use [`present_value_timed`].
+///
+/// # Arguments
+/// * `rate` - The periodic discount rate, expressed as a floating point number. For instance 0.05
+/// would mean 5%.
+/// * `flows` - Pairs of the offset in periods from now and the amount of the cashflow. An offset of
+/// zero is not discounted. The pairs may be in any order.
+///
+/// # Panics
+/// The call will fail if `rate` is less than or equal to -1.0 or if any offset or amount is not
+/// finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let flows = [(2.75, 2_500.0), (0.0, -5_000.0), (1.25, 2_000.0), (0.5, 1_200.0)];
+/// let solution = present_value_timed_solution(0.06, &flows);
+/// solution.print_table();
+///
+/// // The series is sorted by offset.
+/// let series = solution.series();
+/// assert_rounded_4!(0.5, series[1].time());
+/// assert_rounded_6!(0.971286, series[1].discount_factor());
+/// assert_rounded_4!(154.8987, solution.present_value());
+/// ```
+pub fn present_value_timed_solution(rate: f64, flows: &[(f64, f64)]) -> DiscountedCashflowSolution {
+    assert!(rate.is_finite(), "The rate must be finite (not NaN or infinity)");
+    assert!(rate > -1.0, "The rate must be greater than -1.0 (-100%).");
+    for (offset, amount) in flows.iter() {
+        assert!(offset.is_finite(), "The offsets must be finite (not NaN or infinity)");
+        assert!(amount.is_finite(), "The amounts must be finite (not NaN or infinity)");
+    }
+    let mut flows = flows.to_vec();
+    // The offsets are all finite so they can be compared.
+    flows.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    let series = flows.iter()
+        .map(|(offset, amount)| {
+            let discount_factor = 1.0 / (1.0 + rate).powf(*offset);
+            let formula = format!("{:.4} = {:.4} / ({:.6} ^ {:.4})", amount * discount_factor, amount, 1.0 + rate, offset);
+            DiscountedCashflow::new(*offset, rate, *amount, discount_factor, &formula)
+        })
+        .collect();
+    DiscountedCashflowSolution::new(series, "pv = sum(amount_i / (1 + r)^offset_i)")
+}
+
+/// Returns the present value of a series of cashflows discounted with a discount curve, where the
+/// curve is a function giving the discount factor for any point in time.
+///
+/// Related functions:
+/// * To see the discount factor and contribution of each cashflow use
+/// [`present_value_curve_solution`].
+/// * For a single flat rate use [`present_value_timed`].
+///
+/// This is the most general way of discounting in the crate. A flat rate r is the curve
+/// `|t| (1.0 / (1.0 + r)).powf(t)` and a term structure of rates can be written as a closure that
+/// interpolates between them.
+///
+/// As with the other functions in this module the present value has the same sign as the
+/// cashflows.
+///
+/// # Arguments
+/// * `discount_fn` - The discount factor at each point in time, such as 0.95 for an amount that's
+/// worth 95% of its face value today.
+/// * `flows` - Pairs of the time of the cashflow and its amount. The pairs may be in any order.
+///
+/// # Panics
+/// The call will fail if any time or amount is not finite or if `discount_fn` returns a discount
+/// factor that's negative or not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // A two-year bond paying a 4% coupon twice a year, discounted with continuously compounded
+/// // zero rates that start at 3% and rise by half a percent a year.
+/// let flows = [(0.5, 40.0), (1.0, 40.0), (1.5, 40.0), (2.0, 1_040.0)];
+/// let curve = |t: f64| (-(0.03 + 0.005 * t) * t).exp();
+/// assert_rounded_4!(1_075.8326, present_value_curve(curve, &flows));
+///
+/// // A flat curve gives the same result as discounting at a single rate.
+/// let flat = |t: f64| (1.0 / 1.02_f64).powf(t);
+/// assert_rounded_4!(present_value_timed(0.02, &flows), present_value_curve(flat, &flows));
+/// ```
+pub fn present_value_curve<F>(discount_fn: F, flows: &[(f64, f64)]) -> f64
+    where F: Fn(f64) -> f64
+{
+    present_value_curve_solution(discount_fn, flows).present_value()
+}
+
+/// Returns the present value of a series of cashflows discounted with a discount curve, along with
+/// the discount factor and contribution of each cashflow.
+///
+/// Related functions:
+/// * To calculate only the present value as an `f64` use [`present_value_curve`].
+///
+/// The rate shown for each cashflow is the periodic zero rate implied by its discount factor, or
+/// zero for a cashflow at time zero.
+///
+/// # Arguments
+/// * `discount_fn` - The discount factor at each point in time, such as 0.95 for an amount that's
+/// worth 95% of its face value today.
+/// * `flows` - Pairs of the time of the cashflow and its amount. The pairs may be in any order.
+///
+/// # Panics
+/// The call will fail if any time or amount is not finite or if `discount_fn` returns a discount
+/// factor that's negative or not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let flows = [(2.0, 1_040.0), (0.5, 40.0), (1.5, 40.0), (1.0, 40.0)];
+/// let solution = present_value_curve_solution(|t| (-(0.03 + 0.005 * t) * t).exp(), &flows);
+/// solution.print_table();
+///
+/// // The series is sorted by time.
+/// let series = solution.series();
+/// assert_rounded_4!(1.5, series[2].time());
+/// assert_rounded_6!(0.945303, series[2].discount_factor());
+/// assert_rounded_6!(0.038212, series[2].rate());
+/// assert_rounded_4!(1_075.8326, solution.present_value());
+/// ```
+pub fn present_value_curve_solution<F>(discount_fn: F, flows: &[(f64, f64)]) -> DiscountedCashflowSolution
+    where F: Fn(f64) -> f64
+{
+    for (time, amount) in flows.iter() {
+        assert!(time.is_finite(), "The times must be finite (not NaN or infinity)");
+        assert!(amount.is_finite(), "The amounts must be finite (not NaN or infinity)");
+    }
+    let mut flows = flows.to_vec();
+    // The times are all finite so they can be compared.
+    flows.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    let series = flows.iter()
+        .map(|(time, amount)| {
+            let discount_factor = discount_fn(*time);
+            assert!(discount_factor.is_finite(), "The discount factors must be finite (not NaN or infinity)");
+            assert!(discount_factor >= 0.0, "The discount factors must be zero or positive.");
+            let rate = if *time == 0.0 || discount_factor == 0.0 { 0.0 } else { discount_factor.powf(-1.0 / time) - 1.0 };
+            let formula = format!("{:.4} = {:.4} * {:.6}", amount * discount_factor, amount, discount_factor);
+            DiscountedCashflow::new(*time, rate, *amount, discount_factor, &formula)
+        })
+        .collect();
+    DiscountedCashflowSolution::new(series, "pv = sum(amount_i * discount_factor(t_i))")
+}
+
+/// Returns the present value of a level cashflow received at the end of each period from a
+/// borrower or counterparty that may default, weighting each cashflow by the probability that it's
+/// actually paid.
+///
+/// Related functions:
+/// * To keep the risk-adjusted cashflow and present value of each period use
+/// [`present_value_risky_solution`].
+/// * For cashflows that are certain to be paid use [`present_value_annuity`].
+///
+/// The formula is:
+///
+/// present_value = sum( cash_flow * survival<sub>1</sub> * ... * survival<sub>t</sub> / (1 + rate)<sup>t</sup> )
+///
+/// The product of the survival probabilities up to a period is the cumulative probability that the
+/// counterparty hasn't defaulted by then. As with [`present_value_annuity`], which this reduces to
+/// when every survival probability is 1.0, the result is negative if the cashflow is positive.
+///
+/// # Arguments
+/// * `rate` - The periodic discount rate, expressed as a floating point number. For instance 0.05
+/// would mean 5%.
+/// * `periods` - The number of cashflows.
+/// * `cash_flow` - The cashflow promised at the end of each period.
+/// * `survival_probs` - The probability of not defaulting during each period given that there was
+/// no default before it, such as 0.98 for a 2% chance of default. There must be one per period.
+///
+/// # Panics
+/// The call will fail if `survival_probs` doesn't have one entry per period, if any probability is
+/// outside the range 0.0 to 1.0, if `rate` is less than or equal to -1.0, or if any value is not
+/// finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // $1,000 a year for five years from a borrower whose chance of default rises over time.
+/// let survival_probs = [0.98, 0.97, 0.95, 0.95, 0.90];
+/// let present_value = present_value_risky(0.06, 5, 1_000, &survival_probs);
+/// assert_rounded_4!(-3_785.3207, present_value);
+///
+/// // A borrower that can't default is worth the same as an annuity.
+/// let present_value = present_value_risky(0.06, 5, 1_000, &[1.0; 5]);
+/// assert_rounded_4!(present_value_annuity(0.06, 5, 1_000, false), present_value);
+/// ```
+pub fn present_value_risky<C>(rate: f64, periods: u32, cash_flow: C, survival_probs: &[f64]) -> f64
+    where C: Into<f64> + Copy
+{
+    present_value_risky_solution(rate, periods, cash_flow, survival_probs).present_value()
+}
+
+/// Returns the present value of a level cashflow from a counterparty that may default, along with
+/// the risk-adjusted cashflow and present value of each period.
+///
+/// Related functions:
+/// * To calculate only the present value as an `f64` use [`present_value_risky`].
+///
+/// The risk-adjusted cashflow and present value of each period in the series have the same sign as
+/// the cashflow and the total has the opposite sign, as with [`present_value_annuity`].
+///
+/// # Arguments
+/// * `rate` - The periodic discount rate, expressed as a floating point number. For instance 0.05
+/// would mean 5%.
+/// * `periods` - The number of cashflows.
+/// * `cash_flow` - The cashflow promised at the end of each period.
+/// * `survival_probs` - The probability of not defaulting during each period given that there was
+/// no default before it. There must be one per period.
+///
+/// # Panics
+/// The call will fail if `survival_probs` doesn't have one entry per period, if any probability is
+/// outside the range 0.0 to 1.0, if `rate` is less than or equal to -1.0, or if any value is not
+/// finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let survival_probs = [0.98, 0.97, 0.95, 0.95, 0.90];
+/// let solution = present_value_risky_solution(0.06, 5, 1_000, &survival_probs);
+/// solution.print_table();
+///
+/// // The series shows the expected cashflow after the chance of default. The borrower has a 95.06%
+/// // chance of making the second payment.
+/// let series = solution.series();
+/// assert_rounded_4!(950.6, series[1].cashflow());
+/// assert_rounded_4!(846.0306, series[1].present_value());
+/// assert_rounded_4!(-3_785.3207, solution.present_value());
+/// ```
+pub fn present_value_risky_solution<C>(rate: f64, periods: u32, cash_flow: C, survival_probs: &[f64]) -> DiscountedCashflowSolution
+    where C: Into<f64> + Copy
+{
+    let cash_flow = cash_flow.into();
+    assert!(rate.is_finite(), "The rate must be finite (not NaN or infinity)");
+    assert!(rate > -1.0, "The rate must be greater than -1.0 (-100%).");
+    assert!(cash_flow.is_finite(), "The cashflow must be finite (not NaN or infinity)");
+    assert_eq!(periods as usize, survival_probs.len(), "There must be one survival probability for each period.");
+    let mut series = vec![];
+    let mut cumulative_survival = 1.0;
+    for (index, survival) in survival_probs.iter().enumerate() {
+        assert!(*survival >= 0.0 && *survival <= 1.0, "The survival probabilities must be between 0.0 and 1.0.");
+        cumulative_survival *= survival;
+        let period = index as u32 + 1;
+        let expected_cash_flow = cash_flow * cumulative_survival;
+        let discount_factor = 1.0 / (1.0 + rate).powi(period as i32);
+        let formula = format!("{:.4} = {:.4} * {:.6} / ({:.6} ^ {})", expected_cash_flow * discount_factor, cash_flow, cumulative_survival, 1.0 + rate, period);
+        series.push(DiscountedCashflow::new(period as f64, rate, expected_cash_flow, discount_factor, &formula));
+    }
+    DiscountedCashflowSolution::new_annuity(series, "pv = -sum(cash_flow * cumulative_survival_t / (1 + r)^t)")
+}
+
+/// Returns the present value of a payment at the end of each period that's adjusted by an index
+/// such as a cost-of-living adjustment tied to inflation.
+///
+/// Related functions:
+/// * To keep the indexed payment and present value of each period use
+/// [`present_value_annuity_indexed_solution`].
+/// * For a payment that's the same in every period use [`present_value_annuity`].
+///
+/// The formula is:
+///
+/// present_value = sum( base_payment * index_factor<sub>t</sub> / (1 + rate)<sup>t</sup> )
+///
+/// As with [`present_value_annuity`] the result is negative if the payment is positive.
+///
+/// # Arguments
+/// * `rate` - The periodic discount rate, expressed as a floating point number. For instance 0.05
+/// would mean 5%.
+/// * `base_payment` - The payment before any indexing.
+/// * `index_factors` - The factor applied to the base payment in each period, such as 1.03 after
+/// prices have risen by 3%. There's one payment for each factor.
+///
+/// # Panics
+/// The call will fail if `rate` is less than or equal to -1.0, if any index factor is negative, or
+/// if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // A $30,000 pension with cost-of-living adjustments following an inflation index.
+/// let index_factors = [1.0, 1.025, 1.05, 1.08, 1.10];
+/// assert_rounded_4!(-140_098.8930, present_value_annuity_indexed(0.04, 30_000, &index_factors));
+///
+/// // With no adjustments this is an ordinary annuity.
+/// let present_value = present_value_annuity_indexed(0.04, 30_000, &[1.0; 5]);
+/// assert_rounded_4!(present_value_annuity(0.04, 5, 30_000, false), present_value);
+/// ```
+pub fn present_value_annuity_indexed<P>(rate: f64, base_payment: P, index_factors: &[f64]) -> f64
+    where P: Into<f64> + Copy
+{
+    present_value_annuity_indexed_solution(rate, base_payment, index_factors).present_value()
+}
+
+/// Returns the present value of a payment that's adjusted by an index in each period, along with
+/// the indexed payment and present value of each period.
+///
+/// Related functions:
+/// * To calculate only the present value as an `f64` use [`present_value_annuity_indexed`].
+///
+/// The present value of each period in the series has the same sign as the payment and the total
+/// has the opposite sign, as with [`present_value_annuity`].
+///
+/// # Arguments
+/// * `rate` - The periodic discount rate, expressed as a floating point number. For instance 0.05
+/// would mean 5%.
+/// * `base_payment` - The payment before any indexing.
+/// * `index_factors` - The factor applied to the base payment in each period. There's one payment
+/// for each factor.
+///
+/// # Panics
+/// The call will fail if `rate` is less than or equal to -1.0, if any index factor is negative, or
+/// if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = present_value_annuity_indexed_solution(0.04, 30_000, &[1.0, 1.025, 1.05, 1.08, 1.10]);
+/// solution.print_table();
+///
+/// let series = solution.series();
+/// assert_eq!(5, series.len());
+/// assert_rounded_4!(32_400.0, series[3].cashflow());
+/// assert_rounded_4!(27_695.6558, series[3].present_value());
+/// assert_rounded_4!(-140_098.8930, solution.present_value());
+/// ```
+pub fn present_value_annuity_indexed_solution<P>(rate: f64, base_payment: P, index_factors: &[f64]) -> DiscountedCashflowSolution
+    where P: Into<f64> + Copy
+{
+    let base_payment = base_payment.into();
+    assert!(rate.is_finite(), "The rate must be finite (not NaN or infinity)");
+    assert!(rate > -1.0, "The rate must be greater than -1.0 (-100%).");
+    assert!(base_payment.is_finite(), "The base payment must be finite (not NaN or infinity)");
+    let series = index_factors.iter().enumerate()
+        .map(|(index, index_factor)| {
+            assert!(index_factor.is_finite(), "The index factors must be finite (not NaN or infinity)");
+            assert!(*index_factor >= 0.0, "The index factors must be zero or positive.");
+            let period = index as u32 + 1;
+            let payment = base_payment * index_factor;
+            let discount_factor = 1.0 / (1.0 + rate).powi(period as i32);
+            let formula = format!("{:.4} = {:.4} * {:.6} / ({:.6} ^ {})", payment * discount_factor, base_payment, index_factor, 1.0 + rate, period);
+            DiscountedCashflow::new(period as f64, rate, payment, discount_factor, &formula)
+        })
+        .collect();
+    DiscountedCashflowSolution::new_annuity(series, "pv = -sum(base_payment * index_factor_t / (1 + r)^t)")
+}
+
+/// Returns the present value of a level payment at the end of each period from a start period to
+/// an end period, such as a pension that starts in five years and stops after twenty.
+///
+/// Related functions:
+/// * To see the payment and present value of each period use
+/// [`present_value_annuity_window_solution`].
+/// * For payments that start in the first period use [`present_value_annuity`].
+///
+/// The formula is:
+///
+/// present_value = sum( payment / (1 + rate)<sup>t</sup> ) for t from start_period to end_period
+///
+/// This is the same as the present value of an annuity running to the end period less the present
+/// value of one running to the period before the start. As with [`present_value_annuity`] the
+/// result is negative if the payment is positive.
+///
+/// # Arguments
+/// * `rate` - The periodic discount rate, expressed as a floating point number. For instance 0.05
+/// would mean 5%.
+/// * `payment` - The payment in each period of the window.
+/// * `start_period` - The first period with a payment. Zero means the first payment is made now
+/// and isn't discounted.
+/// * `end_period` - The last period with a payment.
+///
+/// # Panics
+/// The call will fail if `start_period` is greater than `end_period`, if `rate` is less than or
+/// equal to -1.0, or if `rate` or `payment` is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // $20,000 a year from year 5 through year 20 discounted at 5%.
+/// assert_rounded_4!(-178_325.1968, present_value_annuity_window(0.05, 20_000, 5, 20));
+///
+/// // The same as twenty years of payments less the first four.
+/// let difference = present_value_annuity(0.05, 20, 20_000, false) - present_value_annuity(0.05, 4, 20_000, false);
+/// assert_rounded_4!(difference, present_value_annuity_window(0.05, 20_000, 5, 20));
+/// ```
+pub fn present_value_annuity_window<P>(rate: f64, payment: P, start_period: u32, end_period: u32) -> f64
+    where P: Into<f64> + Copy
+{
+    present_value_annuity_window_solution(rate, payment, start_period, end_period).present_value()
+}
+
+/// Returns the present value of a level payment from a start period to an end period, along with
+/// the payment and present value of each period.
+///
+/// Related functions:
+/// * To calculate only the present value as an `f64` use [`present_value_annuity_window`].
+///
+/// The series runs from period 1, or period 0 if the start period is zero, through the end period.
+/// The periods before the start period have a cashflow of zero. The present value of each period
+/// in the series has the same sign as the payment and the total has the opposite sign, as with
+/// [`present_value_annuity`].
+///
+/// # Arguments
+/// * `rate` - The periodic discount rate, expressed as a floating point number. For instance 0.05
+/// would mean 5%.
+/// * `payment` - The payment in each period of the window.
+/// * `start_period` - The first period with a payment. Zero means the first payment is made now
+/// and isn't discounted.
+/// * `end_period` - The last period with a payment.
+///
+/// # Panics
+/// The call will fail if `start_period` is greater than `end_period`, if `rate` is less than or
+/// equal to -1.0, or if `rate` or `payment` is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = present_value_annuity_window_solution(0.05, 20_000, 5, 20);
+/// solution.print_table();
+///
+/// let series = solution.series();
+/// assert_eq!(20, series.len());
+/// assert_rounded_4!(0.0, series[3].cashflow());
+/// assert_rounded_4!(15_670.5233, series[4].present_value());
+/// assert_rounded_4!(7_537.7897, series[19].present_value());
+/// assert_rounded_4!(-178_325.1968, solution.present_value());
+/// ```
+pub fn present_value_annuity_window_solution<P>(rate: f64, payment: P, start_period: u32, end_period: u32) -> DiscountedCashflowSolution
+    where P: Into<f64> + Copy
+{
+    let payment = payment.into();
+    assert!(rate.is_finite(), "The rate must be finite (not NaN or infinity)");
+    assert!(rate > -1.0, "The rate must be greater than -1.0 (-100%).");
+    assert!(payment.is_finite(), "The payment must be finite (not NaN or infinity)");
+    assert!(start_period <= end_period, "The start period must be less than or equal to the end period.");
+    let series = (start_period.min(1)..=end_period)
+        .map(|period| {
+            let cashflow = if period >= start_period { payment } else { 0.0 };
+            let discount_factor = 1.0 / (1.0 + rate).powi(period as i32);
+            let formula = format!("{:.4} = {:.4} / ({:.6} ^ {})", cashflow * discount_factor, cashflow, 1.0 + rate, period);
+            DiscountedCashflow::new(period as f64, rate, cashflow, discount_factor, &formula)
+        })
+        .collect();
+    DiscountedCashflowSolution::new_annuity(series, "pv = -sum(payment / (1 + r)^t) for start <= t <= end")
+}
+
+/// A record of a present value calculation where each cashflow has its own discount factor, such
+/// as the result of calling [`present_value_per_flow_rate_solution`].
+#[derive(Clone, Debug)]
+pub struct DiscountedCashflowSolution {
+    present_value: f64,
+    sum_of_cashflows: f64,
+    series: DiscountedCashflowSeries,
+    symbolic_formula: String,
+}
+
+impl DiscountedCashflowSolution {
+    pub(crate) fn new(series: Vec<DiscountedCashflow>, symbolic_formula: &str) -> Self {
+        let present_value = series.iter().map(|entry| entry.present_value).sum();
+        let sum_of_cashflows = series.iter().map(|entry| entry.cashflow).sum();
+        Self {
+            present_value,
+            sum_of_cashflows,
+            series: DiscountedCashflowSeries::new(series),
+            symbolic_formula: symbolic_formula.to_string(),
+        }
+    }
+
+    // For the functions that follow present_value_annuity, where the present value has the
+    // opposite sign to the payments in the series.
+    pub(crate) fn new_annuity(series: Vec<DiscountedCashflow>, symbolic_formula: &str) -> Self {
+        let solution = Self::new(series, symbolic_formula);
+        Self {
+            present_value: -solution.present_value,
+            ..solution
+        }
+    }
+
+    /// Returns the present value. For a series of cashflows this is the sum of the discounted
+    /// cashflows. For a payment made every period, as with [`present_value_annuity_window`], it's
+    /// the negative of that sum following the convention of [`present_value_annuity`].
+    pub fn present_value(&self) -> f64 {
+        self.present_value
+    }
+
+    /// Returns the sum of the cashflows before discounting.
+    pub fn sum_of_cashflows(&self) -> f64 {
+        self.sum_of_cashflows
+    }
+
+    /// Returns the number of cashflows.
+    pub fn len(&self) -> usize {
+        self.series.len()
+    }
+
+    /// Returns true if there are no cashflows.
+    pub fn is_empty(&self) -> bool {
+        self.series.is_empty()
+    }
+
+    /// Returns a text version of the formula used to calculate the present value with variables
+    /// such as r for the rate. The formula for each cashflow with the actual values is available
+    /// in the series.
+    pub fn symbolic_formula(&self) -> &str {
+        &self.symbolic_formula
+    }
+
+    /// Returns the discount factor and present value of each cashflow.
+    pub fn series(&self) -> DiscountedCashflowSeries {
+        self.series.clone()
+    }
+
+    /// Prints a formatted table with the details of each cashflow.
+    pub fn print_table(&self) {
+        self.series.print_table();
+    }
+
+    /// Prints a formatted table with the details of each cashflow using the given locale and
+    /// precision for money amounts.
+    pub fn print_table_locale(&self, locale: &num_format::Locale, precision: usize) {
+        self.series.print_table_locale(locale, precision);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DiscountedCashflowSeries(Vec<DiscountedCashflow>);
+
+impl DiscountedCashflowSeries {
+    pub(crate) fn new(series: Vec<DiscountedCashflow>) -> Self {
+        Self(series)
+    }
+
+    pub fn filter<P>(&self, predicate: P) -> Self
+        where P: Fn(&&DiscountedCashflow) -> bool
+    {
+        Self(self.iter().filter(|x| predicate(x)).cloned().collect())
+    }
+
+    pub fn print_table(&self) {
+        self.print_table_locale_opt(None, None);
+    }
+
+    pub fn print_table_locale(&self, locale: &num_format::Locale, precision: usize) {
+        self.print_table_locale_opt(Some(locale), Some(precision));
+    }
+
+    fn print_table_locale_opt(&self, locale: Option<&num_format::Locale>, precision: Option<usize>) {
+        // Show the time as a whole number unless some cashflow falls partway through a period.
+        let time_type = if self.iter().all(|entry| entry.time.fract() == 0.0) { "i" } else { "f" };
+        let columns = columns_with_strings(&[("period", time_type, true), ("rate", "r", true), ("cashflow", "f", true), ("discount_factor", "r", true), ("present_value", "f", true)]);
+        let data = self.iter()
+            .map(|entry| vec![entry.time.to_string(), entry.rate.to_string(), entry.cashflow.to_string(), entry.discount_factor.to_string(), entry.present_value.to_string()])
+            .collect::<Vec<_>>();
+        print_table_locale_opt(&columns, data, locale, precision);
+    }
+}
+
+impl Deref for DiscountedCashflowSeries {
+    type Target = Vec<DiscountedCashflow>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// One cashflow within a [`DiscountedCashflowSeries`] along with the discount factor applied to it.
+#[derive(Clone, Debug)]
+pub struct DiscountedCashflow {
+    time: f64,
+    rate: f64,
+    cashflow: f64,
+    discount_factor: f64,
+    present_value: f64,
+    formula: String,
+}
+
+impl DiscountedCashflow {
+    pub(crate) fn new(time: f64, rate: f64, cashflow: f64, discount_factor: f64, formula: &str) -> Self {
+        assert!(discount_factor.is_finite());
+        let present_value = cashflow * discount_factor;
+        assert!(present_value.is_finite());
+        Self {
+            time,
+            rate,
+            cashflow,
+            discount_factor,
+            present_value,
+            formula: formula.to_string(),
+        }
+    }
+
+    /// Returns the time of the cashflow measured in periods. This is a whole number unless the
+    /// cashflow falls partway through a period.
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    /// Returns the periodic rate used to discount this cashflow.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the cashflow before discounting.
+    pub fn cashflow(&self) -> f64 {
+        self.cashflow
+    }
+
+    /// Returns the factor by which the cashflow is multiplied to find its present value.
+    pub fn discount_factor(&self) -> f64 {
+        self.discount_factor
+    }
+
+    /// Returns the present value of the cashflow, its contribution to the overall present value.
+    pub fn present_value(&self) -> f64 {
+        self.present_value
+    }
+
+    /// Returns a text version of the formula used to calculate the present value of the cashflow.
+    pub fn formula(&self) -> &str {
+        &self.formula
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_present_value_per_flow_rate_same_rate() {
+        // With the same rate for every cashflow this is an ordinary net present value without an
+        // initial investment.
+        let cashflows = [300.0, 400.0, 500.0];
+        let rates = [0.034, 0.034, 0.034];
+        let present_value = present_value_per_flow_rate(&cashflows, &rates);
+        let npv = net_present_value_schedule(&rates, &[0.0, 300.0, 400.0, 500.0]);
+        assert_approx_equal!(npv, present_value);
+    }
+
+    #[test]
+    fn test_present_value_per_flow_rate_series() {
+        let solution = present_value_per_flow_rate_solution(&[100, 200], &[0.10, 0.20]);
+        let series = solution.series();
+        assert_eq!(2, series.len());
+        assert_approx_equal!(1.0 / 1.1, series[0].discount_factor());
+        assert_approx_equal!(1.0 / 1.44, series[1].discount_factor());
+        assert_approx_equal!(series[0].present_value() + series[1].present_value(), solution.present_value());
+        assert_approx_equal!(300.0, solution.sum_of_cashflows());
+    }
+
+    #[test]
+    fn test_present_value_timed_matches_per_flow_rate() {
+        let rate = 0.045;
+        let cashflows = [800.0, 0.0, 1_500.0, 2_200.0];
+        // Out of order on purpose.
+        let flows = [(3.0, 1_500.0), (1.0, 800.0), (4.0, 2_200.0), (2.0, 0.0)];
+        let solution = present_value_timed_solution(rate, &flows);
+        assert_approx_equal!(present_value_per_flow_rate(&cashflows, &[rate; 4]), solution.present_value());
+        let times = solution.series().iter().map(|entry| entry.time()).collect::<Vec<_>>();
+        assert_eq!(vec![1.0, 2.0, 3.0, 4.0], times);
+    }
+
+    #[test]
+    fn test_present_value_risky_certain_default() {
+        // Once the borrower is certain to have defaulted no later cashflows count.
+        let solution = present_value_risky_solution(0.05, 4, 500, &[0.9, 0.0, 1.0, 1.0]);
+        assert_approx_equal!(-450.0 / 1.05, solution.present_value());
+        assert_approx_equal!(450.0, solution.sum_of_cashflows());
+    }
+
+    #[test]
+    fn test_present_value_annuity_indexed_rising_index() {
+        let flat = present_value_annuity_indexed(0.05, 1_200, &[1.0; 10]);
+        let index_factors = (0..10).map(|period| 1.02_f64.powi(period)).collect::<Vec<_>>();
+        let rising = present_value_annuity_indexed_solution(0.05, 1_200, &index_factors);
+        assert!(rising.present_value() < flat);
+        // Indexing that grows at a constant rate matches a growing annuity.
+        assert_approx_equal!(present_value_annuity_growing(0.05, 0.02, 10, 1_200, false), rising.present_value());
+    }
+
+    #[test]
+    fn test_present_value_curve_flat() {
+        // A flat curve matches the per-flow rate function when every rate is the same.
+        let flows = [(1.0, 250.0), (2.0, -75.0), (3.0, 1_300.0), (4.0, 410.0)];
+        let rate: f64 = 0.065;
+        let solution = present_value_curve_solution(|t| (1.0 + rate).powf(-t), &flows);
+        let expected = present_value_per_flow_rate(&[250.0, -75.0, 1_300.0, 410.0], &[rate; 4]);
+        assert_approx_equal!(expected, solution.present_value());
+        for entry in solution.series().iter() {
+            assert_approx_equal!(rate, entry.rate());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_present_value_curve_negative_discount_factor() {
+        present_value_curve(|t| 1.0 - t, &[(0.5, 100.0), (2.0, 100.0)]);
+    }
+
+    #[test]
+    fn test_present_value_annuity_window_single_period() {
+        // A window of one period is a single discounted payment, and a window starting now matches
+        // an annuity due.
+        assert_approx_equal!(present_value(0.07, 9, 3_500, false), present_value_annuity_window(0.07, 3_500, 9, 9));
+        let annuity_due = present_value_annuity(0.07, 10, 3_500, true);
+        assert_approx_equal!(annuity_due, present_value_annuity_window(0.07, 3_500, 0, 9));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_present_value_annuity_window_reversed() {
+        present_value_annuity_window(0.05, 1_000, 10, 5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_present_value_risky_length_mismatch() {
+        present_value_risky(0.05, 3, 500, &[0.99, 0.99]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_present_value_per_flow_rate_length_mismatch() {
+        present_value_per_flow_rate(&[100, 200, 300], &[0.10, 0.20]);
+    }
+}
@@ -0,0 +1,226 @@
+//! **H-model.** A two-stage dividend discount model where growth starts at a high rate and
+//! declines in a straight line to a lower long-term rate. The growth rate falls off over 2H
+//! periods where H is the half-life of the high-growth stage.
+//!
+//! The closed form treats the value as a growing perpetuity at the long-term rate plus a premium
+//! for the extra growth in the early periods:
+//!
+//! present_value = -(dividend * (1 + g<sub>L</sub>) + dividend * H * (g<sub>S</sub> - g<sub>L</sub>)) / (r - g<sub>L</sub>)
+//!
+//! where g<sub>S</sub> is the initial growth rate and g<sub>L</sub> is the terminal growth rate.
+//! As with [`present_value_perpetuity_growing`](../present_value_perpetuity/fn.present_value_perpetuity_growing.html)
+//! the result is negative if the dividend is positive.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // A stock that just paid $2 whose dividend growth fades from 8% to 4% with a half-life of five
+//! // years.
+//! let solution = present_value_h_model_solution(0.10, 0.08, 0.04, 5, 2);
+//! dbg!(&solution);
+//! ```
+
+use crate::*;
+
+/// Returns the **present value of a stream of dividends under the H-model**, where the growth
+/// rate declines in a straight line from an initial rate to a terminal rate.
+///
+/// Related functions:
+/// * To see the value split into its stable-growth and extraordinary-growth parts use
+/// [`present_value_h_model_solution`].
+/// * For a constant growth rate use [`present_value_perpetuity_growing`].
+///
+/// See the [present_value_h_model](./index.html) module page for the formula.
+///
+/// # Arguments
+/// * `rate` - The periodic discount rate, expressed as a floating point number. For instance 0.10
+/// would mean 10%.
+/// * `initial_growth` - The growth rate at the start of the high-growth stage.
+/// * `terminal_growth` - The growth rate once the high-growth stage is over, which continues
+/// forever.
+/// * `half_life_periods` - Half the number of periods over which growth declines from the initial
+/// rate to the terminal rate.
+/// * `dividend` - The most recent dividend, paid just before the valuation date.
+///
+/// # Panics
+/// The call will fail if `rate` is not greater than `terminal_growth`, if `half_life_periods` is
+/// negative, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let present_value = present_value_h_model(0.10, 0.08, 0.04, 5, 2);
+/// assert_rounded_4!(-41.3333, present_value);
+///
+/// // When the initial and terminal growth rates are the same this is the Gordon growth model,
+/// // where the first dividend is one period of growth past the current dividend.
+/// let present_value = present_value_h_model(0.10, 0.04, 0.04, 5, 2);
+/// assert_rounded_4!(present_value_perpetuity_growing(0.10, 0.04, 2.0 * 1.04), present_value);
+/// ```
+pub fn present_value_h_model<H, D>(rate: f64, initial_growth: f64, terminal_growth: f64, half_life_periods: H, dividend: D) -> f64
+    where
+        H: Into<f64> + Copy,
+        D: Into<f64> + Copy
+{
+    present_value_h_model_solution(rate, initial_growth, terminal_growth, half_life_periods, dividend).present_value()
+}
+
+/// Returns the **present value of a stream of dividends under the H-model** along with the value
+/// from stable growth and the value from the extra early growth.
+///
+/// Related functions:
+/// * To calculate only the present value as an `f64` use [`present_value_h_model`].
+///
+/// # Arguments
+/// * `rate` - The periodic discount rate, expressed as a floating point number. For instance 0.10
+/// would mean 10%.
+/// * `initial_growth` - The growth rate at the start of the high-growth stage.
+/// * `terminal_growth` - The growth rate once the high-growth stage is over, which continues
+/// forever.
+/// * `half_life_periods` - Half the number of periods over which growth declines from the initial
+/// rate to the terminal rate.
+/// * `dividend` - The most recent dividend, paid just before the valuation date.
+///
+/// # Panics
+/// The call will fail if `rate` is not greater than `terminal_growth`, if `half_life_periods` is
+/// negative, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = present_value_h_model_solution(0.10, 0.08, 0.04, 5, 2);
+/// assert_rounded_4!(-34.6667, solution.stable_growth_value());
+/// assert_rounded_4!(-6.6667, solution.extraordinary_growth_value());
+/// assert_rounded_4!(-41.3333, solution.present_value());
+/// ```
+pub fn present_value_h_model_solution<H, D>(rate: f64, initial_growth: f64, terminal_growth: f64, half_life_periods: H, dividend: D) -> HModelSolution
+    where
+        H: Into<f64> + Copy,
+        D: Into<f64> + Copy
+{
+    let half_life_periods = half_life_periods.into();
+    let dividend = dividend.into();
+    assert!(rate.is_finite(), "The rate must be finite (not NaN or infinity)");
+    assert!(initial_growth.is_finite(), "The initial growth rate must be finite (not NaN or infinity)");
+    assert!(terminal_growth.is_finite(), "The terminal growth rate must be finite (not NaN or infinity)");
+    assert!(half_life_periods.is_finite(), "The half-life must be finite (not NaN or infinity)");
+    assert!(dividend.is_finite(), "The dividend must be finite (not NaN or infinity)");
+    assert!(half_life_periods >= 0.0, "The half-life must be zero or greater.");
+    assert!(rate > terminal_growth, "The rate must be greater than the terminal growth rate or the dividends have no finite value.");
+    HModelSolution::new(rate, initial_growth, terminal_growth, half_life_periods, dividend)
+}
+
+/// A record of a present value calculated with the H-model. Created by calling
+/// [`present_value_h_model_solution`].
+#[derive(Clone, Debug)]
+pub struct HModelSolution {
+    rate: f64,
+    initial_growth: f64,
+    terminal_growth: f64,
+    half_life_periods: f64,
+    dividend: f64,
+    stable_growth_value: f64,
+    extraordinary_growth_value: f64,
+    present_value: f64,
+    formula: String,
+    symbolic_formula: String,
+}
+
+impl HModelSolution {
+    pub(crate) fn new(rate: f64, initial_growth: f64, terminal_growth: f64, half_life_periods: f64, dividend: f64) -> Self {
+        let stable_growth_value = -dividend * (1.0 + terminal_growth) / (rate - terminal_growth);
+        let extraordinary_growth_value = -dividend * half_life_periods * (initial_growth - terminal_growth) / (rate - terminal_growth);
+        let present_value = stable_growth_value + extraordinary_growth_value;
+        assert!(present_value.is_finite());
+        let formula = format!("{:.4} = -({:.4} * {:.6} + {:.4} * {:.4} * ({:.6} - {:.6})) / ({:.6} - {:.6})",
+                              present_value, dividend, 1.0 + terminal_growth, dividend, half_life_periods, initial_growth, terminal_growth, rate, terminal_growth);
+        let symbolic_formula = "pv = -(d * (1 + gL) + d * H * (gS - gL)) / (r - gL)".to_string();
+        Self {
+            rate,
+            initial_growth,
+            terminal_growth,
+            half_life_periods,
+            dividend,
+            stable_growth_value,
+            extraordinary_growth_value,
+            present_value,
+            formula,
+            symbolic_formula,
+        }
+    }
+
+    /// Returns the periodic discount rate.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the growth rate at the start of the high-growth stage.
+    pub fn initial_growth(&self) -> f64 {
+        self.initial_growth
+    }
+
+    /// Returns the long-term growth rate.
+    pub fn terminal_growth(&self) -> f64 {
+        self.terminal_growth
+    }
+
+    /// Returns half the number of periods over which growth declines to the terminal rate.
+    pub fn half_life_periods(&self) -> f64 {
+        self.half_life_periods
+    }
+
+    /// Returns the most recent dividend.
+    pub fn dividend(&self) -> f64 {
+        self.dividend
+    }
+
+    /// Returns the value of the dividends if they grew at the terminal rate from the start.
+    pub fn stable_growth_value(&self) -> f64 {
+        self.stable_growth_value
+    }
+
+    /// Returns the additional value from growth above the terminal rate during the high-growth
+    /// stage.
+    pub fn extraordinary_growth_value(&self) -> f64 {
+        self.extraordinary_growth_value
+    }
+
+    /// Returns the total present value of the dividends.
+    pub fn present_value(&self) -> f64 {
+        self.present_value
+    }
+
+    /// Returns a text version of the formula used to calculate the present value with the actual
+    /// values.
+    pub fn formula(&self) -> &str {
+        &self.formula
+    }
+
+    /// Returns a text version of the formula used to calculate the present value using variables
+    /// such as r for the rate.
+    pub fn symbolic_formula(&self) -> &str {
+        &self.symbolic_formula
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_present_value_h_model_between_gordon_values() {
+        let (rate, initial_growth, terminal_growth, half_life_periods, dividend) = (0.09, 0.06, 0.03, 4.0, 1.5);
+        let present_value = present_value_h_model(rate, initial_growth, terminal_growth, half_life_periods, dividend);
+        let gordon_terminal = present_value_perpetuity_growing(rate, terminal_growth, dividend * (1.0 + terminal_growth));
+        let gordon_initial = present_value_perpetuity_growing(rate, initial_growth, dividend * (1.0 + initial_growth));
+        // The values are negative so the more valuable Gordon value is the lower one.
+        assert!(present_value < gordon_terminal);
+        assert!(present_value > gordon_initial);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_present_value_h_model_rate_below_terminal_growth() {
+        present_value_h_model(0.03, 0.08, 0.04, 5, 2);
+    }
+}
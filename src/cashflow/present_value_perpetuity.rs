@@ -0,0 +1,245 @@
+//! **Present value _perpetuity_ calculations**. Given a cashflow that continues forever and grows
+//! at a constant rate, what is the value of the whole stream right now?
+//!
+//! For a perpetuity whose growth only begins after an explicit forecast horizon, as in the terminal
+//! value of a two-stage discounted cashflow model, use
+//! [`present_value_perpetuity_growing_deferred_solution`](./fn.present_value_perpetuity_growing_deferred_solution.html).
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! let (rate, growth, first_payment, deferral_periods) = (0.09, 0.03, 1_200, 5);
+//! let solution = present_value_perpetuity_growing_deferred_solution(rate, growth, first_payment, deferral_periods);
+//! dbg!(solution);
+//! ```
+
+use crate::*;
+
+/// Returns the **present value of a growing perpetuity**, a cashflow that continues forever and
+/// grows at a constant rate, with the first payment at the end of the first period.
+///
+/// Related functions:
+/// * To value a growing perpetuity that begins only after a number of periods use
+/// [`present_value_perpetuity_growing_deferred`].
+///
+/// The formula is:
+///
+/// present_value = -first_payment / (rate - growth)
+///
+/// # Arguments
+/// * `rate` - The periodic discount rate, expressed as a floating point number. For instance 0.05
+/// would mean 5%. Often appears as `r` or `i` in formulas.
+/// * `growth` - The rate at which the payment grows each period. Often appears as `g`.
+/// * `first_payment` - The first payment, made at the end of the first period.
+///
+/// # Panics
+/// The call will fail if `growth` is not less than `rate` since the perpetuity would have no finite
+/// value.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // A dividend of $2.00 next year growing at 4% per year, discounted at 10%.
+/// let present_value = present_value_perpetuity_growing(0.10, 0.04, 2);
+/// assert_rounded_4!(-33.3333, present_value);
+/// ```
+pub fn present_value_perpetuity_growing<T>(rate: f64, growth: f64, first_payment: T) -> f64
+    where T: Into<f64> + Copy
+{
+    let first_payment = first_payment.into();
+    check_perpetuity_growing_parameters(rate, growth, first_payment);
+    -first_payment / (rate - growth)
+}
+
+/// Returns the **present value of a growing perpetuity that begins after a deferral**. The
+/// perpetuity is valued at the end of the deferral as a terminal value and that value is then
+/// discounted back to the present.
+///
+/// Related functions:
+/// * To keep the terminal value and the formulas in a struct use
+/// [`present_value_perpetuity_growing_deferred_solution`].
+/// * For a perpetuity with no deferral use [`present_value_perpetuity_growing`].
+///
+/// The formula is:
+///
+/// present_value = (-first_payment / (rate - growth)) / (1 + rate)<sup>deferral_periods</sup>
+///
+/// # Arguments
+/// * `rate` - The periodic discount rate, expressed as a floating point number. For instance 0.05
+/// would mean 5%. Often appears as `r` or `i` in formulas.
+/// * `growth` - The rate at which the payment grows each period once the perpetuity begins. Often
+/// appears as `g`.
+/// * `first_payment` - The first payment of the perpetuity, made at the end of the period
+/// following the deferral.
+/// * `deferral_periods` - The number of periods before the perpetuity begins, such as the length
+/// of an explicit forecast. With a value of 0 this is a plain growing perpetuity.
+///
+/// # Panics
+/// The call will fail if `growth` is not less than `rate` since the perpetuity would have no finite
+/// value.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // After a five year forecast, free cashflow of $1,200 grows at 3% per year forever. The
+/// // discount rate is 9%.
+/// let present_value = present_value_perpetuity_growing_deferred(0.09, 0.03, 1_200, 5);
+/// assert_rounded_2!(-12_998.63, present_value);
+/// ```
+pub fn present_value_perpetuity_growing_deferred<T>(rate: f64, growth: f64, first_payment: T, deferral_periods: u32) -> f64
+    where T: Into<f64> + Copy
+{
+    present_value_perpetuity_growing_deferred_solution(rate, growth, first_payment, deferral_periods).present_value()
+}
+
+/// Returns the **present value of a growing perpetuity that begins after a deferral** along with
+/// the terminal value at the end of the deferral and the formulas used.
+///
+/// Related functions:
+/// * To calculate only the present value as an `f64` use
+/// [`present_value_perpetuity_growing_deferred`].
+///
+/// # Arguments
+/// * `rate` - The periodic discount rate, expressed as a floating point number. For instance 0.05
+/// would mean 5%. Often appears as `r` or `i` in formulas.
+/// * `growth` - The rate at which the payment grows each period once the perpetuity begins. Often
+/// appears as `g`.
+/// * `first_payment` - The first payment of the perpetuity, made at the end of the period
+/// following the deferral.
+/// * `deferral_periods` - The number of periods before the perpetuity begins.
+///
+/// # Panics
+/// The call will fail if `growth` is not less than `rate` since the perpetuity would have no finite
+/// value.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = present_value_perpetuity_growing_deferred_solution(0.09, 0.03, 1_200, 5);
+/// dbg!(&solution);
+///
+/// // The terminal value is the growing perpetuity valued at the end of year 5.
+/// assert_rounded_2!(-20_000.00, solution.terminal_value());
+///
+/// // The present value is the terminal value discounted back five years.
+/// assert_rounded_2!(-12_998.63, solution.present_value());
+/// ```
+pub fn present_value_perpetuity_growing_deferred_solution<T>(rate: f64, growth: f64, first_payment: T, deferral_periods: u32) -> PerpetuitySolution
+    where T: Into<f64> + Copy
+{
+    let first_payment = first_payment.into();
+    let terminal_value = present_value_perpetuity_growing(rate, growth, first_payment);
+    PerpetuitySolution::new(rate, growth, first_payment, deferral_periods, terminal_value)
+}
+
+/// A record of a growing perpetuity calculation created by calling
+/// [`present_value_perpetuity_growing_deferred_solution`].
+#[derive(Clone, Debug)]
+pub struct PerpetuitySolution {
+    rate: f64,
+    growth: f64,
+    first_payment: f64,
+    deferral_periods: u32,
+    terminal_value: f64,
+    present_value: f64,
+    formula: String,
+    symbolic_formula: String,
+}
+
+impl PerpetuitySolution {
+    pub(crate) fn new(rate: f64, growth: f64, first_payment: f64, deferral_periods: u32, terminal_value: f64) -> Self {
+        let present_value = terminal_value / (1.0 + rate).powi(deferral_periods as i32);
+        assert!(terminal_value.is_finite());
+        assert!(present_value.is_finite());
+        let formula = format!("{:.4} = ({:.4} / ({:.6} - {:.6})) / ({:.6} ^ {})", present_value, -first_payment, rate, growth, 1.0 + rate, deferral_periods);
+        let symbolic_formula = "pv = (-pmt / (r - g)) / (1 + r)^n".to_string();
+        Self {
+            rate,
+            growth,
+            first_payment,
+            deferral_periods,
+            terminal_value,
+            present_value,
+            formula,
+            symbolic_formula,
+        }
+    }
+
+    /// Returns the periodic discount rate.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the rate at which the payment grows each period.
+    pub fn growth(&self) -> f64 {
+        self.growth
+    }
+
+    /// Returns the first payment of the perpetuity.
+    pub fn first_payment(&self) -> f64 {
+        self.first_payment
+    }
+
+    /// Returns the number of periods before the perpetuity begins.
+    pub fn deferral_periods(&self) -> u32 {
+        self.deferral_periods
+    }
+
+    /// Returns the value of the perpetuity at the end of the deferral, that is before it's
+    /// discounted back to the present.
+    pub fn terminal_value(&self) -> f64 {
+        self.terminal_value
+    }
+
+    /// Returns the present value of the terminal value.
+    pub fn present_value(&self) -> f64 {
+        self.present_value
+    }
+
+    /// Returns a text version of the formula used to calculate the present value with the actual
+    /// values rather than variable names.
+    pub fn formula(&self) -> &str {
+        &self.formula
+    }
+
+    /// Returns a text version of the formula used to calculate the present value with variables
+    /// such as r for the rate.
+    pub fn symbolic_formula(&self) -> &str {
+        &self.symbolic_formula
+    }
+}
+
+fn check_perpetuity_growing_parameters(rate: f64, growth: f64, first_payment: f64) {
+    assert!(rate.is_finite(), "The rate must be finite (not NaN or infinity)");
+    assert!(growth.is_finite(), "The growth rate must be finite (not NaN or infinity)");
+    assert!(first_payment.is_finite(), "The first payment must be finite (not NaN or infinity)");
+    assert!(growth < rate, "The growth rate must be less than the discount rate or the perpetuity has no finite value.");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_present_value_perpetuity_growing_deferred_no_deferral() {
+        let (rate, growth, first_payment) = (0.08, 0.02, 500.0);
+        let plain = present_value_perpetuity_growing(rate, growth, first_payment);
+        let solution = present_value_perpetuity_growing_deferred_solution(rate, growth, first_payment, 0);
+        assert_approx_equal!(plain, solution.present_value());
+        assert_approx_equal!(plain, solution.terminal_value());
+    }
+
+    #[test]
+    fn test_present_value_perpetuity_growing_deferred_matches_discounted_terminal_value() {
+        let (rate, growth, first_payment, deferral_periods) = (0.10, 0.04, 2.5, 7);
+        let solution = present_value_perpetuity_growing_deferred_solution(rate, growth, first_payment, deferral_periods);
+        let expected = present_value(rate, deferral_periods, -solution.terminal_value(), false);
+        assert_approx_equal!(expected, solution.present_value());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_present_value_perpetuity_growing_deferred_growth_not_less_than_rate() {
+        present_value_perpetuity_growing_deferred(0.05, 0.05, 100, 3);
+    }
+}
@@ -0,0 +1,234 @@
+//! **Present value of a step annuity.** Some leases and structured settlements pay one amount for
+//! a number of periods and then step up (or down) to a different amount for the remaining
+//! periods. This is two consecutive level annuities where the second one is deferred until the
+//! first one ends.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // A lease paying $2,000 a month for the first year and $2,400 a month for the next two years.
+//! let solution = present_value_step_annuity_solution(0.005, 2_000, 12, 2_400, 24);
+//! dbg!(&solution);
+//! solution.print_table();
+//! ```
+
+use crate::*;
+
+/// Returns the **present value of a step annuity** that pays one amount for a number of periods
+/// followed by a different amount for a further number of periods. The payments are made at the
+/// end of each period.
+///
+/// Related functions:
+/// * To see the present value of each payment use [`present_value_step_annuity_solution`].
+/// * For a single level payment use [`present_value_annuity`].
+///
+/// The formula is the present value of the first annuity plus the present value of the second
+/// annuity discounted back over the first one:
+///
+/// present_value = -(pmt1 * (1 - (1 + r)<sup>-n1</sup>) / r + pmt2 * (1 - (1 + r)<sup>-n2</sup>) / r / (1 + r)<sup>n1</sup>)
+///
+/// As with [`present_value_annuity`] the result is negative if the payments are positive.
+///
+/// # Arguments
+/// * `rate` - The periodic rate, expressed as a floating point number. For instance 0.01 would
+/// mean 1%.
+/// * `payment1` - The payment for each of the first `periods1` periods.
+/// * `periods1` - The number of periods with the first payment. This may be zero.
+/// * `payment2` - The payment for each of the following `periods2` periods.
+/// * `periods2` - The number of periods with the second payment. This may be zero.
+///
+/// # Panics
+/// The call will fail if `rate` is less than or equal to -1.0 or if the rate or either payment is
+/// not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // $2,000 a month for a year then $2,400 a month for two years at 0.5% a month.
+/// let pv = present_value_step_annuity(0.005, 2_000, 12, 2_400, 24);
+/// assert_rounded_4!(-74_242.8661, pv);
+///
+/// // With no step this is an ordinary annuity.
+/// let pv = present_value_step_annuity(0.005, 2_000, 12, 2_000, 24);
+/// assert_rounded_4!(present_value_annuity(0.005, 36, 2_000, false), pv);
+/// ```
+pub fn present_value_step_annuity<C1, C2>(rate: f64, payment1: C1, periods1: u32, payment2: C2, periods2: u32) -> f64
+    where
+        C1: Into<f64> + Copy,
+        C2: Into<f64> + Copy
+{
+    present_value_step_annuity_solution(rate, payment1, periods1, payment2, periods2).present_value()
+}
+
+/// Returns the **present value of a step annuity** along with the present value of each of the
+/// two payment levels and a period-by-period schedule of the payments.
+///
+/// Related functions:
+/// * To calculate only the present value as an `f64` use [`present_value_step_annuity`].
+///
+/// # Arguments
+/// * `rate` - The periodic rate, expressed as a floating point number. For instance 0.01 would
+/// mean 1%.
+/// * `payment1` - The payment for each of the first `periods1` periods.
+/// * `periods1` - The number of periods with the first payment. This may be zero.
+/// * `payment2` - The payment for each of the following `periods2` periods.
+/// * `periods2` - The number of periods with the second payment. This may be zero.
+///
+/// # Panics
+/// The call will fail if `rate` is less than or equal to -1.0 or if the rate or either payment is
+/// not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = present_value_step_annuity_solution(0.005, 2_000, 12, 2_400, 24);
+/// assert_rounded_4!(-23_237.8641, solution.present_value_first());
+/// assert_rounded_4!(-51_005.0020, solution.present_value_second());
+/// assert_rounded_4!(solution.present_value_first() + solution.present_value_second(), solution.present_value());
+///
+/// // The series has one entry per payment. Each entry shows the payment and its discounted value
+/// // with the same sign as the payment.
+/// let series = solution.series();
+/// assert_eq!(36, series.len());
+/// assert_rounded_4!(2_000.0, series[11].cashflow());
+/// assert_rounded_4!(2_400.0, series[12].cashflow());
+/// ```
+pub fn present_value_step_annuity_solution<C1, C2>(rate: f64, payment1: C1, periods1: u32, payment2: C2, periods2: u32) -> StepAnnuitySolution
+    where
+        C1: Into<f64> + Copy,
+        C2: Into<f64> + Copy
+{
+    let payment1 = payment1.into();
+    let payment2 = payment2.into();
+    assert!(rate.is_finite(), "The rate must be finite (not NaN or infinity)");
+    assert!(rate > -1.0, "The rate must be greater than -1.0 (-100%).");
+    assert!(payment1.is_finite(), "The first payment must be finite (not NaN or infinity)");
+    assert!(payment2.is_finite(), "The second payment must be finite (not NaN or infinity)");
+    StepAnnuitySolution::new(rate, payment1, periods1, payment2, periods2)
+}
+
+/// A record of the present value of a step annuity. Created by calling
+/// [`present_value_step_annuity_solution`].
+#[derive(Clone, Debug)]
+pub struct StepAnnuitySolution {
+    rate: f64,
+    payment1: f64,
+    periods1: u32,
+    payment2: f64,
+    periods2: u32,
+    present_value_first: f64,
+    present_value_second: f64,
+    present_value: f64,
+    series: DiscountedCashflowSeries,
+}
+
+impl StepAnnuitySolution {
+    pub(crate) fn new(rate: f64, payment1: f64, periods1: u32, payment2: f64, periods2: u32) -> Self {
+        let mut series = vec![];
+        for period in 1..=(periods1 + periods2) {
+            let payment = if period <= periods1 { payment1 } else { payment2 };
+            let discount_factor = 1.0 / (1.0 + rate).powi(period as i32);
+            let formula = format!("{:.4} = {:.4} / ({:.6} ^ {})", payment * discount_factor, payment, 1.0 + rate, period);
+            series.push(DiscountedCashflow::new(period as f64, rate, payment, discount_factor, &formula));
+        }
+        // Follow the sign convention of present_value_annuity() where the present value has the
+        // opposite sign from the payments.
+        let present_value_first = -series.iter().take(periods1 as usize).map(|entry| entry.present_value()).sum::<f64>();
+        let present_value_second = -series.iter().skip(periods1 as usize).map(|entry| entry.present_value()).sum::<f64>();
+        let present_value = present_value_first + present_value_second;
+        assert!(present_value.is_finite());
+        Self {
+            rate,
+            payment1,
+            periods1,
+            payment2,
+            periods2,
+            present_value_first,
+            present_value_second,
+            present_value,
+            series: DiscountedCashflowSeries::new(series),
+        }
+    }
+
+    /// Returns the periodic rate.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the payment for each of the first periods.
+    pub fn payment1(&self) -> f64 {
+        self.payment1
+    }
+
+    /// Returns the number of periods with the first payment.
+    pub fn periods1(&self) -> u32 {
+        self.periods1
+    }
+
+    /// Returns the payment for each of the periods after the step.
+    pub fn payment2(&self) -> f64 {
+        self.payment2
+    }
+
+    /// Returns the number of periods with the second payment.
+    pub fn periods2(&self) -> u32 {
+        self.periods2
+    }
+
+    /// Returns the present value of the payments before the step.
+    pub fn present_value_first(&self) -> f64 {
+        self.present_value_first
+    }
+
+    /// Returns the present value of the payments after the step, discounted all the way back to
+    /// the start.
+    pub fn present_value_second(&self) -> f64 {
+        self.present_value_second
+    }
+
+    /// Returns the present value of all of the payments.
+    pub fn present_value(&self) -> f64 {
+        self.present_value
+    }
+
+    /// Returns the payment and discounted value for each period.
+    pub fn series(&self) -> DiscountedCashflowSeries {
+        self.series.clone()
+    }
+
+    pub fn print_table(&self) {
+        self.series.print_table();
+    }
+
+    pub fn print_table_locale(&self, locale: &num_format::Locale, precision: usize) {
+        self.series.print_table_locale(locale, precision);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_present_value_step_annuity_matches_deferred_annuities() {
+        let (rate, payment1, periods1, payment2, periods2) = (0.0075, 850.0, 18, 1_100.0, 42);
+        let first = present_value_annuity(rate, periods1, payment1, false);
+        // Defer the second annuity by discounting its present value over the first segment.
+        let second = present_value_annuity(rate, periods2, payment2, false) / (1.0 + rate).powi(periods1 as i32);
+        let solution = present_value_step_annuity_solution(rate, payment1, periods1, payment2, periods2);
+        assert_approx_equal!(first, solution.present_value_first());
+        assert_approx_equal!(second, solution.present_value_second());
+        assert_approx_equal!(first + second, solution.present_value());
+    }
+
+    #[test]
+    fn test_present_value_step_annuity_empty_segments() {
+        let pv = present_value_step_annuity(0.01, 500, 0, 700, 10);
+        assert_approx_equal!(present_value_annuity(0.01, 10, 700, false), pv);
+        let pv = present_value_step_annuity(0.01, 500, 10, 700, 0);
+        assert_approx_equal!(present_value_annuity(0.01, 10, 500, false), pv);
+        let solution = present_value_step_annuity_solution(0.01, 500, 0, 700, 0);
+        assert!(solution.series().is_empty());
+        assert_eq!(0.0, solution.present_value());
+    }
+}
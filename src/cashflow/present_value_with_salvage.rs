@@ -0,0 +1,202 @@
+//! **Present value with a salvage value.** Many projects produce a level cashflow each period and
+//! then have a terminal or salvage value at the end, such as the resale value of equipment. The
+//! present value is the present value of the annuity plus the discounted salvage value.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // A machine that saves $12,000 a year for eight years and can be sold for $5,000 at the end.
+//! let solution = present_value_with_salvage_solution(0.09, 8, 12_000, 5_000);
+//! dbg!(&solution);
+//! ```
+
+use crate::*;
+
+/// Returns the **present value of a level cashflow plus a salvage value** received at the end of
+/// the last period.
+///
+/// Related functions:
+/// * To see the two parts of the present value separately use
+/// [`present_value_with_salvage_solution`].
+/// * For the cashflows alone use [`present_value_annuity`] and for the salvage value alone use
+/// [`present_value`].
+///
+/// The formula is:
+///
+/// present_value = -(cash_flow * (1 - (1 + r)<sup>-n</sup>) / r + salvage_value / (1 + r)<sup>n</sup>)
+///
+/// As with [`present_value_annuity`] and [`present_value`] the result is negative if the cashflow
+/// and salvage value are positive.
+///
+/// # Arguments
+/// * `rate` - The periodic rate, expressed as a floating point number. For instance 0.05 would
+/// mean 5%.
+/// * `periods` - The number of periods such as years.
+/// * `annual_cash_flow` - The cashflow received at the end of each period.
+/// * `salvage_value` - The amount received at the end of the last period in addition to the last
+/// cashflow.
+///
+/// # Panics
+/// The call will fail if `rate` is less than or equal to -1.0 or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let pv = present_value_with_salvage(0.09, 8, 12_000, 5_000);
+/// assert_rounded_4!(-68_927.1608, pv);
+///
+/// // With no salvage value this is an ordinary annuity.
+/// assert_rounded_4!(present_value_annuity(0.09, 8, 12_000, false), present_value_with_salvage(0.09, 8, 12_000, 0));
+/// ```
+pub fn present_value_with_salvage<C, S>(rate: f64, periods: u32, annual_cash_flow: C, salvage_value: S) -> f64
+    where
+        C: Into<f64> + Copy,
+        S: Into<f64> + Copy
+{
+    present_value_with_salvage_solution(rate, periods, annual_cash_flow, salvage_value).present_value()
+}
+
+/// Returns the **present value of a level cashflow plus a salvage value** along with the present
+/// value of each of the two parts.
+///
+/// Related functions:
+/// * To calculate only the present value as an `f64` use [`present_value_with_salvage`].
+///
+/// # Arguments
+/// * `rate` - The periodic rate, expressed as a floating point number. For instance 0.05 would
+/// mean 5%.
+/// * `periods` - The number of periods such as years.
+/// * `annual_cash_flow` - The cashflow received at the end of each period.
+/// * `salvage_value` - The amount received at the end of the last period in addition to the last
+/// cashflow.
+///
+/// # Panics
+/// The call will fail if `rate` is less than or equal to -1.0 or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = present_value_with_salvage_solution(0.09, 8, 12_000, 5_000);
+/// assert_rounded_4!(-66_417.8294, solution.present_value_annuity());
+/// assert_rounded_4!(-2_509.3314, solution.present_value_salvage());
+/// assert_rounded_4!(-68_927.1608, solution.present_value());
+/// ```
+pub fn present_value_with_salvage_solution<C, S>(rate: f64, periods: u32, annual_cash_flow: C, salvage_value: S) -> SalvageValueSolution
+    where
+        C: Into<f64> + Copy,
+        S: Into<f64> + Copy
+{
+    let cash_flow = annual_cash_flow.into();
+    let salvage_value = salvage_value.into();
+    assert!(rate.is_finite(), "The rate must be finite (not NaN or infinity)");
+    assert!(rate > -1.0, "The rate must be greater than -1.0 (-100%).");
+    assert!(cash_flow.is_finite(), "The cashflow must be finite (not NaN or infinity)");
+    assert!(salvage_value.is_finite(), "The salvage value must be finite (not NaN or infinity)");
+    SalvageValueSolution::new(rate, periods, cash_flow, salvage_value)
+}
+
+/// A record of the present value of a level cashflow plus a salvage value. Created by calling
+/// [`present_value_with_salvage_solution`].
+#[derive(Clone, Debug)]
+pub struct SalvageValueSolution {
+    rate: f64,
+    periods: u32,
+    cash_flow: f64,
+    salvage_value: f64,
+    present_value_annuity: f64,
+    present_value_salvage: f64,
+    present_value: f64,
+    formula: String,
+    symbolic_formula: String,
+}
+
+impl SalvageValueSolution {
+    pub(crate) fn new(rate: f64, periods: u32, cash_flow: f64, salvage_value: f64) -> Self {
+        let discount_factor = 1.0 / (1.0 + rate).powf(periods as f64);
+        let annuity_factor = if rate == 0.0 { periods as f64 } else { (1.0 - discount_factor) / rate };
+        let present_value_annuity = -cash_flow * annuity_factor;
+        let present_value_salvage = -salvage_value * discount_factor;
+        let present_value = present_value_annuity + present_value_salvage;
+        assert!(present_value.is_finite());
+        let formula = format!("{:.4} = -({:.4} * (1 - {:.6}^-{}) / {:.6} + {:.4} / {:.6}^{})",
+                              present_value, cash_flow, 1.0 + rate, periods, rate, salvage_value, 1.0 + rate, periods);
+        let symbolic_formula = "pv = -(cash_flow * (1 - (1 + r)^-n) / r + salvage_value / (1 + r)^n)".to_string();
+        Self {
+            rate,
+            periods,
+            cash_flow,
+            salvage_value,
+            present_value_annuity,
+            present_value_salvage,
+            present_value,
+            formula,
+            symbolic_formula,
+        }
+    }
+
+    /// Returns the periodic rate.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the number of periods.
+    pub fn periods(&self) -> u32 {
+        self.periods
+    }
+
+    /// Returns the cashflow received at the end of each period.
+    pub fn cash_flow(&self) -> f64 {
+        self.cash_flow
+    }
+
+    /// Returns the amount received at the end of the last period in addition to the last cashflow.
+    pub fn salvage_value(&self) -> f64 {
+        self.salvage_value
+    }
+
+    /// Returns the present value of the level cashflows alone.
+    pub fn present_value_annuity(&self) -> f64 {
+        self.present_value_annuity
+    }
+
+    /// Returns the present value of the salvage value alone.
+    pub fn present_value_salvage(&self) -> f64 {
+        self.present_value_salvage
+    }
+
+    /// Returns the total present value of the cashflows and the salvage value.
+    pub fn present_value(&self) -> f64 {
+        self.present_value
+    }
+
+    /// Returns a text version of the formula used to calculate the present value with the actual
+    /// values.
+    pub fn formula(&self) -> &str {
+        &self.formula
+    }
+
+    /// Returns a text version of the formula used to calculate the present value using variables
+    /// such as r for the rate.
+    pub fn symbolic_formula(&self) -> &str {
+        &self.symbolic_formula
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_present_value_with_salvage_parts() {
+        let (rate, periods, cash_flow, salvage_value) = (0.055, 15, 3_400.0, 20_000.0);
+        let solution = present_value_with_salvage_solution(rate, periods, cash_flow, salvage_value);
+        assert_approx_equal!(present_value_annuity(rate, periods, cash_flow, false), solution.present_value_annuity());
+        assert_approx_equal!(present_value(rate, periods, salvage_value, false), solution.present_value_salvage());
+        assert_approx_equal!(solution.present_value_annuity() + solution.present_value_salvage(), solution.present_value());
+    }
+
+    #[test]
+    fn test_present_value_with_salvage_zero_rate() {
+        assert_approx_equal!(-25_000.0, present_value_with_salvage(0.0, 10, 2_000, 5_000));
+    }
+}
@@ -0,0 +1,243 @@
+//! **Present value with a stub period.** A loan or deal that closes partway through a period has a
+//! short first period, called a stub, before the regular periods begin. The first regular payment
+//! comes one full period after the stub ends, so the whole stream of payments and the lump sum at
+//! the end are pushed back by the length of the stub.
+//!
+//! The present value is the ordinary present value of the payments and the lump sum as of the end
+//! of the stub, discounted once more over the stub:
+//!
+//! present_value = -(cash_flow * (1 - (1 + r)<sup>-n</sup>) / r + future_value / (1 + r)<sup>n</sup>) / (1 + r)<sup>stub_fraction</sup>
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // A loan that closes halfway through the month with 24 monthly payments of $500 after that and
+//! // a $2,000 balloon at the end, at 1% a month.
+//! let solution = present_value_with_stub_solution(0.01, 24, 0.5, 500, 2_000);
+//! dbg!(&solution);
+//! ```
+
+use crate::*;
+
+/// Returns the **present value of a level cashflow plus a lump sum when a short stub period comes
+/// before the regular periods**.
+///
+/// Related functions:
+/// * To see the length of the stub and the present value as of the end of the stub use
+/// [`present_value_with_stub_solution`].
+/// * Without a stub this is the same as [`present_value_with_salvage`].
+///
+/// See the [present_value_with_stub](./index.html) module page for the formula. As with
+/// [`present_value_annuity`] and [`present_value`] the result is negative if the cashflow and
+/// future value are positive.
+///
+/// # Arguments
+/// * `rate` - The periodic rate, expressed as a floating point number. For instance 0.05 would
+/// mean 5%.
+/// * `full_periods` - The number of regular periods after the stub, each of which ends with a
+/// payment.
+/// * `stub_fraction` - The length of the stub as a fraction of a regular period, such as 0.5 for a
+/// loan that closes halfway through a month. There is no payment at the end of the stub.
+/// * `cash_flow_per_period` - The cashflow received at the end of each regular period.
+/// * `future_value` - The amount received at the end of the last regular period in addition to the
+/// last cashflow.
+///
+/// # Panics
+/// The call will fail if `rate` is less than or equal to -1.0, if `stub_fraction` is negative or
+/// not less than 1.0, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let pv = present_value_with_stub(0.01, 24, 0.5, 500, 2_000);
+/// assert_rounded_4!(-12_136.2954, pv);
+///
+/// // With no stub this is an ordinary annuity plus a lump sum.
+/// assert_rounded_4!(present_value_with_salvage(0.01, 24, 500, 2_000), present_value_with_stub(0.01, 24, 0.0, 500, 2_000));
+/// ```
+pub fn present_value_with_stub<C, F>(rate: f64, full_periods: u32, stub_fraction: f64, cash_flow_per_period: C, future_value: F) -> f64
+    where
+        C: Into<f64> + Copy,
+        F: Into<f64> + Copy
+{
+    present_value_with_stub_solution(rate, full_periods, stub_fraction, cash_flow_per_period, future_value).present_value()
+}
+
+/// Returns the **present value of a level cashflow plus a lump sum when a short stub period comes
+/// before the regular periods** along with the length of the stub and the present value as of the
+/// end of the stub.
+///
+/// Related functions:
+/// * To calculate only the present value as an `f64` use [`present_value_with_stub`].
+///
+/// # Arguments
+/// * `rate` - The periodic rate, expressed as a floating point number. For instance 0.05 would
+/// mean 5%.
+/// * `full_periods` - The number of regular periods after the stub, each of which ends with a
+/// payment.
+/// * `stub_fraction` - The length of the stub as a fraction of a regular period. There is no
+/// payment at the end of the stub.
+/// * `cash_flow_per_period` - The cashflow received at the end of each regular period.
+/// * `future_value` - The amount received at the end of the last regular period in addition to the
+/// last cashflow.
+///
+/// # Panics
+/// The call will fail if `rate` is less than or equal to -1.0, if `stub_fraction` is negative or
+/// not less than 1.0, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = present_value_with_stub_solution(0.01, 24, 0.5, 500, 2_000);
+/// assert_rounded_4!(0.5, solution.stub_fraction());
+/// assert_rounded_4!(24.5, solution.total_periods());
+///
+/// // The value as of the end of the stub, when the regular periods begin.
+/// assert_rounded_4!(-12_196.8259, solution.present_value_at_stub_end());
+///
+/// // Discounted back over the half-period stub.
+/// assert_rounded_6!(0.995037, solution.stub_discount_factor());
+/// assert_rounded_4!(-12_136.2954, solution.present_value());
+/// ```
+pub fn present_value_with_stub_solution<C, F>(rate: f64, full_periods: u32, stub_fraction: f64, cash_flow_per_period: C, future_value: F) -> StubPeriodSolution
+    where
+        C: Into<f64> + Copy,
+        F: Into<f64> + Copy
+{
+    let cash_flow = cash_flow_per_period.into();
+    let future_value = future_value.into();
+    assert!(rate.is_finite(), "The rate must be finite (not NaN or infinity)");
+    assert!(rate > -1.0, "The rate must be greater than -1.0 (-100%).");
+    assert!(stub_fraction.is_finite(), "The stub fraction must be finite (not NaN or infinity)");
+    assert!((0.0..1.0).contains(&stub_fraction), "The stub fraction must be at least 0.0 and less than 1.0.");
+    assert!(cash_flow.is_finite(), "The cashflow must be finite (not NaN or infinity)");
+    assert!(future_value.is_finite(), "The future value must be finite (not NaN or infinity)");
+    StubPeriodSolution::new(rate, full_periods, stub_fraction, cash_flow, future_value)
+}
+
+/// A record of the present value of a level cashflow plus a lump sum after a stub period. Created
+/// by calling [`present_value_with_stub_solution`].
+#[derive(Clone, Debug)]
+pub struct StubPeriodSolution {
+    rate: f64,
+    full_periods: u32,
+    stub_fraction: f64,
+    cash_flow: f64,
+    future_value: f64,
+    stub_discount_factor: f64,
+    present_value_at_stub_end: f64,
+    present_value: f64,
+    formula: String,
+    symbolic_formula: String,
+}
+
+impl StubPeriodSolution {
+    pub(crate) fn new(rate: f64, full_periods: u32, stub_fraction: f64, cash_flow: f64, future_value: f64) -> Self {
+        let discount_factor = 1.0 / (1.0 + rate).powf(full_periods as f64);
+        let annuity_factor = if rate == 0.0 { full_periods as f64 } else { (1.0 - discount_factor) / rate };
+        let present_value_at_stub_end = -(cash_flow * annuity_factor + future_value * discount_factor);
+        let stub_discount_factor = 1.0 / (1.0 + rate).powf(stub_fraction);
+        let present_value = present_value_at_stub_end * stub_discount_factor;
+        assert!(present_value.is_finite());
+        let formula = format!("{:.4} = -({:.4} * (1 - {:.6}^-{}) / {:.6} + {:.4} / {:.6}^{}) / {:.6}^{:.4}",
+                              present_value, cash_flow, 1.0 + rate, full_periods, rate, future_value, 1.0 + rate, full_periods, 1.0 + rate, stub_fraction);
+        let symbolic_formula = "pv = -(cash_flow * (1 - (1 + r)^-n) / r + fv / (1 + r)^n) / (1 + r)^stub_fraction".to_string();
+        Self {
+            rate,
+            full_periods,
+            stub_fraction,
+            cash_flow,
+            future_value,
+            stub_discount_factor,
+            present_value_at_stub_end,
+            present_value,
+            formula,
+            symbolic_formula,
+        }
+    }
+
+    /// Returns the periodic rate.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the number of regular periods after the stub.
+    pub fn full_periods(&self) -> u32 {
+        self.full_periods
+    }
+
+    /// Returns the length of the stub as a fraction of a regular period.
+    pub fn stub_fraction(&self) -> f64 {
+        self.stub_fraction
+    }
+
+    /// Returns the length of the stub plus the regular periods, which is when the last payment and
+    /// the lump sum are received.
+    pub fn total_periods(&self) -> f64 {
+        self.stub_fraction + self.full_periods as f64
+    }
+
+    /// Returns the cashflow received at the end of each regular period.
+    pub fn cash_flow(&self) -> f64 {
+        self.cash_flow
+    }
+
+    /// Returns the amount received at the end of the last regular period.
+    pub fn future_value(&self) -> f64 {
+        self.future_value
+    }
+
+    /// Returns the factor that discounts a value at the end of the stub back to the start, such as
+    /// 0.995 for half a period at 1%.
+    pub fn stub_discount_factor(&self) -> f64 {
+        self.stub_discount_factor
+    }
+
+    /// Returns the present value of the cashflows and the lump sum as of the end of the stub, which
+    /// is the present value there would be without a stub.
+    pub fn present_value_at_stub_end(&self) -> f64 {
+        self.present_value_at_stub_end
+    }
+
+    /// Returns the present value as of the start of the stub.
+    pub fn present_value(&self) -> f64 {
+        self.present_value
+    }
+
+    /// Returns a text version of the formula used to calculate the present value with the actual
+    /// values.
+    pub fn formula(&self) -> &str {
+        &self.formula
+    }
+
+    /// Returns a text version of the formula used to calculate the present value using variables
+    /// such as r for the rate.
+    pub fn symbolic_formula(&self) -> &str {
+        &self.symbolic_formula
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_present_value_with_stub_zero_stub() {
+        let (rate, periods, cash_flow, future_value) = (0.0075, 36, 420.0, 1_500.0);
+        let solution = present_value_with_stub_solution(rate, periods, 0.0, cash_flow, future_value);
+        assert_approx_equal!(1.0, solution.stub_discount_factor());
+        assert_approx_equal!(present_value_annuity(rate, periods, cash_flow, false) + present_value(rate, periods, future_value, false), solution.present_value());
+    }
+
+    #[test]
+    fn test_present_value_with_stub_shifts_discounting() {
+        // Each cashflow is discounted over its period plus the stub.
+        let (rate, periods, stub_fraction, cash_flow, future_value) = (0.02, 10, 0.5, 300.0, 1_000.0);
+        let expected = (1..=periods)
+            .map(|period| present_value_shifted(rate, cash_flow, period as f64 + stub_fraction, 0.0, false))
+            .sum::<f64>()
+            + present_value_shifted(rate, future_value, periods as f64 + stub_fraction, 0.0, false);
+        assert_approx_equal!(expected, present_value_with_stub(rate, periods, stub_fraction, cash_flow, future_value));
+        assert_approx_equal!(-4_000.0, present_value_with_stub(0.0, periods, stub_fraction, cash_flow, future_value));
+    }
+}
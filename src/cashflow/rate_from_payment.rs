@@ -0,0 +1,298 @@
+//! **Rate implied by a payment.** Given a loan amount, a number of periods, and the payment quoted
+//! for it, what periodic rate is being charged? This is the reverse of [`payment`] and is useful for
+//! lease quotes and other offers that state the payment but not the rate.
+//!
+//! There's no closed-form solution so the rate is found by bisection on the equation:
+//!
+//! 0 = present_value + payment * (1 + r * due) * (1 - (1 + r)<sup>-n</sup>) / r + future_value * (1 + r)<sup>-n</sup>
+//!
+//! As with [`payment`] the payment has the opposite sign to the present value.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // A $30,000 car lease for 60 months at $550 a month with a $5,000 residual value.
+//! let solution = rate_from_payment_solution(-550, 60, 30_000, -5_000, false);
+//! dbg!(&solution);
+//! ```
+
+use crate::*;
+
+/// Returns the **periodic rate implied by a payment** for a loan or lease with the given number of
+/// periods, present value, and future value.
+///
+/// Related functions:
+/// * To see the number of iterations used by the solver use [`rate_from_payment_solution`].
+/// * To calculate the payment from the rate use [`payment`].
+///
+/// See the [rate_from_payment](./index.html) module page for the equation that's solved.
+///
+/// # Arguments
+/// * `payment` - The payment made each period, with the opposite sign to `present_value`.
+/// * `periods` - The number of payments.
+/// * `present_value` - The amount of the loan.
+/// * `future_value` - The balance left at the end such as a residual value, zero or with the same
+/// sign as `payment`.
+/// * `due_at_beginning` - True if the payments are due at the beginning of each period rather than
+/// at the end.
+///
+/// # Panics
+/// The call will fail if `periods` is zero, if `present_value` or `payment` is zero, if `payment`
+/// or `future_value` has the same sign as `present_value`, if the payments are due at the beginning
+/// and the first payment alone repays the loan, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // A $30,000 car lease for 60 months at $550 a month with a $5,000 residual value works out to
+/// // about 0.72% a month.
+/// let rate = rate_from_payment(-550, 60, 30_000, -5_000, false);
+/// assert_rounded_6!(0.007169, rate);
+///
+/// // Payments that add up to exactly the amount of the loan mean there's no interest.
+/// assert_eq!(0.0, rate_from_payment(-500, 24, 12_000, 0, false));
+/// ```
+pub fn rate_from_payment<C, P, F>(payment: C, periods: u32, present_value: P, future_value: F, due_at_beginning: bool) -> f64
+    where
+        C: Into<f64> + Copy,
+        P: Into<f64> + Copy,
+        F: Into<f64> + Copy
+{
+    rate_from_payment_solution(payment, periods, present_value, future_value, due_at_beginning).rate()
+}
+
+/// Returns the **periodic rate implied by a payment** along with the inputs, the formula, and the
+/// work done by the solver.
+///
+/// Related functions:
+/// * To calculate only the rate as an `f64` use [`rate_from_payment`].
+/// * To control the precision of the solver use [`rate_from_payment_solution_with_config`].
+///
+/// # Arguments
+/// * `payment` - The payment made each period, with the opposite sign to `present_value`.
+/// * `periods` - The number of payments.
+/// * `present_value` - The amount of the loan.
+/// * `future_value` - The balance left at the end such as a residual value, zero or with the same
+/// sign as `payment`.
+/// * `due_at_beginning` - True if the payments are due at the beginning of each period rather than
+/// at the end.
+///
+/// # Panics
+/// The call will fail if `periods` is zero, if `present_value` or `payment` is zero, if `payment`
+/// or `future_value` has the same sign as `present_value`, if the payments are due at the beginning
+/// and the first payment alone repays the loan, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // The same lease with the payments made at the start of each month.
+/// let solution = rate_from_payment_solution(-550, 60, 30_000, -5_000, true);
+/// assert_rounded_6!(0.007377, solution.rate());
+/// assert!(solution.converged());
+///
+/// // Feeding the rate back in reproduces the payment.
+/// assert_rounded_4!(-550.0, payment(solution.rate(), 60, 30_000, -5_000, true));
+/// ```
+pub fn rate_from_payment_solution<C, P, F>(payment: C, periods: u32, present_value: P, future_value: F, due_at_beginning: bool) -> PaymentRateSolution
+    where
+        C: Into<f64> + Copy,
+        P: Into<f64> + Copy,
+        F: Into<f64> + Copy
+{
+    rate_from_payment_solution_with_config(payment, periods, present_value, future_value, due_at_beginning, SolverConfig::default())
+}
+
+/// Returns the **periodic rate implied by a payment** using the given tolerance and maximum number
+/// of iterations.
+///
+/// Related functions:
+/// * To solve to the full precision of an `f64` use [`rate_from_payment_solution`].
+///
+/// # Arguments
+/// * `payment` - The payment made each period, with the opposite sign to `present_value`.
+/// * `periods` - The number of payments.
+/// * `present_value` - The amount of the loan.
+/// * `future_value` - The balance left at the end such as a residual value, zero or with the same
+/// sign as `payment`.
+/// * `due_at_beginning` - True if the payments are due at the beginning of each period rather than
+/// at the end.
+/// * `config` - The tolerance and maximum number of iterations for the solver.
+///
+/// # Panics
+/// The call will fail if `periods` is zero, if `present_value` or `payment` is zero, if `payment`
+/// or `future_value` has the same sign as `present_value`, if the payments are due at the beginning
+/// and the first payment alone repays the loan, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let config = SolverConfig::new(0.000_001, 100);
+/// let solution = rate_from_payment_solution_with_config(-500, 48, 20_000, 0, false, config);
+/// assert!(solution.converged());
+/// assert_rounded_4!(0.0077, solution.rate());
+/// ```
+pub fn rate_from_payment_solution_with_config<C, P, F>(payment: C, periods: u32, present_value: P, future_value: F, due_at_beginning: bool, config: SolverConfig) -> PaymentRateSolution
+    where
+        C: Into<f64> + Copy,
+        P: Into<f64> + Copy,
+        F: Into<f64> + Copy
+{
+    let payment = payment.into();
+    let present_value = present_value.into();
+    let future_value = future_value.into();
+    assert!(payment.is_finite(), "The payment must be finite (not NaN or infinity)");
+    assert!(present_value.is_finite(), "The present value must be finite (not NaN or infinity)");
+    assert!(future_value.is_finite(), "The future value must be finite (not NaN or infinity)");
+    assert!(periods > 0, "The number of periods must be greater than zero.");
+    assert!(present_value != 0.0, "The present value must not be zero.");
+    assert!(payment != 0.0 && payment.signum() != present_value.signum(), "The payment must be nonzero and have the opposite sign to the present value.");
+    assert!(future_value * present_value <= 0.0, "The future value must be zero or have the opposite sign to the present value.");
+    if due_at_beginning {
+        assert!((present_value + payment) * present_value > 0.0, "The first payment must be less than the present value when payments are due at the beginning.");
+    }
+
+    let balance = |rate: f64| payment_rate_balance(rate, payment, periods, present_value, future_value, due_at_beginning);
+
+    let outcome = if balance(0.0) == 0.0 {
+        // The payments add up to exactly the amount owed so no interest is being charged.
+        solver::SolverOutcome { value: 0.0, iterations: 0, converged: true }
+    } else {
+        // The balance moves in the direction of the present value as the rate rises. Near -100% the
+        // payments and future value outweigh the present value and at high rates they don't, given
+        // the checks above, so there's always a rate to find.
+        solver::bracket_and_bisect(&config, |rate| balance(rate) * present_value < 0.0)
+            .expect("Unable to find the rate.")
+    };
+
+    PaymentRateSolution::new(payment, periods, present_value, future_value, due_at_beginning, outcome)
+}
+
+fn payment_rate_balance(rate: f64, payment: f64, periods: u32, present_value: f64, future_value: f64, due_at_beginning: bool) -> f64 {
+    if rate == 0.0 {
+        return present_value + payment * periods as f64 + future_value;
+    }
+    let discount = (1.0 + rate).powf(-(periods as f64));
+    let due_factor = if due_at_beginning { 1.0 + rate } else { 1.0 };
+    present_value + payment * due_factor * (1.0 - discount) / rate + future_value * discount
+}
+
+/// A record of the rate implied by a payment. Created by calling [`rate_from_payment_solution`].
+#[derive(Clone, Debug)]
+pub struct PaymentRateSolution {
+    payment: f64,
+    periods: u32,
+    present_value: f64,
+    future_value: f64,
+    due_at_beginning: bool,
+    rate: f64,
+    iterations: u32,
+    converged: bool,
+    formula: String,
+    symbolic_formula: String,
+}
+
+impl PaymentRateSolution {
+    pub(crate) fn new(payment: f64, periods: u32, present_value: f64, future_value: f64, due_at_beginning: bool, outcome: solver::SolverOutcome) -> Self {
+        let solver::SolverOutcome { value: rate, iterations, converged } = outcome;
+        assert!(rate.is_finite());
+        let due = if due_at_beginning { 1.0 } else { 0.0 };
+        let formula = format!("0 = {:.4} + {:.4} * (1 + {:.6} * {}) * (1 - {:.6}^-{}) / {:.6} + {:.4} * {:.6}^-{}",
+                              present_value, payment, rate, due, 1.0 + rate, periods, rate, future_value, 1.0 + rate, periods);
+        let symbolic_formula = "0 = pv + pmt * (1 + r * due) * (1 - (1 + r)^-n) / r + fv * (1 + r)^-n".to_string();
+        Self {
+            payment,
+            periods,
+            present_value,
+            future_value,
+            due_at_beginning,
+            rate,
+            iterations,
+            converged,
+            formula,
+            symbolic_formula,
+        }
+    }
+
+    /// Returns the payment made each period.
+    pub fn payment(&self) -> f64 {
+        self.payment
+    }
+
+    /// Returns the number of payments.
+    pub fn periods(&self) -> u32 {
+        self.periods
+    }
+
+    /// Returns the amount of the loan.
+    pub fn present_value(&self) -> f64 {
+        self.present_value
+    }
+
+    /// Returns the balance left at the end.
+    pub fn future_value(&self) -> f64 {
+        self.future_value
+    }
+
+    /// Returns true if the payments are due at the beginning of each period.
+    pub fn due_at_beginning(&self) -> bool {
+        self.due_at_beginning
+    }
+
+    /// Returns the periodic rate implied by the payment.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the number of iterations the solver used to find the rate. This is zero if the
+    /// payments add up to exactly the amount owed.
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    /// Returns true if the rate was found to within the solver's tolerance before it ran out of
+    /// iterations.
+    pub fn converged(&self) -> bool {
+        self.converged
+    }
+
+    /// Returns a text version of the equation that was solved with the actual values.
+    pub fn formula(&self) -> &str {
+        &self.formula
+    }
+
+    /// Returns a text version of the equation that was solved using variables such as r for the
+    /// rate.
+    pub fn symbolic_formula(&self) -> &str {
+        &self.symbolic_formula
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_from_payment_symmetry() {
+        for due_at_beginning in [false, true].iter() {
+            for (rate, periods, present_value, future_value) in [(0.005, 360, 250_000.0, 0.0), (0.012, 36, -8_000.0, 2_000.0), (-0.002, 24, 5_000.0, 0.0)].iter() {
+                let solution = payment_solution(*rate, *periods, *present_value, *future_value, *due_at_beginning);
+                let implied = rate_from_payment_solution(solution.payment(), *periods, *present_value, *future_value, *due_at_beginning);
+                assert!(implied.converged());
+                assert_rounded_8!(*rate, implied.rate());
+            }
+        }
+    }
+
+    #[test]
+    fn test_rate_from_payment_zero_rate() {
+        let solution = rate_from_payment_solution(-250, 40, 11_000, -1_000, true);
+        assert_eq!(0.0, solution.rate());
+        assert_eq!(0, solution.iterations());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rate_from_payment_same_sign() {
+        rate_from_payment(550, 60, 30_000, 0, false);
+    }
+}
@@ -0,0 +1,313 @@
+//! **Required rate for a savings goal.** Given a starting balance, a regular contribution, and a
+//! number of periods, what rate of return is needed to reach a target balance?
+//!
+//! The starting balance and contributions follow the cash flow sign convention used throughout the
+//! crate so they're negative (money paid into the investment) while the goal is positive (money
+//! that could be taken out at the end).
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // Starting with $100,000 and adding $1,000 at the end of every month, what monthly return is
+//! // needed to reach $1 million in 20 years?
+//! let solution = required_rate_for_goal_solution(-100_000, -1_000, 240, 1_000_000, false).unwrap();
+//! dbg!(&solution);
+//! ```
+
+use crate::*;
+
+// The highest periodic rate considered when searching for the required rate. If the goal can't be
+// reached at 1,000% per period it's treated as unreachable.
+const MAX_GOAL_RATE: f64 = 10.0;
+
+/// Returns the **periodic rate needed to reach a savings goal** from a starting balance and a
+/// regular contribution, or `None` if the goal is already met with a rate of zero or can't be
+/// reached at any reasonable rate.
+///
+/// Related functions:
+/// * To keep the inputs and the formula in a struct use [`required_rate_for_goal_solution`].
+///
+/// The rate is found by bisection on the equation:
+///
+/// future_value_goal = -(present_value * (1 + r)<sup>n</sup> + payment * (1 + r * due) * ((1 + r)<sup>n</sup> - 1) / r)
+///
+/// # Arguments
+/// * `present_value` - The starting balance, zero or negative since it's paid into the investment.
+/// * `payment` - The contribution made each period, zero or negative since it's paid into the
+/// investment.
+/// * `periods` - The number of periods until the goal should be reached.
+/// * `future_value_goal` - The target balance at the end of the last period, a positive amount.
+/// * `due_at_beginning` - True if the contributions are made at the beginning of each period
+/// rather than at the end.
+///
+/// # Panics
+/// The call will fail if `present_value` or `payment` are positive, if `future_value_goal` is not
+/// positive, if `periods` is zero, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // $100,000 plus $1,000 a month grows to $1 million in 20 years at about 0.625% a month, which
+/// // is roughly 7.5% a year.
+/// let rate = required_rate_for_goal(-100_000, -1_000, 240, 1_000_000, false).unwrap();
+/// assert_rounded_6!(0.006251, rate);
+///
+/// // Contributions of $5,000 a month reach the goal with no return at all.
+/// assert_eq!(None, required_rate_for_goal(-100_000, -5_000, 240, 1_000_000, false));
+/// ```
+pub fn required_rate_for_goal<P, C, F>(present_value: P, payment: C, periods: u32, future_value_goal: F, due_at_beginning: bool) -> Option<f64>
+    where
+        P: Into<f64> + Copy,
+        C: Into<f64> + Copy,
+        F: Into<f64> + Copy
+{
+    required_rate_for_goal_solution(present_value, payment, periods, future_value_goal, due_at_beginning).map(|solution| solution.rate())
+}
+
+/// Returns the **periodic rate needed to reach a savings goal** along with the inputs and the
+/// formula, or `None` if the goal is already met with a rate of zero or can't be reached at any
+/// reasonable rate.
+///
+/// Related functions:
+/// * To calculate only the rate as an `f64` use [`required_rate_for_goal`].
+///
+/// # Arguments
+/// * `present_value` - The starting balance, zero or negative since it's paid into the investment.
+/// * `payment` - The contribution made each period, zero or negative since it's paid into the
+/// investment.
+/// * `periods` - The number of periods until the goal should be reached.
+/// * `future_value_goal` - The target balance at the end of the last period, a positive amount.
+/// * `due_at_beginning` - True if the contributions are made at the beginning of each period
+/// rather than at the end.
+///
+/// # Panics
+/// The call will fail if `present_value` or `payment` are positive, if `future_value_goal` is not
+/// positive, if `periods` is zero, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // Contributions at the start of each month need a slightly lower return.
+/// let solution = required_rate_for_goal_solution(-100_000, -1_000, 240, 1_000_000, true).unwrap();
+/// assert_rounded_6!(0.006233, solution.rate());
+///
+/// // With no savings at all the goal can't be reached.
+/// assert!(required_rate_for_goal_solution(0, 0, 240, 1_000_000, true).is_none());
+/// ```
+pub fn required_rate_for_goal_solution<P, C, F>(present_value: P, payment: C, periods: u32, future_value_goal: F, due_at_beginning: bool) -> Option<GoalRateSolution>
+    where
+        P: Into<f64> + Copy,
+        C: Into<f64> + Copy,
+        F: Into<f64> + Copy
+{
+    required_rate_for_goal_solution_with_config(present_value, payment, periods, future_value_goal, due_at_beginning, SolverConfig::default())
+}
+
+/// Returns the **periodic rate needed to reach a savings goal** using the given tolerance and
+/// maximum number of iterations, or `None` if the goal is already met with a rate of zero or can't
+/// be reached at any reasonable rate.
+///
+/// Related functions:
+/// * To solve to the full precision of an `f64` use [`required_rate_for_goal_solution`].
+///
+/// # Arguments
+/// * `present_value` - The starting balance, zero or negative since it's paid into the investment.
+/// * `payment` - The contribution made each period, zero or negative since it's paid into the
+/// investment.
+/// * `periods` - The number of periods until the goal should be reached.
+/// * `future_value_goal` - The target balance at the end of the last period, a positive amount.
+/// * `due_at_beginning` - True if the contributions are made at the beginning of each period
+/// rather than at the end.
+/// * `config` - The tolerance and maximum number of iterations for the solver.
+///
+/// # Panics
+/// The call will fail if `present_value` or `payment` are positive, if `future_value_goal` is not
+/// positive, if `periods` is zero, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let config = SolverConfig::new(0.000_01, 100);
+/// let solution = required_rate_for_goal_solution_with_config(-100_000, -1_000, 240, 1_000_000, true, config).unwrap();
+/// assert!(solution.converged());
+/// assert_rounded_4!(0.0062, solution.rate());
+///
+/// // Stopping after a few iterations leaves the rate imprecise.
+/// let config = SolverConfig::new(0.000_01, 3);
+/// let solution = required_rate_for_goal_solution_with_config(-100_000, -1_000, 240, 1_000_000, true, config).unwrap();
+/// assert!(!solution.converged());
+/// assert_eq!(3, solution.iterations());
+/// ```
+pub fn required_rate_for_goal_solution_with_config<P, C, F>(present_value: P, payment: C, periods: u32, future_value_goal: F, due_at_beginning: bool, config: SolverConfig) -> Option<GoalRateSolution>
+    where
+        P: Into<f64> + Copy,
+        C: Into<f64> + Copy,
+        F: Into<f64> + Copy
+{
+    let present_value = present_value.into();
+    let payment = payment.into();
+    let future_value_goal = future_value_goal.into();
+    assert!(present_value.is_finite(), "The present value must be finite (not NaN or infinity)");
+    assert!(payment.is_finite(), "The payment must be finite (not NaN or infinity)");
+    assert!(future_value_goal.is_finite(), "The future value goal must be finite (not NaN or infinity)");
+    assert!(present_value <= 0.0 && payment <= 0.0, "The present value and payment must be zero or negative since they're paid into the investment.");
+    assert!(future_value_goal > 0.0, "The future value goal must be greater than zero.");
+    assert!(periods > 0, "The number of periods must be greater than zero.");
+
+    let balance = |rate: f64| goal_balance(rate, present_value, payment, periods, due_at_beginning);
+
+    // The balance only grows as the rate rises so if the goal is met with no return, or can't be
+    // met even at the highest rate considered, there's no rate to report.
+    if balance(0.0) >= future_value_goal || balance(MAX_GOAL_RATE) < future_value_goal {
+        return None;
+    }
+
+    let outcome = solver::bisect(0.0, MAX_GOAL_RATE, &config, |rate| balance(rate) < future_value_goal);
+    Some(GoalRateSolution::new(present_value, payment, periods, future_value_goal, due_at_beginning, outcome))
+}
+
+fn goal_balance(rate: f64, present_value: f64, payment: f64, periods: u32, due_at_beginning: bool) -> f64 {
+    if rate == 0.0 {
+        return -(present_value + payment * periods as f64);
+    }
+    let growth = (1.0 + rate).powf(periods as f64);
+    let due_factor = if due_at_beginning { 1.0 + rate } else { 1.0 };
+    -(present_value * growth + payment * due_factor * (growth - 1.0) / rate)
+}
+
+/// A record of the rate needed to reach a savings goal. Created by calling
+/// [`required_rate_for_goal_solution`].
+#[derive(Clone, Debug)]
+pub struct GoalRateSolution {
+    present_value: f64,
+    payment: f64,
+    periods: u32,
+    future_value_goal: f64,
+    due_at_beginning: bool,
+    rate: f64,
+    iterations: u32,
+    converged: bool,
+    formula: String,
+    symbolic_formula: String,
+}
+
+impl GoalRateSolution {
+    pub(crate) fn new(present_value: f64, payment: f64, periods: u32, future_value_goal: f64, due_at_beginning: bool, outcome: solver::SolverOutcome) -> Self {
+        let solver::SolverOutcome { value: rate, iterations, converged } = outcome;
+        assert!(rate.is_finite());
+        let due = if due_at_beginning { 1.0 } else { 0.0 };
+        let formula = format!("{:.4} = -({:.4} * {:.6}^{} + {:.4} * (1 + {:.6} * {}) * ({:.6}^{} - 1) / {:.6})",
+                              future_value_goal, present_value, 1.0 + rate, periods, payment, rate, due, 1.0 + rate, periods, rate);
+        let symbolic_formula = "fv = -(pv * (1 + r)^n + pmt * (1 + r * due) * ((1 + r)^n - 1) / r)".to_string();
+        Self {
+            present_value,
+            payment,
+            periods,
+            future_value_goal,
+            due_at_beginning,
+            rate,
+            iterations,
+            converged,
+            formula,
+            symbolic_formula,
+        }
+    }
+
+    /// Returns the starting balance.
+    pub fn present_value(&self) -> f64 {
+        self.present_value
+    }
+
+    /// Returns the contribution made each period.
+    pub fn payment(&self) -> f64 {
+        self.payment
+    }
+
+    /// Returns the number of periods until the goal is reached.
+    pub fn periods(&self) -> u32 {
+        self.periods
+    }
+
+    /// Returns the target balance.
+    pub fn future_value_goal(&self) -> f64 {
+        self.future_value_goal
+    }
+
+    /// Returns true if the contributions are made at the beginning of each period.
+    pub fn due_at_beginning(&self) -> bool {
+        self.due_at_beginning
+    }
+
+    /// Returns the periodic rate needed to reach the goal.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the number of iterations the solver used to find the rate.
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    /// Returns true if the rate was found to within the solver's tolerance before it ran out of
+    /// iterations.
+    pub fn converged(&self) -> bool {
+        self.converged
+    }
+
+    /// Returns a text version of the equation that was solved with the actual values.
+    pub fn formula(&self) -> &str {
+        &self.formula
+    }
+
+    /// Returns a text version of the equation that was solved using variables such as r for the
+    /// rate.
+    pub fn symbolic_formula(&self) -> &str {
+        &self.symbolic_formula
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_rate_for_goal_reproduces_goal() {
+        for due_at_beginning in [false, true].iter() {
+            let (present_value, payment, periods, goal) = (-25_000.0, -400.0, 120, 150_000.0);
+            let rate = required_rate_for_goal(present_value, payment, periods, goal, *due_at_beginning).unwrap();
+            let balance = future_value(rate, periods, present_value, false)
+                + future_value_annuity(rate, periods, payment, *due_at_beginning);
+            assert_approx_equal!(goal, balance);
+        }
+    }
+
+    #[test]
+    fn test_required_rate_for_goal_lump_sum_only() {
+        // With no contributions this is the same as solving for the rate of a single amount.
+        let rate = required_rate_for_goal(-10_000, 0, 15, 20_000, false).unwrap();
+        assert_approx_equal!(rate_solution(15, -10_000, 20_000, false).rate(), rate);
+    }
+
+    #[test]
+    fn test_required_rate_for_goal_unreachable() {
+        assert!(required_rate_for_goal(0, -1, 1, 1_000_000, false).is_none());
+    }
+
+    #[test]
+    fn test_required_rate_for_goal_with_config() {
+        let (present_value, payment, periods, goal) = (-5_000.0, -250.0, 60, 25_000.0);
+        let exact = required_rate_for_goal_solution(present_value, payment, periods, goal, false).unwrap();
+        assert!(exact.converged());
+        let config = SolverConfig::new(0.000_001, 200);
+        let solution = required_rate_for_goal_solution_with_config(present_value, payment, periods, goal, false, config).unwrap();
+        assert!(solution.converged());
+        assert!(solution.iterations() < exact.iterations());
+        assert!((solution.rate() - exact.rate()).abs() <= 0.000_001);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_required_rate_for_goal_positive_payment() {
+        required_rate_for_goal(-10_000, 500, 15, 20_000, false);
+    }
+}
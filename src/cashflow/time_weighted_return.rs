@@ -0,0 +1,213 @@
+//! **Time-weighted return.** The return of a portfolio over several sub-periods found by linking
+//! the return of each sub-period. A new sub-period starts at every external flow such as a
+//! contribution or withdrawal, so the size and timing of the flows don't affect the result. This
+//! makes it the standard measure of a manager's performance, while the
+//! [Modified Dietz](../modified_dietz/index.html) return and the internal rate of return are
+//! money-weighted and reflect the investor's timing as well.
+//!
+//! time_weighted_return = (1 + r<sub>1</sub>) * (1 + r<sub>2</sub>) * ... * (1 + r<sub>n</sub>) - 1
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // A quarter made up of three monthly returns.
+//! let solution = time_weighted_return_solution(&[0.02, -0.01, 0.03]);
+//! dbg!(&solution);
+//! ```
+
+/// Returns the **time-weighted return** found by linking the returns of several sub-periods.
+///
+/// Related functions:
+/// * To see the growth factor of each sub-period use [`time_weighted_return_solution`].
+/// * To find the sub-period returns from portfolio values and flows use
+/// [`sub_period_returns_from_flows`].
+///
+/// See the [time_weighted_return](./index.html) module page for the formula.
+///
+/// # Arguments
+/// * `sub_period_returns` - The return of each sub-period, such as 0.02 for 2%.
+///
+/// # Panics
+/// The call will fail if any return is less than -1.0 or is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let twr = time_weighted_return(&[0.02, -0.01, 0.03]);
+/// assert_rounded_6!(0.040094, twr);
+///
+/// // With no sub-periods there's no growth.
+/// assert_eq!(0.0, time_weighted_return(&[]));
+/// ```
+pub fn time_weighted_return(sub_period_returns: &[f64]) -> f64 {
+    time_weighted_return_solution(sub_period_returns).time_weighted_return()
+}
+
+/// Returns the **time-weighted return** found by linking the returns of several sub-periods along
+/// with the growth factor of each sub-period and the cumulative growth.
+///
+/// Related functions:
+/// * To calculate only the return as an `f64` use [`time_weighted_return`].
+///
+/// # Arguments
+/// * `sub_period_returns` - The return of each sub-period, such as 0.02 for 2%.
+///
+/// # Panics
+/// The call will fail if any return is less than -1.0 or is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // A $100,000 portfolio gains 10%, then receives a $1,000,000 contribution just before it loses
+/// // 5%.
+/// let observations = [(100_000.0, 0.0, 110_000.0), (110_000.0, 1_000_000.0, 1_054_500.0)];
+/// let returns = sub_period_returns_from_flows(&observations);
+/// let solution = time_weighted_return_solution(&returns);
+/// assert_rounded_6!(0.045, solution.time_weighted_return());
+/// assert_rounded_6!(1.1, solution.growth_factors()[0]);
+/// assert_rounded_6!(1.045, solution.cumulative_growth_factors()[1]);
+///
+/// // The internal rate of return is dragged down by the loss on the large contribution.
+/// let irr = all_irrs(&[-100_000.0, -1_000_000.0, 1_054_500.0], (-0.5, 1.0), 100)[0];
+/// assert_rounded_6!(-0.038037, irr);
+/// ```
+pub fn time_weighted_return_solution(sub_period_returns: &[f64]) -> TwrSolution {
+    for sub_period_return in sub_period_returns.iter() {
+        assert!(sub_period_return.is_finite(), "The returns must be finite (not NaN or infinity)");
+        assert!(*sub_period_return >= -1.0, "The returns must be greater than or equal to -1.0 (-100%).");
+    }
+    TwrSolution::new(sub_period_returns.to_vec())
+}
+
+/// Returns the return of each sub-period from the portfolio's values and external flows.
+///
+/// Each observation is one sub-period given as the value before the flow at the start of the
+/// sub-period, the flow itself, and the value at the end of the sub-period. The flow is invested
+/// for the whole sub-period so the return is:
+///
+/// return = value_after / (value_before_flow + flow) - 1
+///
+/// Contributions are positive and withdrawals are negative. Pass the result to
+/// [`time_weighted_return`] to link the returns.
+///
+/// # Arguments
+/// * `observations` - Tuples of the value before the flow, the flow, and the value at the end of
+/// each sub-period.
+///
+/// # Panics
+/// The call will fail if any value is not finite or if the value after any flow is not positive.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // A $5,000 withdrawal at the start of the second sub-period.
+/// let returns = sub_period_returns_from_flows(&[(50_000.0, 0.0, 52_000.0), (52_000.0, -5_000.0, 48_880.0)]);
+/// assert_rounded_6!(0.04, returns[0]);
+/// assert_rounded_6!(0.04, returns[1]);
+/// ```
+pub fn sub_period_returns_from_flows(observations: &[(f64, f64, f64)]) -> Vec<f64> {
+    observations.iter()
+        .map(|(value_before_flow, flow, value_after)| {
+            assert!(value_before_flow.is_finite() && flow.is_finite() && value_after.is_finite(), "The values and flows must be finite (not NaN or infinity)");
+            let invested = value_before_flow + flow;
+            assert!(invested > 0.0, "The value after each flow must be greater than zero.");
+            value_after / invested - 1.0
+        })
+        .collect()
+}
+
+/// A record of a time-weighted return calculation. Created by calling
+/// [`time_weighted_return_solution`].
+#[derive(Clone, Debug)]
+pub struct TwrSolution {
+    sub_period_returns: Vec<f64>,
+    growth_factors: Vec<f64>,
+    cumulative_growth_factors: Vec<f64>,
+    time_weighted_return: f64,
+    formula: String,
+    symbolic_formula: String,
+}
+
+impl TwrSolution {
+    pub(crate) fn new(sub_period_returns: Vec<f64>) -> Self {
+        let growth_factors = sub_period_returns.iter().map(|sub_period_return| 1.0 + sub_period_return).collect::<Vec<f64>>();
+        let mut cumulative_growth_factor = 1.0;
+        let cumulative_growth_factors = growth_factors.iter()
+            .map(|growth_factor| {
+                cumulative_growth_factor *= growth_factor;
+                cumulative_growth_factor
+            })
+            .collect::<Vec<f64>>();
+        let time_weighted_return = cumulative_growth_factor - 1.0;
+        assert!(time_weighted_return.is_finite());
+        let formula = format!("{:.6} = {} - 1", time_weighted_return,
+                              growth_factors.iter().map(|growth_factor| format!("{:.6}", growth_factor)).collect::<Vec<_>>().join(" * "));
+        let symbolic_formula = "twr = product(1 + r) - 1".to_string();
+        Self {
+            sub_period_returns,
+            growth_factors,
+            cumulative_growth_factors,
+            time_weighted_return,
+            formula,
+            symbolic_formula,
+        }
+    }
+
+    /// Returns the return of each sub-period.
+    pub fn sub_period_returns(&self) -> &[f64] {
+        &self.sub_period_returns
+    }
+
+    /// Returns the number of sub-periods.
+    pub fn periods(&self) -> u32 {
+        self.sub_period_returns.len() as u32
+    }
+
+    /// Returns one plus the return of each sub-period.
+    pub fn growth_factors(&self) -> &[f64] {
+        &self.growth_factors
+    }
+
+    /// Returns the growth of one dollar from the start through the end of each sub-period.
+    pub fn cumulative_growth_factors(&self) -> &[f64] {
+        &self.cumulative_growth_factors
+    }
+
+    /// Returns the linked return over all of the sub-periods.
+    pub fn time_weighted_return(&self) -> f64 {
+        self.time_weighted_return
+    }
+
+    /// Returns a text version of the formula used to calculate the return with the actual values.
+    pub fn formula(&self) -> &str {
+        &self.formula
+    }
+
+    /// Returns a text version of the formula used to calculate the return using variables such as
+    /// r for the return of each sub-period.
+    pub fn symbolic_formula(&self) -> &str {
+        &self.symbolic_formula
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn test_time_weighted_return_ignores_flow_size() {
+        // The same sub-period returns with a small and a large contribution give the same result.
+        let small = sub_period_returns_from_flows(&[(10_000.0, 0.0, 10_800.0), (10_800.0, 200.0, 10_450.0)]);
+        let large = sub_period_returns_from_flows(&[(10_000.0, 0.0, 10_800.0), (10_800.0, 89_200.0, 95_000.0)]);
+        assert_approx_equal!(time_weighted_return(&small), time_weighted_return(&large));
+        assert_approx_equal!(1.08 * 0.95 - 1.0, time_weighted_return(&large));
+    }
+
+    #[test]
+    fn test_time_weighted_return_total_loss() {
+        let solution = time_weighted_return_solution(&[0.25, -1.0, 0.5]);
+        assert_approx_equal!(-1.0, solution.time_weighted_return());
+        assert_eq!(3, solution.periods());
+    }
+}
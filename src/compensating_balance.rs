@@ -0,0 +1,171 @@
+//! **Compensating balances.** A bank lending to a business may require it to keep part of the loan
+//! on deposit in an account that earns no interest. Interest is charged on the whole loan but only
+//! the rest of it can be used, so the effective rate is higher than the stated rate:
+//!
+//! effective_rate = stated_rate / (1 - compensating_balance_fraction)
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // A 10% loan where 20% of the amount borrowed has to stay on deposit.
+//! let solution = effective_rate_with_compensating_balance_solution(0.10, 0.20);
+//! dbg!(&solution);
+//! ```
+
+/// Returns the **effective interest rate on a loan that requires a compensating balance**.
+///
+/// Related functions:
+/// * To see the usable part of the loan and the formula use
+/// [`effective_rate_with_compensating_balance_solution`].
+///
+/// The formula is:
+///
+/// effective_rate = stated_rate / (1 - compensating_balance_fraction)
+///
+/// # Arguments
+/// * `stated_rate` - The interest rate charged on the full amount of the loan, expressed as a
+/// floating point number. For instance 0.10 would mean 10%.
+/// * `compensating_balance_fraction` - The part of the loan that must be kept on deposit without
+/// earning interest, such as 0.20 for 20%.
+///
+/// # Panics
+/// The call will fail if `compensating_balance_fraction` is negative or greater than or equal to
+/// 1.0, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // A 10% loan with a 20% compensating balance costs 12.5% on the money that can be used.
+/// assert_rounded_6!(0.125, effective_rate_with_compensating_balance(0.10, 0.20));
+///
+/// // With no compensating balance it's the stated rate.
+/// assert_rounded_6!(0.10, effective_rate_with_compensating_balance(0.10, 0.0));
+/// ```
+pub fn effective_rate_with_compensating_balance(stated_rate: f64, compensating_balance_fraction: f64) -> f64 {
+    effective_rate_with_compensating_balance_solution(stated_rate, compensating_balance_fraction).effective_rate()
+}
+
+/// Returns the **effective interest rate on a loan that requires a compensating balance** along
+/// with the usable part of the loan and the formula.
+///
+/// Related functions:
+/// * To calculate only the effective rate as an `f64` use
+/// [`effective_rate_with_compensating_balance`].
+///
+/// # Arguments
+/// * `stated_rate` - The interest rate charged on the full amount of the loan, expressed as a
+/// floating point number. For instance 0.10 would mean 10%.
+/// * `compensating_balance_fraction` - The part of the loan that must be kept on deposit without
+/// earning interest, such as 0.20 for 20%.
+///
+/// # Panics
+/// The call will fail if `compensating_balance_fraction` is negative or greater than or equal to
+/// 1.0, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = effective_rate_with_compensating_balance_solution(0.10, 0.20);
+/// assert_rounded_6!(0.125, solution.effective_rate());
+/// assert_rounded_6!(0.80, solution.usable_funds_fraction());
+/// assert_eq!("0.125000 = 0.100000 / (1 - 0.200000)", solution.formula());
+///
+/// // To have $400,000 to use the business has to borrow $500,000.
+/// assert_rounded_4!(500_000.0, solution.loan_amount_for_usable_funds(400_000));
+/// ```
+pub fn effective_rate_with_compensating_balance_solution(stated_rate: f64, compensating_balance_fraction: f64) -> CompensatingBalanceSolution {
+    assert!(stated_rate.is_finite(), "The stated rate must be finite (not NaN or infinity)");
+    assert!(compensating_balance_fraction.is_finite(), "The compensating balance fraction must be finite (not NaN or infinity)");
+    assert!(compensating_balance_fraction >= 0.0, "The compensating balance fraction must be zero or positive.");
+    assert!(compensating_balance_fraction < 1.0, "The compensating balance fraction must be less than 1.0 (100%) or none of the loan could be used.");
+    CompensatingBalanceSolution::new(stated_rate, compensating_balance_fraction)
+}
+
+/// A record of the effective rate on a loan with a compensating balance. Created by calling
+/// [`effective_rate_with_compensating_balance_solution`].
+#[derive(Clone, Debug)]
+pub struct CompensatingBalanceSolution {
+    stated_rate: f64,
+    compensating_balance_fraction: f64,
+    effective_rate: f64,
+    formula: String,
+    symbolic_formula: String,
+}
+
+impl CompensatingBalanceSolution {
+    pub(crate) fn new(stated_rate: f64, compensating_balance_fraction: f64) -> Self {
+        let effective_rate = stated_rate / (1.0 - compensating_balance_fraction);
+        assert!(effective_rate.is_finite());
+        let formula = format!("{:.6} = {:.6} / (1 - {:.6})", effective_rate, stated_rate, compensating_balance_fraction);
+        let symbolic_formula = "effective_rate = stated_rate / (1 - compensating_balance_fraction)".to_string();
+        Self {
+            stated_rate,
+            compensating_balance_fraction,
+            effective_rate,
+            formula,
+            symbolic_formula,
+        }
+    }
+
+    /// Returns the interest rate charged on the full amount of the loan.
+    pub fn stated_rate(&self) -> f64 {
+        self.stated_rate
+    }
+
+    /// Returns the part of the loan that must be kept on deposit.
+    pub fn compensating_balance_fraction(&self) -> f64 {
+        self.compensating_balance_fraction
+    }
+
+    /// Returns the part of the loan that can be used, such as 0.80 with a 20% compensating
+    /// balance.
+    pub fn usable_funds_fraction(&self) -> f64 {
+        1.0 - self.compensating_balance_fraction
+    }
+
+    /// Returns the interest rate on the usable part of the loan.
+    pub fn effective_rate(&self) -> f64 {
+        self.effective_rate
+    }
+
+    /// Returns the amount that has to be borrowed to have the given amount to use after setting
+    /// aside the compensating balance.
+    pub fn loan_amount_for_usable_funds<T>(&self, usable_funds: T) -> f64
+        where T: Into<f64> + Copy
+    {
+        usable_funds.into() / self.usable_funds_fraction()
+    }
+
+    /// Returns a text version of the formula used in the calculation with the actual values.
+    pub fn formula(&self) -> &str {
+        &self.formula
+    }
+
+    /// Returns a text version of the formula used in the calculation using variables such as
+    /// stated_rate.
+    pub fn symbolic_formula(&self) -> &str {
+        &self.symbolic_formula
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn test_effective_rate_with_compensating_balance_interest_on_usable_funds() {
+        // The interest on the full loan as a rate on the usable funds.
+        let (stated_rate, fraction, loan) = (0.085, 0.15, 250_000.0);
+        let solution = effective_rate_with_compensating_balance_solution(stated_rate, fraction);
+        let usable_funds = loan * solution.usable_funds_fraction();
+        assert_approx_equal!(loan * stated_rate / usable_funds, solution.effective_rate());
+        assert_approx_equal!(loan, solution.loan_amount_for_usable_funds(usable_funds));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_effective_rate_with_compensating_balance_full_balance() {
+        effective_rate_with_compensating_balance(0.10, 1.0);
+    }
+}
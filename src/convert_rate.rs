@@ -720,12 +720,63 @@ pub fn convert_epr_to_apr_solution(epr: f64, compounding_periods_in_year: u32) -
     self::epr(epr, compounding_periods_in_year)
 }
 
+/// Expand annual rates into monthly rates. Each effective annual rate (EAR) is converted to the
+/// equivalent monthly rate and repeated 12 times, so the result has 12 entries for every annual
+/// rate and can be passed to schedule functions such as [`future_value_schedule`](./fn.future_value_schedule.html)
+/// to model month by month.
+///
+/// The formula for each monthly rate is:
+///
+/// monthly_rate = (1 + annual_rate)<sup>(1 / 12)</sup> - 1
+///
+/// # Arguments
+/// * `annual_rates` - The effective annual rate for each year, expressed as floating point numbers.
+/// For instance 0.05 would mean 5%.
+///
+/// # Panics
+/// * `annual_rates` - each rate must be greater than -1.0.
+///
+/// # Example
+/// Turn a three-year forecast of annual rates into 36 monthly rates.
+/// ```
+/// use finance_solution::*;
+/// let monthly_rates = rates_annual_to_monthly(&[0.05, 0.06, 0.04]);
+/// assert_eq!(36, monthly_rates.len());
+///
+/// // Compounding the monthly rates over the first year reproduces the first annual rate.
+/// let growth: f64 = monthly_rates[..12].iter().map(|rate| 1.0 + rate).product();
+/// assert_approx_equal!(1.05, growth);
+///
+/// // The monthly rates can be used to find a future value month by month.
+/// let future_value = future_value_schedule(&monthly_rates, -1_000);
+/// assert_rounded_4!(1_157.5200, future_value);
+/// ```
+pub fn rates_annual_to_monthly(annual_rates: &[f64]) -> Vec<f64> {
+    const MONTHS_IN_YEAR: u32 = 12;
+    annual_rates.iter()
+        .flat_map(|annual_rate| vec![convert_ear_to_epr(*annual_rate, MONTHS_IN_YEAR); MONTHS_IN_YEAR as usize])
+        .collect()
+}
+
 
 
 #[cfg(test)]
 mod tests {
     use super::*;
     
+    #[test]
+    fn test_rates_annual_to_monthly() {
+        let annual_rates = [0.05, -0.02, 0.10];
+        let monthly_rates = rates_annual_to_monthly(&annual_rates);
+        assert_eq!(annual_rates.len() * 12, monthly_rates.len());
+        for (year, annual_rate) in annual_rates.iter().enumerate() {
+            let year_rates = &monthly_rates[year * 12..(year + 1) * 12];
+            let compounded = year_rates.iter().fold(1.0, |acc, rate| acc * (1.0 + rate)) - 1.0;
+            assert_approx_equal!(*annual_rate, compounded);
+        }
+        assert!(rates_annual_to_monthly(&[]).is_empty());
+    }
+
     #[test]
     fn test_convert_rate_apr_symmetry() {
         let apr_rates = vec!(0.034, -0.034, 0.00283333333, -0.00283333333, 0.0345348693603, 0.0, -0.0, 1.0, 2.1, 0.00001);
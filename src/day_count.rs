@@ -0,0 +1,197 @@
+//! **Day count conventions.** Functions that work with calendar dates need to turn the time between
+//! two dates into a fraction of a year, and there are several conventions for doing so.
+//!
+//! The date-aware functions such as [`future_value_dated`](./fn.future_value_dated.html) take a
+//! [`DayCountBasis`] and use [`year_fraction`] to measure the time between dates.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! use chrono::NaiveDate;
+//! let from = NaiveDate::from_ymd_opt(2021, 2, 28).unwrap();
+//! let to = NaiveDate::from_ymd_opt(2021, 3, 31).unwrap();
+//!
+//! // There are 31 actual days between the dates but 30/360 counts 33.
+//! assert_rounded_6!(31.0 / 365.0, year_fraction(from, to, DayCountBasis::Actual365));
+//! assert_rounded_6!(33.0 / 360.0, year_fraction(from, to, DayCountBasis::Thirty360));
+//! ```
+
+use chrono::{Datelike, NaiveDate};
+use std::fmt;
+
+/// The convention used to count the days between two dates and convert them to a fraction of a
+/// year.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum DayCountBasis {
+    /// The actual number of days divided by 360, common for money market instruments.
+    Actual360,
+    /// The actual number of days divided by 365.
+    #[default]
+    Actual365,
+    /// The actual number of days where the days falling in a leap year are divided by 366 and the
+    /// rest are divided by 365, as in the ISDA convention.
+    ActualActual,
+    /// Every month is counted as 30 days and the year as 360 days, with the standard adjustments
+    /// for dates on the 31st of the month. Also known as the bond basis.
+    Thirty360,
+}
+
+impl DayCountBasis {
+    /// Returns the number of days between two dates as counted by this convention. The result is
+    /// negative if `to` is before `from`.
+    pub fn day_count(&self, from: NaiveDate, to: NaiveDate) -> i64 {
+        match self {
+            DayCountBasis::Thirty360 => thirty_360_days(from, to),
+            _ => to.signed_duration_since(from).num_days(),
+        }
+    }
+
+    // The length of a year in days for calculations that work with a plain number of days rather
+    // than two dates. Actual/Actual has no fixed length so it's treated as 365 days.
+    pub(crate) fn days_in_year(&self) -> f64 {
+        match self {
+            DayCountBasis::Actual360 | DayCountBasis::Thirty360 => 360.0,
+            DayCountBasis::Actual365 | DayCountBasis::ActualActual => 365.0,
+        }
+    }
+}
+
+impl fmt::Display for DayCountBasis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DayCountBasis::Actual360 => write!(f, "Actual/360"),
+            DayCountBasis::Actual365 => write!(f, "Actual/365"),
+            DayCountBasis::ActualActual => write!(f, "Actual/Actual"),
+            DayCountBasis::Thirty360 => write!(f, "30/360"),
+        }
+    }
+}
+
+/// Returns the time between two dates as a fraction of a year using the given day count convention.
+/// The result is negative if `to` is before `from`.
+///
+/// # Arguments
+/// * `from` - The starting date.
+/// * `to` - The ending date.
+/// * `basis` - The day count convention.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// use chrono::NaiveDate;
+/// let from = NaiveDate::from_ymd_opt(2020, 7, 1).unwrap();
+/// let to = NaiveDate::from_ymd_opt(2021, 7, 1).unwrap();
+///
+/// // 365 actual days.
+/// assert_rounded_6!(365.0 / 360.0, year_fraction(from, to, DayCountBasis::Actual360));
+/// assert_rounded_6!(1.0, year_fraction(from, to, DayCountBasis::Actual365));
+/// assert_rounded_6!(1.0, year_fraction(from, to, DayCountBasis::Thirty360));
+///
+/// // 184 of the days fall in 2020, a leap year, and 181 in 2021.
+/// assert_rounded_6!(184.0 / 366.0 + 181.0 / 365.0, year_fraction(from, to, DayCountBasis::ActualActual));
+/// ```
+pub fn year_fraction(from: NaiveDate, to: NaiveDate, basis: DayCountBasis) -> f64 {
+    if to < from {
+        return -year_fraction(to, from, basis);
+    }
+    match basis {
+        DayCountBasis::Actual360 => basis.day_count(from, to) as f64 / 360.0,
+        DayCountBasis::Actual365 => basis.day_count(from, to) as f64 / 365.0,
+        DayCountBasis::Thirty360 => basis.day_count(from, to) as f64 / 360.0,
+        DayCountBasis::ActualActual => {
+            let mut fraction = 0.0;
+            for year in from.year()..=to.year() {
+                let year_start = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
+                let next_year_start = NaiveDate::from_ymd_opt(year + 1, 1, 1).unwrap();
+                let start = if from > year_start { from } else { year_start };
+                let end = if to < next_year_start { to } else { next_year_start };
+                let days_in_year = next_year_start.signed_duration_since(year_start).num_days();
+                fraction += end.signed_duration_since(start).num_days() as f64 / days_in_year as f64;
+            }
+            fraction
+        },
+    }
+}
+
+// Moves a date by a number of months, which may be negative. If the day doesn't exist in the new
+// month it's moved back to the last day of that month, so January 31 plus one month is the end of
+// February. If `end_of_month` is true and the date is the last day of its month the result is also
+// the last day of its month, as with coupon dates for a bond that matures at the end of a month.
+pub(crate) fn add_months(date: NaiveDate, months: i32, end_of_month: bool) -> NaiveDate {
+    let month_index = date.year() * 12 + (date.month() - 1) as i32 + months;
+    let year = month_index.div_euclid(12);
+    let month = month_index.rem_euclid(12) as u32 + 1;
+    let last_day = days_in_month(year, month);
+    let day = if end_of_month && date.day() == days_in_month(date.year(), date.month()) {
+        last_day
+    } else {
+        date.day().min(last_day)
+    };
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    let first = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+    let next_first = NaiveDate::from_ymd_opt(next_year, next_month, 1).unwrap();
+    next_first.signed_duration_since(first).num_days() as u32
+}
+
+fn thirty_360_days(from: NaiveDate, to: NaiveDate) -> i64 {
+    let mut d1 = from.day() as i64;
+    let mut d2 = to.day() as i64;
+    if d1 == 31 {
+        d1 = 30;
+    }
+    if d2 == 31 && d1 == 30 {
+        d2 = 30;
+    }
+    360 * (to.year() - from.year()) as i64 + 30 * (to.month() as i64 - from.month() as i64) + (d2 - d1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_thirty_360_end_of_month() {
+        let days = |from, to| DayCountBasis::Thirty360.day_count(from, to);
+        assert_eq!(33, days(date(2021, 2, 28), date(2021, 3, 31)));
+        assert_eq!(31, days(date(2021, 1, 31), date(2021, 3, 1)));
+        assert_eq!(30, days(date(2021, 3, 31), date(2021, 4, 30)));
+        assert_eq!(60, days(date(2021, 1, 31), date(2021, 3, 31)));
+        assert_eq!(16, days(date(2021, 3, 15), date(2021, 3, 31)));
+        assert_eq!(359, days(date(2020, 2, 29), date(2021, 2, 28)));
+    }
+
+    #[test]
+    fn test_add_months() {
+        assert_eq!(date(2021, 2, 28), add_months(date(2021, 1, 31), 1, false));
+        assert_eq!(date(2020, 2, 29), add_months(date(2020, 8, 31), -6, false));
+        assert_eq!(date(2019, 11, 15), add_months(date(2022, 5, 15), -30, false));
+        // With the end of month rule the last day of a month stays at the end of the month.
+        assert_eq!(date(2021, 8, 31), add_months(date(2021, 2, 28), 6, true));
+        assert_eq!(date(2021, 8, 28), add_months(date(2021, 2, 28), 6, false));
+    }
+
+    #[test]
+    fn test_year_fraction_reversed() {
+        for basis in [DayCountBasis::Actual360, DayCountBasis::Actual365, DayCountBasis::ActualActual, DayCountBasis::Thirty360].iter() {
+            let forward = year_fraction(date(2019, 11, 15), date(2022, 5, 31), *basis);
+            let backward = year_fraction(date(2022, 5, 31), date(2019, 11, 15), *basis);
+            assert!(forward > 2.0);
+            assert_eq!(forward, -backward);
+        }
+    }
+
+    #[test]
+    fn test_year_fraction_actual_actual_whole_years() {
+        assert_approx_equal!(4.0, year_fraction(date(2019, 1, 1), date(2023, 1, 1), DayCountBasis::ActualActual));
+        assert_approx_equal!(0.0, year_fraction(date(2019, 6, 1), date(2019, 6, 1), DayCountBasis::ActualActual));
+    }
+}
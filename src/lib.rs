@@ -78,14 +78,38 @@ use itertools::Itertools;
 extern crate float_cmp;
 pub extern crate num_format;
 
+pub mod break_even;
+#[doc(inline)]
+pub use break_even::*;
+
+pub mod compensating_balance;
+#[doc(inline)]
+pub use compensating_balance::*;
+
 pub mod convert_rate;
 #[doc(inline)]
 pub use convert_rate::*;
 
+pub mod day_count;
+#[doc(inline)]
+pub use day_count::*;
+
 pub mod round;
 #[doc(inline)]
 pub use round::*;
 
+pub mod solver;
+#[doc(inline)]
+pub use solver::*;
+
+pub mod tax_return;
+#[doc(inline)]
+pub use tax_return::*;
+
+pub mod bond;
+#[doc(inline)]
+pub use bond::*;
+
 pub mod cashflow;
 #[doc(inline)]
 pub use cashflow::*;
@@ -273,6 +297,96 @@ pub(crate) fn format_rate<T>(val: T) -> String
     format_float_locale_opt(val, None, Some(6))
 }
 
+/// Formats a rate as a percentage with a given number of decimal places, so 0.045 becomes
+/// "4.5000%" with a precision of 4. Like the rest of the crate's formatting this uses underscores as
+/// the thousands separator and "." as the decimal separator. To use the separators of a locale
+/// call [`format_rate_percent_locale`].
+///
+/// # Examples
+/// ```
+/// assert_eq!("4.5000%", finance_solution::format_rate_percent(0.045, 4));
+/// assert_eq!("-12.68%", finance_solution::format_rate_percent(-0.12682, 2));
+/// ```
+pub fn format_rate_percent(rate: f64, precision: usize) -> String {
+    format_rate_percent_locale_opt(rate, None, Some(precision))
+}
+
+/// Formats a rate as a percentage with a given number of decimal places using the thousands and
+/// decimal separators of the given locale, so 0.045 becomes "4,5000%" in French with a precision
+/// of 4.
+///
+/// # Examples
+/// ```
+/// use finance_solution::*;
+/// use num_format::Locale;
+///
+/// assert_eq!("4,5000%", format_rate_percent_locale(0.045, &Locale::fr, 4));
+/// assert_eq!("1.250,0%", format_rate_percent_locale(12.5, &Locale::de, 1));
+/// assert_eq!("-12.68%", format_rate_percent_locale(-0.12682, &Locale::en, 2));
+/// ```
+pub fn format_rate_percent_locale(rate: f64, locale: &Locale, precision: usize) -> String {
+    format_rate_percent_locale_opt(rate, Some(locale), Some(precision))
+}
+
+/// Controls how numbers are written in [`format_float_styled`] and in tables printed with methods
+/// such as [`TvmSeries::print_table_styled`]. This is an alternative to passing a locale for when
+/// the output is meant to be read by another program rather than a person.
+///
+/// The default matches the crate's usual formatting with underscores between groups of thousands
+/// and four decimal places.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NumberStyle {
+    /// True to separate groups of thousands with underscores, as in "10_000.00", or false to write
+    /// the digits together, as in "10000.00".
+    pub grouping: bool,
+    /// The number of decimal places for money amounts. Rates are shown with at least six.
+    pub decimals: usize,
+}
+
+impl NumberStyle {
+    /// Creates a style with the given grouping and number of decimal places.
+    pub fn new(grouping: bool, decimals: usize) -> Self {
+        Self {
+            grouping,
+            decimals,
+        }
+    }
+}
+
+impl Default for NumberStyle {
+    fn default() -> Self {
+        Self::new(true, 4)
+    }
+}
+
+/// Formats a number with the grouping and decimal places of the given style, using "." as the
+/// decimal separator.
+///
+/// # Examples
+/// ```
+/// use finance_solution::*;
+///
+/// assert_eq!("10_000.00", format_float_styled(10_000.0, &NumberStyle::new(true, 2)));
+/// assert_eq!("10000.00", format_float_styled(10_000.0, &NumberStyle::new(false, 2)));
+/// assert_eq!("-1234568", format_float_styled(-1_234_567.8, &NumberStyle::new(false, 0)));
+/// ```
+pub fn format_float_styled(val: f64, style: &NumberStyle) -> String {
+    let formatted = format_float_locale_opt(val, None, Some(style.decimals));
+    if style.grouping {
+        formatted
+    } else {
+        formatted.replace("_", "")
+    }
+}
+
+pub(crate) fn format_rate_percent_locale_opt(rate: f64, locale: Option<&Locale>, precision: Option<usize>) -> String {
+    if rate.is_finite() {
+        format!("{}%", format_float_locale_opt(rate * 100.0, locale, precision))
+    } else {
+        format!("{:?}", rate)
+    }
+}
+
 pub(crate) fn format_float_locale_opt<T>(val: T, locale: Option<&Locale>, precision: Option<usize>) -> String
     where T: Into<f64>
 {
@@ -293,26 +407,28 @@ pub(crate) fn format_float_locale_opt<T>(val: T, locale: Option<&Locale>, precis
     }
 }
 
-pub(crate) fn print_table_locale_opt(columns: &[(String, String, bool)], mut data: Vec<Vec<String>>, locale: Option<&num_format::Locale>, precision: Option<usize>) {
-    if columns.is_empty() || data.is_empty() {
-        return;
-    }
-
-    let column_separator = "  ";
+// The text shown in every column of a table row that stands for rows that were left out, such as
+// the periods skipped by TvmSolution::series_capped.
+pub(crate) const TABLE_GAP_MARKER: &str = "...";
 
-    let column_count = data[0].len();
+// Formats the values in each visible column according to the column's type, such as "f" for a
+// money amount or "r" for a rate.
+fn format_table_data(columns: &[(String, String, bool)], mut data: Vec<Vec<String>>, locale: Option<&num_format::Locale>, precision: Option<usize>) -> Vec<Vec<String>> {
+    let column_count = data.first().map(|row| row.len()).unwrap_or(0);
 
     for row_index in 0..data.len() {
         for col_index in 0..column_count {
             let visible = columns[col_index].2;
             if visible {
-                // If the data in this cell is an empty string we're going to leave it with that
-                // value regardless of the type.
-                if !data[row_index][col_index].is_empty() {
+                // If the data in this cell is an empty string or a gap marker we're going to leave it
+                // with that value regardless of the type.
+                if !data[row_index][col_index].is_empty() && data[row_index][col_index] != TABLE_GAP_MARKER {
                     let col_type = columns[col_index].1.to_lowercase();
                     //bg!(&col_type, &data[row_index][col_index]);
                     if col_type != "s" {
-                        data[row_index][col_index] = if col_type == "f" || col_type == "r" {
+                        data[row_index][col_index] = if col_type == "p" {
+                            format_rate_percent_locale_opt(data[row_index][col_index].parse::<f64>().unwrap(), locale, precision)
+                        } else if col_type == "f" || col_type == "r" {
                             let precision = if col_type == "f" {
                                 precision
                             } else {
@@ -330,13 +446,70 @@ pub(crate) fn print_table_locale_opt(columns: &[(String, String, bool)], mut dat
             }
         }
     }
+    data
+}
+
+// Returns a GitHub-flavored markdown table with the visible columns. Numbers are formatted as in
+// the printed tables and right-aligned.
+pub(crate) fn markdown_table(columns: &[(String, String, bool)], data: Vec<Vec<String>>) -> String {
+    let data = format_table_data(columns, data, None, None);
+    let visible_columns = columns.iter().enumerate().filter(|(_, (_, _, visible))| *visible).collect::<Vec<_>>();
+    let mut lines = vec![];
+    lines.push(format!("| {} |", visible_columns.iter().map(|(_, (header, _, _))| header.as_str()).join(" | ")));
+    lines.push(format!("|{}|", visible_columns.iter()
+        .map(|(_, (_, col_type, _))| if col_type.to_lowercase() == "s" { " --- " } else { " ---: " })
+        .join("|")));
+    for row in data.iter() {
+        lines.push(format!("| {} |", visible_columns.iter().map(|(col_index, _)| row[*col_index].as_str()).join(" | ")));
+    }
+    lines.join("\n") + "\n"
+}
+
+pub(crate) fn print_table_locale_opt(columns: &[(String, String, bool)], data: Vec<Vec<String>>, locale: Option<&num_format::Locale>, precision: Option<usize>) {
+    if columns.is_empty() || data.is_empty() {
+        return;
+    }
+    let data = format_table_data(columns, data, locale, precision);
+    print_formatted_table(columns, &data);
+}
+
+pub(crate) fn print_table_styled(columns: &[(String, String, bool)], data: Vec<Vec<String>>, style: &NumberStyle) {
+    if columns.is_empty() || data.is_empty() {
+        return;
+    }
+    let data = format_table_data_styled(columns, data, style);
+    print_formatted_table(columns, &data);
+}
+
+// Formats the table data without a locale and then removes the thousands separators from the
+// numeric columns if the style doesn't use grouping.
+fn format_table_data_styled(columns: &[(String, String, bool)], data: Vec<Vec<String>>, style: &NumberStyle) -> Vec<Vec<String>> {
+    let mut data = format_table_data(columns, data, None, Some(style.decimals));
+    if !style.grouping {
+        for row in data.iter_mut() {
+            for (col_index, value) in row.iter_mut().enumerate() {
+                if columns[col_index].1.to_lowercase() != "s" {
+                    *value = value.replace("_", "");
+                }
+            }
+        }
+    }
+    data
+}
+
+// Prints a table whose values have already been formatted, with each visible column right-aligned
+// to the width of its widest value or header.
+fn print_formatted_table(columns: &[(String, String, bool)], data: &[Vec<String>]) {
+    let column_separator = "  ";
+
+    let column_count = data[0].len();
 
     let mut column_widths = vec![];
     for col_index in 0..column_count {
         let visible = columns[col_index].2;
         let width = if visible {
             let mut width = columns[col_index].0.len();
-            for row in &data {
+            for row in data {
                 width = max(width, row[col_index].len());
             }
             width
@@ -544,6 +717,44 @@ impl Schedule {
             }
         }
     }
+
+    /// Returns the number of values in the schedule. For a repeating schedule this is the number
+    /// of periods.
+    pub fn len(&self) -> usize {
+        match self {
+            Schedule::Repeating { periods, .. } => *periods as usize,
+            Schedule::Custom { values, .. } => values.len(),
+        }
+    }
+
+    /// Returns true if the schedule has no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Checks that the schedule has one value for each of the expected number of periods. Call
+    /// this before passing the schedule to a function that reads a value for each period so that
+    /// a mismatch is reported up front rather than causing a panic partway through a calculation.
+    ///
+    /// # Examples
+    /// ```
+    /// use finance_solution::*;
+    /// let schedule = Schedule::new_custom(ValueType::Payment, &[100.0, 100.0, 150.0]);
+    /// assert_eq!(3, schedule.len());
+    /// assert!(schedule.validate_length(3).is_ok());
+    /// assert_eq!(
+    ///     Err("The custom schedule has 3 values but 4 periods were expected.".to_string()),
+    ///     schedule.validate_length(4));
+    /// ```
+    pub fn validate_length(&self, expected_periods: u32) -> Result<(), String> {
+        if self.len() == expected_periods as usize {
+            return Ok(());
+        }
+        match self {
+            Schedule::Repeating { periods, .. } => Err(format!("The repeating schedule has {} periods but {} periods were expected.", periods, expected_periods)),
+            Schedule::Custom { values, .. } => Err(format!("The custom schedule has {} values but {} periods were expected.", values.len(), expected_periods)),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -591,13 +802,98 @@ impl ScenarioList {
         self.print_table_locale_opt(Some(locale), Some(precision));
     }
 
+    /// Returns the entry whose output is nearest to a target value. If two entries are equally
+    /// near the first one is returned.
+    ///
+    /// # Arguments
+    /// * `target_output` - The output value to look for.
+    ///
+    /// # Panics
+    /// The call will fail if the list has no entries or if `target_output` is not finite.
+    ///
+    /// # Examples
+    /// ```
+    /// # use finance_solution::*;
+    /// let scenarios = net_present_value_vary_rate(&[-10_000, 3_000, 4_200, 6_800], &[0.05, 0.08, 0.10, 0.12, 0.20]);
+    /// let entry = scenarios.closest_to(1_000.0);
+    /// assert_rounded_6!(0.12, entry.input);
+    /// assert_rounded_4!(866.8914, entry.output);
+    /// ```
+    pub fn closest_to(&self, target_output: f64) -> &ScenarioEntry {
+        assert!(target_output.is_finite(), "The target output must be finite (not NaN or infinity)");
+        assert!(!self.entries.is_empty(), "The scenario list must have at least one entry.");
+        let mut closest = &self.entries[0];
+        for entry in self.entries.iter().skip(1) {
+            if (entry.output - target_output).abs() < (closest.output - target_output).abs() {
+                closest = entry;
+            }
+        }
+        closest
+    }
+
+    /// Returns the input that would produce a target output by interpolating linearly between the
+    /// first pair of neighboring entries whose outputs are on either side of the target, or `None`
+    /// if no pair of neighboring entries brackets the target. This is a quick way to goal-seek from
+    /// a set of scenarios that's already been calculated, with an error that shrinks as the inputs
+    /// get closer together.
+    ///
+    /// # Arguments
+    /// * `target_output` - The output value to look for.
+    ///
+    /// # Panics
+    /// The call will fail if `target_output` is not finite.
+    ///
+    /// # Examples
+    /// ```
+    /// # use finance_solution::*;
+    /// let scenarios = net_present_value_vary_rate(&[-10_000, 3_000, 4_200, 6_800], &[0.05, 0.08, 0.10, 0.12, 0.20]);
+    ///
+    /// // The net present value is zero somewhere between 12% and 20%, which is an estimate of the
+    /// // internal rate of return.
+    /// assert_rounded_6!(0.165775, scenarios.interpolate_input_for(0.0).unwrap());
+    ///
+    /// // No scenario is worth more than $3,000.
+    /// assert_eq!(None, scenarios.interpolate_input_for(3_000.0));
+    /// ```
+    pub fn interpolate_input_for(&self, target_output: f64) -> Option<f64> {
+        assert!(target_output.is_finite(), "The target output must be finite (not NaN or infinity)");
+        if let Some(entry) = self.entries.iter().find(|entry| entry.output == target_output) {
+            return Some(entry.input);
+        }
+        self.entries.windows(2)
+            .find(|pair| (pair[0].output - target_output) * (pair[1].output - target_output) < 0.0)
+            .map(|pair| {
+                let fraction = (target_output - pair[0].output) / (pair[1].output - pair[0].output);
+                pair[0].input + fraction * (pair[1].input - pair[0].input)
+            })
+    }
+
+    /// Returns the scenarios as a GitHub-flavored markdown table with the same columns and number
+    /// formatting as [`ScenarioList::print_table`], for pasting into a README or an issue comment.
+    ///
+    /// # Examples
+    /// ```
+    /// # use finance_solution::*;
+    /// let scenarios = net_present_value_vary_rate(&[-10_000, 3_000, 4_200, 6_800], &[0.05, 0.20]);
+    /// let markdown = scenarios.to_markdown();
+    /// assert!(markdown.ends_with("| 0.050000 | 2_540.7623 |\n| 0.200000 | -648.1481 |\n"));
+    /// ```
+    pub fn to_markdown(&self) -> String {
+        let (columns, data) = self.table_columns_and_data();
+        markdown_table(&columns, data)
+    }
+
     fn print_table_locale_opt(&self, locale: Option<&num_format::Locale>, precision: Option<usize>) {
+        let (columns, data) = self.table_columns_and_data();
+        print_table_locale_opt(&columns, data, locale, precision);
+    }
+
+    fn table_columns_and_data(&self) -> TableColumnsAndData {
         let columns = vec![self.input_variable.table_column_spec(true), self.output_variable.table_column_spec(true)];
-        // let columns = columns_with_strings.iter().map(|x| &x.0[..], &x.1[..], x.2);
         let data = self.entries.iter()
             .map(|entry| vec![entry.input.to_string(), entry.output.to_string()])
             .collect::<Vec<_>>();
-        print_table_locale_opt(&columns, data, locale, precision);
+        (columns, data)
     }
 
 }
@@ -616,6 +912,10 @@ impl Debug for ScenarioEntry {
     }
 }
 
+// The column specs and the unformatted values of a table, ready to be printed or converted to
+// markdown.
+pub(crate) type TableColumnsAndData = (Vec<(String, String, bool)>, Vec<Vec<String>>);
+
 pub(crate) fn columns_with_strings(columns: &[(&str, &str, bool)]) -> Vec<(String, String, bool)> {
     columns.iter().map(|(label, data_type, visible)| (label.to_string(), data_type.to_string(), *visible)).collect()
 }
@@ -657,6 +957,62 @@ mod tests {
         assert_same_sign_or_zero!(100.0, -0.00000000001864464138634503);
     }
 
+    #[test]
+    fn test_format_rate_percent() {
+        assert_eq!("4.5000%", format_rate_percent(0.045, 4));
+        assert_eq!("0.00%", format_rate_percent(0.0, 2));
+        assert_eq!("1_250.0%", format_rate_percent(12.5, 1));
+        assert_eq!("4,5000%", format_rate_percent_locale_opt(0.045, Some(&Locale::fr), Some(4)));
+    }
+
+    #[test]
+    fn test_format_rate_percent_locale() {
+        // German uses a comma for the decimal separator and a period between thousands.
+        assert_eq!("4,50%", format_rate_percent_locale(0.045, &Locale::de, 2));
+        assert_eq!("-1.234,5678%", format_rate_percent_locale(-12.345678, &Locale::de, 4));
+        assert_eq!(format_rate_percent(0.0725, 3), format_rate_percent_locale(0.0725, &Locale::en, 3).replace(",", "_"));
+    }
+
+    #[test]
+    fn test_schedule_validate_length() {
+        let schedule = Schedule::new_repeating(ValueType::Rate, 0.05, 12);
+        assert_eq!(12, schedule.len());
+        assert!(schedule.validate_length(12).is_ok());
+        assert!(schedule.validate_length(11).is_err());
+
+        let schedule = Schedule::new_custom(ValueType::Payment, &[]);
+        assert!(schedule.is_empty());
+        assert!(schedule.validate_length(0).is_ok());
+        assert!(schedule.validate_length(1).is_err());
+    }
+
+    #[test]
+    fn test_scenario_list_interpolate_input_for() {
+        // The future value grows with the number of periods so each target between the first and
+        // last outputs falls between two neighboring entries.
+        let scenarios = ScenarioList::new("".to_string(), TvmVariable::Periods, TvmVariable::FutureValue, vec![(1.0, 105.0), (2.0, 110.25), (3.0, 115.7625)]);
+        assert_approx_equal!(2.0, scenarios.interpolate_input_for(110.25).unwrap());
+        assert_approx_equal!(2.5, scenarios.interpolate_input_for((110.25 + 115.7625) / 2.0).unwrap());
+        assert_eq!(None, scenarios.interpolate_input_for(100.0));
+        assert_approx_equal!(3.0, scenarios.closest_to(200.0).input);
+    }
+
+    #[test]
+    fn test_markdown_table_hidden_columns() {
+        let columns = columns_with_strings(&[("name", "s", true), ("hidden", "f", false), ("amount", "f", true)]);
+        let data = vec![vec!["a".to_string(), "1".to_string(), "1234.5".to_string()]];
+        assert_eq!("| name | amount |\n| --- | ---: |\n| a | 1_234.5000 |\n", markdown_table(&columns, data));
+    }
+
+    #[test]
+    fn test_format_table_data_styled_without_grouping() {
+        // String columns keep their underscores while numbers lose their grouping.
+        let columns = columns_with_strings(&[("name", "s", true), ("period", "i", true), ("rate", "r", true), ("amount", "f", true)]);
+        let data = vec![vec!["a_b".to_string(), "12000".to_string(), "0.05".to_string(), "-1234567.891".to_string()]];
+        let formatted = format_table_data_styled(&columns, data, &NumberStyle::new(false, 2));
+        assert_eq!(vec!["a_b", "12000", "0.050000", "-1234567.89"], formatted[0]);
+    }
+
     #[should_panic]
     #[test]
     fn test_assert_same_sign_or_zero_fail_diff_sign() {
@@ -1,27 +1,54 @@
 //! **Utilities for rounding** money amounts to the nearest hundredth or ten-thousandth part.
 
+/// The largest number of decimal places supported by [`round_to`]. An `f64` holds only about 15
+/// significant decimal digits so rounding to more places than this wouldn't change the value.
+pub const MAX_ROUND_DECIMALS: u32 = 15;
+
+/// Round to the given number of decimal places. This function uses f64::round() which rounds
+/// halfway cases away from 0.0.
+///
+/// If `decimals` is greater than [`MAX_ROUND_DECIMALS`] the value is rounded to
+/// `MAX_ROUND_DECIMALS` places instead. If the value is so large that scaling it would overflow,
+/// it's returned unchanged since it has no fractional part to round.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// assert_eq!(1.235, round_to(1.23456, 3));
+/// assert_eq!(-2.0, round_to(-1.5, 0));
+/// ```
+pub fn round_to(val: f64, decimals: u32) -> f64 {
+    let factor = 10f64.powi(decimals.min(MAX_ROUND_DECIMALS) as i32);
+    let scaled = val * factor;
+    if scaled.is_finite() {
+        scaled.round() / factor
+    } else {
+        val
+    }
+}
+
 /// Round to two decimal places. This function uses f64::round() which rounds halfway cases away
 /// from 0.0.
 pub fn round_2(val: f64) -> f64 {
-    (val * 100.0).round() / 100.0
+    round_to(val, 2)
 }
 
 /// Round to four decimal places. This function uses f64::round() which rounds halfway cases away
 /// from 0.0.
 pub fn round_4(val: f64) -> f64 {
-    (val * 10_000.0).round() / 10_000.0
+    round_to(val, 4)
 }
 
 /// Round to six decimal places. This function uses f64::round() which rounds halfway cases away
 /// from 0.0.
 pub fn round_6(val: f64) -> f64 {
-    (val * 1_000_000.0).round() / 1_000_000.0
+    round_to(val, 6)
 }
 
 /// Round to eight decimal places. This function uses f64::round() which rounds halfway cases away
 /// from 0.0.
 pub fn round_8(val: f64) -> f64 {
-    (val * 100_000_000.0).round() / 100_000_000.0
+    round_to(val, 8)
 }
 
 #[inline(always)]
@@ -94,6 +121,23 @@ mod tests {
         assert_ne!(295_489.94512333, round_8(295_489.9451249999));
     }
 
+    #[test]
+    fn test_round_to() {
+        assert_eq!(1.235, round_to(1.23456, 3));
+        assert_eq!(1.23456, round_to(1.23456, 5));
+        assert_eq!(1.0, round_to(1.23456, 0));
+        for val in [295_489.941849, 295_489.94175, -0.00005, 1.0 / 3.0].iter() {
+            assert_eq!(round_4(*val), round_to(*val, 4));
+        }
+    }
+
+    #[test]
+    fn test_round_to_large_decimals() {
+        // Extra decimal places are capped rather than overflowing the scaling factor.
+        assert_eq!(round_to(0.1 + 0.2, MAX_ROUND_DECIMALS), round_to(0.1 + 0.2, 400));
+        assert_eq!(1.0e300, round_to(1.0e300, 8));
+    }
+
     #[test]
     fn test_assert_rounded_2_nominal() {
         assert_rounded_2!(53_243.7448, 53_243.7401);
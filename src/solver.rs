@@ -0,0 +1,159 @@
+//! **Settings for the iterative solvers.** Some values such as the rate needed to reach a savings
+//! goal have no closed-form solution so they're found by narrowing down a range of candidates. A
+//! [`SolverConfig`] controls how precise the answer must be and how much work may be done to find
+//! it.
+//!
+//! Functions that solve iteratively have a `_with_config` variant that takes a `SolverConfig`, and
+//! their solution structs report the number of iterations used and whether the solver converged.
+//! The variants without a config use [`SolverConfig::default`].
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // Settle for a rate that's within a hundredth of a basis point.
+//! let config = SolverConfig::new(0.000_001, 100);
+//! let solution = npv_zero_rate_solution_with_config(-10_000, 1_200, 10, config).unwrap();
+//! assert!(solution.converged());
+//! assert_rounded_4!(0.0346, solution.rate());
+//!
+//! // The default config keeps going until the rate is as precise as an f64 allows, which takes
+//! // more iterations.
+//! let default_solution = npv_zero_rate_solution(-10_000, 1_200, 10).unwrap();
+//! assert!(default_solution.iterations() > solution.iterations());
+//! ```
+
+/// Controls the precision and the amount of work done by the iterative solvers.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SolverConfig {
+    /// The largest acceptable difference between the reported value and the true value. A
+    /// tolerance of zero means the solver continues until the value is as precise as an `f64`
+    /// allows.
+    pub tolerance: f64,
+    /// The most iterations the solver may use. If the tolerance hasn't been reached by then the
+    /// best value so far is returned and the solution reports that it didn't converge.
+    pub max_iterations: u32,
+}
+
+impl SolverConfig {
+    /// Creates a config with the given tolerance and maximum number of iterations.
+    ///
+    /// # Panics
+    /// The call will fail if `tolerance` is negative or not finite.
+    pub fn new(tolerance: f64, max_iterations: u32) -> Self {
+        assert!(tolerance.is_finite() && tolerance >= 0.0, "The tolerance must be zero or a positive number.");
+        Self {
+            tolerance,
+            max_iterations,
+        }
+    }
+}
+
+impl Default for SolverConfig {
+    /// Solve to the full precision of an `f64` using at most 200 iterations.
+    fn default() -> Self {
+        Self::new(0.0, 200)
+    }
+}
+
+// The result of an iterative solver, which the solution structs keep so they can report the number
+// of iterations and whether the solver converged.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct SolverOutcome {
+    pub(crate) value: f64,
+    pub(crate) iterations: u32,
+    pub(crate) converged: bool,
+}
+
+// Narrows the range from low to high by bisection. `is_below` is called with a candidate value and
+// returns true if the answer is above that value. Returns the midpoint of the final range along
+// with the number of iterations used and whether the range was narrowed to within the tolerance.
+pub(crate) fn bisect<F>(mut low: f64, mut high: f64, config: &SolverConfig, is_below: F) -> SolverOutcome
+    where F: Fn(f64) -> bool
+{
+    let mut iterations = 0;
+    let mut converged = false;
+    while iterations < config.max_iterations {
+        let mid = (low + high) / 2.0;
+        // Stop once the range is within the tolerance or can't be split any further.
+        if (high - low) / 2.0 <= config.tolerance || mid <= low || mid >= high {
+            converged = true;
+            break;
+        }
+        iterations += 1;
+        if is_below(mid) {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    if !converged {
+        let mid = (low + high) / 2.0;
+        converged = (high - low) / 2.0 <= config.tolerance || mid <= low || mid >= high;
+    }
+    SolverOutcome {
+        value: (low + high) / 2.0,
+        iterations,
+        converged,
+    }
+}
+
+// Widens the range of rates, starting from -50% to 100%, until the answer is known to be inside it
+// and then narrows it down by bisection. `is_below` is called as with `bisect`. Returns None if no
+// range above -100% contains the answer.
+pub(crate) fn bracket_and_bisect<F>(config: &SolverConfig, is_below: F) -> Option<SolverOutcome>
+    where F: Fn(f64) -> bool
+{
+    let mut low = -0.5;
+    while !is_below(low) && low > -1.0 + f64::EPSILON {
+        low = (low - 1.0) / 2.0;
+    }
+    let mut high = 1.0;
+    while is_below(high) && high < f64::MAX / 4.0 {
+        high *= 2.0;
+    }
+    if !is_below(low) || is_below(high) {
+        return None;
+    }
+    Some(bisect(low, high, config, is_below))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn test_bisect_tolerance() {
+        // Find the square root of two.
+        let is_below = |x: f64| x * x < 2.0;
+        let outcome = bisect(0.0, 2.0, &SolverConfig::default(), is_below);
+        assert!(outcome.converged);
+        assert_approx_equal!(2.0_f64.sqrt(), outcome.value);
+
+        let config = SolverConfig::new(0.001, 200);
+        let outcome = bisect(0.0, 2.0, &config, is_below);
+        assert!(outcome.converged);
+        assert!((outcome.value - 2.0_f64.sqrt()).abs() <= 0.001);
+        assert_eq!(10, outcome.iterations);
+
+        // Not enough iterations to reach the tolerance.
+        let config = SolverConfig::new(0.001, 5);
+        let outcome = bisect(0.0, 2.0, &config, is_below);
+        assert!(!outcome.converged);
+        assert_eq!(5, outcome.iterations);
+    }
+
+    #[test]
+    fn test_bracket_and_bisect() {
+        // The answer is outside the starting range in both directions.
+        let outcome = bracket_and_bisect(&SolverConfig::default(), |x| x < 5.5).unwrap();
+        assert!(outcome.converged);
+        assert_approx_equal!(5.5, outcome.value);
+        let outcome = bracket_and_bisect(&SolverConfig::default(), |x| x < -0.9).unwrap();
+        assert_approx_equal!(-0.9, outcome.value);
+
+        // No range above -100% contains an answer.
+        assert!(bracket_and_bisect(&SolverConfig::default(), |_| true).is_none());
+        assert!(bracket_and_bisect(&SolverConfig::default(), |x| x < -1.0).is_none());
+    }
+}
@@ -0,0 +1,221 @@
+//! **Pre-tax and after-tax returns.** A return earned in a taxable account is reduced by the tax on
+//! it, so comparing a taxable investment with a tax-advantaged one means converting one return to
+//! the other's basis.
+//!
+//! aftertax_rate = pretax_rate * (1 - tax_rate)
+//!
+//! pretax_rate = aftertax_rate / (1 - tax_rate)
+//!
+//! The tax is assumed to be paid on the full return each period, as with interest income.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // What does a taxable account need to earn to match 5% in a tax-free account at a 30% tax rate?
+//! let solution = pretax_return_for_aftertax_solution(0.05, 0.30);
+//! dbg!(&solution);
+//! ```
+
+/// Returns the **pre-tax return needed to earn a target return after taxes**.
+///
+/// Related functions:
+/// * To see the tax rate and the formula use [`pretax_return_for_aftertax_solution`].
+/// * To go the other way from a pre-tax return use [`aftertax_return`].
+///
+/// The formula is:
+///
+/// pretax_rate = target_aftertax_rate / (1 - tax_rate)
+///
+/// # Arguments
+/// * `target_aftertax_rate` - The return to be kept after taxes, expressed as a floating point
+/// number. For instance 0.05 would mean 5%.
+/// * `tax_rate` - The tax rate on the return, such as 0.30 for 30%.
+///
+/// # Panics
+/// The call will fail if `tax_rate` is negative or greater than or equal to 1.0, or if any value is
+/// not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // A 5% after-tax target at a 30% tax rate needs about 7.14% before taxes.
+/// assert_rounded_6!(0.071429, pretax_return_for_aftertax(0.05, 0.30));
+/// ```
+pub fn pretax_return_for_aftertax(target_aftertax_rate: f64, tax_rate: f64) -> f64 {
+    pretax_return_for_aftertax_solution(target_aftertax_rate, tax_rate).pretax_rate()
+}
+
+/// Returns the **pre-tax return needed to earn a target return after taxes** along with the tax
+/// rate and the formula.
+///
+/// Related functions:
+/// * To calculate only the pre-tax return as an `f64` use [`pretax_return_for_aftertax`].
+///
+/// # Arguments
+/// * `target_aftertax_rate` - The return to be kept after taxes, expressed as a floating point
+/// number. For instance 0.05 would mean 5%.
+/// * `tax_rate` - The tax rate on the return, such as 0.30 for 30%.
+///
+/// # Panics
+/// The call will fail if `tax_rate` is negative or greater than or equal to 1.0, or if any value is
+/// not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = pretax_return_for_aftertax_solution(0.05, 0.30);
+/// assert_rounded_6!(0.071429, solution.pretax_rate());
+/// assert_rounded_6!(0.05, solution.aftertax_rate());
+/// assert_rounded_6!(0.021429, solution.tax_drag());
+/// assert_eq!("0.071429 = 0.050000 / (1 - 0.300000)", solution.formula());
+/// ```
+pub fn pretax_return_for_aftertax_solution(target_aftertax_rate: f64, tax_rate: f64) -> TaxReturnSolution {
+    assert!(target_aftertax_rate.is_finite(), "The after-tax rate must be finite (not NaN or infinity)");
+    check_tax_rate(tax_rate);
+    let pretax_rate = target_aftertax_rate / (1.0 - tax_rate);
+    let formula = format!("{:.6} = {:.6} / (1 - {:.6})", pretax_rate, target_aftertax_rate, tax_rate);
+    let symbolic_formula = "pretax_rate = aftertax_rate / (1 - tax_rate)";
+    TaxReturnSolution::new(pretax_rate, target_aftertax_rate, tax_rate, &formula, symbolic_formula)
+}
+
+/// Returns the **return kept after taxes** from a pre-tax return.
+///
+/// Related functions:
+/// * To see the tax rate and the formula use [`aftertax_return_solution`].
+/// * To find the pre-tax return needed for a target after-tax return use
+/// [`pretax_return_for_aftertax`].
+///
+/// The formula is:
+///
+/// aftertax_rate = pretax_rate * (1 - tax_rate)
+///
+/// # Arguments
+/// * `pretax_rate` - The return before taxes, expressed as a floating point number. For instance
+/// 0.07 would mean 7%.
+/// * `tax_rate` - The tax rate on the return, such as 0.30 for 30%.
+///
+/// # Panics
+/// The call will fail if `tax_rate` is negative or greater than or equal to 1.0, or if any value is
+/// not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// assert_rounded_6!(0.049, aftertax_return(0.07, 0.30));
+/// ```
+pub fn aftertax_return(pretax_rate: f64, tax_rate: f64) -> f64 {
+    aftertax_return_solution(pretax_rate, tax_rate).aftertax_rate()
+}
+
+/// Returns the **return kept after taxes** from a pre-tax return along with the tax rate and the
+/// formula.
+///
+/// Related functions:
+/// * To calculate only the after-tax return as an `f64` use [`aftertax_return`].
+///
+/// # Arguments
+/// * `pretax_rate` - The return before taxes, expressed as a floating point number. For instance
+/// 0.07 would mean 7%.
+/// * `tax_rate` - The tax rate on the return, such as 0.30 for 30%.
+///
+/// # Panics
+/// The call will fail if `tax_rate` is negative or greater than or equal to 1.0, or if any value is
+/// not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = aftertax_return_solution(0.07, 0.30);
+/// assert_rounded_6!(0.049, solution.aftertax_rate());
+/// assert_rounded_6!(0.021, solution.tax_drag());
+/// assert_eq!("aftertax_rate = pretax_rate * (1 - tax_rate)", solution.symbolic_formula());
+/// ```
+pub fn aftertax_return_solution(pretax_rate: f64, tax_rate: f64) -> TaxReturnSolution {
+    assert!(pretax_rate.is_finite(), "The pre-tax rate must be finite (not NaN or infinity)");
+    check_tax_rate(tax_rate);
+    let aftertax_rate = pretax_rate * (1.0 - tax_rate);
+    let formula = format!("{:.6} = {:.6} * (1 - {:.6})", aftertax_rate, pretax_rate, tax_rate);
+    let symbolic_formula = "aftertax_rate = pretax_rate * (1 - tax_rate)";
+    TaxReturnSolution::new(pretax_rate, aftertax_rate, tax_rate, &formula, symbolic_formula)
+}
+
+fn check_tax_rate(tax_rate: f64) {
+    assert!(tax_rate.is_finite(), "The tax rate must be finite (not NaN or infinity)");
+    assert!(tax_rate >= 0.0, "The tax rate must be zero or positive.");
+    assert!(tax_rate < 1.0, "The tax rate must be less than 1.0 (100%) or no return would be kept after taxes.");
+}
+
+/// A record of a conversion between a pre-tax and an after-tax return. Created by calling
+/// [`pretax_return_for_aftertax_solution`] or [`aftertax_return_solution`].
+#[derive(Clone, Debug)]
+pub struct TaxReturnSolution {
+    pretax_rate: f64,
+    aftertax_rate: f64,
+    tax_rate: f64,
+    formula: String,
+    symbolic_formula: String,
+}
+
+impl TaxReturnSolution {
+    pub(crate) fn new(pretax_rate: f64, aftertax_rate: f64, tax_rate: f64, formula: &str, symbolic_formula: &str) -> Self {
+        assert!(pretax_rate.is_finite());
+        Self {
+            pretax_rate,
+            aftertax_rate,
+            tax_rate,
+            formula: formula.to_string(),
+            symbolic_formula: symbolic_formula.to_string(),
+        }
+    }
+
+    /// Returns the return before taxes.
+    pub fn pretax_rate(&self) -> f64 {
+        self.pretax_rate
+    }
+
+    /// Returns the return kept after taxes.
+    pub fn aftertax_rate(&self) -> f64 {
+        self.aftertax_rate
+    }
+
+    /// Returns the tax rate on the return.
+    pub fn tax_rate(&self) -> f64 {
+        self.tax_rate
+    }
+
+    /// Returns the part of the pre-tax return lost to taxes.
+    pub fn tax_drag(&self) -> f64 {
+        self.pretax_rate - self.aftertax_rate
+    }
+
+    /// Returns a text version of the formula used in the calculation with the actual values.
+    pub fn formula(&self) -> &str {
+        &self.formula
+    }
+
+    /// Returns a text version of the formula used in the calculation using variables such as
+    /// tax_rate.
+    pub fn symbolic_formula(&self) -> &str {
+        &self.symbolic_formula
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn test_pretax_return_for_aftertax_round_trip() {
+        for &(rate, tax_rate) in [(0.05, 0.30), (-0.02, 0.15), (0.12, 0.0), (0.08, 0.99)].iter() {
+            let pretax_rate = pretax_return_for_aftertax(rate, tax_rate);
+            assert_approx_equal!(rate, aftertax_return(pretax_rate, tax_rate));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_pretax_return_for_aftertax_full_tax() {
+        pretax_return_for_aftertax(0.05, 1.0);
+    }
+}
@@ -0,0 +1,288 @@
+//! **Effective yield with a promotional rate.** Savings accounts often pay a teaser rate for the
+//! first few months and a lower regular rate after that. The single periodic rate that grows the
+//! balance by the same amount over the whole horizon is the geometric blend of the two:
+//!
+//! blended_rate = ((1 + intro_rate)<sup>k</sup> * (1 + regular_rate)<sup>n - k</sup>)<sup>1/n</sup> - 1
+//!
+//! where k is the number of intro periods and n is the total number of periods. The blended rate
+//! is then converted to an effective annual yield with
+//! [`convert_epr_to_ear`](../convert_rate/fn.convert_epr_to_ear.html).
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // 5% APR for the first six months then 1% APR, compounded monthly, over two years.
+//! let solution = blended_effective_yield_solution(0.05 / 12.0, 6, 0.01 / 12.0, 24, 12);
+//! dbg!(&solution);
+//! solution.print_table();
+//! ```
+
+use crate::*;
+use std::ops::Deref;
+
+/// Returns the **effective annual yield of an account that pays an intro rate and then a regular
+/// rate**.
+///
+/// Related functions:
+/// * To see the blended periodic rate and the growth in each segment use
+/// [`blended_effective_yield_solution`].
+///
+/// See the [blended_effective_yield](./index.html) module page for the formula.
+///
+/// # Arguments
+/// * `intro_rate` - The periodic rate during the promotion, such as 0.05 / 12 for 5% a year
+/// compounded monthly.
+/// * `intro_periods` - The number of periods the intro rate is paid.
+/// * `regular_rate` - The periodic rate after the promotion.
+/// * `total_periods` - The number of periods over which the yield is measured, including the intro
+/// periods.
+/// * `periods_per_year` - The number of compounding periods in a year, such as 12 for monthly
+/// rates. This is needed to turn the blended periodic rate into an annual yield.
+///
+/// # Panics
+/// The call will fail if `total_periods` or `periods_per_year` is zero, if `intro_periods` is
+/// greater than `total_periods`, if either rate is less than or equal to -1.0, or if any value is
+/// not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // 5% APR for the first six months then 1% APR, compounded monthly, measured over one year.
+/// let yield_one_year = blended_effective_yield(0.05 / 12.0, 6, 0.01 / 12.0, 12, 12);
+/// assert_rounded_6!(0.030399, yield_one_year);
+///
+/// // Over two years the promotion makes less difference.
+/// let yield_two_years = blended_effective_yield(0.05 / 12.0, 6, 0.01 / 12.0, 24, 12);
+/// assert!(yield_two_years < yield_one_year);
+///
+/// // Without a promotion it's the effective annual rate of the regular rate.
+/// let regular = blended_effective_yield(0.01 / 12.0, 6, 0.01 / 12.0, 12, 12);
+/// assert_rounded_6!(convert_epr_to_ear(0.01 / 12.0, 12), regular);
+/// ```
+pub fn blended_effective_yield(intro_rate: f64, intro_periods: u32, regular_rate: f64, total_periods: u32, periods_per_year: u32) -> f64 {
+    blended_effective_yield_solution(intro_rate, intro_periods, regular_rate, total_periods, periods_per_year).effective_annual_yield()
+}
+
+/// Returns the **effective annual yield of an account that pays an intro rate and then a regular
+/// rate** along with the blended periodic rate and the two rate segments.
+///
+/// Related functions:
+/// * To calculate only the effective annual yield as an `f64` use [`blended_effective_yield`].
+///
+/// # Arguments
+/// * `intro_rate` - The periodic rate during the promotion, such as 0.05 / 12 for 5% a year
+/// compounded monthly.
+/// * `intro_periods` - The number of periods the intro rate is paid.
+/// * `regular_rate` - The periodic rate after the promotion.
+/// * `total_periods` - The number of periods over which the yield is measured, including the intro
+/// periods.
+/// * `periods_per_year` - The number of compounding periods in a year, such as 12 for monthly
+/// rates.
+///
+/// # Panics
+/// The call will fail if `total_periods` or `periods_per_year` is zero, if `intro_periods` is
+/// greater than `total_periods`, if either rate is less than or equal to -1.0, or if any value is
+/// not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = blended_effective_yield_solution(0.05 / 12.0, 6, 0.01 / 12.0, 12, 12);
+/// assert_rounded_6!(0.002499, solution.blended_rate());
+/// assert_rounded_6!(0.030399, solution.effective_annual_yield());
+///
+/// // The intro segment and the regular segment.
+/// let segments = solution.segments();
+/// assert_eq!(2, segments.len());
+/// assert_eq!(6, segments[0].periods());
+/// assert_rounded_6!(1.025262, segments[0].growth_factor());
+/// assert_rounded_6!(1.005010, segments[1].growth_factor());
+///
+/// // The period-by-period growth of $1,000.
+/// let schedule = solution.schedule(-1_000);
+/// assert_rounded_4!(1_030.3989, schedule.future_value());
+/// ```
+pub fn blended_effective_yield_solution(intro_rate: f64, intro_periods: u32, regular_rate: f64, total_periods: u32, periods_per_year: u32) -> BlendedYieldSolution {
+    assert!(intro_rate.is_finite(), "The intro rate must be finite (not NaN or infinity)");
+    assert!(intro_rate > -1.0, "The intro rate must be greater than -1.0 (-100%).");
+    assert!(regular_rate.is_finite(), "The regular rate must be finite (not NaN or infinity)");
+    assert!(regular_rate > -1.0, "The regular rate must be greater than -1.0 (-100%).");
+    assert!(total_periods > 0, "The total number of periods must be greater than zero.");
+    assert!(intro_periods <= total_periods, "The number of intro periods must not be greater than the total number of periods.");
+    assert!(periods_per_year > 0, "The number of periods per year must be greater than zero.");
+
+    let segments = vec![
+        RateSegment::new("intro", intro_periods, intro_rate),
+        RateSegment::new("regular", total_periods - intro_periods, regular_rate),
+    ];
+    let total_growth = segments.iter().map(|segment| segment.growth_factor()).product::<f64>();
+    let blended_rate = total_growth.powf(1.0 / total_periods as f64) - 1.0;
+    let effective_annual_yield = convert_epr_to_ear(blended_rate, periods_per_year);
+    BlendedYieldSolution::new(total_periods, periods_per_year, blended_rate, effective_annual_yield, segments)
+}
+
+/// A record of the effective yield of an account with an intro rate. Created by calling
+/// [`blended_effective_yield_solution`].
+#[derive(Clone, Debug)]
+pub struct BlendedYieldSolution {
+    total_periods: u32,
+    periods_per_year: u32,
+    blended_rate: f64,
+    effective_annual_yield: f64,
+    segments: RateSegments,
+}
+
+impl BlendedYieldSolution {
+    pub(crate) fn new(total_periods: u32, periods_per_year: u32, blended_rate: f64, effective_annual_yield: f64, segments: Vec<RateSegment>) -> Self {
+        assert!(effective_annual_yield.is_finite());
+        Self {
+            total_periods,
+            periods_per_year,
+            blended_rate,
+            effective_annual_yield,
+            segments: RateSegments::new(segments),
+        }
+    }
+
+    /// Returns the periodic rate during the promotion.
+    pub fn intro_rate(&self) -> f64 {
+        self.segments[0].rate
+    }
+
+    /// Returns the number of periods the intro rate is paid.
+    pub fn intro_periods(&self) -> u32 {
+        self.segments[0].periods
+    }
+
+    /// Returns the periodic rate after the promotion.
+    pub fn regular_rate(&self) -> f64 {
+        self.segments[1].rate
+    }
+
+    /// Returns the number of periods over which the yield is measured.
+    pub fn total_periods(&self) -> u32 {
+        self.total_periods
+    }
+
+    /// Returns the number of compounding periods in a year.
+    pub fn periods_per_year(&self) -> u32 {
+        self.periods_per_year
+    }
+
+    /// Returns the single periodic rate that gives the same growth over all of the periods.
+    pub fn blended_rate(&self) -> f64 {
+        self.blended_rate
+    }
+
+    /// Returns the blended rate converted to an effective annual yield.
+    pub fn effective_annual_yield(&self) -> f64 {
+        self.effective_annual_yield
+    }
+
+    /// Returns the intro segment followed by the regular segment.
+    pub fn segments(&self) -> RateSegments {
+        self.segments.clone()
+    }
+
+    /// Returns a schedule with the intro rate for each intro period followed by the regular rate,
+    /// starting from the given present value. As elsewhere in the crate the future value has the
+    /// opposite sign from the present value.
+    pub fn schedule<T>(&self, present_value: T) -> TvmScheduleSolution
+        where T: Into<f64> + Copy
+    {
+        let mut rates = vec![];
+        for segment in self.segments.iter() {
+            rates.extend(vec![segment.rate; segment.periods as usize]);
+        }
+        future_value_schedule_solution(&rates, present_value)
+    }
+
+    /// Prints a formatted table with one row for each segment.
+    pub fn print_table(&self) {
+        self.segments.print_table();
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RateSegments(Vec<RateSegment>);
+
+impl RateSegments {
+    pub(crate) fn new(segments: Vec<RateSegment>) -> Self {
+        Self(segments)
+    }
+
+    pub fn print_table(&self) {
+        let columns = columns_with_strings(&[("segment", "s", true), ("periods", "i", true), ("rate", "r", true), ("growth_factor", "r", true)]);
+        let data = self.iter()
+            .map(|segment| vec![segment.name.clone(), segment.periods.to_string(), segment.rate.to_string(), segment.growth_factor().to_string()])
+            .collect::<Vec<_>>();
+        print_table_locale_opt(&columns, data, None, None);
+    }
+}
+
+impl Deref for RateSegments {
+    type Target = Vec<RateSegment>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// A run of periods at the same rate within a [`BlendedYieldSolution`].
+#[derive(Clone, Debug)]
+pub struct RateSegment {
+    name: String,
+    periods: u32,
+    rate: f64,
+}
+
+impl RateSegment {
+    pub(crate) fn new(name: &str, periods: u32, rate: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            periods,
+            rate,
+        }
+    }
+
+    /// Returns "intro" or "regular".
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the number of periods in the segment.
+    pub fn periods(&self) -> u32 {
+        self.periods
+    }
+
+    /// Returns the periodic rate in the segment.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns how much the balance grows over the segment, such as 1.05 for 5% growth.
+    pub fn growth_factor(&self) -> f64 {
+        (1.0 + self.rate).powi(self.periods as i32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blended_effective_yield_matches_schedule() {
+        let solution = blended_effective_yield_solution(0.045 / 12.0, 4, 0.015 / 12.0, 18, 12);
+        let schedule = solution.schedule(-500.0);
+        assert_eq!(18, schedule.periods());
+        assert_approx_equal!(future_value(solution.blended_rate(), 18, -500.0, false), schedule.future_value());
+        assert!(solution.effective_annual_yield() > convert_epr_to_ear(0.015 / 12.0, 12));
+    }
+
+    #[test]
+    fn test_blended_effective_yield_no_promotion() {
+        // With no intro periods or equal rates the blend is the regular rate.
+        assert_approx_equal!(convert_epr_to_ear(0.0025, 4), blended_effective_yield(0.02, 0, 0.0025, 8, 4));
+        assert_approx_equal!(convert_epr_to_ear(0.0025, 4), blended_effective_yield(0.0025, 5, 0.0025, 8, 4));
+    }
+}
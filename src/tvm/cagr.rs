@@ -0,0 +1,173 @@
+//! **Compound annual growth rate.** Given a starting value, an ending value, and the number of years
+//! in between, what steady annual rate of growth would take the starting value to the ending value?
+//!
+//! Unlike [`rate`](../rate/fn.rate.html) the number of years may be fractional and both values are
+//! positive, which is how the figure is usually quoted for a portfolio or a company's revenue.
+//!
+//! cagr = (ending_value / beginning_value)<sup>1 / years</sup> - 1
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // A portfolio that grew from $100,000 to $150,000 over three and a half years.
+//! let solution = cagr_solution(100_000, 150_000, 3.5);
+//! dbg!(&solution);
+//! ```
+
+/// Returns the **compound annual growth rate** that takes a beginning value to an ending value
+/// over a number of years, which may be fractional.
+///
+/// Related functions:
+/// * To keep the inputs and the formula in a struct use [`cagr_solution`].
+/// * For a whole number of periods with the crate's usual signs use [`rate`].
+///
+/// The formula is:
+///
+/// cagr = (ending_value / beginning_value)<sup>1 / years</sup> - 1
+///
+/// # Arguments
+/// * `beginning_value` - The value at the start, a positive amount.
+/// * `ending_value` - The value at the end, a positive amount.
+/// * `years` - The number of years between the two values, which may include a fraction of a
+/// year.
+///
+/// # Panics
+/// The call will fail if either value is not positive, if `years` is not positive, or if any value
+/// is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // A portfolio that grew from $100,000 to $150,000 over three and a half years.
+/// assert_rounded_6!(0.122824, cagr(100_000, 150_000, 3.5));
+///
+/// // A value that doubles in seven years grows by a little over 10% a year.
+/// assert_rounded_6!(0.104090, cagr(50, 100, 7.0));
+/// ```
+pub fn cagr<B, E>(beginning_value: B, ending_value: E, years: f64) -> f64
+    where
+        B: Into<f64> + Copy,
+        E: Into<f64> + Copy
+{
+    cagr_solution(beginning_value, ending_value, years).cagr()
+}
+
+/// Returns the **compound annual growth rate** along with the inputs and the formula.
+///
+/// Related functions:
+/// * To calculate only the growth rate as an `f64` use [`cagr`].
+///
+/// # Arguments
+/// * `beginning_value` - The value at the start, a positive amount.
+/// * `ending_value` - The value at the end, a positive amount.
+/// * `years` - The number of years between the two values, which may include a fraction of a
+/// year.
+///
+/// # Panics
+/// The call will fail if either value is not positive, if `years` is not positive, or if any value
+/// is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = cagr_solution(100_000, 150_000, 3.5);
+/// assert_rounded_6!(0.122824, solution.cagr());
+/// assert_eq!("0.122824 = (150000.0000 / 100000.0000)^(1 / 3.5000) - 1", solution.formula());
+/// ```
+pub fn cagr_solution<B, E>(beginning_value: B, ending_value: E, years: f64) -> CagrSolution
+    where
+        B: Into<f64> + Copy,
+        E: Into<f64> + Copy
+{
+    let beginning_value = beginning_value.into();
+    let ending_value = ending_value.into();
+    assert!(beginning_value.is_finite(), "The beginning value must be finite (not NaN or infinity)");
+    assert!(ending_value.is_finite(), "The ending value must be finite (not NaN or infinity)");
+    assert!(years.is_finite(), "The number of years must be finite (not NaN or infinity)");
+    assert!(beginning_value > 0.0, "The beginning value must be greater than zero.");
+    assert!(ending_value > 0.0, "The ending value must be greater than zero.");
+    assert!(years > 0.0, "The number of years must be greater than zero.");
+    CagrSolution::new(beginning_value, ending_value, years)
+}
+
+/// A record of a compound annual growth rate calculation. Created by calling [`cagr_solution`].
+#[derive(Clone, Debug)]
+pub struct CagrSolution {
+    beginning_value: f64,
+    ending_value: f64,
+    years: f64,
+    cagr: f64,
+    formula: String,
+    symbolic_formula: String,
+}
+
+impl CagrSolution {
+    pub(crate) fn new(beginning_value: f64, ending_value: f64, years: f64) -> Self {
+        let cagr = (ending_value / beginning_value).powf(1.0 / years) - 1.0;
+        assert!(cagr.is_finite());
+        let formula = format!("{:.6} = ({:.4} / {:.4})^(1 / {:.4}) - 1", cagr, ending_value, beginning_value, years);
+        let symbolic_formula = "cagr = (ending_value / beginning_value)^(1 / years) - 1".to_string();
+        Self {
+            beginning_value,
+            ending_value,
+            years,
+            cagr,
+            formula,
+            symbolic_formula,
+        }
+    }
+
+    /// Returns the value at the start.
+    pub fn beginning_value(&self) -> f64 {
+        self.beginning_value
+    }
+
+    /// Returns the value at the end.
+    pub fn ending_value(&self) -> f64 {
+        self.ending_value
+    }
+
+    /// Returns the number of years between the two values.
+    pub fn years(&self) -> f64 {
+        self.years
+    }
+
+    /// Returns the compound annual growth rate.
+    pub fn cagr(&self) -> f64 {
+        self.cagr
+    }
+
+    /// Returns a text version of the formula used to calculate the growth rate with the actual
+    /// values.
+    pub fn formula(&self) -> &str {
+        &self.formula
+    }
+
+    /// Returns a text version of the formula used to calculate the growth rate using variables such
+    /// as years for the number of years.
+    pub fn symbolic_formula(&self) -> &str {
+        &self.symbolic_formula
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn test_cagr_matches_rate() {
+        // With a whole number of years this is the same as the periodic rate with the usual signs.
+        let expected = rate(6, -2_500, 4_100, false);
+        assert_approx_equal!(expected, cagr(2_500, 4_100, 6.0));
+        // Growing at the CAGR for the fractional number of years reaches the ending value.
+        let solution = cagr_solution(100_000, 150_000, 3.5);
+        assert_approx_equal!(150_000.0, 100_000.0 * (1.0 + solution.cagr()).powf(3.5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cagr_negative_value() {
+        cagr(-100, 150, 3.0);
+    }
+}
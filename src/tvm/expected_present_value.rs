@@ -0,0 +1,241 @@
+//! **Expected present value over rate scenarios.** When the discount rate is uncertain, each of
+//! several possible rates can be given a probability. The expected present value is the
+//! probability-weighted average of the present value under each rate:
+//!
+//! expected_present_value = sum( probability * -future_value / (1 + rate)<sup>n</sup> )
+//!
+//! Because the present value curves as the rate changes this is not the same as discounting at the
+//! expected rate. Uncertainty in the rate raises the expected size of the present value.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! let scenarios = [(0.03, 0.25), (0.05, 0.50), (0.08, 0.25)];
+//! let solution = expected_present_value_solution(&scenarios, 10, 10_000);
+//! solution.print_table();
+//! ```
+
+use crate::*;
+
+// How far the probabilities may add up to something other than 1.0 to allow for rounding.
+const PROBABILITY_TOLERANCE: f64 = 0.000_001;
+
+/// Returns the **probability-weighted present value** of a future amount across several rate
+/// scenarios.
+///
+/// Related functions:
+/// * To see the present value and weighted contribution of each scenario use
+/// [`expected_present_value_solution`].
+/// * For a single known rate use [`present_value`].
+///
+/// See the [expected_present_value](./index.html) module page for the formula. As with
+/// [`present_value`] the result is negative if the future value is positive.
+///
+/// # Arguments
+/// * `scenarios` - Pairs of the periodic rate and the probability of each scenario. The
+/// probabilities must add up to 1.0.
+/// * `periods` - The number of periods until the future amount is received.
+/// * `future_value` - The amount to be discounted.
+///
+/// # Panics
+/// The call will fail if there are no scenarios, if any rate is less than or equal to -1.0, if any
+/// probability is negative, if the probabilities don't add up to 1.0, or if any value is not
+/// finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let scenarios = [(0.03, 0.25), (0.05, 0.50), (0.08, 0.25)];
+/// let pv = expected_present_value(&scenarios, 10, 10_000);
+/// assert_rounded_4!(-6_087.7848, pv);
+///
+/// // Discounting at the expected rate of 5.25% gives a smaller present value.
+/// assert_rounded_4!(-5_994.8588, present_value(0.0525, 10, 10_000, false));
+/// ```
+pub fn expected_present_value<T>(scenarios: &[(f64, f64)], periods: u32, future_value: T) -> f64
+    where T: Into<f64> + Copy
+{
+    expected_present_value_solution(scenarios, periods, future_value).expected_present_value()
+}
+
+/// Returns the **probability-weighted present value** of a future amount across several rate
+/// scenarios along with the present value and weighted contribution of each scenario.
+///
+/// Related functions:
+/// * To calculate only the expected present value as an `f64` use [`expected_present_value`].
+///
+/// # Arguments
+/// * `scenarios` - Pairs of the periodic rate and the probability of each scenario. The
+/// probabilities must add up to 1.0.
+/// * `periods` - The number of periods until the future amount is received.
+/// * `future_value` - The amount to be discounted.
+///
+/// # Panics
+/// The call will fail if there are no scenarios, if any rate is less than or equal to -1.0, if any
+/// probability is negative, if the probabilities don't add up to 1.0, or if any value is not
+/// finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let scenarios = [(0.03, 0.25), (0.05, 0.50), (0.08, 0.25)];
+/// let solution = expected_present_value_solution(&scenarios, 10, 10_000);
+/// let entries = solution.scenarios();
+/// assert_rounded_4!(-7_440.9391, entries[0].present_value());
+/// assert_rounded_4!(-1_860.2348, entries[0].weighted_present_value());
+/// assert_rounded_6!(0.0525, solution.expected_rate());
+/// assert_rounded_4!(-6_087.7848, solution.expected_present_value());
+/// ```
+/// Output of `solution.print_table()`:
+/// ```text
+///     rate  probability  present_value  weighted_present_value
+/// --------  -----------  -------------  ----------------------
+/// 0.030000     0.250000    -7_440.9391             -1_860.2348
+/// 0.050000     0.500000    -6_139.1325             -3_069.5663
+/// 0.080000     0.250000    -4_631.9349             -1_157.9837
+/// ```
+pub fn expected_present_value_solution<T>(scenarios: &[(f64, f64)], periods: u32, future_value: T) -> ExpectedPresentValueSolution
+    where T: Into<f64> + Copy
+{
+    let future_value = future_value.into();
+    assert!(!scenarios.is_empty(), "There must be at least one scenario.");
+    assert!(future_value.is_finite(), "The future value must be finite (not NaN or infinity)");
+    for (rate, probability) in scenarios.iter() {
+        assert!(rate.is_finite(), "The rates must be finite (not NaN or infinity)");
+        assert!(*rate > -1.0, "The rates must be greater than -1.0 (-100%).");
+        assert!(probability.is_finite(), "The probabilities must be finite (not NaN or infinity)");
+        assert!(*probability >= 0.0, "The probabilities must not be negative.");
+    }
+    let total_probability = scenarios.iter().map(|(_, probability)| probability).sum::<f64>();
+    assert!((total_probability - 1.0).abs() <= PROBABILITY_TOLERANCE, "The probabilities must add up to 1.0.");
+    let scenarios = scenarios.iter()
+        .map(|(rate, probability)| RateScenario::new(*rate, *probability, -future_value / (1.0 + rate).powf(periods as f64)))
+        .collect();
+    ExpectedPresentValueSolution::new(periods, future_value, scenarios)
+}
+
+/// A record of an expected present value across rate scenarios. Created by calling
+/// [`expected_present_value_solution`].
+#[derive(Clone, Debug)]
+pub struct ExpectedPresentValueSolution {
+    periods: u32,
+    future_value: f64,
+    scenarios: Vec<RateScenario>,
+    expected_present_value: f64,
+}
+
+impl ExpectedPresentValueSolution {
+    pub(crate) fn new(periods: u32, future_value: f64, scenarios: Vec<RateScenario>) -> Self {
+        let expected_present_value = scenarios.iter().map(|scenario| scenario.weighted_present_value).sum::<f64>();
+        assert!(expected_present_value.is_finite());
+        Self {
+            periods,
+            future_value,
+            scenarios,
+            expected_present_value,
+        }
+    }
+
+    /// Returns the number of periods.
+    pub fn periods(&self) -> u32 {
+        self.periods
+    }
+
+    /// Returns the amount that was discounted.
+    pub fn future_value(&self) -> f64 {
+        self.future_value
+    }
+
+    /// Returns each scenario with its present value and weighted contribution.
+    pub fn scenarios(&self) -> &[RateScenario] {
+        &self.scenarios
+    }
+
+    /// Returns the probability-weighted average of the rates.
+    pub fn expected_rate(&self) -> f64 {
+        self.scenarios.iter().map(|scenario| scenario.rate * scenario.probability).sum()
+    }
+
+    /// Returns the probability-weighted average of the present values, which is negative if the
+    /// future value is positive.
+    pub fn expected_present_value(&self) -> f64 {
+        self.expected_present_value
+    }
+
+    /// Prints a formatted table with the present value and weighted contribution of each scenario.
+    pub fn print_table(&self) {
+        self.print_table_locale_opt(None, None);
+    }
+
+    /// Prints a formatted table with the present value and weighted contribution of each scenario
+    /// using the given locale and precision for money amounts.
+    pub fn print_table_locale(&self, locale: &num_format::Locale, precision: usize) {
+        self.print_table_locale_opt(Some(locale), Some(precision));
+    }
+
+    fn print_table_locale_opt(&self, locale: Option<&num_format::Locale>, precision: Option<usize>) {
+        let columns = columns_with_strings(&[("rate", "r", true), ("probability", "r", true), ("present_value", "f", true), ("weighted_present_value", "f", true)]);
+        let data = self.scenarios.iter()
+            .map(|scenario| vec![scenario.rate.to_string(), scenario.probability.to_string(), scenario.present_value.to_string(), scenario.weighted_present_value.to_string()])
+            .collect::<Vec<_>>();
+        print_table_locale_opt(&columns, data, locale, precision);
+    }
+}
+
+/// One rate scenario within an [`ExpectedPresentValueSolution`].
+#[derive(Clone, Debug)]
+pub struct RateScenario {
+    rate: f64,
+    probability: f64,
+    present_value: f64,
+    weighted_present_value: f64,
+}
+
+impl RateScenario {
+    pub(crate) fn new(rate: f64, probability: f64, present_value: f64) -> Self {
+        assert!(present_value.is_finite());
+        Self {
+            rate,
+            probability,
+            present_value,
+            weighted_present_value: present_value * probability,
+        }
+    }
+
+    /// Returns the periodic rate for this scenario.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the probability of this scenario.
+    pub fn probability(&self) -> f64 {
+        self.probability
+    }
+
+    /// Returns the present value at this scenario's rate.
+    pub fn present_value(&self) -> f64 {
+        self.present_value
+    }
+
+    /// Returns the present value times the probability, which is this scenario's contribution to
+    /// the expected present value.
+    pub fn weighted_present_value(&self) -> f64 {
+        self.weighted_present_value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expected_present_value_single_scenario() {
+        assert_approx_equal!(present_value(0.045, 12, 25_000, false), expected_present_value(&[(0.045, 1.0)], 12, 25_000));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_expected_present_value_probabilities_must_add_to_one() {
+        expected_present_value(&[(0.03, 0.5), (0.06, 0.4)], 5, 1_000);
+    }
+}
@@ -311,6 +311,9 @@ pub fn future_value_schedule<T>(rates: &[f64], present_value: T) -> f64
 {
     let present_value= present_value.into();
     let periods = rates.len();
+    if periods == 0 {
+        warn!("The rates are empty so the schedule has no periods. Was a multi-period calculation intended?");
+    }
 
     // Check the parameters including all of the provided rates.
     for rate in rates {
@@ -392,7 +395,12 @@ pub(crate) fn future_value_internal(rate: f64, periods: f64, present_value: f64,
 
 pub(crate) fn future_value_solution_internal(rate: f64, periods: f64, present_value: f64, continuous_compounding: bool) -> TvmSolution {
     let future_value = future_value_internal(rate, periods, present_value, continuous_compounding);
-    let (formula, symbolic_formula) = if continuous_compounding {
+    let (formula, symbolic_formula) = future_value_formula(rate, periods, present_value, future_value, continuous_compounding);
+    TvmSolution::new_fractional_periods(TvmVariable::FutureValue, continuous_compounding, rate, periods, present_value, future_value, &formula, symbolic_formula)
+}
+
+pub(crate) fn future_value_formula(rate: f64, periods: f64, present_value: f64, future_value: f64, continuous_compounding: bool) -> (String, &'static str) {
+    if continuous_compounding {
         let formula = format!("{:.4} = {:.4} * {:.6}^({:.6} * {})", future_value, -present_value, std::f64::consts::E, rate, periods);
         let symbolic_formula = "fv = -pv * e^(rt)";
         (formula, symbolic_formula)
@@ -402,8 +410,7 @@ pub(crate) fn future_value_solution_internal(rate: f64, periods: f64, present_va
         let formula = format!("{:.4} = {:.4} * ({:.6} ^ {})", future_value, -present_value, rate_multiplier, periods);
         let symbolic_formula = "fv = -pv * (1 + r)^n";
         (formula, symbolic_formula)
-    };
-    TvmSolution::new_fractional_periods(TvmVariable::FutureValue, continuous_compounding, rate, periods, present_value, future_value, &formula, symbolic_formula)
+    }
 }
 
 fn check_future_value_parameters(rate: f64, _periods: f64, present_value: f64) {
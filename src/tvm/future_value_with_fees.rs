@@ -0,0 +1,344 @@
+//! **Future value after fees.** An investment account usually charges a percentage fee on the
+//! assets it holds, such as a fund's expense ratio, and sometimes a flat account fee as well. Both
+//! come out of the balance every period so the fees themselves lose the growth they would have
+//! earned, which is why a 1% fee costs far more than 1% of the ending value over a long horizon.
+//!
+//! In each period the balance first grows at the rate and then the fees are taken out:
+//!
+//! before<sub>t</sub> = after<sub>t-1</sub> * (1 + r)
+//!
+//! after<sub>t</sub> = before<sub>t</sub> * (1 - fee_rate) - fixed_fee
+//!
+//! with after<sub>0</sub> equal to the size of the present value. The balance never goes below zero. Once the
+//! fixed fee has used up the account the remaining periods show a zero balance.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // $10,000 for 30 years at 7% a year with a 1% annual fee and a $25 annual account fee.
+//! let solution = future_value_with_fees_solution(0.07, 30, -10_000, 0.01, 25);
+//! dbg!(&solution);
+//! solution.series().filter(|entry| entry.period() % 5 == 0).print_table();
+//! ```
+
+use crate::*;
+use std::ops::Deref;
+
+/// Returns the **future value of an investment after a percentage fee and a fixed fee are taken
+/// out each period**.
+///
+/// Related functions:
+/// * To see the value before and after fees in each period use [`future_value_with_fees_solution`].
+/// * For the future value with no fees use [`future_value`].
+///
+/// See the [future_value_with_fees](./index.html) module page for the formula. As with
+/// [`future_value`] the result has the opposite sign to the present value.
+///
+/// # Arguments
+/// * `rate` - The rate at which the investment grows per period, expressed as a floating point
+/// number. For instance 0.07 would mean 7%.
+/// * `periods` - The number of periods.
+/// * `present_value` - The starting value of the investment.
+/// * `annual_fee_rate` - The fee taken out each period as a fraction of the balance after growth,
+/// such as 0.01 for a 1% expense ratio. The name reflects the usual case of yearly periods; for
+/// monthly periods pass the monthly fee rate.
+/// * `fixed_fee_per_period` - The flat fee taken out each period, expressed as a positive number.
+///
+/// # Panics
+/// The call will fail if `rate` is less than or equal to -1.0, if `annual_fee_rate` is not between
+/// 0.0 and 1.0, if `fixed_fee_per_period` is negative, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // $10,000 for 30 years at 7% a year with a 1% annual fee.
+/// let fv = future_value_with_fees(0.07, 30, -10_000, 0.01, 0);
+/// assert_rounded_4!(56_307.8790, fv);
+///
+/// // With no fees it's the plain future value.
+/// let no_fees = future_value_with_fees(0.07, 30, -10_000, 0.0, 0);
+/// assert_rounded_4!(future_value(0.07, 30, -10_000, false), no_fees);
+/// ```
+pub fn future_value_with_fees<P, F>(rate: f64, periods: u32, present_value: P, annual_fee_rate: f64, fixed_fee_per_period: F) -> f64
+    where
+        P: Into<f64> + Copy,
+        F: Into<f64> + Copy
+{
+    future_value_with_fees_solution(rate, periods, present_value, annual_fee_rate, fixed_fee_per_period).future_value()
+}
+
+/// Returns the **future value of an investment after a percentage fee and a fixed fee are taken
+/// out each period** along with the inputs and a period-by-period schedule.
+///
+/// Related functions:
+/// * To calculate only the future value as an `f64` use [`future_value_with_fees`].
+///
+/// # Arguments
+/// * `rate` - The rate at which the investment grows per period, expressed as a floating point
+/// number. For instance 0.07 would mean 7%.
+/// * `periods` - The number of periods.
+/// * `present_value` - The starting value of the investment.
+/// * `annual_fee_rate` - The fee taken out each period as a fraction of the balance after growth,
+/// such as 0.01 for a 1% expense ratio.
+/// * `fixed_fee_per_period` - The flat fee taken out each period, expressed as a positive number.
+///
+/// # Panics
+/// The call will fail if `rate` is less than or equal to -1.0, if `annual_fee_rate` is not between
+/// 0.0 and 1.0, if `fixed_fee_per_period` is negative, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = future_value_with_fees_solution(0.07, 3, -10_000, 0.01, 25);
+/// assert_rounded_4!(11_807.0446, solution.future_value());
+///
+/// let series = solution.series();
+/// assert_eq!(3, series.len());
+/// assert_rounded_4!(10_700.0, series[0].value_before_fees());
+/// assert_rounded_4!(107.0, series[0].percentage_fee());
+/// assert_rounded_4!(25.0, series[0].fixed_fee());
+/// assert_rounded_4!(10_568.0, series[0].value_after_fees());
+///
+/// // The fees cost more than their sum because they would also have grown.
+/// assert_rounded_4!(12_250.43, solution.future_value_without_fees());
+/// assert_rounded_4!(443.3854, solution.fee_drag());
+/// assert!(solution.fee_drag() > solution.sum_of_fees());
+/// ```
+pub fn future_value_with_fees_solution<P, F>(rate: f64, periods: u32, present_value: P, annual_fee_rate: f64, fixed_fee_per_period: F) -> FeeSolution
+    where
+        P: Into<f64> + Copy,
+        F: Into<f64> + Copy
+{
+    let present_value = present_value.into();
+    let fixed_fee_per_period = fixed_fee_per_period.into();
+    assert!(rate.is_finite(), "The rate must be finite (not NaN or infinity)");
+    assert!(rate > -1.0, "The rate must be greater than -1.0 (-100%).");
+    assert!(present_value.is_finite(), "The present value must be finite (not NaN or infinity)");
+    assert!(annual_fee_rate.is_finite(), "The fee rate must be finite (not NaN or infinity)");
+    assert!((0.0..=1.0).contains(&annual_fee_rate), "The fee rate must be between 0.0 and 1.0.");
+    assert!(fixed_fee_per_period.is_finite(), "The fixed fee must be finite (not NaN or infinity)");
+    assert!(fixed_fee_per_period >= 0.0, "The fixed fee must be zero or positive.");
+
+    // Work with the size of the balance and apply the sign at the end so that the fixed fee always
+    // shrinks the balance toward zero. As with future_value() the result has the opposite sign.
+    let sign = if present_value < 0.0 { 1.0 } else { -1.0 };
+    let mut balance = present_value.abs();
+    let series = (1..=periods)
+        .map(|period| {
+            let value_before_fees = balance * (1.0 + rate);
+            let percentage_fee = value_before_fees * annual_fee_rate;
+            let fixed_fee = fixed_fee_per_period.min(value_before_fees - percentage_fee);
+            balance = value_before_fees - percentage_fee - fixed_fee;
+            FeePeriod::new(period, sign * value_before_fees, sign * percentage_fee, sign * fixed_fee, sign * balance)
+        })
+        .collect();
+    FeeSolution::new(rate, periods, present_value, annual_fee_rate, fixed_fee_per_period, sign * balance, series)
+}
+
+/// A record of the future value of an investment after fees. Created by calling
+/// [`future_value_with_fees_solution`].
+#[derive(Clone, Debug)]
+pub struct FeeSolution {
+    rate: f64,
+    periods: u32,
+    present_value: f64,
+    annual_fee_rate: f64,
+    fixed_fee_per_period: f64,
+    future_value: f64,
+    future_value_without_fees: f64,
+    series: FeeSeries,
+}
+
+impl FeeSolution {
+    pub(crate) fn new(rate: f64, periods: u32, present_value: f64, annual_fee_rate: f64, fixed_fee_per_period: f64, future_value: f64, series: Vec<FeePeriod>) -> Self {
+        assert!(future_value.is_finite());
+        let future_value_without_fees = crate::future_value(rate, periods, present_value, false);
+        Self {
+            rate,
+            periods,
+            present_value,
+            annual_fee_rate,
+            fixed_fee_per_period,
+            future_value,
+            future_value_without_fees,
+            series: FeeSeries::new(series),
+        }
+    }
+
+    /// Returns the periodic rate.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the number of periods.
+    pub fn periods(&self) -> u32 {
+        self.periods
+    }
+
+    /// Returns the starting value of the investment.
+    pub fn present_value(&self) -> f64 {
+        self.present_value
+    }
+
+    /// Returns the fee taken out each period as a fraction of the balance.
+    pub fn annual_fee_rate(&self) -> f64 {
+        self.annual_fee_rate
+    }
+
+    /// Returns the flat fee taken out each period.
+    pub fn fixed_fee_per_period(&self) -> f64 {
+        self.fixed_fee_per_period
+    }
+
+    /// Returns the value at the end of the last period after all fees.
+    pub fn future_value(&self) -> f64 {
+        self.future_value
+    }
+
+    /// Returns the value at the end of the last period if no fees had been charged.
+    pub fn future_value_without_fees(&self) -> f64 {
+        self.future_value_without_fees
+    }
+
+    /// Returns the sum of the percentage and fixed fees actually taken out over all periods.
+    pub fn sum_of_fees(&self) -> f64 {
+        self.series.iter().map(|entry| entry.percentage_fee + entry.fixed_fee).sum()
+    }
+
+    /// Returns how much lower the future value is because of the fees, including the growth the
+    /// fees would have earned.
+    pub fn fee_drag(&self) -> f64 {
+        self.future_value_without_fees - self.future_value
+    }
+
+    /// Returns the value before and after fees in each period. The amounts have the same sign as
+    /// the future value.
+    pub fn series(&self) -> FeeSeries {
+        self.series.clone()
+    }
+
+    /// Prints a formatted table with the details of each period.
+    pub fn print_table(&self) {
+        self.series.print_table();
+    }
+
+    /// Prints a formatted table with the details of each period using the given locale and
+    /// precision for money amounts.
+    pub fn print_table_locale(&self, locale: &num_format::Locale, precision: usize) {
+        self.series.print_table_locale(locale, precision);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FeeSeries(Vec<FeePeriod>);
+
+impl FeeSeries {
+    pub(crate) fn new(series: Vec<FeePeriod>) -> Self {
+        Self(series)
+    }
+
+    pub fn filter<P>(&self, predicate: P) -> Self
+        where P: Fn(&&FeePeriod) -> bool
+    {
+        Self(self.iter().filter(|x| predicate(x)).cloned().collect())
+    }
+
+    pub fn print_table(&self) {
+        self.print_table_locale_opt(None, None);
+    }
+
+    pub fn print_table_locale(&self, locale: &num_format::Locale, precision: usize) {
+        self.print_table_locale_opt(Some(locale), Some(precision));
+    }
+
+    fn print_table_locale_opt(&self, locale: Option<&num_format::Locale>, precision: Option<usize>) {
+        let columns = columns_with_strings(&[("period", "i", true), ("value_before_fees", "f", true), ("percentage_fee", "f", true), ("fixed_fee", "f", true), ("value_after_fees", "f", true)]);
+        let data = self.iter()
+            .map(|entry| vec![entry.period.to_string(), entry.value_before_fees.to_string(), entry.percentage_fee.to_string(), entry.fixed_fee.to_string(), entry.value_after_fees.to_string()])
+            .collect::<Vec<_>>();
+        print_table_locale_opt(&columns, data, locale, precision);
+    }
+}
+
+impl Deref for FeeSeries {
+    type Target = Vec<FeePeriod>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// One period within a [`FeeSeries`].
+#[derive(Clone, Debug)]
+pub struct FeePeriod {
+    period: u32,
+    value_before_fees: f64,
+    percentage_fee: f64,
+    fixed_fee: f64,
+    value_after_fees: f64,
+}
+
+impl FeePeriod {
+    pub(crate) fn new(period: u32, value_before_fees: f64, percentage_fee: f64, fixed_fee: f64, value_after_fees: f64) -> Self {
+        assert!(value_after_fees.is_finite());
+        Self {
+            period,
+            value_before_fees,
+            percentage_fee,
+            fixed_fee,
+            value_after_fees,
+        }
+    }
+
+    /// Returns the period number, starting with 1.
+    pub fn period(&self) -> u32 {
+        self.period
+    }
+
+    /// Returns the value after this period's growth and before its fees.
+    pub fn value_before_fees(&self) -> f64 {
+        self.value_before_fees
+    }
+
+    /// Returns the fee charged as a percentage of the value before fees.
+    pub fn percentage_fee(&self) -> f64 {
+        self.percentage_fee
+    }
+
+    /// Returns the flat fee charged, which is less than the full fixed fee only if the account ran
+    /// out in this period.
+    pub fn fixed_fee(&self) -> f64 {
+        self.fixed_fee
+    }
+
+    /// Returns the value at the end of the period after fees.
+    pub fn value_after_fees(&self) -> f64 {
+        self.value_after_fees
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_future_value_with_fees_zero_fees() {
+        for &(rate, periods, present_value) in [(0.07, 30, 10_000.0), (0.004, 120, -2_500.0), (0.0, 5, 100.0)].iter() {
+            let solution = future_value_with_fees_solution(rate, periods, present_value, 0.0, 0.0);
+            assert_rounded_6!(future_value(rate, periods, present_value, false), solution.future_value());
+            assert_rounded_6!(0.0, solution.fee_drag());
+        }
+    }
+
+    #[test]
+    fn test_future_value_with_fees_exhausted() {
+        // The fixed fee empties the account in the third period and it stays empty.
+        let solution = future_value_with_fees_solution(0.0, 5, 250, 0.0, 100);
+        let series = solution.series();
+        assert_approx_equal!(-50.0, series[2].fixed_fee());
+        assert_approx_equal!(0.0, series[2].value_after_fees());
+        assert_approx_equal!(0.0, series[4].fixed_fee());
+        assert_approx_equal!(0.0, solution.future_value());
+        assert_approx_equal!(-250.0, solution.sum_of_fees());
+    }
+}
@@ -9,10 +9,30 @@ pub mod future_value;
 #[doc(inline)]
 pub use future_value::*;
 
+pub mod future_value_with_fees;
+#[doc(inline)]
+pub use future_value_with_fees::*;
+
 pub mod present_value;
 #[doc(inline)]
 pub use present_value::*;
 
+pub mod present_value_fx;
+#[doc(inline)]
+pub use present_value_fx::*;
+
+pub mod present_value_integrated_rate;
+#[doc(inline)]
+pub use present_value_integrated_rate::*;
+
+pub mod present_value_collared;
+#[doc(inline)]
+pub use present_value_collared::*;
+
+pub mod expected_present_value;
+#[doc(inline)]
+pub use expected_present_value::*;
+
 pub mod periods;
 #[doc(inline)]
 pub use periods::*;
@@ -21,6 +41,18 @@ pub mod rate;
 #[doc(inline)]
 pub use rate::*;
 
+pub mod blended_effective_yield;
+#[doc(inline)]
+pub use blended_effective_yield::*;
+
+pub mod cagr;
+#[doc(inline)]
+pub use cagr::*;
+
+pub mod portfolio;
+#[doc(inline)]
+pub use portfolio::*;
+
 /// Enumeration used for the `calculated_field` field in [`TvmSolution`] and [`TvmSchedule`] to keep
 /// track of what was calculated, either the periodic rate, the number of periods, the present
 /// value, or the future value.
@@ -43,6 +75,16 @@ pub struct TvmSolution {
     future_value: f64,
     formula: String,
     symbolic_formula: String,
+    periods_per_year: Option<u32>,
+}
+
+/// A pair of Time Value of Money calculations made with the same inputs, one compounded period by
+/// period and one compounded continuously. It's the result of calling
+/// [`present_value_compare_compounding`].
+#[derive(Clone, Debug)]
+pub struct CompoundingComparison {
+    discrete: TvmSolution,
+    continuous: TvmSolution,
 }
 
 /// A record of a Time Value of Money calculation where the rate may vary by period.
@@ -59,7 +101,7 @@ pub struct TvmScheduleSolution {
 }
 
 #[derive(Clone, Debug)]
-pub struct TvmSeries(Vec<TvmPeriod>);
+pub struct TvmSeries(Vec<TvmPeriod>, Option<(u32, u32)>);
 
 /// The value of an investment at the end of a given period, part of a Time Value of Money
 /// calculation.
@@ -171,9 +213,84 @@ impl TvmSolution {
             future_value,
             formula: formula.to_string(),
             symbolic_formula: symbolic_formula.to_string(),
+            periods_per_year: None,
         }
     }
 
+    /// Returns the solution with the number of periods in a year recorded, such as 12 for a monthly
+    /// model. This doesn't change any of the calculated values but lets
+    /// [`TvmSolution::annualized_rate`] and [`TvmSolution::print_series_table`] report the annual
+    /// rate without the caller having to keep track of the basis.
+    ///
+    /// # Arguments
+    /// * `periods_per_year` - The number of periods in a year, such as 12 if the rate is monthly.
+    ///
+    /// # Panics
+    /// The call will fail if `periods_per_year` is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// # use finance_solution::*;
+    /// // A monthly rate of 0.5%.
+    /// let solution = future_value_solution(0.005, 120, -10_000, false).with_periods_per_year(12);
+    /// assert_eq!(Some(12), solution.periods_per_year());
+    /// assert_rounded_6!(0.061678, solution.annualized_rate());
+    /// ```
+    pub fn with_periods_per_year(mut self, periods_per_year: u32) -> Self {
+        assert!(periods_per_year > 0, "The number of periods per year must be greater than zero.");
+        self.periods_per_year = Some(periods_per_year);
+        self
+    }
+
+    /// Creates a `TvmSolution` from known values without solving for anything, for instance to
+    /// restore a solution that was saved earlier. The formulas are generated from the values as if
+    /// the solution had been calculated by the function matching `calculated_field`, such as
+    /// [`future_value_solution`] for `TvmVariable::FutureValue`.
+    ///
+    /// The values are taken as given so it's up to the caller to pass a consistent set. For
+    /// instance the future value is not checked against the rate, periods, and present value.
+    ///
+    /// # Arguments
+    /// * `calculated_field` - The value that was originally calculated.
+    /// * `continuous_compounding` - True if the value is compounded continuously rather than
+    /// period-by-period.
+    /// * `rate` - The periodic rate.
+    /// * `periods` - The number of periods. This may be fractional if the number of periods was
+    /// calculated.
+    /// * `present_value` - The present value.
+    /// * `future_value` - The future value.
+    ///
+    /// # Panics
+    /// The call will fail if any of the values are not finite, if the rate is less than -1.0, or if
+    /// the number of periods is negative.
+    ///
+    /// # Examples
+    /// ```
+    /// use finance_solution::*;
+    ///
+    /// let solution = future_value_solution(0.034, 10, 1_000, false);
+    ///
+    /// // Rebuild the solution from its stored values.
+    /// let restored = TvmSolution::from_values(TvmVariable::FutureValue, false, 0.034, 10.0, 1_000.0, solution.future_value());
+    /// assert_eq!(solution, restored);
+    /// assert_eq!("fv = -pv * (1 + r)^n", restored.symbolic_formula());
+    /// ```
+    pub fn from_values(calculated_field: TvmVariable, continuous_compounding: bool, rate: f64, periods: f64, present_value: f64, future_value: f64) -> Self {
+        assert!(rate.is_finite(), "The rate must be finite (not NaN or infinity)");
+        assert!(periods.is_finite(), "The number of periods must be finite (not NaN or infinity)");
+        assert!(present_value.is_finite(), "The present value must be finite (not NaN or infinity)");
+        assert!(future_value.is_finite(), "The future value must be finite (not NaN or infinity)");
+        let (formula, symbolic_formula) = match calculated_field {
+            TvmVariable::Rate => rate_formula(rate, round_fractional_periods(periods), present_value, future_value, continuous_compounding),
+            TvmVariable::Periods => periods_formula(rate, periods, present_value, future_value, continuous_compounding),
+            TvmVariable::PresentValue => present_value_formula(rate, periods, present_value, future_value, continuous_compounding),
+            TvmVariable::FutureValue => future_value_formula(rate, periods, present_value, future_value, continuous_compounding),
+        };
+        let solution = Self::new_fractional_periods(calculated_field, continuous_compounding, rate, periods, present_value, future_value, &formula, symbolic_formula);
+        solution.invariant();
+        solution
+    }
+
     /// Calculates the value of an investment after each period.
     ///
     /// # Examples
@@ -257,11 +374,78 @@ impl TvmSolution {
     /// dbg!(&filtered_series);
     /// assert_eq!(2, filtered_series.len());
     /// ```
+    ///
+    /// The series has one entry per period plus one for period 0, all held in memory at once. With
+    /// a very large number of periods such as a million this can exhaust the available memory. In
+    /// that case use [`TvmSolution::series_capped`] which limits the number of rows.
     pub fn series(&self) -> TvmSeries {
         let rates = initialized_vector(self.periods as usize, self.rate);
         series_internal(self.calculated_field.clone(), self.continuous_compounding, &rates, self.fractional_periods, self.present_value, self.future_value)
     }
 
+    /// Calculates the value of an investment after each period like [`TvmSolution::series`] but
+    /// never creates more than a given number of rows.
+    ///
+    /// If the solution has few enough periods this is the same as calling `series()`. Otherwise the
+    /// result holds the first and last periods, split as evenly as possible, and skips the periods
+    /// in between. [`TvmSeries::skipped_periods`] returns the range that was left out and printed
+    /// tables show a row of "..." in its place. Each value is calculated directly from the present
+    /// value rather than from the previous period so no intermediate periods have to be created.
+    ///
+    /// # Arguments
+    /// * `max_rows` - The maximum number of entries in the result, including period 0.
+    ///
+    /// # Panics
+    /// The call will fail if `max_rows` is less than 2 since the result must be able to hold at least
+    /// period 0 and the last period.
+    ///
+    /// # Examples
+    /// ```
+    /// // An investment of $100 compounded daily for a million days.
+    /// let solution = finance_solution::future_value_solution(0.00001, 1_000_000, 100, false);
+    ///
+    /// // Keep only ten rows: periods 0 through 4 and the last five periods.
+    /// let series = solution.series_capped(10);
+    /// assert_eq!(10, series.len());
+    /// assert_eq!(4, series[4].period());
+    /// assert_eq!(999_996, series[5].period());
+    /// assert_eq!(1_000_000, series[9].period());
+    /// assert_eq!(Some((5, 999_995)), series.skipped_periods());
+    /// finance_solution::assert_rounded_4!(solution.future_value(), series[9].value());
+    /// series.print_table();
+    /// ```
+    pub fn series_capped(&self, max_rows: usize) -> TvmSeries {
+        assert!(max_rows >= 2, "The maximum number of rows must be at least 2 to hold period 0 and the last period.");
+        let periods = self.periods as usize;
+        if periods < max_rows {
+            return self.series();
+        }
+
+        let head_rows = max_rows / 2;
+        let tail_rows = max_rows - head_rows;
+        let mut series = vec![];
+        for period in (0..head_rows).chain(periods + 1 - tail_rows..=periods) {
+            let (rate, value, formula, symbolic_formula) = if period == 0 {
+                let value = -self.present_value;
+                (0.0, value, format!("{:.4}", value), "value = pv")
+            } else if period == periods {
+                let value = self.future_value;
+                (self.rate, value, format!("{:.4}", value), "value = fv")
+            } else if self.continuous_compounding {
+                let value = -self.present_value * std::f64::consts::E.powf(self.rate * period as f64);
+                let formula = format!("{:.4} = {:.4} * {:.6}^({:.6} * {})", value, -self.present_value, std::f64::consts::E, self.rate, period);
+                (self.rate, value, formula, "value = -pv * e^(rt)")
+            } else {
+                let rate_multiplier = 1.0 + self.rate;
+                let value = -self.present_value * rate_multiplier.powf(period as f64);
+                let formula = format!("{:.4} = {:.4} * ({:.6} ^ {})", value, -self.present_value, rate_multiplier, period);
+                (self.rate, value, formula, "value = -pv * (1 + r)^n")
+            };
+            series.push(TvmPeriod::new(period as u32, rate, value, &formula, symbolic_formula));
+        }
+        TvmSeries::new_with_skipped_periods(series, (head_rows as u32, (periods - tail_rows) as u32))
+    }
+
     /// Prints a formatted table with the period-by-period details of a time-value-of-money
     /// calculation.
     ///
@@ -285,8 +469,12 @@ impl TvmSolution {
     ///      4  0.045000  11_925.1860
     ///      5  0.045000  12_461.8194
     /// ```
+    ///
+    /// If the number of periods per year was set with [`TvmSolution::with_periods_per_year`] the
+    /// annualized rate is printed below the table.
     pub fn print_series_table(&self) {
         self.series().print_table();
+        self.print_annualized_rate_note();
     }
 
     /// Prints a formatted table with the period-by-period details of a time-value-of-money
@@ -326,6 +514,13 @@ impl TvmSolution {
     /// ```
     pub fn print_series_table_locale(&self, locale: &num_format::Locale, precision: usize) {
         self.series().print_table_locale(locale, precision);
+        self.print_annualized_rate_note();
+    }
+
+    fn print_annualized_rate_note(&self) {
+        if let Some(periods_per_year) = self.periods_per_year {
+            println!("annualized rate: {:.6} ({} periods per year)", self.annualized_rate(), periods_per_year);
+        }
     }
 
     /// Returns a variant of [`TvmVariable`] showing which value was calculated, either the periodic
@@ -395,7 +590,161 @@ impl TvmSolution {
     pub fn symbolic_formula(&self) -> &str {
         &self.symbolic_formula
     }
-    
+
+    /// Returns the periodic rate converted to an annual rate. With period-by-period compounding
+    /// this is the effective annual rate (EAR) found by compounding the periodic rate over a year.
+    /// With continuous compounding it's the annual continuously compounded rate.
+    ///
+    /// Related functions:
+    /// * For the simple annual rate without compounding use [`nominal_annual_rate`](./struct.TvmSolution.html#method.nominal_annual_rate).
+    ///
+    /// The formula is:
+    ///
+    /// annual_rate = (1 + rate)<sup>periods_per_year</sup> - 1
+    ///
+    /// or with continuous compounding:
+    ///
+    /// annual_rate = rate * periods_per_year
+    ///
+    /// # Arguments
+    /// * `periods_per_year` - The number of periods in a year, such as 12 if the rate is monthly.
+    ///
+    /// # Panics
+    /// The call will fail if `periods_per_year` is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// # use finance_solution::*;
+    /// // A monthly rate of 1%.
+    /// let solution = future_value_solution(0.01, 36, -5_000, false);
+    /// assert_rounded_6!(0.126825, solution.annual_rate(12));
+    /// assert_rounded_6!(0.120000, solution.nominal_annual_rate(12));
+    /// ```
+    pub fn annual_rate(&self, periods_per_year: u32) -> f64 {
+        assert!(periods_per_year > 0, "The number of periods per year must be greater than zero.");
+        if self.continuous_compounding {
+            self.nominal_annual_rate(periods_per_year)
+        } else {
+            (1.0 + self.rate).powi(periods_per_year as i32) - 1.0
+        }
+    }
+
+    /// Returns the periodic rate converted to a nominal annual rate, also known as the annual
+    /// percentage rate (APR). This is the periodic rate multiplied by the number of periods in a
+    /// year without any compounding.
+    ///
+    /// Related functions:
+    /// * For the effective annual rate use [`annual_rate`](./struct.TvmSolution.html#method.annual_rate).
+    ///
+    /// # Arguments
+    /// * `periods_per_year` - The number of periods in a year, such as 12 if the rate is monthly.
+    ///
+    /// # Panics
+    /// The call will fail if `periods_per_year` is zero.
+    pub fn nominal_annual_rate(&self, periods_per_year: u32) -> f64 {
+        assert!(periods_per_year > 0, "The number of periods per year must be greater than zero.");
+        self.rate * periods_per_year as f64
+    }
+
+    /// Returns the number of periods in a year if it was set with
+    /// [`TvmSolution::with_periods_per_year`].
+    pub fn periods_per_year(&self) -> Option<u32> {
+        self.periods_per_year
+    }
+
+    /// Returns the periodic rate converted to an annual rate as with
+    /// [`annual_rate`](./struct.TvmSolution.html#method.annual_rate) using the number of periods
+    /// per year set with [`TvmSolution::with_periods_per_year`]. If that hasn't been set each
+    /// period is taken to be a year so this is the same as the periodic rate.
+    pub fn annualized_rate(&self) -> f64 {
+        self.annual_rate(self.periods_per_year.unwrap_or(1))
+    }
+
+    /// Returns the simple interest rate per period that would turn the present value into the same
+    /// future value over the same number of periods. With simple interest only the original amount
+    /// earns interest, so over more than one period the equivalent simple rate is higher than the
+    /// compound rate and the gap widens as the number of periods grows.
+    ///
+    /// The formula is:
+    ///
+    /// simple_rate = (-fv / pv - 1) / n
+    ///
+    /// where the present and future values have opposite signs as elsewhere in the crate.
+    ///
+    /// # Panics
+    /// The call will fail if the number of periods or the present value is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// # use finance_solution::*;
+    /// // $10,000 invested at 5% for ten years.
+    /// let solution = future_value_solution(0.05, 10, -10_000, false);
+    /// assert_rounded_4!(16_288.9463, solution.future_value());
+    ///
+    /// // Simple interest would need almost 6.3% per year to keep up.
+    /// assert_rounded_6!(0.062889, solution.equivalent_simple_interest_rate());
+    ///
+    /// // Over one period the two are the same.
+    /// let solution = future_value_solution(0.05, 1, -10_000, false);
+    /// assert_rounded_6!(0.05, solution.equivalent_simple_interest_rate());
+    /// ```
+    pub fn equivalent_simple_interest_rate(&self) -> f64 {
+        assert!(self.fractional_periods != 0.0, "The number of periods must not be zero.");
+        assert!(self.present_value != 0.0, "The present value must not be zero.");
+        (-self.future_value / self.present_value - 1.0) / self.fractional_periods
+    }
+
+    /// Returns a schedule in which the rate changes to a new rate after a given period, such as to
+    /// see how an investment's trajectory changes if rates jump partway through the horizon.
+    ///
+    /// The schedule starts from this solution's present value and runs for the same number of
+    /// periods. The periods up to and including `shock_period` use the original rate and the
+    /// periods after it use `new_rate`, so the series matches [`TvmSolution::series`] through the
+    /// shock period.
+    ///
+    /// If this solution was compounded continuously both rates are converted to the equivalent
+    /// periodic rate since a schedule always compounds period by period.
+    ///
+    /// # Arguments
+    /// * `shock_period` - The last period at the original rate. Zero means every period uses the
+    /// new rate.
+    /// * `new_rate` - The periodic rate after the shock period.
+    ///
+    /// # Panics
+    /// The call will fail if `shock_period` is greater than the number of periods or if `new_rate`
+    /// is not finite or is less than -1.0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use finance_solution::*;
+    /// // $10,000 invested at 5% for ten years, with the rate rising to 8% after the fourth year.
+    /// let solution = future_value_solution(0.05, 10, -10_000, false);
+    /// assert_rounded_4!(16_288.9463, solution.future_value());
+    /// let shocked = solution.with_rate_shock(4, 0.08);
+    /// assert_rounded_4!(19_288.5566, shocked.future_value());
+    ///
+    /// // The two paths are the same until the shock.
+    /// let series = solution.series();
+    /// let shocked_series = shocked.series();
+    /// assert_rounded_4!(12_155.0625, shocked_series[4].value());
+    /// assert_rounded_4!(series[4].value(), shocked_series[4].value());
+    /// assert!(shocked_series[5].value() > series[5].value());
+    /// ```
+    pub fn with_rate_shock(&self, shock_period: u32, new_rate: f64) -> TvmScheduleSolution {
+        assert!(shock_period <= self.periods, "The shock period must not be greater than the number of periods.");
+        assert!(new_rate.is_finite(), "The new rate must be finite (not NaN or infinity)");
+        assert!(new_rate >= -1.0, "The new rate must be greater than or equal to -1.0 (-100%).");
+        let (rate, new_rate) = if self.continuous_compounding {
+            (self.rate.exp() - 1.0, new_rate.exp() - 1.0)
+        } else {
+            (self.rate, new_rate)
+        };
+        let rates = (1..=self.periods)
+            .map(|period| if period <= shock_period { rate } else { new_rate })
+            .collect::<Vec<_>>();
+        future_value_schedule_solution(&rates, self.present_value)
+    }
+
     pub fn rate_solution(&self, continuous_compounding: bool, compounding_periods: Option<u32>) -> TvmSolution {
         let periods= compounding_periods.unwrap_or(self.periods);
         rate_solution_internal(periods, self.present_value, self.future_value, continuous_compounding)
@@ -421,6 +770,50 @@ impl TvmSolution {
         future_value_solution_internal(rate, periods, self.present_value, continuous_compounding)
     }
 
+    /// Returns a payment solution that pays off this solution's present value over a number of
+    /// periods at the same periodic rate. This is useful for discounting a future obligation to
+    /// today and then working out the level payment that would amortize it.
+    ///
+    /// The present value becomes the amount of the loan and the loan is paid off completely so the
+    /// payment solution's future value is always zero. This solution's future value is not carried
+    /// over as a balloon payment since at the same rate and number of periods the present value
+    /// would grow into it on its own and leave nothing to pay. Instead, if `periods` matches this
+    /// solution's number of periods and the payments are due at the end of each period, the
+    /// payments grow at the rate to exactly offset this solution's future value.
+    ///
+    /// The payments always use period-by-period compounding. If this solution was compounded
+    /// continuously its rate is converted to the equivalent periodic rate, as with
+    /// [`TvmSolution::with_rate_shock`], so the endpoints still match.
+    ///
+    /// # Arguments
+    /// * `periods` - The number of payments.
+    /// * `due_at_beginning` - True if the payments are due at the beginning of each period rather
+    /// than at the end.
+    ///
+    /// # Examples
+    /// ```
+    /// # use finance_solution::*;
+    /// // An obligation of $50,000 is due in five years. Money earns 0.5% per month.
+    /// let solution = present_value_solution(0.005, 60, 50_000, false);
+    /// assert_rounded_4!(-37_068.6098, solution.present_value());
+    ///
+    /// // Spread that amount over 60 monthly payments.
+    /// let payment_solution = solution.into_payment_solution(60, false);
+    /// assert_rounded_4!(716.6401, payment_solution.payment());
+    ///
+    /// // The schedule starts with the present value and ends with nothing remaining.
+    /// let series = payment_solution.series();
+    /// assert_rounded_4!(0.0, series.last().unwrap().principal_remaining());
+    ///
+    /// // With continuous compounding the loan uses the equivalent periodic rate.
+    /// let continuous = present_value_solution(0.05, 10, 1_000, true).into_payment_solution(10, false);
+    /// assert_rounded_6!(0.051271, continuous.rate());
+    /// ```
+    pub fn into_payment_solution(&self, periods: u32, due_at_beginning: bool) -> PaymentSolution {
+        let rate = if self.continuous_compounding { self.rate.exp() - 1.0 } else { self.rate };
+        payment_solution(rate, periods, self.present_value, 0.0, due_at_beginning)
+    }
+
     /// Returns a struct with a set of what-if scenarios for the present value needed with a variety
     /// of compounding periods.
     ///
@@ -589,12 +982,38 @@ impl TvmSolution {
     ///
     /// <img src="http://i.upmath.me/svg/%24%24%5Cbegin%7Btikzpicture%7D%5Bscale%3D1.0544%5D%5Csmall%0A%5Cbegin%7Baxis%7D%5Baxis%20line%20style%3Dgray%2C%0A%09samples%3D12%2C%0A%09width%3D9.0cm%2Cheight%3D6.4cm%2C%0A%09xmin%3D0%2C%20xmax%3D12%2C%0A%09ymin%3D119%2C%20ymax%3D123%2C%0A%09restrict%20y%20to%20domain%3D0%3A1000%2C%0A%09ytick%3D%7B120%2C%20121%2C%20122%7D%2C%0A%09xtick%3D%7B1%2C2%2C3%2C4%2C5%2C6%2C7%2C8%2C9%2C10%2C11%2C12%7D%2C%0A%09axis%20x%20line%3Dcenter%2C%0A%09axis%20y%20line%3Dcenter%2C%0A%09xlabel%3D%24n%24%2Cylabel%3D%24fv%24%5D%0A%5Caddplot%5Bblue%2Cdomain%3D1%3A12%2Cthick%2C%20only%20marks%5D%7B100*((1%2B(0.2%2Fx))%5Ex)%7D%3B%0A%5Caddplot%5Bblack%2Cdomain%3D1%3A12%2Cthick%5D%7B100*(e%5E(0.2))%7D%3B%0A%5Caddplot%5B%5D%20coordinates%20%7B(2.5%2C122.4)%7D%20node%7B%24fv%3D100e%5E%7B0.2%7D%24%7D%3B%0A%5Caddplot%5Bblue%5D%20coordinates%20%7B(4.8%2C120.7)%7D%20node%7B%24fv%3D100(1%2B%7B0.2%20%5Cover%20n%7D)%5En%24%7D%3B%0A%5Cpath%20(axis%20cs%3A0%2C122)%20node%20%5Banchor%3Dnorth%20west%2Cyshift%3D-0.07cm%5D%3B%0A%5Cend%7Baxis%7D%0A%5Cend%7Btikzpicture%7D%24%24" />
     pub fn future_value_vary_compounding_periods(&self, compounding_periods: &[u32], include_continuous_compounding: bool) -> ScenarioList {
+        self.future_value_vary_compounding_periods_iter(compounding_periods.iter().copied(), include_continuous_compounding)
+    }
+
+    /// Returns a struct with a set of what-if scenarios for the future value of an investment given
+    /// a variety of compounding periods. This is the same as
+    /// [`TvmSolution::future_value_vary_compounding_periods`] except that the compounding periods
+    /// may come from any iterator rather than a slice, so they can be generated lazily.
+    ///
+    /// # Arguments
+    /// * `compounding_periods` - The compounding periods to include in the scenarios. The result
+    /// will have a computed future value for each compounding period produced by the iterator.
+    /// * `include_continuous_compounding` - If true, adds one scenario at the end of the results
+    /// with continuous compounding instead of a given number of compounding periods.
+    ///
+    /// # Examples
+    /// ```
+    /// let solution = finance_solution::future_value_solution(0.05, 4, 100, false);
+    ///
+    /// // Compound 1, 2, 4, 8, ... up to 524,288 times.
+    /// let scenarios = solution.future_value_vary_compounding_periods_iter((0..20).map(|k| 2u32.pow(k)), true);
+    /// assert_eq!(21, scenarios.entries.len());
+    /// scenarios.print_table();
+    /// ```
+    pub fn future_value_vary_compounding_periods_iter<I>(&self, compounding_periods: I, include_continuous_compounding: bool) -> ScenarioList
+        where I: IntoIterator<Item = u32>
+    {
         let rate_for_single_period = self.rate * self.fractional_periods;
         let mut entries = vec![];
         for periods in compounding_periods {
-            let rate = rate_for_single_period / *periods as f64;
-            let future_value = future_value_internal(rate, *periods as f64, self.present_value, self.continuous_compounding);
-            entries.push((*periods as f64, future_value));
+            let rate = rate_for_single_period / periods as f64;
+            let future_value = future_value_internal(rate, periods as f64, self.present_value, self.continuous_compounding);
+            entries.push((periods as f64, future_value));
         }
         if include_continuous_compounding {
             let rate = rate_for_single_period;
@@ -657,6 +1076,51 @@ impl TvmSolution {
     }
 }
 
+impl CompoundingComparison {
+    pub(crate) fn new(discrete: TvmSolution, continuous: TvmSolution) -> Self {
+        assert!(!discrete.continuous_compounding());
+        assert!(continuous.continuous_compounding());
+        assert_eq!(discrete.calculated_field(), continuous.calculated_field());
+        Self {
+            discrete,
+            continuous,
+        }
+    }
+
+    /// Returns the solution with period-by-period compounding.
+    pub fn discrete(&self) -> &TvmSolution {
+        &self.discrete
+    }
+
+    /// Returns the solution with continuous compounding.
+    pub fn continuous(&self) -> &TvmSolution {
+        &self.continuous
+    }
+
+    /// Returns the calculated value with continuous compounding minus the calculated value with
+    /// period-by-period compounding.
+    pub fn difference(&self) -> f64 {
+        match self.discrete.calculated_field() {
+            TvmVariable::Rate => self.continuous.rate() - self.discrete.rate(),
+            TvmVariable::Periods => self.continuous.fractional_periods() - self.discrete.fractional_periods(),
+            TvmVariable::PresentValue => self.continuous.present_value() - self.discrete.present_value(),
+            TvmVariable::FutureValue => self.continuous.future_value() - self.discrete.future_value(),
+        }
+    }
+
+    /// Prints the two solutions side by side with the period-by-period compounding as "a" and the
+    /// continuous compounding as "b".
+    pub fn print_ab_comparison(&self) {
+        self.discrete.print_ab_comparison(&self.continuous);
+    }
+
+    /// Prints the two solutions side by side using the given locale and precision for money
+    /// amounts.
+    pub fn print_ab_comparison_locale(&self, locale: &num_format::Locale, precision: usize) {
+        self.discrete.print_ab_comparison_locale(&self.continuous, locale, precision);
+    }
+}
+
 impl PartialEq for TvmSolution {
     fn eq(&self, other: &Self) -> bool {
         self.calculated_field == other.calculated_field
@@ -668,6 +1132,7 @@ impl PartialEq for TvmSolution {
             && is_approx_equal!(self.future_value, other.future_value)
             && self.formula == other.formula
             && self.symbolic_formula == other.symbolic_formula
+            && self.periods_per_year == other.periods_per_year
     }
 }
 
@@ -717,6 +1182,63 @@ impl TvmScheduleSolution {
         self.periods
     }
 
+    /// Returns true if no rates were passed to the function. In that case there are no periods and
+    /// the calculated value simply mirrors the input value, which usually means the rates failed to
+    /// load rather than that a zero-period calculation was intended.
+    ///
+    /// # Examples
+    /// ```
+    /// let solution = finance_solution::future_value_schedule_solution(&[], -100_000);
+    /// assert!(solution.is_empty_schedule());
+    /// assert_eq!(100_000.0, solution.future_value());
+    ///
+    /// let solution = finance_solution::future_value_schedule_solution(&[0.05, 0.07, 0.05], -100_000);
+    /// assert!(!solution.is_empty_schedule());
+    /// ```
+    pub fn is_empty_schedule(&self) -> bool {
+        self.rates.is_empty()
+    }
+
+    /// Returns the one-period forward rates implied by the rates in this schedule when they're
+    /// interpreted as spot rates, where the rate for period t is the rate earned each period by
+    /// an investment running from the start to the end of period t.
+    ///
+    /// This is a different reading of the rates from the one used to calculate the present and
+    /// future values, where each rate applies only to its own period. Use it only when the rates
+    /// are in fact a spot rate curve.
+    ///
+    /// The formula is:
+    ///
+    /// f<sub>t</sub> = (1 + z<sub>t</sub>)<sup>t</sup> / (1 + z<sub>t-1</sub>)<sup>t-1</sup> - 1
+    ///
+    /// where z<sub>t</sub> is the spot rate for period t. The first forward rate is the first spot
+    /// rate.
+    ///
+    /// # Examples
+    /// ```
+    /// # use finance_solution::*;
+    /// // Spot rates of 2%, 3%, and 3.5% for one, two, and three years.
+    /// let solution = future_value_schedule_solution(&[0.02, 0.03, 0.035], -1_000);
+    /// let forward_rates = solution.forward_rates();
+    /// assert_rounded_6!(0.020000, forward_rates[0]);
+    /// assert_rounded_6!(0.040098, forward_rates[1]);
+    /// assert_rounded_6!(0.045073, forward_rates[2]);
+    ///
+    /// // Compounding the forward rates gives the same growth as the three-year spot rate.
+    /// let growth: f64 = forward_rates.iter().map(|rate| 1.0 + rate).product();
+    /// assert_approx_equal!(1.035_f64.powi(3), growth);
+    /// ```
+    pub fn forward_rates(&self) -> Vec<f64> {
+        let mut forward_rates = vec![];
+        let mut previous_factor = 1.0;
+        for (index, spot_rate) in self.rates.iter().enumerate() {
+            let factor = (1.0 + spot_rate).powi(index as i32 + 1);
+            forward_rates.push(factor / previous_factor - 1.0);
+            previous_factor = factor;
+        }
+        forward_rates
+    }
+
     /// Returns the present value which is a calculated value if this `TvmSchedule` struct is the
     /// result of a call to [`present_value_schedule_solution`] and otherwise is one of the input
     /// values.
@@ -775,33 +1297,166 @@ impl TvmScheduleSolution {
 
 impl TvmSeries {
     pub(crate) fn new(series: Vec<TvmPeriod>) -> Self {
-        Self {
-            0: series,
-        }
+        Self(series, None)
+    }
+
+    pub(crate) fn new_with_skipped_periods(series: Vec<TvmPeriod>, skipped_periods: (u32, u32)) -> Self {
+        Self(series, Some(skipped_periods))
     }
 
     pub fn filter<P>(&self, predicate: P) -> Self
         where P: Fn(&&TvmPeriod) -> bool
     {
-        Self {
-            0: self.iter().filter(|x| predicate(x)).cloned().collect()
-        }
+        Self(self.iter().filter(|x| predicate(x)).cloned().collect(), self.1)
+    }
+
+    /// Returns the first and last periods that were left out of a series created by
+    /// [`TvmSolution::series_capped`], or `None` if the series has every period. Tables printed
+    /// from the series show a row of "..." where the periods were skipped.
+    pub fn skipped_periods(&self) -> Option<(u32, u32)> {
+        self.1
+    }
+
+    /// Returns true if the value in any period has the opposite sign from the value in the period
+    /// before it. With valid rates the value never changes sign so this is a useful check on the
+    /// input data. See [`TvmSeries::sign_change_periods`] for the periods involved.
+    ///
+    /// # Examples
+    /// ```
+    /// let series = finance_solution::future_value_schedule_solution(&[0.05, -0.35, -1.0, 0.02], 1_000).series();
+    /// // Dropping to zero isn't a change of sign.
+    /// assert!(!series.has_sign_change());
+    /// ```
+    pub fn has_sign_change(&self) -> bool {
+        !self.sign_change_periods().is_empty()
+    }
+
+    /// Returns the periods where the value has the opposite sign from the value in the period
+    /// before it. A value of zero is not treated as either positive or negative so going to or
+    /// from zero is not a sign change.
+    pub fn sign_change_periods(&self) -> Vec<u32> {
+        self.windows(2)
+            .filter(|pair| pair[0].value * pair[1].value < 0.0)
+            .map(|pair| pair[1].period)
+            .collect()
+    }
+
+    /// Returns the period and value of each entry as a pair, such as for plotting the growth of an
+    /// investment. The values are not rounded.
+    ///
+    /// # Examples
+    /// ```
+    /// let points = finance_solution::future_value_solution(0.05, 2, -1_000, false).series().points();
+    /// assert_eq!(3, points.len());
+    /// assert_eq!((0, 1_000.0), points[0]);
+    /// assert_eq!(2, points[2].0);
+    /// finance_solution::assert_rounded_4!(1_102.5, points[2].1);
+    /// ```
+    pub fn points(&self) -> Vec<(u32, f64)> {
+        self.iter().map(|entry| (entry.period, entry.value)).collect()
+    }
+
+    /// Returns the period and rate of each entry as a pair. The rates are not rounded.
+    pub fn rate_points(&self) -> Vec<(u32, f64)> {
+        self.iter().map(|entry| (entry.period, entry.rate)).collect()
     }
 
     pub fn print_table(&self) {
-        self.print_table_locale_opt(None, None);
+        self.print_table_locale_opt(false, None, None);
     }
 
     pub fn print_table_locale(&self, locale: &num_format::Locale, precision: usize) {
-        self.print_table_locale_opt(Some(locale), Some(precision));
+        self.print_table_locale_opt(false, Some(locale), Some(precision));
+    }
+
+    /// Prints a formatted table like [`TvmSeries::print_table`] except that the rate column is shown
+    /// as a percentage such as "4.5000%" rather than as a decimal such as "0.045000".
+    ///
+    /// # Examples
+    /// ```
+    /// finance_solution::future_value_solution(0.045, 3, 10_000, false)
+    ///     .series()
+    ///     .print_table_percent();
+    /// ```
+    /// Output:
+    /// ```text
+    /// period     rate        value
+    /// ------  -------  -----------
+    ///      0  0.0000%  10_000.0000
+    ///      1  4.5000%  10_450.0000
+    ///      2  4.5000%  10_920.2500
+    ///      3  4.5000%  11_411.6612
+    /// ```
+    pub fn print_table_percent(&self) {
+        self.print_table_locale_opt(true, None, None);
+    }
+
+    /// Prints a formatted table with the rate column shown as a percentage, using the given locale
+    /// for the thousands and decimal separators. The precision applies to both the money amounts
+    /// and the percentages.
+    pub fn print_table_percent_locale(&self, locale: &num_format::Locale, precision: usize) {
+        self.print_table_locale_opt(true, Some(locale), Some(precision));
+    }
+
+    /// Prints a formatted table using the grouping and decimal places of the given style rather
+    /// than a locale. For instance turning off grouping writes 10_450.00 as 10450.00, which is
+    /// easier for another program to parse.
+    ///
+    /// # Examples
+    /// ```
+    /// # use finance_solution::*;
+    /// future_value_solution(0.045, 3, 10_000, false)
+    ///     .series()
+    ///     .print_table_styled(&NumberStyle::new(false, 2));
+    /// ```
+    /// Output:
+    /// ```text
+    /// period      rate     value
+    /// ------  --------  --------
+    ///      0  0.000000  10000.00
+    ///      1  0.045000  10450.00
+    ///      2  0.045000  10920.25
+    ///      3  0.045000  11411.66
+    /// ```
+    pub fn print_table_styled(&self, style: &NumberStyle) {
+        let (columns, data) = self.table_columns_and_data(false);
+        print_table_styled(&columns, data, style);
     }
 
-    fn print_table_locale_opt(&self, locale: Option<&num_format::Locale>, precision: Option<usize>) {
-        let columns = columns_with_strings(&[("period", "i", true), ("rate", "r", true), ("value", "f", true)]);
-        let data = self.iter()
+    /// Returns the series as a GitHub-flavored markdown table with the same columns and number
+    /// formatting as [`TvmSeries::print_table`], for pasting into a README or an issue comment.
+    ///
+    /// # Examples
+    /// ```
+    /// let series = finance_solution::future_value_solution(0.045, 2, -10_000, false).series();
+    /// let expected = "| period | rate | value |\n\
+    ///                 | ---: | ---: | ---: |\n\
+    ///                 | 0 | 0.000000 | 10_000.0000 |\n\
+    ///                 | 1 | 0.045000 | 10_450.0000 |\n\
+    ///                 | 2 | 0.045000 | 10_920.2500 |\n";
+    /// assert_eq!(expected, series.to_markdown());
+    /// ```
+    pub fn to_markdown(&self) -> String {
+        let (columns, data) = self.table_columns_and_data(false);
+        markdown_table(&columns, data)
+    }
+
+    fn print_table_locale_opt(&self, rate_as_percent: bool, locale: Option<&num_format::Locale>, precision: Option<usize>) {
+        let (columns, data) = self.table_columns_and_data(rate_as_percent);
+        print_table_locale_opt(&columns, data, locale, precision);
+    }
+
+    fn table_columns_and_data(&self, rate_as_percent: bool) -> TableColumnsAndData {
+        let rate_type = if rate_as_percent { "p" } else { "r" };
+        let columns = columns_with_strings(&[("period", "i", true), ("rate", rate_type, true), ("value", "f", true)]);
+        let mut data = self.iter()
             .map(|entry| vec![entry.period.to_string(), entry.rate.to_string(), entry.value.to_string()])
             .collect::<Vec<_>>();
-        print_table_locale_opt(&columns, data, locale, precision);
+        if let Some((first_skipped, _)) = self.1 {
+            let gap_index = self.iter().position(|entry| entry.period > first_skipped).unwrap_or(data.len());
+            data.insert(gap_index, vec![TABLE_GAP_MARKER.to_string(); columns.len()]);
+        }
+        (columns, data)
     }
 
     pub fn print_ab_comparison(
@@ -1645,6 +2300,60 @@ mod tests {
         */
     }
 
+    #[test]
+    fn test_from_values() {
+        let solutions = vec![
+            rate_solution(12, -500.0, 812.5, false),
+            periods_solution(0.04, -500.0, 812.5, true),
+            present_value_solution(0.04, 12, 812.5, false),
+            future_value_solution(0.04, 12, -500.0, true),
+        ];
+        for solution in solutions.iter() {
+            let restored = TvmSolution::from_values(solution.calculated_field().clone(), solution.continuous_compounding(), solution.rate(), solution.fractional_periods(), solution.present_value(), solution.future_value());
+            assert_eq!(solution, &restored);
+        }
+    }
+
+    #[test]
+    fn test_series_capped() {
+        // With few enough periods the capped series is the same as the full series.
+        let solution = future_value_solution(0.05, 6, -1_000.0, false);
+        let full = solution.series();
+        let capped = solution.series_capped(20);
+        assert_eq!(full.len(), capped.len());
+
+        // Otherwise we get the first and last periods and the values match the full series.
+        assert_eq!(None, capped.skipped_periods());
+        let capped = solution.series_capped(4);
+        let periods = capped.iter().map(|x| x.period()).collect::<Vec<_>>();
+        assert_eq!(vec![0, 1, 5, 6], periods);
+        assert_eq!(Some((2, 4)), capped.skipped_periods());
+
+        // Printed tables have a marker row in place of the skipped periods.
+        let (_, data) = capped.table_columns_and_data(false);
+        assert_eq!(5, data.len());
+        assert_eq!(vec![TABLE_GAP_MARKER; 3], data[2]);
+        assert!(capped.to_markdown().contains("| ... | ... | ... |"));
+        for entry in capped.iter() {
+            assert_approx_equal!(full[entry.period() as usize].value(), entry.value());
+        }
+
+        // The same for a present value calculation with continuous compounding.
+        let solution = present_value_solution(0.02, 9, 500.0, true);
+        let full = solution.series();
+        let capped = solution.series_capped(5);
+        assert_eq!(5, capped.len());
+        for entry in capped.iter() {
+            assert_approx_equal!(full[entry.period() as usize].value(), entry.value());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_series_capped_too_few_rows() {
+        future_value_solution(0.05, 6, -1_000.0, false).series_capped(1);
+    }
+
     fn setup_for_compounding_periods() -> (TvmSolution, Vec<u32>) {
         let rate = 0.10;
         let periods = 4;
@@ -1666,6 +2375,150 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_future_value_vary_compounding_periods_iter() {
+        let (solution, compounding_periods) = setup_for_compounding_periods();
+        let from_slice = solution.future_value_vary_compounding_periods(&compounding_periods, true);
+        let from_iter = solution.future_value_vary_compounding_periods_iter(compounding_periods.clone().into_iter(), true);
+        assert_eq!(from_slice.entries.len(), from_iter.entries.len());
+        for (a, b) in from_slice.entries.iter().zip(from_iter.entries.iter()) {
+            assert_eq!(a.input, b.input);
+            assert_approx_equal!(a.output, b.output);
+        }
+    }
+
+    #[test]
+    fn test_into_payment_solution() {
+        let solution = present_value_solution(0.0075, 48, 20_000, false);
+        let payment_solution = solution.into_payment_solution(48, false);
+        assert_approx_equal!(solution.present_value(), payment_solution.present_value());
+        assert_approx_equal!(0.0, payment_solution.future_value());
+        // The payments grow to offset the original future value.
+        let payments_future_value = future_value_annuity(solution.rate(), 48, payment_solution.payment(), false);
+        assert_approx_equal!(solution.future_value(), -payments_future_value);
+        let series = payment_solution.series();
+        assert_approx_equal!(0.0, series.last().unwrap().principal_remaining());
+    }
+
+    #[test]
+    fn test_into_payment_solution_continuous() {
+        let solution = present_value_solution(0.05, 10, 1_000, true);
+        let payment_solution = solution.into_payment_solution(10, false);
+        // The loan starts at the original present value.
+        let series = payment_solution.series();
+        assert_approx_equal!(solution.present_value(), payment_solution.present_value());
+        assert_approx_equal!(-solution.present_value(), series[0].principal_remaining() + series[0].principal());
+        // The payments grow at the equivalent periodic rate to the original future value.
+        let payments_future_value = (1..=10)
+            .map(|period| payment_solution.payment() * (1.0 + payment_solution.rate()).powf((10 - period) as f64))
+            .sum::<f64>();
+        assert_approx_equal!(solution.future_value(), payments_future_value);
+        assert_approx_equal!(0.0, series.last().unwrap().principal_remaining());
+    }
+
+    #[test]
+    fn test_forward_rates() {
+        // A flat spot curve has the same forward rates.
+        let solution = present_value_schedule_solution(&[0.04, 0.04, 0.04, 0.04], 1_000);
+        for forward_rate in solution.forward_rates() {
+            assert_approx_equal!(0.04, forward_rate);
+        }
+        // Compounding the forward rates for any number of periods reproduces the spot rate.
+        let spot_rates = [0.05, 0.045, 0.041, 0.043, 0.05];
+        let forward_rates = future_value_schedule_solution(&spot_rates, 1_000).forward_rates();
+        assert_eq!(spot_rates.len(), forward_rates.len());
+        for periods in 1..=spot_rates.len() {
+            let growth: f64 = forward_rates[..periods].iter().map(|rate| 1.0 + rate).product();
+            assert_approx_equal!((1.0 + spot_rates[periods - 1]).powi(periods as i32), growth);
+        }
+    }
+
+    #[test]
+    fn test_sign_change_periods() {
+        let series = future_value_solution(0.05, 10, 1_000, false).series();
+        assert!(!series.has_sign_change());
+        assert!(series.sign_change_periods().is_empty());
+
+        // Build a series with a bad rate that the calculation functions would reject.
+        let values = [100.0, 105.0, -52.5, -55.125, 0.0, 10.0, -10.0];
+        let series = TvmSeries::new(values.iter().enumerate()
+            .map(|(period, value)| TvmPeriod::new(period as u32, 0.05, *value, "test", "test"))
+            .collect());
+        assert!(series.has_sign_change());
+        assert_eq!(vec![2, 6], series.sign_change_periods());
+    }
+
+    #[test]
+    fn test_series_points() {
+        let series = future_value_schedule_solution(&[0.02, 0.04, 0.03], 5_000).series();
+        let points = series.points();
+        let rate_points = series.rate_points();
+        assert_eq!(series.len(), points.len());
+        for (entry, (point, rate_point)) in series.iter().zip(points.iter().zip(rate_points.iter())) {
+            assert_eq!((entry.period(), entry.value()), *point);
+            assert_eq!((entry.period(), entry.rate()), *rate_point);
+        }
+    }
+
+    #[test]
+    fn test_annual_rate() {
+        let solution = future_value_solution(0.01, 12, -100, false);
+        assert_rounded_4!(0.1268, solution.annual_rate(12));
+        assert_approx_equal!(0.12, solution.nominal_annual_rate(12));
+        // With one period per year the periodic rate is already annual.
+        assert_approx_equal!(0.01, solution.annual_rate(1));
+
+        let solution = future_value_solution(0.01, 12, -100, true);
+        assert_approx_equal!(0.12, solution.annual_rate(12));
+        assert_approx_equal!(0.12, solution.nominal_annual_rate(12));
+    }
+
+    #[test]
+    fn test_annualized_rate() {
+        // Without a number of periods per year each period is treated as a year.
+        let solution = future_value_solution(0.0125, 8, -2_500, false);
+        assert_eq!(None, solution.periods_per_year());
+        assert_approx_equal!(0.0125, solution.annualized_rate());
+
+        // Setting the basis only adds metadata so the calculated values are unchanged.
+        let quarterly = solution.clone().with_periods_per_year(4);
+        assert_approx_equal!(solution.annual_rate(4), quarterly.annualized_rate());
+        assert_approx_equal!(solution.future_value(), quarterly.future_value());
+        assert_ne!(solution, quarterly);
+    }
+
+    #[test]
+    fn test_equivalent_simple_interest_rate() {
+        // Simple interest at the equivalent rate reaches the same future value.
+        let solution = present_value_solution(0.05, 10, 25_000, false);
+        let simple_rate = solution.equivalent_simple_interest_rate();
+        assert!(simple_rate > 0.05);
+        assert_approx_equal!(solution.future_value(), -solution.present_value() * (1.0 + simple_rate * 10.0));
+
+        // With a negative rate compounding shrinks the losses, so simple interest needs a smaller
+        // loss per period to end up in the same place.
+        let solution = future_value_solution(-0.03, 6, -1_000, false);
+        assert_rounded_6!(-0.027838, solution.equivalent_simple_interest_rate());
+    }
+
+    #[test]
+    fn test_with_rate_shock() {
+        // Starting from a present value calculation the shocked schedule starts from the same
+        // amount and a shock to the same rate changes nothing.
+        let solution = present_value_solution(0.006, 24, 5_000, false);
+        let unchanged = solution.with_rate_shock(10, 0.006);
+        assert_approx_equal!(solution.present_value(), unchanged.present_value());
+        assert_approx_equal!(solution.future_value(), unchanged.future_value());
+
+        // A shock at the last period also leaves the schedule unchanged, while a shock at period 0
+        // applies the new rate throughout. Continuous compounding carries over to the schedule.
+        let solution = future_value_solution(0.03, 6, -1_000, true);
+        assert_approx_equal!(solution.future_value(), solution.with_rate_shock(6, 0.09).future_value());
+        let shocked = solution.with_rate_shock(0, 0.01);
+        assert_approx_equal!(future_value(0.01, 6, -1_000, true), shocked.future_value());
+        assert_eq!(6, shocked.periods());
+    }
+
     #[test]
     fn test_with_compounding_periods_vary_present_value() {
         println!("\ntest_with_compounding_periods_vary_present_value()\n");
@@ -302,7 +302,12 @@ pub(crate) fn periods_internal(rate: f64, present_value: f64, future_value: f64,
 pub(crate) fn periods_solution_internal(rate: f64, present_value: f64, future_value: f64, continuous_compounding: bool) -> TvmSolution {
     let fractional_periods = periods_internal(rate, present_value, future_value, continuous_compounding);
     assert!(fractional_periods >= 0.0);
-    let (formula, symbolic_formula) = if continuous_compounding {
+    let (formula, symbolic_formula) = periods_formula(rate, fractional_periods, present_value, future_value, continuous_compounding);
+    TvmSolution::new_fractional_periods(TvmVariable::Periods,continuous_compounding, rate, fractional_periods, present_value, future_value, &formula, symbolic_formula)
+}
+
+pub(crate) fn periods_formula(rate: f64, fractional_periods: f64, present_value: f64, future_value: f64, continuous_compounding: bool) -> (String, &'static str) {
+    if continuous_compounding {
         let formula = format!("{:.2} = ln({:.4} / {:.4}) / {:.6}", fractional_periods, -future_value, present_value, rate);
         let symbolic_formula = "n = ln(-fv / pv) / r";
         (formula, symbolic_formula)
@@ -311,8 +316,7 @@ pub(crate) fn periods_solution_internal(rate: f64, present_value: f64, future_va
         let formula = format!("{:.2} = log({:.4} / {:.4}, base {:.6})", fractional_periods, -future_value, present_value, rate_multiplier);
         let symbolic_formula = "n = log(-fv / pv, base (1 + r))";
         (formula, symbolic_formula)
-    };
-    TvmSolution::new_fractional_periods(TvmVariable::Periods,continuous_compounding, rate, fractional_periods, present_value, future_value, &formula, symbolic_formula)
+    }
 }
 
 fn check_periods_parameters(rate: f64, present_value: f64, future_value: f64) {
@@ -0,0 +1,136 @@
+//! **Portfolios of Time Value of Money calculations.** A [`Portfolio`] holds several
+//! [`TvmSolution`]s, such as each of a client's accounts, and rolls them up into combined present
+//! and future values and a combined period-by-period series.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! let mut portfolio = Portfolio::new();
+//! portfolio.add(future_value_solution(0.05, 10, -10_000, false));
+//! portfolio.add(future_value_solution(0.03, 5, -25_000, false));
+//! portfolio.series().print_table();
+//! ```
+
+use crate::*;
+
+/// A collection of [`TvmSolution`]s that are summarized together.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // A retirement account growing at 6% for 20 years and a savings account growing at 2% for 5.
+/// let mut portfolio = Portfolio::new();
+/// portfolio.add(future_value_solution(0.06, 20, -50_000, false));
+/// portfolio.add(future_value_solution(0.02, 5, -10_000, false));
+/// assert_eq!(2, portfolio.holdings().len());
+/// assert_rounded_4!(-60_000.0, portfolio.total_present_value());
+/// assert_rounded_4!(171_397.5816, portfolio.total_future_value());
+///
+/// // The retirement account is five sixths of the starting value.
+/// assert_rounded_6!(0.053333, portfolio.weighted_rate());
+///
+/// // The savings account stops growing after period 5 but is still counted in the total.
+/// let series = portfolio.series();
+/// assert_eq!(21, series.len());
+/// assert_rounded_4!(portfolio.total_future_value(), series[20].value());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Portfolio {
+    holdings: Vec<TvmSolution>,
+}
+
+impl Portfolio {
+    /// Creates an empty portfolio.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a holding to the portfolio.
+    pub fn add(&mut self, solution: TvmSolution) {
+        self.holdings.push(solution);
+    }
+
+    /// Returns the holdings in the order they were added.
+    pub fn holdings(&self) -> &[TvmSolution] {
+        &self.holdings
+    }
+
+    /// Returns the sum of the present values of the holdings.
+    pub fn total_present_value(&self) -> f64 {
+        self.holdings.iter().map(|holding| holding.present_value()).sum()
+    }
+
+    /// Returns the sum of the future values of the holdings. The holdings may have different
+    /// numbers of periods so this is the total of the values each one reaches at its own end.
+    pub fn total_future_value(&self) -> f64 {
+        self.holdings.iter().map(|holding| holding.future_value()).sum()
+    }
+
+    /// Returns the periodic rate of the holdings weighted by the size of each holding's present
+    /// value, or zero if the portfolio is empty or all of the present values are zero.
+    pub fn weighted_rate(&self) -> f64 {
+        let total_weight: f64 = self.holdings.iter().map(|holding| holding.present_value().abs()).sum();
+        if total_weight == 0.0 {
+            return 0.0;
+        }
+        self.holdings.iter().map(|holding| holding.rate() * holding.present_value().abs()).sum::<f64>() / total_weight
+    }
+
+    /// Calculates the combined value of the holdings at the end of each period, starting with
+    /// period 0. A holding with fewer periods than the longest one keeps its final value for the
+    /// remaining periods. The rate for each period is the growth in the combined value from the
+    /// period before.
+    ///
+    /// As with [`TvmSolution::series`] the values have the same sign as the future values.
+    pub fn series(&self) -> TvmSeries {
+        let holding_series = self.holdings.iter().map(|holding| holding.series()).collect::<Vec<_>>();
+        let rows = holding_series.iter().map(|series| series.len()).max().unwrap_or(0);
+        let mut series = vec![];
+        let mut previous_value = 0.0;
+        for row in 0..rows {
+            let values = holding_series.iter()
+                .filter_map(|series| series.get(row).or_else(|| series.last()).map(|entry| entry.value()))
+                .collect::<Vec<_>>();
+            let value: f64 = values.iter().sum();
+            let rate = if row == 0 || previous_value == 0.0 { 0.0 } else { value / previous_value - 1.0 };
+            let formula = format!("{:.4} = {}", value, values.iter().map(|value| format!("{:.4}", value)).collect::<Vec<_>>().join(" + "));
+            series.push(TvmPeriod::new(row as u32, rate, value, &formula, "value = sum(holding_value)"));
+            previous_value = value;
+        }
+        TvmSeries::new(series)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_portfolio_totals() {
+        let solutions = vec![
+            present_value_solution(0.04, 8, 15_000, false),
+            future_value_solution(0.07, 12, -2_000, false),
+            future_value_solution(0.01, 3, -40_000, true),
+        ];
+        let mut portfolio = Portfolio::new();
+        for solution in solutions.iter() {
+            portfolio.add(solution.clone());
+        }
+        let present_value: f64 = solutions.iter().map(|solution| solution.present_value()).sum();
+        let future_value: f64 = solutions.iter().map(|solution| solution.future_value()).sum();
+        assert_approx_equal!(present_value, portfolio.total_present_value());
+        assert_approx_equal!(future_value, portfolio.total_future_value());
+        let series = portfolio.series();
+        assert_eq!(13, series.len());
+        assert_approx_equal!(-present_value, series[0].value());
+        assert_approx_equal!(future_value, series[12].value());
+    }
+
+    #[test]
+    fn test_portfolio_empty() {
+        let portfolio = Portfolio::new();
+        assert_eq!(0.0, portfolio.total_present_value());
+        assert_eq!(0.0, portfolio.weighted_rate());
+        assert!(portfolio.series().is_empty());
+    }
+}
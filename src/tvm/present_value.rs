@@ -126,6 +126,63 @@ pub fn present_value<T>(rate: f64, periods: u32, future_value: T, continuous_com
     present_value_internal(rate, periods as f64, future_value.into(), continuous_compounding)
 }
 
+/// Returns the current value of a future amount using a fixed rate, working out the discount in
+/// log space for better precision over very long horizons.
+///
+/// The result is the same as [`present_value`] except for rounding. Rather than compounding
+/// (1 + rate) the discount is found as a single exponent:
+///
+/// pv = -fv * e<sup>-n * ln(1 + r)</sup>
+///
+/// where ln(1 + r) is calculated directly from the rate. This avoids the rounding of 1 + r itself
+/// when the rate is small and the error that builds up when the value is discounted one period at
+/// a time as in the series from [`present_value_solution`]. With continuous compounding the
+/// exponent is simply -r * n.
+///
+/// # Arguments
+/// * `rate` - The rate at which the investment grows or shrinks per period,
+/// expressed as a floating point number. For instance 0.05 would mean 5% growth. Often appears as
+/// `r` or `i` in formulas.
+/// * `periods` - The number of periods such as quarters or years. Often appears as `n` or `t`.
+/// * `future_value` - The final value of the investment.
+/// * `continuous_compounding` - True for continuous compounding, false for simple compounding.
+///
+/// # Panics
+/// The call will fail if `rate` is less than -1.0, if the future value is zero, or if the result is
+/// not finite. With zero periods the result is simply the negative of the future value whatever
+/// the rate.
+///
+/// # Examples
+/// ```
+/// use finance_solution::*;
+///
+/// // $1,000,000 in 1,000 periods at 0.1% per period.
+/// let stable = present_value_stable(0.001, 1_000, 1_000_000, false);
+/// assert_rounded_8!(-368_063.30428878, stable);
+///
+/// // Continuous compounding.
+/// let stable = present_value_stable(0.015, 48, 50_000, true);
+/// assert_rounded_4!(present_value(0.015, 48, 50_000, true), stable);
+/// ```
+pub fn present_value_stable<T>(rate: f64, periods: u32, future_value: T, continuous_compounding: bool) -> f64
+    where T: Into<f64> + Copy
+{
+    let future_value = future_value.into();
+    check_present_value_parameters(rate, periods as f64, future_value);
+    if periods == 0 {
+        // Nothing is discounted. Returning here also avoids 0 * ln(0) when the rate is -100%.
+        return -future_value;
+    }
+    let exponent = if continuous_compounding {
+        rate * periods as f64
+    } else {
+        periods as f64 * rate.ln_1p()
+    };
+    let present_value = -future_value * (-exponent).exp();
+    assert!(present_value.is_finite());
+    present_value
+}
+
 /// Calculates the current value of a future amount using a fixed rate and returns a struct
 /// with the inputs and the calculated value. This is used for keeping track of a collection of
 /// financial scenarios so that they can be examined later.
@@ -248,6 +305,207 @@ pub fn present_value_solution<T>(rate: f64, periods: u32, future_value: T, conti
     present_value_solution_internal(rate, periods as f64, future_value.into(), continuous_compounding)
 }
 
+/// Calculates the present value with both period-by-period and continuous compounding and returns
+/// the two solutions together so that they can be compared.
+///
+/// Related functions:
+/// * To calculate a present value with only one kind of compounding use [`present_value_solution`].
+///
+/// # Arguments
+/// * `rate` - The rate at which the investment grows or shrinks per period, expressed as a
+/// floating point number. For instance 0.05 would mean 5%. Often appears as `r` or `i` in formulas.
+/// * `periods` - The number of periods such as quarters or years. Often appears as `n` or `t`.
+/// * `future_value` - The final value of the investment.
+///
+/// # Panics
+/// The call will fail if `rate` is less than -1.0 as described in [`present_value_solution`].
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let comparison = present_value_compare_compounding(0.08, 10, 50_000);
+///
+/// // Continuous compounding grows faster so less money is needed today.
+/// assert_rounded_4!(-23_159.6744, comparison.discrete().present_value());
+/// assert_rounded_4!(-22_466.4482, comparison.continuous().present_value());
+/// assert_rounded_4!(693.2262, comparison.difference());
+///
+/// // Show the two solutions side by side along with their period-by-period values.
+/// comparison.print_ab_comparison();
+/// ```
+pub fn present_value_compare_compounding<T>(rate: f64, periods: u32, future_value: T) -> CompoundingComparison
+    where T: Into<f64> + Copy
+{
+    let discrete = present_value_solution(rate, periods, future_value, false);
+    let continuous = present_value_solution(rate, periods, future_value, true);
+    CompoundingComparison::new(discrete, continuous)
+}
+
+/// Returns the present value of an amount that arrives part of a period earlier than the end of
+/// the last period.
+///
+/// The value is discounted for `periods - fraction_offset` periods. An offset of 0.0 is the usual
+/// end-of-period timing and an offset of 1.0 is the beginning-of-period timing of an annuity due.
+/// An offset of 0.5 is for an amount that arrives in the middle of the last period.
+///
+/// Related functions:
+/// * To see the number of periods used for discounting use [`present_value_shifted_solution`].
+/// * For an amount that arrives at the end of a whole number of periods use [`present_value`].
+///
+/// # Arguments
+/// * `rate` - The rate at which the investment grows or shrinks per period, expressed as a
+/// floating point number. For instance 0.05 would mean 5%. Often appears as `r` or `i` in formulas.
+/// * `future_value` - The amount that arrives.
+/// * `periods` - The number of periods such as quarters or years until the end of the period in
+/// which the amount arrives. This may be fractional.
+/// * `fraction_offset` - How much of a period before the end of the last period the amount arrives,
+/// from 0.0 to 1.0.
+/// * `continuous_compounding` - True for continuous compounding, false for simple compounding.
+///
+/// # Panics
+/// The call will fail if `fraction_offset` is outside the range 0.0 to 1.0, if it's greater than
+/// `periods`, or for the same reasons as [`present_value`].
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // $10,000 arriving in the middle of the fifth year, discounted at 6% a year.
+/// let pv = present_value_shifted(0.06, 10_000, 5.0, 0.5, false);
+/// assert_rounded_4!(-7_693.4938, pv);
+///
+/// // With no offset this is the same as the present value at the end of the fifth year.
+/// assert_rounded_4!(present_value(0.06, 5, 10_000, false), present_value_shifted(0.06, 10_000, 5.0, 0.0, false));
+/// ```
+pub fn present_value_shifted<T>(rate: f64, future_value: T, periods: f64, fraction_offset: f64, continuous_compounding: bool) -> f64
+    where T: Into<f64> + Copy
+{
+    present_value_shifted_solution(rate, future_value, periods, fraction_offset, continuous_compounding).present_value()
+}
+
+/// Calculates the present value of an amount that arrives part of a period earlier than the end of
+/// the last period and returns a struct with the inputs and the calculated value.
+///
+/// The solution's [`TvmSolution::fractional_periods`] is the number of periods actually used for
+/// discounting, which is `periods - fraction_offset`.
+///
+/// Related functions:
+/// * To calculate only the present value as an `f64` use [`present_value_shifted`].
+///
+/// # Arguments
+/// * `rate` - The rate at which the investment grows or shrinks per period, expressed as a
+/// floating point number. For instance 0.05 would mean 5%. Often appears as `r` or `i` in formulas.
+/// * `future_value` - The amount that arrives.
+/// * `periods` - The number of periods such as quarters or years until the end of the period in
+/// which the amount arrives. This may be fractional.
+/// * `fraction_offset` - How much of a period before the end of the last period the amount arrives,
+/// from 0.0 to 1.0.
+/// * `continuous_compounding` - True for continuous compounding, false for simple compounding.
+///
+/// # Panics
+/// The call will fail if `fraction_offset` is outside the range 0.0 to 1.0, if it's greater than
+/// `periods`, or for the same reasons as [`present_value`].
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = present_value_shifted_solution(0.015, 2_500, 8.0, 0.25, false);
+/// assert_rounded_4!(7.75, solution.fractional_periods());
+/// assert_eq!("pv = -fv / (1 + r)^n", solution.symbolic_formula());
+/// ```
+pub fn present_value_shifted_solution<T>(rate: f64, future_value: T, periods: f64, fraction_offset: f64, continuous_compounding: bool) -> TvmSolution
+    where T: Into<f64> + Copy
+{
+    assert!(periods.is_finite(), "The number of periods must be finite (not NaN or infinity)");
+    assert!((0.0..=1.0).contains(&fraction_offset), "The fraction offset must be from 0.0 to 1.0.");
+    assert!(fraction_offset <= periods, "The fraction offset must not be greater than the number of periods.");
+    present_value_solution_internal(rate, periods - fraction_offset, future_value.into(), continuous_compounding)
+}
+
+/// Returns the present value of a future amount where interest compounds several times within
+/// each period, such as a rate stated per year that compounds monthly.
+///
+/// The rate for each subperiod is `rate / subperiods_per_period` and it's compounded
+/// `periods * subperiods_per_period` times. With one subperiod per period this is the same as
+/// [`present_value`] with simple compounding and as the number of subperiods grows it approaches
+/// [`present_value`] with continuous compounding.
+///
+/// Related functions:
+/// * To see the effective rate per period use [`present_value_subperiod_solution`].
+///
+/// # Arguments
+/// * `rate` - The nominal rate per period, expressed as a floating point number. For instance 0.05
+/// would mean 5%.
+/// * `periods` - The number of periods such as quarters or years.
+/// * `future_value` - The final value of the investment.
+/// * `subperiods_per_period` - The number of times interest compounds within each period.
+///
+/// # Panics
+/// The call will fail if `subperiods_per_period` is zero, if the rate per subperiod is less than
+/// or equal to -1.0, or if `rate` or `future_value` is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // $10,000 due in five years at 6% a year compounded monthly.
+/// assert_rounded_4!(-7_413.7220, present_value_subperiod(0.06, 5, 10_000, 12));
+///
+/// // Compounding more often brings it close to continuous compounding.
+/// let continuous = present_value(0.06, 5, 10_000, true);
+/// assert_rounded_4!(-7_408.1822, continuous);
+/// assert!((present_value_subperiod(0.06, 5, 10_000, 1_000_000) - continuous).abs() < 0.001);
+/// ```
+pub fn present_value_subperiod<T>(rate: f64, periods: u32, future_value: T, subperiods_per_period: u32) -> f64
+    where T: Into<f64> + Copy
+{
+    present_value_subperiod_solution(rate, periods, future_value, subperiods_per_period).present_value()
+}
+
+/// Calculates the present value of a future amount where interest compounds several times within
+/// each period and returns a struct with the inputs and the calculated value.
+///
+/// The solution's [`TvmSolution::rate`] is the effective rate per period, which is
+/// (1 + rate / subperiods_per_period)<sup>subperiods_per_period</sup> - 1, so the period-by-period
+/// values in [`TvmSolution::series`] are at the end of each full period.
+///
+/// Related functions:
+/// * To calculate only the present value as an `f64` use [`present_value_subperiod`].
+///
+/// # Arguments
+/// * `rate` - The nominal rate per period, expressed as a floating point number. For instance 0.05
+/// would mean 5%.
+/// * `periods` - The number of periods such as quarters or years.
+/// * `future_value` - The final value of the investment.
+/// * `subperiods_per_period` - The number of times interest compounds within each period.
+///
+/// # Panics
+/// The call will fail if `subperiods_per_period` is zero, if the rate per subperiod is less than
+/// or equal to -1.0, or if `rate` or `future_value` is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = present_value_subperiod_solution(0.06, 5, 10_000, 12);
+/// assert_rounded_6!(0.061678, solution.rate());
+/// assert_rounded_4!(-7_413.7220, solution.present_value());
+/// assert_eq!("pv = -fv / (1 + r/m)^(n*m)", solution.symbolic_formula());
+/// ```
+pub fn present_value_subperiod_solution<T>(rate: f64, periods: u32, future_value: T, subperiods_per_period: u32) -> TvmSolution
+    where T: Into<f64> + Copy
+{
+    let future_value = future_value.into();
+    assert!(rate.is_finite(), "The rate must be finite (not NaN or infinity)");
+    assert!(future_value.is_finite(), "The future value must be finite (not NaN or infinity)");
+    assert!(subperiods_per_period > 0, "The number of subperiods per period must be greater than zero.");
+    let subperiod_rate = rate / subperiods_per_period as f64;
+    assert!(subperiod_rate > -1.0, "The rate per subperiod must be greater than -1.0 (-100%).");
+    let subperiods = periods as f64 * subperiods_per_period as f64;
+    let effective_rate = (1.0 + subperiod_rate).powf(subperiods_per_period as f64) - 1.0;
+    let present_value = -future_value / (1.0 + subperiod_rate).powf(subperiods);
+    assert!(present_value.is_finite());
+    let formula = format!("{:.4} = {:.4} / (1 + {:.6} / {})^({} * {})", present_value, -future_value, rate, subperiods_per_period, periods, subperiods_per_period);
+    TvmSolution::new(TvmVariable::PresentValue, false, effective_rate, periods, present_value, future_value, &formula, "pv = -fv / (1 + r/m)^(n*m)")
+}
+
 /// Calculates a present value based on rates that change for each period.
 ///
 /// Related functions:
@@ -282,6 +540,9 @@ pub fn present_value_schedule<T>(rates: &[f64], future_value: T) -> f64
     where T: Into<f64> + Copy
 {
     let periods = rates.len();
+    if periods == 0 {
+        warn!("The rates are empty so the schedule has no periods. Was a multi-period calculation intended?");
+    }
     let future_value = future_value.into();
 
     // Check the parameters including all of the provided rates.
@@ -360,9 +621,14 @@ pub(crate) fn present_value_internal(rate: f64, periods: f64, future_value: f64,
 
 pub(crate) fn present_value_solution_internal(rate: f64, periods: f64, future_value: f64, continuous_compounding: bool) -> TvmSolution {
     let present_value = present_value_internal(rate, periods, future_value, continuous_compounding);
+    let (formula, symbolic_formula) = present_value_formula(rate, periods, present_value, future_value, continuous_compounding);
+    TvmSolution::new_fractional_periods(TvmVariable::PresentValue, continuous_compounding, rate, periods, present_value, future_value, &formula, symbolic_formula)
+}
+
+pub(crate) fn present_value_formula(rate: f64, periods: f64, present_value: f64, future_value: f64, continuous_compounding: bool) -> (String, &'static str) {
     let rate_multiplier = 1.0 + rate;
     assert!(rate_multiplier >= 0.0);
-    let (formula, symbolic_formula) = if continuous_compounding {
+    if continuous_compounding {
         let formula = format!("{:.4} = {:.4} / {:.6}^({:.6} * {})", present_value, -future_value, std::f64::consts::E, rate, periods);
         let symbolic_formula = "pv = -fv / e^(rt)";
         (formula, symbolic_formula)
@@ -370,8 +636,7 @@ pub(crate) fn present_value_solution_internal(rate: f64, periods: f64, future_va
         let formula = format!("{:.4} = {:.4} / ({:.6} ^ {})", present_value, -future_value, rate_multiplier, periods);
         let symbolic_formula = "pv = -fv / (1 + r)^n";
         (formula, symbolic_formula)
-    };
-    TvmSolution::new_fractional_periods(TvmVariable::PresentValue, continuous_compounding, rate, periods, present_value, future_value, &formula, symbolic_formula)
+    }
 }
 
 fn check_present_value_parameters(rate: f64, _periods: f64, future_value: f64) {
@@ -388,7 +653,41 @@ fn check_present_value_parameters(rate: f64, _periods: f64, future_value: f64) {
 mod tests {
     use super::*;
     use crate::*;
-    
+
+    #[test]
+    fn test_present_value_stable_long_horizon() {
+        // The exact value to 40 significant digits is 368063.3042887770634...
+        let exact = 368_063.304_288_777_06;
+        let stable = -present_value_stable(0.001, 1_000, 1_000_000, false);
+        let iterative = present_value_solution(0.001, 1_000, 1_000_000, false).series()[0].value();
+        assert!((stable - exact).abs() < (iterative - exact).abs());
+        assert!((stable - exact).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_present_value_stable_matches_present_value() {
+        for &(rate, periods, continuous_compounding) in [(0.05, 20, false), (-0.02, 35, false), (0.0, 10, false), (0.08, 15, true)].iter() {
+            let expected = present_value(rate, periods, 1_000, continuous_compounding);
+            assert_approx_equal!(expected, present_value_stable(rate, periods, 1_000, continuous_compounding));
+        }
+    }
+
+    #[test]
+    fn test_present_value_stable_zero_periods() {
+        for &rate in [-1.0, -0.5, 0.0, 0.07].iter() {
+            assert_eq!(-1_000.0, present_value_stable(rate, 0, 1_000, false));
+            assert_eq!(-1_000.0, present_value_stable(rate, 0, 1_000, true));
+        }
+    }
+
+    #[test]
+    fn test_present_value_schedule_empty() {
+        let solution = present_value_schedule_solution(&[], 2_500);
+        assert!(solution.is_empty_schedule());
+        assert_eq!(0, solution.periods());
+        assert_approx_equal!(-2_500.0, solution.present_value());
+    }
+
     #[test]
     fn test_present_value_schedule() {
         let rates = [0.04, 0.07, -0.12, -0.03, 0.11];
@@ -512,6 +811,55 @@ mod tests {
         assert_approx_equal!(fv, solution.future_value());
     }
 
+    #[test]
+    fn test_present_value_compare_compounding() {
+        for rate in [0.001, 0.05, 0.5].iter() {
+            for future_value in [1_000.0, -1_000.0].iter() {
+                let comparison = present_value_compare_compounding(*rate, 12, *future_value);
+                assert!(!comparison.discrete().continuous_compounding());
+                assert!(comparison.continuous().continuous_compounding());
+                // With a positive rate the continuous present value is always smaller.
+                assert!(comparison.continuous().present_value().abs() < comparison.discrete().present_value().abs());
+                assert_approx_equal!(comparison.continuous().present_value() - comparison.discrete().present_value(), comparison.difference());
+            }
+        }
+    }
+
+    #[test]
+    fn test_present_value_shifted_offsets() {
+        for continuous_compounding in [false, true].iter() {
+            let (rate, future_value, periods) = (0.045, 12_000.0, 6);
+            // No offset is ordinary end-of-period discounting.
+            let pv = present_value_shifted(rate, future_value, periods as f64, 0.0, *continuous_compounding);
+            assert_approx_equal!(present_value(rate, periods, future_value, *continuous_compounding), pv);
+            // A full period offset is the same as arriving at the beginning of the last period.
+            let pv = present_value_shifted(rate, future_value, periods as f64, 1.0, *continuous_compounding);
+            assert_approx_equal!(present_value(rate, periods - 1, future_value, *continuous_compounding), pv);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_present_value_shifted_offset_out_of_range() {
+        present_value_shifted(0.045, 12_000, 6.0, 1.5, false);
+    }
+
+    #[test]
+    fn test_present_value_subperiod_limits() {
+        let (rate, periods, future_value) = (0.09, 8, 25_000.0);
+        // One subperiod is ordinary compounding.
+        assert_approx_equal!(present_value(rate, periods, future_value, false), present_value_subperiod(rate, periods, future_value, 1));
+        // More subperiods move steadily toward continuous compounding.
+        let continuous = present_value(rate, periods, future_value, true);
+        let mut previous_gap = f64::MAX;
+        for subperiods_per_period in [2, 12, 365, 100_000].iter() {
+            let gap = (present_value_subperiod(rate, periods, future_value, *subperiods_per_period) - continuous).abs();
+            assert!(gap < previous_gap);
+            previous_gap = gap;
+        }
+        assert!(previous_gap < 0.01);
+    }
+
     #[test]
     fn test_present_value_against_excel() {
         compare_to_excel(1, 0.01f64, 90, 1f64, -0.408391185151344f64, -0.408391185151344f64, -0.406569659740599f64);
@@ -0,0 +1,316 @@
+//! **Present value with a collared floating rate.** A floating-rate instrument may have a cap, a
+//! highest rate it will pay, and a floor, a lowest rate it will pay. Together these form a collar.
+//! Each period's base rate, such as a reference rate plus a spread, is clamped to the range from
+//! the floor to the cap before it's used for discounting:
+//!
+//! rate<sub>t</sub> = min(max(base_rate<sub>t</sub>, floor), cap)
+//!
+//! present_value = -future_value / ((1 + rate<sub>1</sub>) * (1 + rate<sub>2</sub>) * ... * (1 + rate<sub>n</sub>))
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! let solution = present_value_collared_solution(&[0.01, 0.02, 0.05, 0.07], 0.02, 0.06, 1_000);
+//! dbg!(&solution);
+//! solution.print_table();
+//! ```
+
+use crate::*;
+use std::ops::Deref;
+
+/// Returns the **present value of a future amount discounted at floating rates held within a floor
+/// and a cap**.
+///
+/// Related functions:
+/// * To see the base and clamped rate for each period use [`present_value_collared_solution`].
+/// * For rates that aren't clamped use [`present_value_schedule`].
+///
+/// See the [present_value_collared](./index.html) module page for the formula. As with
+/// [`present_value_schedule`] the result is negative if the future value is positive.
+///
+/// # Arguments
+/// * `base_rates` - The floating rate for each period before the collar is applied.
+/// * `floor` - The lowest rate used for any period. Use `f64::NEG_INFINITY` for no floor.
+/// * `cap` - The highest rate used for any period. Use `f64::INFINITY` for no cap.
+/// * `future_value` - The amount to be discounted.
+///
+/// # Panics
+/// The call will fail if `floor` is greater than `cap`, if `floor`, `cap`, or any base rate is
+/// NaN, if any clamped rate is less than -1.0 or not finite, or if `future_value` is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // The first rate is raised to the floor and the last one is lowered to the cap.
+/// let pv = present_value_collared(&[0.01, 0.02, 0.05, 0.07], 0.02, 0.06, 1_000);
+/// assert_rounded_4!(-863.5838, pv);
+/// assert_rounded_4!(present_value_schedule(&[0.02, 0.02, 0.05, 0.06], 1_000), pv);
+/// ```
+pub fn present_value_collared<T>(base_rates: &[f64], floor: f64, cap: f64, future_value: T) -> f64
+    where T: Into<f64> + Copy
+{
+    present_value_collared_solution(base_rates, floor, cap, future_value).present_value()
+}
+
+/// Returns the **present value of a future amount discounted at floating rates held within a floor
+/// and a cap** along with the base and clamped rate for each period.
+///
+/// Related functions:
+/// * To calculate only the present value as an `f64` use [`present_value_collared`].
+///
+/// # Arguments
+/// * `base_rates` - The floating rate for each period before the collar is applied.
+/// * `floor` - The lowest rate used for any period. Use `f64::NEG_INFINITY` for no floor.
+/// * `cap` - The highest rate used for any period. Use `f64::INFINITY` for no cap.
+/// * `future_value` - The amount to be discounted.
+///
+/// # Panics
+/// The call will fail if `floor` is greater than `cap`, if `floor`, `cap`, or any base rate is
+/// NaN, if any clamped rate is less than -1.0 or not finite, or if `future_value` is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = present_value_collared_solution(&[0.01, 0.02, 0.05, 0.07], 0.02, 0.06, 1_000);
+/// assert_eq!(vec![0.02, 0.02, 0.05, 0.06], solution.rates());
+/// assert_eq!(1, solution.floored_periods());
+/// assert_eq!(1, solution.capped_periods());
+///
+/// // The series starts with the present value and grows at the clamped rates to the future value.
+/// let series = solution.series();
+/// assert_eq!(5, series.len());
+/// assert_eq!(0.07, series[4].base_rate());
+/// assert_eq!(0.06, series[4].rate());
+/// assert_rounded_4!(863.5838, series[0].value());
+/// assert_rounded_4!(880.8555, series[1].value());
+/// assert_rounded_4!(1_000.0, series[4].value());
+/// ```
+pub fn present_value_collared_solution<T>(base_rates: &[f64], floor: f64, cap: f64, future_value: T) -> CollaredRateSolution
+    where T: Into<f64> + Copy
+{
+    assert!(!floor.is_nan() && !cap.is_nan(), "The floor and cap must be numbers (not NaN)");
+    assert!(floor <= cap, "The floor must not be greater than the cap.");
+    let rates = base_rates.iter()
+        .map(|base_rate| {
+            assert!(!base_rate.is_nan(), "The base rates must be numbers (not NaN)");
+            base_rate.max(floor).min(cap)
+        })
+        .collect::<Vec<f64>>();
+    let present_value = present_value_schedule(&rates, future_value);
+    CollaredRateSolution::new(base_rates.to_vec(), floor, cap, rates, future_value.into(), present_value)
+}
+
+/// A record of a present value calculated with collared floating rates. Created by calling
+/// [`present_value_collared_solution`].
+#[derive(Clone, Debug)]
+pub struct CollaredRateSolution {
+    base_rates: Vec<f64>,
+    floor: f64,
+    cap: f64,
+    rates: Vec<f64>,
+    future_value: f64,
+    present_value: f64,
+    formula: String,
+    symbolic_formula: String,
+}
+
+impl CollaredRateSolution {
+    pub(crate) fn new(base_rates: Vec<f64>, floor: f64, cap: f64, rates: Vec<f64>, future_value: f64, present_value: f64) -> Self {
+        assert!(present_value.is_finite());
+        let formula = format!("{:.4} = {:.4} / ({})", present_value, -future_value,
+                              rates.iter().map(|rate| format!("{:.6}", 1.0 + rate)).collect::<Vec<_>>().join(" * "));
+        let symbolic_formula = "pv = -fv / product(1 + min(max(base_rate, floor), cap))".to_string();
+        Self {
+            base_rates,
+            floor,
+            cap,
+            rates,
+            future_value,
+            present_value,
+            formula,
+            symbolic_formula,
+        }
+    }
+
+    /// Returns the floating rate for each period before the collar is applied.
+    pub fn base_rates(&self) -> Vec<f64> {
+        self.base_rates.clone()
+    }
+
+    /// Returns the lowest rate used for any period.
+    pub fn floor(&self) -> f64 {
+        self.floor
+    }
+
+    /// Returns the highest rate used for any period.
+    pub fn cap(&self) -> f64 {
+        self.cap
+    }
+
+    /// Returns the rate used for each period after the collar is applied.
+    pub fn rates(&self) -> Vec<f64> {
+        self.rates.clone()
+    }
+
+    /// Returns the number of periods.
+    pub fn periods(&self) -> u32 {
+        self.rates.len() as u32
+    }
+
+    /// Returns the number of periods where the base rate was below the floor.
+    pub fn floored_periods(&self) -> u32 {
+        self.base_rates.iter().filter(|base_rate| **base_rate < self.floor).count() as u32
+    }
+
+    /// Returns the number of periods where the base rate was above the cap.
+    pub fn capped_periods(&self) -> u32 {
+        self.base_rates.iter().filter(|base_rate| **base_rate > self.cap).count() as u32
+    }
+
+    /// Returns the amount that was discounted.
+    pub fn future_value(&self) -> f64 {
+        self.future_value
+    }
+
+    /// Returns the present value, which is negative if the future value is positive.
+    pub fn present_value(&self) -> f64 {
+        self.present_value
+    }
+
+    /// Returns a text version of the formula used to calculate the present value with the actual
+    /// values.
+    pub fn formula(&self) -> &str {
+        &self.formula
+    }
+
+    /// Returns a text version of the formula used to calculate the present value using variables
+    /// such as fv for the future value.
+    pub fn symbolic_formula(&self) -> &str {
+        &self.symbolic_formula
+    }
+
+    /// Calculates the value at the end of each period starting with period 0. As with
+    /// [`TvmSolution::series`] the values have the same sign as the future value.
+    pub fn series(&self) -> CollaredRateSeries {
+        let mut value = -self.present_value;
+        let mut series = vec![CollaredRatePeriod::new(0, 0.0, 0.0, value)];
+        for (index, (base_rate, rate)) in self.base_rates.iter().zip(self.rates.iter()).enumerate() {
+            value *= 1.0 + rate;
+            series.push(CollaredRatePeriod::new(index as u32 + 1, *base_rate, *rate, value));
+        }
+        CollaredRateSeries::new(series)
+    }
+
+    /// Prints a formatted table with the base rate, clamped rate, and value for each period.
+    pub fn print_table(&self) {
+        self.series().print_table();
+    }
+
+    /// Prints a formatted table with the base rate, clamped rate, and value for each period using
+    /// the given locale and precision for money amounts.
+    pub fn print_table_locale(&self, locale: &num_format::Locale, precision: usize) {
+        self.series().print_table_locale(locale, precision);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CollaredRateSeries(Vec<CollaredRatePeriod>);
+
+impl CollaredRateSeries {
+    pub(crate) fn new(series: Vec<CollaredRatePeriod>) -> Self {
+        Self(series)
+    }
+
+    pub fn filter<P>(&self, predicate: P) -> Self
+        where P: Fn(&&CollaredRatePeriod) -> bool
+    {
+        Self(self.iter().filter(|x| predicate(x)).cloned().collect())
+    }
+
+    pub fn print_table(&self) {
+        self.print_table_locale_opt(None, None);
+    }
+
+    pub fn print_table_locale(&self, locale: &num_format::Locale, precision: usize) {
+        self.print_table_locale_opt(Some(locale), Some(precision));
+    }
+
+    fn print_table_locale_opt(&self, locale: Option<&num_format::Locale>, precision: Option<usize>) {
+        let columns = columns_with_strings(&[("period", "i", true), ("base_rate", "r", true), ("rate", "r", true), ("value", "f", true)]);
+        let data = self.iter()
+            .map(|entry| vec![entry.period.to_string(), entry.base_rate.to_string(), entry.rate.to_string(), entry.value.to_string()])
+            .collect::<Vec<_>>();
+        print_table_locale_opt(&columns, data, locale, precision);
+    }
+}
+
+impl Deref for CollaredRateSeries {
+    type Target = Vec<CollaredRatePeriod>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// One period within a [`CollaredRateSeries`].
+#[derive(Clone, Debug)]
+pub struct CollaredRatePeriod {
+    period: u32,
+    base_rate: f64,
+    rate: f64,
+    value: f64,
+}
+
+impl CollaredRatePeriod {
+    pub(crate) fn new(period: u32, base_rate: f64, rate: f64, value: f64) -> Self {
+        assert!(value.is_finite());
+        Self {
+            period,
+            base_rate,
+            rate,
+            value,
+        }
+    }
+
+    /// Returns the period number. Period 0 shows the starting value and has rates of zero.
+    pub fn period(&self) -> u32 {
+        self.period
+    }
+
+    /// Returns the floating rate for the period before the collar is applied.
+    pub fn base_rate(&self) -> f64 {
+        self.base_rate
+    }
+
+    /// Returns the rate used for the period after the collar is applied.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the value at the end of the period.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_present_value_collared_floor_above_all_rates() {
+        let base_rates = [0.005, 0.012, 0.008, 0.015, 0.01];
+        let pv = present_value_collared(&base_rates, 0.02, 0.05, 2_500);
+        assert_approx_equal!(present_value(0.02, 5, 2_500, false), pv);
+    }
+
+    #[test]
+    fn test_present_value_collared_open_collar() {
+        // With no floor or cap the rates are used as given.
+        let base_rates = [0.03, -0.01, 0.04, 0.12];
+        let solution = present_value_collared_solution(&base_rates, f64::NEG_INFINITY, f64::INFINITY, 800);
+        assert_eq!(base_rates.to_vec(), solution.rates());
+        assert_eq!(0, solution.floored_periods() + solution.capped_periods());
+        assert_approx_equal!(present_value_schedule(&base_rates, 800), solution.present_value());
+    }
+}
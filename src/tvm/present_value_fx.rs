@@ -0,0 +1,219 @@
+//! **Present value of a foreign cashflow in the base currency.** A future amount in a foreign
+//! currency is discounted at the given rate and then converted to the base currency at the forward
+//! exchange rate for the date of the cashflow.
+//!
+//! The forward rate is the spot rate adjusted by a fixed number of forward points for each period:
+//!
+//! forward_fx_rate = fx_rate + fx_forward_points_per_period * periods
+//!
+//! The forward points are expressed in the same units as the exchange rate, so 0.0050 rather than
+//! 50 pips.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // €100,000 due in three years discounted at 4% and converted to dollars at a spot rate of 1.08
+//! // with the euro gaining half a cent per year.
+//! let solution = present_value_fx_solution(0.04, 3, 100_000, 1.08, 0.005);
+//! dbg!(&solution);
+//! ```
+
+use crate::*;
+
+/// Returns the **present value of a foreign cashflow converted to the base currency** at the
+/// forward exchange rate for the date of the cashflow.
+///
+/// Related functions:
+/// * To see the present value in the foreign currency and the forward rate use
+/// [`present_value_fx_solution`].
+/// * For a cashflow in the base currency use [`present_value`].
+///
+/// The formula is:
+///
+/// present_value_base = -future_value_foreign / (1 + rate)<sup>periods</sup> * (fx_rate + fx_forward_points_per_period * periods)
+///
+/// As with [`present_value`] the result is negative if the future value is positive.
+///
+/// # Arguments
+/// * `rate` - The periodic rate used to discount the foreign cashflow, expressed as a floating
+/// point number. For instance 0.05 would mean 5%.
+/// * `periods` - The number of periods until the cashflow.
+/// * `future_value_foreign` - The cashflow in the foreign currency.
+/// * `fx_rate` - The spot exchange rate as units of the base currency per unit of the foreign
+/// currency.
+/// * `fx_forward_points_per_period` - The change in the exchange rate for each period forward,
+/// which may be negative.
+///
+/// # Panics
+/// The call will fail if `rate` is less than or equal to -1.0, if `fx_rate` or the resulting
+/// forward rate is not positive, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let pv = present_value_fx(0.04, 3, 100_000, 1.08, 0.005);
+/// assert_rounded_4!(-97_345.1013, pv);
+///
+/// // With no forward points this is the foreign present value converted at the spot rate.
+/// let pv = present_value_fx(0.04, 3, 100_000, 1.08, 0.0);
+/// assert_rounded_4!(present_value(0.04, 3, 100_000, false) * 1.08, pv);
+/// ```
+pub fn present_value_fx<F>(rate: f64, periods: u32, future_value_foreign: F, fx_rate: f64, fx_forward_points_per_period: f64) -> f64
+    where F: Into<f64> + Copy
+{
+    present_value_fx_solution(rate, periods, future_value_foreign, fx_rate, fx_forward_points_per_period).present_value_base()
+}
+
+/// Returns the **present value of a foreign cashflow converted to the base currency** along with
+/// the present value in the foreign currency and the forward exchange rate used.
+///
+/// Related functions:
+/// * To calculate only the present value in the base currency as an `f64` use
+/// [`present_value_fx`].
+///
+/// # Arguments
+/// * `rate` - The periodic rate used to discount the foreign cashflow, expressed as a floating
+/// point number. For instance 0.05 would mean 5%.
+/// * `periods` - The number of periods until the cashflow.
+/// * `future_value_foreign` - The cashflow in the foreign currency.
+/// * `fx_rate` - The spot exchange rate as units of the base currency per unit of the foreign
+/// currency.
+/// * `fx_forward_points_per_period` - The change in the exchange rate for each period forward,
+/// which may be negative.
+///
+/// # Panics
+/// The call will fail if `rate` is less than or equal to -1.0, if `fx_rate` or the resulting
+/// forward rate is not positive, or if any value is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = present_value_fx_solution(0.04, 3, 100_000, 1.08, 0.005);
+/// assert_rounded_4!(1.095, solution.forward_fx_rate());
+/// assert_rounded_4!(-88_899.6359, solution.present_value_foreign());
+/// assert_rounded_4!(-97_345.1013, solution.present_value_base());
+/// ```
+pub fn present_value_fx_solution<F>(rate: f64, periods: u32, future_value_foreign: F, fx_rate: f64, fx_forward_points_per_period: f64) -> FxPresentValueSolution
+    where F: Into<f64> + Copy
+{
+    let future_value_foreign = future_value_foreign.into();
+    assert!(rate.is_finite(), "The rate must be finite (not NaN or infinity)");
+    assert!(rate > -1.0, "The rate must be greater than -1.0 (-100%).");
+    assert!(future_value_foreign.is_finite(), "The future value must be finite (not NaN or infinity)");
+    assert!(fx_rate.is_finite(), "The exchange rate must be finite (not NaN or infinity)");
+    assert!(fx_forward_points_per_period.is_finite(), "The forward points must be finite (not NaN or infinity)");
+    assert!(fx_rate > 0.0, "The exchange rate must be greater than zero.");
+    let forward_fx_rate = fx_rate + fx_forward_points_per_period * periods as f64;
+    assert!(forward_fx_rate > 0.0, "The forward exchange rate must be greater than zero.");
+    FxPresentValueSolution::new(rate, periods, future_value_foreign, fx_rate, fx_forward_points_per_period, forward_fx_rate)
+}
+
+/// A record of the present value of a foreign cashflow in both the foreign and base currencies.
+/// Created by calling [`present_value_fx_solution`].
+#[derive(Clone, Debug)]
+pub struct FxPresentValueSolution {
+    rate: f64,
+    periods: u32,
+    future_value_foreign: f64,
+    fx_rate: f64,
+    fx_forward_points_per_period: f64,
+    forward_fx_rate: f64,
+    present_value_foreign: f64,
+    present_value_base: f64,
+    formula: String,
+    symbolic_formula: String,
+}
+
+impl FxPresentValueSolution {
+    pub(crate) fn new(rate: f64, periods: u32, future_value_foreign: f64, fx_rate: f64, fx_forward_points_per_period: f64, forward_fx_rate: f64) -> Self {
+        let present_value_foreign = present_value(rate, periods, future_value_foreign, false);
+        let present_value_base = present_value_foreign * forward_fx_rate;
+        assert!(present_value_base.is_finite());
+        let formula = format!("{:.4} = -{:.4} / {:.6}^{} * ({:.6} + {:.6} * {})",
+                              present_value_base, future_value_foreign, 1.0 + rate, periods, fx_rate, fx_forward_points_per_period, periods);
+        let symbolic_formula = "pv_base = -fv_foreign / (1 + r)^n * (fx + points * n)".to_string();
+        Self {
+            rate,
+            periods,
+            future_value_foreign,
+            fx_rate,
+            fx_forward_points_per_period,
+            forward_fx_rate,
+            present_value_foreign,
+            present_value_base,
+            formula,
+            symbolic_formula,
+        }
+    }
+
+    /// Returns the periodic rate used to discount the foreign cashflow.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the number of periods until the cashflow.
+    pub fn periods(&self) -> u32 {
+        self.periods
+    }
+
+    /// Returns the cashflow in the foreign currency.
+    pub fn future_value_foreign(&self) -> f64 {
+        self.future_value_foreign
+    }
+
+    /// Returns the spot exchange rate.
+    pub fn fx_rate(&self) -> f64 {
+        self.fx_rate
+    }
+
+    /// Returns the change in the exchange rate for each period forward.
+    pub fn fx_forward_points_per_period(&self) -> f64 {
+        self.fx_forward_points_per_period
+    }
+
+    /// Returns the forward exchange rate for the date of the cashflow.
+    pub fn forward_fx_rate(&self) -> f64 {
+        self.forward_fx_rate
+    }
+
+    /// Returns the present value in the foreign currency.
+    pub fn present_value_foreign(&self) -> f64 {
+        self.present_value_foreign
+    }
+
+    /// Returns the present value converted to the base currency.
+    pub fn present_value_base(&self) -> f64 {
+        self.present_value_base
+    }
+
+    /// Returns a text version of the formula used to calculate the present value with the actual
+    /// values.
+    pub fn formula(&self) -> &str {
+        &self.formula
+    }
+
+    /// Returns a text version of the formula used to calculate the present value using variables
+    /// such as r for the rate.
+    pub fn symbolic_formula(&self) -> &str {
+        &self.symbolic_formula
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_present_value_fx_no_forward_points() {
+        let (rate, periods, future_value_foreign, fx_rate) = (0.025, 8, 2_500_000.0, 0.0067);
+        let solution = present_value_fx_solution(rate, periods, future_value_foreign, fx_rate, 0.0);
+        assert_approx_equal!(present_value(rate, periods, future_value_foreign, false), solution.present_value_foreign());
+        assert_approx_equal!(solution.present_value_foreign() * fx_rate, solution.present_value_base());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_present_value_fx_negative_forward_rate() {
+        present_value_fx(0.03, 10, 1_000, 1.2, -0.15);
+    }
+}
@@ -0,0 +1,222 @@
+//! **Present value with a time-varying instantaneous rate.** Continuous compounding at a constant
+//! rate discounts a future amount by e<sup>-r * t</sup>. When the rate changes continuously over
+//! time, as with a short-rate model, the discount factor uses the integral of the rate instead:
+//!
+//! present_value = -future_value * e<sup>-∫ r(t) dt</sup>
+//!
+//! where the integral runs from 0 to the horizon. The integral is found numerically with the
+//! trapezoidal rule, which is exact for a rate that's constant or changes linearly over time.
+//!
+//! ## Example
+//! ```
+//! # use finance_solution::*;
+//! // $1,000 due in five years with a short rate that starts at 3% and rises by half a percent a
+//! // year.
+//! let solution = present_value_integrated_rate_solution(|t| 0.03 + 0.005 * t, 5.0, 1_000, 100);
+//! dbg!(&solution);
+//! ```
+
+/// Returns the **present value of a future amount discounted at an instantaneous rate that varies
+/// over time**.
+///
+/// Related functions:
+/// * To see the integrated rate and the discount factor use
+/// [`present_value_integrated_rate_solution`].
+/// * For a constant rate with continuous compounding use [`present_value`].
+///
+/// See the [present_value_integrated_rate](./index.html) module page for the formula.
+///
+/// As with [`present_value`] the result is negative if the future value is positive.
+///
+/// # Arguments
+/// * `rate_fn` - The instantaneous rate at each point in time, such as 0.05 for 5% per year at
+/// time t in years.
+/// * `horizon` - The time until the future amount is received, in the same units as the rate.
+/// * `future_value` - The amount to be discounted.
+/// * `steps` - The number of equal steps used to integrate the rate. More steps are more accurate
+/// when the rate curves.
+///
+/// # Panics
+/// The call will fail if `horizon` is negative, if `steps` is zero, or if `horizon`,
+/// `future_value`, or any rate returned by `rate_fn` is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // A short rate that starts at 3% and rises by half a percent a year.
+/// let pv = present_value_integrated_rate(|t| 0.03 + 0.005 * t, 5.0, 1_000, 100);
+/// assert_rounded_4!(-808.5603, pv);
+///
+/// // A constant rate is the same as continuous compounding.
+/// let pv = present_value_integrated_rate(|_| 0.04, 10.0, 1_000, 10);
+/// assert_rounded_4!(present_value(0.04, 10, 1_000, true), pv);
+/// ```
+pub fn present_value_integrated_rate<F, T>(rate_fn: F, horizon: f64, future_value: T, steps: usize) -> f64
+    where
+        F: Fn(f64) -> f64,
+        T: Into<f64> + Copy
+{
+    present_value_integrated_rate_solution(rate_fn, horizon, future_value, steps).present_value()
+}
+
+/// Returns the **present value of a future amount discounted at an instantaneous rate that varies
+/// over time** along with the integrated rate and the discount factor.
+///
+/// Related functions:
+/// * To calculate only the present value as an `f64` use [`present_value_integrated_rate`].
+///
+/// # Arguments
+/// * `rate_fn` - The instantaneous rate at each point in time, such as 0.05 for 5% per year at
+/// time t in years.
+/// * `horizon` - The time until the future amount is received, in the same units as the rate.
+/// * `future_value` - The amount to be discounted.
+/// * `steps` - The number of equal steps used to integrate the rate. More steps are more accurate
+/// when the rate curves.
+///
+/// # Panics
+/// The call will fail if `horizon` is negative, if `steps` is zero, or if `horizon`,
+/// `future_value`, or any rate returned by `rate_fn` is not finite.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// let solution = present_value_integrated_rate_solution(|t| 0.03 + 0.005 * t, 5.0, 1_000, 100);
+/// assert_rounded_6!(0.2125, solution.integrated_rate());
+/// assert_rounded_6!(0.0425, solution.average_rate());
+/// assert_rounded_6!(0.808560, solution.discount_factor());
+/// assert_eq!(100, solution.steps());
+/// ```
+pub fn present_value_integrated_rate_solution<F, T>(rate_fn: F, horizon: f64, future_value: T, steps: usize) -> IntegratedRateSolution
+    where
+        F: Fn(f64) -> f64,
+        T: Into<f64> + Copy
+{
+    let future_value = future_value.into();
+    assert!(horizon.is_finite(), "The horizon must be finite (not NaN or infinity)");
+    assert!(future_value.is_finite(), "The future value must be finite (not NaN or infinity)");
+    assert!(horizon >= 0.0, "The horizon must be zero or positive.");
+    assert!(steps > 0, "The number of steps must be greater than zero.");
+
+    let step_size = horizon / steps as f64;
+    let rates = (0..=steps)
+        .map(|step| {
+            let rate = rate_fn(step_size * step as f64);
+            assert!(rate.is_finite(), "The rates must be finite (not NaN or infinity)");
+            rate
+        })
+        .collect::<Vec<_>>();
+    let integrated_rate = rates.windows(2).map(|pair| (pair[0] + pair[1]) / 2.0 * step_size).sum();
+    IntegratedRateSolution::new(horizon, future_value, steps, integrated_rate)
+}
+
+/// A record of a present value calculated with a time-varying instantaneous rate. Created by
+/// calling [`present_value_integrated_rate_solution`].
+#[derive(Clone, Debug)]
+pub struct IntegratedRateSolution {
+    horizon: f64,
+    future_value: f64,
+    steps: usize,
+    integrated_rate: f64,
+    discount_factor: f64,
+    present_value: f64,
+    formula: String,
+    symbolic_formula: String,
+}
+
+impl IntegratedRateSolution {
+    pub(crate) fn new(horizon: f64, future_value: f64, steps: usize, integrated_rate: f64) -> Self {
+        let discount_factor = (-integrated_rate).exp();
+        let present_value = -future_value * discount_factor;
+        assert!(present_value.is_finite());
+        let formula = format!("{:.4} = -{:.4} * e^-{:.6}", present_value, future_value, integrated_rate);
+        let symbolic_formula = "pv = -fv * e^-integral(r(t), 0, t)".to_string();
+        Self {
+            horizon,
+            future_value,
+            steps,
+            integrated_rate,
+            discount_factor,
+            present_value,
+            formula,
+            symbolic_formula,
+        }
+    }
+
+    /// Returns the time until the future amount is received.
+    pub fn horizon(&self) -> f64 {
+        self.horizon
+    }
+
+    /// Returns the amount that was discounted.
+    pub fn future_value(&self) -> f64 {
+        self.future_value
+    }
+
+    /// Returns the number of steps used to integrate the rate.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Returns the integral of the instantaneous rate from zero to the horizon.
+    pub fn integrated_rate(&self) -> f64 {
+        self.integrated_rate
+    }
+
+    /// Returns the constant continuously compounded rate that gives the same present value. This
+    /// is zero if the horizon is zero.
+    pub fn average_rate(&self) -> f64 {
+        if self.horizon == 0.0 {
+            0.0
+        } else {
+            self.integrated_rate / self.horizon
+        }
+    }
+
+    /// Returns the discount factor e<sup>-∫ r(t) dt</sup>.
+    pub fn discount_factor(&self) -> f64 {
+        self.discount_factor
+    }
+
+    /// Returns the present value, which is negative if the future value is positive.
+    pub fn present_value(&self) -> f64 {
+        self.present_value
+    }
+
+    /// Returns a text version of the formula used to calculate the present value with the actual
+    /// values.
+    pub fn formula(&self) -> &str {
+        &self.formula
+    }
+
+    /// Returns a text version of the formula used to calculate the present value using variables
+    /// such as r(t) for the instantaneous rate.
+    pub fn symbolic_formula(&self) -> &str {
+        &self.symbolic_formula
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn test_present_value_integrated_rate_constant() {
+        for (rate, periods, future_value) in [(0.05, 3, 2_500.0), (-0.01, 7, 100.0), (0.12, 30, -75_000.0)].iter() {
+            let expected = present_value(*rate, *periods, *future_value, true);
+            let pv = present_value_integrated_rate(|_| *rate, *periods as f64, *future_value, 1);
+            assert_approx_equal!(expected, pv);
+        }
+    }
+
+    #[test]
+    fn test_present_value_integrated_rate_curved() {
+        // The integral of 0.02 + 0.003 * t^2 from 0 to 4 is 0.144. The trapezoidal rule gets closer
+        // to it as the number of steps rises.
+        let coarse = present_value_integrated_rate_solution(|t| 0.02 + 0.003 * t * t, 4.0, 1_000, 4);
+        let fine = present_value_integrated_rate_solution(|t| 0.02 + 0.003 * t * t, 4.0, 1_000, 10_000);
+        assert!((coarse.integrated_rate() - 0.144).abs() > (fine.integrated_rate() - 0.144).abs());
+        assert_rounded_6!(0.144, fine.integrated_rate());
+        assert_rounded_4!(-1_000.0 * (-0.144_f64).exp(), fine.present_value());
+    }
+}
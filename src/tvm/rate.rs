@@ -164,6 +164,80 @@ pub fn rate_solution<P, F>(periods: u32, present_value: P, future_value: F, cont
     rate_solution_internal(periods, present_value.into(), future_value.into(), continuous_compounding)
 }
 
+/// Returns the periodic rate needed for an investment to grow by a given multiple, such as the
+/// rate that triples an investment in ten periods.
+///
+/// Related functions:
+/// * To keep the formula and produce period-by-period values use [`rate_for_multiple_solution`].
+///
+/// The formula is:
+///
+/// rate = multiple<sup>1 / periods</sup> - 1
+///
+/// or with continuous compounding:
+///
+/// rate = ln(multiple) / periods
+///
+/// # Arguments
+/// * `multiple` - The final value of the investment divided by its starting value. For instance 2.0
+/// means the investment doubles.
+/// * `periods` - The number of periods such as quarters or years. Often appears as `n` or `t`.
+/// * `continuous_compounding` - True for continuous compounding, false for simple compounding.
+///
+/// # Panics
+/// The call will fail if `multiple` is not greater than zero or if `periods` is zero.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // Tripling an investment in ten periods takes a rate of about 11.6% per period.
+/// assert_rounded_6!(0.116123, rate_for_multiple(3.0, 10, false));
+///
+/// // With continuous compounding the rate is a bit lower.
+/// assert_rounded_6!(0.109861, rate_for_multiple(3.0, 10, true));
+/// ```
+pub fn rate_for_multiple(multiple: f64, periods: u32, continuous_compounding: bool) -> f64 {
+    rate_for_multiple_solution(multiple, periods, continuous_compounding).rate()
+}
+
+/// Returns the periodic rate needed for an investment to grow by a given multiple, along with a
+/// solution where the present value is -1 and the future value is the multiple.
+///
+/// The present value is negative following the cash flow sign convention used by
+/// [`rate_solution`], so the solution is the same as calling `rate_solution(periods, -1.0,
+/// multiple, continuous_compounding)`.
+///
+/// Related functions:
+/// * To calculate only the rate as an `f64` use [`rate_for_multiple`].
+///
+/// # Arguments
+/// * `multiple` - The final value of the investment divided by its starting value. For instance 2.0
+/// means the investment doubles.
+/// * `periods` - The number of periods such as quarters or years. Often appears as `n` or `t`.
+/// * `continuous_compounding` - True for continuous compounding, false for simple compounding.
+///
+/// # Panics
+/// The call will fail if `multiple` is not greater than zero or if `periods` is zero.
+///
+/// # Examples
+/// ```
+/// # use finance_solution::*;
+/// // What rate doubles an investment in seven years?
+/// let solution = rate_for_multiple_solution(2.0, 7, false);
+/// assert_rounded_6!(0.104090, solution.rate());
+/// assert_eq!(-1.0, solution.present_value());
+/// assert_eq!(2.0, solution.future_value());
+///
+/// // Growing $5,000 at that rate for seven years doubles it.
+/// assert_rounded_4!(10_000.0, future_value(solution.rate(), 7, -5_000, false));
+/// ```
+pub fn rate_for_multiple_solution(multiple: f64, periods: u32, continuous_compounding: bool) -> TvmSolution {
+    assert!(multiple.is_finite(), "The multiple must be finite (not NaN or infinity)");
+    assert!(multiple > 0.0, "The multiple must be greater than zero.");
+    assert!(periods > 0, "The number of periods must be greater than zero.");
+    rate_solution_internal(periods, -1.0, multiple, continuous_compounding)
+}
+
 fn rate_internal(periods: u32, present_value: f64, future_value: f64, continuous_compounding: bool) -> f64 {
     if present_value + future_value == 0.0 {
         // This is a special case where any rate will work.
@@ -193,14 +267,20 @@ fn rate_internal(periods: u32, present_value: f64, future_value: f64, continuous
 pub (crate) fn rate_solution_internal(periods: u32, present_value: f64, future_value: f64, continuous_compounding: bool) -> TvmSolution {
     if present_value == 0.0 && future_value == 0.0 {
         // This is a special case where any rate will work.
-        let formula = "{special case}";
-        let symbolic_formula = "***";
         let rate = 0.0;
-        return TvmSolution::new(TvmVariable::Rate, continuous_compounding, rate, periods, present_value, future_value, formula, symbolic_formula);
+        let (formula, symbolic_formula) = rate_formula(rate, periods, present_value, future_value, continuous_compounding);
+        return TvmSolution::new(TvmVariable::Rate, continuous_compounding, rate, periods, present_value, future_value, &formula, symbolic_formula);
     }
 
     let rate = rate_internal(periods, present_value, future_value, continuous_compounding);
-    let (formula, symbolic_formula) = if continuous_compounding {
+    let (formula, symbolic_formula) = rate_formula(rate, periods, present_value, future_value, continuous_compounding);
+    TvmSolution::new(TvmVariable::Rate, continuous_compounding, rate, periods, present_value, future_value, &formula, symbolic_formula)
+}
+
+pub(crate) fn rate_formula(rate: f64, periods: u32, present_value: f64, future_value: f64, continuous_compounding: bool) -> (String, &'static str) {
+    if present_value == 0.0 && future_value == 0.0 {
+        ("{special case}".to_string(), "***")
+    } else if continuous_compounding {
         let formula = format!("{:.6} = ln({:.4} / {:.4}) / {}", rate, -future_value, present_value, periods);
         let symbolic_formula = "r = ln(-fv / pv) / t";
         (formula, symbolic_formula)
@@ -208,8 +288,7 @@ pub (crate) fn rate_solution_internal(periods: u32, present_value: f64, future_v
         let formula = format!("{:.6} = (({:.4} / {:.4}) ^ (1 / {})) - 1", rate, -future_value, present_value, periods);
         let symbolic_formula = "r = ((-fv / pv) ^ (1 / n)) - 1";
         (formula, symbolic_formula)
-    };
-    TvmSolution::new(TvmVariable::Rate, continuous_compounding, rate, periods, present_value, future_value, &formula, symbolic_formula)
+    }
 }
 
 fn check_rate_parameters(periods: u32, present_value: f64, future_value: f64) {
@@ -234,6 +313,23 @@ mod tests {
         assert_rounded_6(0.0, rate(12, -10_000.0, 10_000.0, false));
     }
 
+    #[test]
+    fn test_rate_for_multiple_round_trip() {
+        for continuous_compounding in [false, true].iter() {
+            for multiple in [0.5, 1.0, 3.0, 25.0].iter() {
+                let solution = rate_for_multiple_solution(*multiple, 10, *continuous_compounding);
+                let future_value = future_value_solution(solution.rate(), 10, 1.0, *continuous_compounding).future_value();
+                assert_approx_equal!(-*multiple, future_value);
+            }
+        }
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_rate_for_multiple_err_zero_multiple() {
+        rate_for_multiple(0.0, 10, false);
+    }
+
     #[should_panic]
     #[test]
     fn test_rate_err_present_value_nan() {